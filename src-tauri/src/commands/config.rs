@@ -1,43 +1,215 @@
+use crate::commands::service;
 use crate::models::{
-    AIConfigOverview, ChannelConfig, ConfiguredModel, ConfiguredProvider,
-    MCPConfig, ModelConfig, OfficialProvider, SuggestedModel,
+    AIConfigOverview, AgentEntry, AppError, ErrorCode, AgentModelConfig, AgentSubagents, BindingConfig, BindingMatch,
+    ChannelConfig, ConfiguredModel, ConfiguredProvider, ContextPruningSettings,
+    FlagOrSettings, HeartbeatSettings, MCPConfig, ManagerSettings, ModelConfig, OfficialProvider,
+    OpenClawConfig, SubagentSettings, SuggestedModel, TelegramAccountConfig, WebToolsConfig,
 };
+use crate::utils::config_history::{self, ConfigDiffEntry, ConfigSnapshot};
+use crate::utils::config_lock::{self, ConfigLock};
+use crate::utils::config_migrations::{self, MigrationReport};
+use crate::utils::config_validation::{self, ConfigValidationIssue};
+use crate::utils::config_watcher::{self, ConfigWatcher};
+use crate::utils::gateway_token::{self, TokenState, TokenStorage};
+use crate::utils::profiles::{self, Profile};
+use crate::utils::secret_refs;
+use crate::utils::secret_scan;
+use crate::utils::secrets_vault;
 use crate::utils::{file, platform, shell, log_sanitizer};
 use log::{debug, error, info, warn};
 use serde_json::{json, Value};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use tauri::command;
+use std::ops::{Deref, DerefMut};
+use tauri::{command, AppHandle, Emitter};
 
 /// Load openclaw.json configuration
-fn load_openclaw_config() -> Result<Value, String> {
+fn load_openclaw_config() -> Result<Value, AppError> {
+    read_openclaw_config().map(|(config, _)| config)
+}
+
+/// Load openclaw.json together with the revision it was read at
+fn read_openclaw_config() -> Result<(Value, Option<String>), AppError> {
     let config_path = platform::get_config_file_path();
 
     if !file::file_exists(&config_path) {
-        return Ok(json!({}));
+        return Ok((json!({}), None));
     }
 
     let content =
-        file::read_file(&config_path).map_err(|e| format!("Failed to read configuration file: {}", e))?;
+        file::read_file(&config_path).map_err(|e| AppError::new(ErrorCode::ConfigRead, "Failed to read configuration file").with_detail(e))?;
+    let revision = config_lock::content_revision(content.as_bytes());
 
     // Strip UTF-8 BOM if present (Windows editors sometimes add this)
     let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
 
-    serde_json::from_str(content).map_err(|e| format!("Failed to parse configuration file: {}", e))
+    let config = serde_json::from_str(content)
+        .map_err(|e| AppError::new(ErrorCode::ConfigParse, "Failed to parse configuration file").with_detail(e))?;
+    Ok((config, Some(revision)))
+}
+
+/// Save openclaw.json configuration.
+/// The previous content is snapshotted into the history first, tagged with `command`.
+fn save_openclaw_config(config: &Value, command: &str) -> Result<(), AppError> {
+    let content =
+        serde_json::to_string_pretty(config).map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to serialize configuration").with_detail(e))?;
+
+    write_openclaw_config_content(&content, command)
 }
 
-/// Save openclaw.json configuration
-fn save_openclaw_config(config: &Value) -> Result<(), String> {
+/// Snapshot the current openclaw.json, then replace it with `content`
+fn write_openclaw_config_content(content: &str, command: &str) -> Result<(), AppError> {
     let config_path = platform::get_config_file_path();
 
-    let content =
-        serde_json::to_string_pretty(config).map_err(|e| format!("Failed to serialize configuration: {}", e))?;
+    if let Ok(previous) = file::read_file(&config_path) {
+        if previous != content {
+            match config_history::save_snapshot(&platform::get_config_history_dir(), &previous, command) {
+                Ok(snapshot) => debug!("[Config History] Saved snapshot {} before {}", snapshot.id, command),
+                Err(e) => warn!("[Config History] Failed to snapshot configuration before {}: {}", command, e),
+            }
+        }
+    }
+
+    config_watcher::note_own_write(&config_path, content.as_bytes());
+    file::write_file(&config_path, content)
+        .map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to write configuration file").with_detail(e))
+}
+
+/// Error for a config that fails schema validation; the issues go into `detail`
+fn invalid_config_error(issues: &[ConfigValidationIssue]) -> AppError {
+    AppError::new(ErrorCode::ConfigInvalid, "Configuration is invalid").with_detail(config_validation::format_issues(issues))
+}
+
+/// Acquire the cross-process lock on openclaw.json
+async fn lock_openclaw_config() -> Result<ConfigLock, AppError> {
+    ConfigLock::acquire_async(&platform::get_config_file_path())
+        .await
+        .map_err(|e| AppError::new(ErrorCode::ConfigLocked, "Failed to lock configuration file").with_detail(e))
+}
+
+/// Replace openclaw.json wholesale (UI editor, import)
+async fn replace_openclaw_config(config: &Value, command: &str) -> Result<(), AppError> {
+    let issues = config_validation::validate(config);
+    if !issues.is_empty() {
+        warn!("[Config] Rejecting invalid configuration from {}: {}", command, config_validation::format_issues(&issues));
+        return Err(invalid_config_error(&issues));
+    }
+
+    let _lock = lock_openclaw_config().await?;
+    save_openclaw_config(config, command)
+}
+
+/// A locked load → mutate → save cycle on openclaw.json.
+///
+/// Holds the advisory lock until committed or dropped, so concurrent Manager
+/// commands serialize. Writers that don't take the lock (the openclaw CLI, a
+/// text editor) are caught by the revision check in `commit`, which merges our
+/// edits onto their version instead of overwriting it.
+struct ConfigTransaction {
+    _lock: ConfigLock,
+    base: Value,
+    base_revision: Option<String>,
+    config: Value,
+}
+
+impl ConfigTransaction {
+    async fn begin() -> Result<Self, AppError> {
+        Self::with_lock(lock_openclaw_config().await?)
+    }
+
+    /// `begin` for synchronous callers outside the async runtime (startup migrations)
+    fn begin_blocking() -> Result<Self, AppError> {
+        let lock = ConfigLock::acquire(&platform::get_config_file_path())
+            .map_err(|e| AppError::new(ErrorCode::ConfigLocked, "Failed to lock configuration file").with_detail(e))?;
+        Self::with_lock(lock)
+    }
+
+    fn with_lock(lock: ConfigLock) -> Result<Self, AppError> {
+        let (base, base_revision) = read_openclaw_config()?;
+        Ok(Self {
+            _lock: lock,
+            config: base.clone(),
+            base,
+            base_revision,
+        })
+    }
+
+    /// Save the edited config; `command` names the caller in the config history.
+    /// Fails if the edit introduces schema violations; problems already in the
+    /// file are only logged, so they don't block unrelated changes.
+    fn commit(self, command: &str) -> Result<(), AppError> {
+        let current_revision = config_lock::file_revision(&platform::get_config_file_path());
+        let (base, config) = if current_revision == self.base_revision {
+            (self.base, self.config)
+        } else {
+            warn!("[Config] openclaw.json changed on disk during the edit, merging changes");
+            let (theirs, _) = read_openclaw_config()?;
+            let merged = config_lock::three_way_merge(&self.base, &self.config, &theirs).map_err(|conflicts| {
+                AppError::new(
+                    ErrorCode::ConfigConflict,
+                    "Configuration was modified by another process. Please reload and try again.",
+                )
+                .with_detail(format!("Conflicting changes at {}", conflicts.join(", ")))
+            })?;
+            (theirs, merged)
+        };
+
+        let issues = config_validation::new_issues(&base, &config);
+        if !issues.is_empty() {
+            warn!("[Config] {} would write an invalid configuration: {}", command, config_validation::format_issues(&issues));
+            return Err(invalid_config_error(&issues));
+        }
+        let existing = config_validation::validate(&config);
+        if !existing.is_empty() {
+            warn!("[Config] Configuration has existing problems: {}", config_validation::format_issues(&existing));
+        }
+
+        save_openclaw_config(&config, command)
+    }
+
+    /// Typed view of the config being edited; sections that don't match the model are
+    /// left out (and left alone by `set_typed`)
+    fn typed(&self) -> Result<OpenClawConfig, AppError> {
+        parse_typed_config(&self.config)
+    }
 
-    file::write_file(&config_path, &content).map_err(|e| format!("Failed to write configuration file: {}", e))
+    /// Apply the edits made to a typed view. Only the changed values are written, so
+    /// everything else in the file is kept as it was.
+    fn set_typed(&mut self, typed: &OpenClawConfig) -> Result<(), AppError> {
+        typed.patch_into(&mut self.config).map_err(|e| AppError::new(ErrorCode::ConfigInvalid, e))
+    }
+}
+
+impl Deref for ConfigTransaction {
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        &self.config
+    }
+}
+
+impl DerefMut for ConfigTransaction {
+    fn deref_mut(&mut self) -> &mut Value {
+        &mut self.config
+    }
+}
+
+/// Load openclaw.json as the typed model
+fn load_typed_config() -> Result<OpenClawConfig, AppError> {
+    parse_typed_config(&load_openclaw_config()?)
+}
+
+fn parse_typed_config(config: &Value) -> Result<OpenClawConfig, AppError> {
+    let (typed, skipped) =
+        OpenClawConfig::from_value_lenient(config).map_err(|e| AppError::new(ErrorCode::ConfigParse, e))?;
+    if !skipped.is_empty() {
+        warn!("[Config] Ignoring section(s) that don't match the schema: {}", skipped.join(", "));
+    }
+    Ok(typed)
 }
 
 /// Load manager.json configuration (manager-specific settings)
-fn load_manager_config() -> Result<Value, String> {
+pub fn load_manager_config() -> Result<Value, AppError> {
     let config_path = platform::get_manager_config_file_path();
 
     if !file::file_exists(&config_path) {
@@ -45,27 +217,28 @@ fn load_manager_config() -> Result<Value, String> {
     }
 
     let content =
-        file::read_file(&config_path).map_err(|e| format!("Failed to read manager configuration file: {}", e))?;
+        file::read_file(&config_path).map_err(|e| AppError::new(ErrorCode::ConfigRead, "Failed to read manager configuration file").with_detail(e))?;
 
     // Strip UTF-8 BOM if present
     let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
 
-    serde_json::from_str(content).map_err(|e| format!("Failed to parse manager configuration file: {}", e))
+    serde_json::from_str(content).map_err(|e| AppError::new(ErrorCode::ConfigParse, "Failed to parse manager configuration file").with_detail(e))
 }
 
-/// Save manager.json configuration
-fn save_manager_config(config: &Value) -> Result<(), String> {
+/// Save manager.json configuration; private because it holds the dashboard tokens
+pub fn save_manager_config(config: &Value) -> Result<(), AppError> {
     let config_path = platform::get_manager_config_file_path();
 
     let content =
-        serde_json::to_string_pretty(config).map_err(|e| format!("Failed to serialize manager configuration: {}", e))?;
+        serde_json::to_string_pretty(config).map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to serialize manager configuration").with_detail(e))?;
 
-    file::write_file(&config_path, &content).map_err(|e| format!("Failed to write manager configuration file: {}", e))
+    file::write_private_file(&config_path, content.as_bytes())
+        .map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to write manager configuration file").with_detail(e))
 }
 
 /// Get complete configuration
 #[command]
-pub async fn get_config() -> Result<Value, String> {
+pub async fn get_config() -> Result<Value, AppError> {
     info!("[Get Config] Reading openclaw.json configuration...");
     let result = load_openclaw_config();
     match &result {
@@ -75,15 +248,24 @@ pub async fn get_config() -> Result<Value, String> {
     result
 }
 
+/// Configuration plus the revision it was read at (for optimistic saves)
+/// Validate a configuration against the openclaw.json schema without saving it
+#[command]
+pub async fn validate_config(config: Value) -> Result<Vec<ConfigValidationIssue>, AppError> {
+    let issues = config_validation::validate(&config);
+    info!("[Validate Config] Found {} issue(s)", issues.len());
+    Ok(issues)
+}
+
 /// Save configuration
 #[command]
-pub async fn save_config(config: Value) -> Result<String, String> {
+pub async fn save_config(config: Value) -> Result<String, AppError> {
     info!("[Save Config] Saving openclaw.json configuration...");
     debug!(
         "[Save Config] Configuration content: {}",
         log_sanitizer::sanitize(&serde_json::to_string_pretty(&config).unwrap_or_default())
     );
-    match save_openclaw_config(&config) {
+    match replace_openclaw_config(&config, "save_config").await {
         Ok(_) => {
             info!("[Save Config] Configuration saved successfully");
             Ok("Configuration saved".to_string())
@@ -97,7 +279,7 @@ pub async fn save_config(config: Value) -> Result<String, String> {
 
 /// Get environment variable value
 #[command]
-pub async fn get_env_value(key: String) -> Result<Option<String>, String> {
+pub async fn get_env_value(key: String) -> Result<Option<String>, AppError> {
     info!("[Get Env] Reading environment variable: {}", key);
     let env_path = platform::get_env_file_path();
     let value = file::read_env_value(&env_path, &key);
@@ -114,7 +296,7 @@ pub async fn get_env_value(key: String) -> Result<Option<String>, String> {
 
 /// Save environment variable value
 #[command]
-pub async fn save_env_value(key: String, value: String) -> Result<String, String> {
+pub async fn save_env_value(key: String, value: String) -> Result<String, AppError> {
     info!("[Save Env] Saving environment variable: {}", key);
     let env_path = platform::get_env_file_path();
     debug!("[Save Env] Environment file path: {}", env_path);
@@ -126,37 +308,19 @@ pub async fn save_env_value(key: String, value: String) -> Result<String, String
         }
         Err(e) => {
             error!("[Save Env] Failed to save: {}", e);
-            Err(format!("Failed to save environment variable: {}", e))
+            Err(AppError::new(ErrorCode::ConfigWrite, "Failed to save environment variable").with_detail(e))
         }
     }
 }
 
 // ============ Gateway Token Commands ============
 
-/// Generate random token
-fn generate_token() -> String {
-    use std::time::{SystemTime, UNIX_EPOCH};
-
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-
-    // Generate token using timestamp and random number
-    let random_part: u64 = (timestamp as u64) ^ 0x5DEECE66Du64;
-    format!("{:016x}{:016x}{:016x}",
-        random_part,
-        random_part.wrapping_mul(0x5DEECE66Du64),
-        timestamp as u64
-    )
-}
-
 /// Get or create Gateway Token
 #[command]
-pub async fn get_or_create_gateway_token() -> Result<String, String> {
+pub async fn get_or_create_gateway_token() -> Result<String, AppError> {
     info!("[Gateway Token] Getting or creating Gateway Token...");
 
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
 
     // Check if token already exists
     if let Some(token) = config
@@ -165,12 +329,16 @@ pub async fn get_or_create_gateway_token() -> Result<String, String> {
     {
         if !token.is_empty() {
             info!("[Gateway Token] Using existing Token");
-            return Ok(token.to_string());
+            return shell::resolve_config_string(token).ok_or_else(|| {
+                AppError::new(ErrorCode::ConfigRead, "The gateway token references a secret that cannot be resolved")
+                    .with_detail(token.to_string())
+                    .with_hint("Unlock the secrets vault in Settings, or set the referenced variable in the env file.")
+            });
         }
     }
 
     // Generate new token
-    let new_token = generate_token();
+    let new_token = gateway_token::generate();
     info!("[Gateway Token] Generated new Token");
 
     // Ensure path exists
@@ -187,7 +355,7 @@ pub async fn get_or_create_gateway_token() -> Result<String, String> {
     config["gateway"]["mode"] = json!("local");
 
     // Save configuration
-    save_openclaw_config(&config)?;
+    config.commit("get_or_create_gateway_token")?;
 
     info!("[Gateway Token] Token saved to configuration");
     Ok(new_token)
@@ -195,20 +363,110 @@ pub async fn get_or_create_gateway_token() -> Result<String, String> {
 
 /// Get Dashboard URL (with token)
 #[command]
-pub async fn get_dashboard_url() -> Result<String, String> {
+pub async fn get_dashboard_url() -> Result<String, AppError> {
     info!("[Dashboard URL] Getting Dashboard URL...");
 
     let token = get_or_create_gateway_token().await?;
-    let url = format!("http://localhost:18789?token={}", token);
+    let url = format!("http://localhost:{}?token={}", shell::get_gateway_port(), token);
 
     info!("[Dashboard URL] URL generated");
     Ok(url)
 }
 
+/// Outcome of a gateway token rotation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenRotation {
+    pub rotated_at: String,
+    /// Whether the running gateway was restarted onto the new token
+    pub restarted: bool,
+}
+
+/// Gateway token facts for the Settings page (never the token itself)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenInfo {
+    pub rotated_at: Option<String>,
+    /// The current token is shorter than a generated one and should be rotated
+    pub weak: bool,
+}
+
+/// When the gateway token was last rotated and whether it looks weak
+#[command]
+pub async fn get_gateway_token_state() -> Result<TokenInfo, AppError> {
+    let state = TokenState::from_manager_config(&load_manager_config()?);
+    let weak = shell::read_gateway_token().is_some_and(|token| gateway_token::is_weak(&token));
+    Ok(TokenInfo { rotated_at: state.rotated_at, weak })
+}
+
+/// Replace the gateway token with a fresh one and restart a running gateway, so the old
+/// token, and every dashboard URL carrying it, stops working. A token kept in the vault
+/// or the env file is replaced there and openclaw.json keeps its reference.
+#[command]
+pub async fn rotate_gateway_token(app: AppHandle) -> Result<TokenRotation, AppError> {
+    info!("[Gateway Token] Rotating gateway token...");
+    let new_token = gateway_token::generate();
+    // The config lock is released before the restart below
+    {
+        let mut config = ConfigTransaction::begin().await?;
+        let configured = config
+            .pointer("/gateway/auth/token")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        let resolver = shell::reference_resolver();
+        if resolver.vault_locked && secret_refs::contains_reference(&configured) {
+            return Err(AppError::new(ErrorCode::ConfigWrite, "The secrets vault is locked")
+                .with_hint("Unlock the secrets vault in Settings, then rotate the token again."));
+        }
+        let in_vault = |name: &str| resolver.secrets.as_ref().is_some_and(|s| s.contains_key(name));
+        match gateway_token::storage(&configured, in_vault) {
+            TokenStorage::Vault(name) => {
+                secrets_vault::update(|secrets| {
+                    secrets.insert(name.clone(), new_token.clone());
+                })
+                .map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to store the token in the vault").with_detail(e))?;
+                info!("[Gateway Token] New token stored in the vault as {}", name);
+            }
+            TokenStorage::EnvFile(name) => {
+                file::set_env_value(&platform::get_env_file_path(), &name, &new_token)
+                    .map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to write the env file").with_detail(e))?;
+                info!("[Gateway Token] New token stored in the env file as {}", name);
+            }
+            TokenStorage::Config => {
+                if config.get("gateway").is_none() {
+                    config["gateway"] = json!({});
+                }
+                if config["gateway"].get("auth").is_none() {
+                    config["gateway"]["auth"] = json!({});
+                }
+                config["gateway"]["auth"]["token"] = json!(vault_secret_value(gateway_token::SECRET_NAME, &new_token)?);
+                config["gateway"]["auth"]["mode"] = json!("token");
+                config.commit("rotate_gateway_token")?;
+                info!("[Gateway Token] New token saved to configuration");
+            }
+        }
+    }
+
+    let rotated_at = chrono::Local::now().to_rfc3339();
+    let mut manager = load_manager_config()?;
+    manager[gateway_token::MANAGER_KEY] = json!(TokenState { rotated_at: Some(rotated_at.clone()) });
+    save_manager_config(&manager)?;
+
+    // The gateway only reads its token at startup
+    let restarted = service::is_port_listening(shell::get_gateway_port());
+    if restarted {
+        service::restart_service(app).await.map_err(|e| {
+            e.with_hint("The new token is saved; restart the gateway to stop accepting the old one.")
+        })?;
+    }
+    info!("[Gateway Token] Token rotated{}", if restarted { ", gateway restarted" } else { "" });
+    Ok(TokenRotation { rotated_at, restarted })
+}
+
 /// Repair device token mismatch by deleting stale identity and paired device files.
 /// After calling this, the gateway should be restarted to regenerate fresh device identity.
 #[command]
-pub async fn repair_device_token() -> Result<String, String> {
+pub async fn repair_device_token() -> Result<String, AppError> {
     info!("[Device Token Repair] Starting device token repair...");
 
     let config_dir = platform::get_config_dir();
@@ -284,11 +542,115 @@ pub async fn repair_device_token() -> Result<String, String> {
     }
 }
 
+/// Store `value` in the secrets vault under `name` when the profile has a vault and return
+/// the `${NAME}` reference to write instead; without a vault the value stays in plaintext
+fn vault_secret_value(name: &str, value: &str) -> Result<String, AppError> {
+    if value.is_empty()
+        || secret_refs::contains_reference(value)
+        || !file::file_exists(&platform::get_secrets_vault_file_path())
+    {
+        return Ok(value.to_string());
+    }
+    secrets_vault::update(|secrets| {
+        secrets.insert(name.to_string(), value.to_string());
+    })
+    .map_err(|e| {
+        AppError::new(ErrorCode::ConfigWrite, "Failed to store the secret in the vault")
+            .with_detail(e)
+            .with_hint("Unlock the secrets vault in Settings, then save again.")
+    })?;
+    Ok(secrets_vault::reference(name))
+}
+
+/// Secrets moved out of plaintext config files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretsMigrationReport {
+    /// Secret names now referenced as `${NAME}`
+    pub moved: Vec<String>,
+    /// Files that were rewritten
+    pub files: Vec<String>,
+}
+
+/// Per-agent models.json files of the active profile
+fn agent_models_files() -> Vec<std::path::PathBuf> {
+    let agents_dir = std::path::Path::new(&platform::get_config_dir()).join("agents");
+    let Ok(entries) = std::fs::read_dir(&agents_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .flat_map(|entry| [entry.path().join("agent").join("models.json"), entry.path().join("models.json")])
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Move provider keys, bot tokens, the Brave key and the gateway token from openclaw.json
+/// and the per-agent models.json files into the unlocked secrets vault
+#[command]
+pub async fn migrate_secrets_to_vault() -> Result<SecretsMigrationReport, AppError> {
+    info!("[Secrets] Moving plaintext secrets into the vault...");
+    let mut secrets = secrets_vault::session_secrets()
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to open the secrets vault").with_detail(e))?
+        .ok_or_else(|| {
+            AppError::new(ErrorCode::InvalidInput, "The secrets vault is locked or does not exist")
+                .with_hint("Create or unlock the secrets vault first.")
+        })?;
+
+    let mut config = ConfigTransaction::begin().await?;
+    let mut moved = secrets_vault::move_plaintext_secrets(&mut config, &mut secrets);
+
+    let mut models_files = Vec::new();
+    for path in agent_models_files() {
+        let Ok(content) = file::read_file(&path.to_string_lossy()) else {
+            continue;
+        };
+        let Ok(mut models) = serde_json::from_str::<Value>(content.trim_start_matches('\u{FEFF}')) else {
+            warn!("[Secrets] Skipping unparseable {}", path.display());
+            continue;
+        };
+        let names = secrets_vault::move_plaintext_secrets(&mut models, &mut secrets);
+        if !names.is_empty() {
+            moved.extend(names);
+            models_files.push((path, models));
+        }
+    }
+
+    let mut report = SecretsMigrationReport { moved, files: Vec::new() };
+    if report.moved.is_empty() {
+        info!("[Secrets] No plaintext secrets found");
+        return Ok(report);
+    }
+
+    // Vault first: a config must never reference a secret that was not stored
+    secrets_vault::update(|vault| *vault = secrets.clone())
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to save the secrets vault").with_detail(e))?;
+
+    if *config != config.base {
+        config.commit("migrate_secrets_to_vault")?;
+        report.files.push(platform::get_config_file_path());
+    }
+    for name in &report.moved {
+        if let Some(value) = secrets.get(name) {
+            redact_config_history(value, &secrets_vault::reference(name));
+        }
+    }
+    for (path, models) in models_files {
+        let content = serde_json::to_string_pretty(&models)
+            .map_err(|e| AppError::new(ErrorCode::Internal, "Failed to serialize models.json").with_detail(e))?;
+        file::write_file(&path.to_string_lossy(), &content)
+            .map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to write models.json").with_detail(e))?;
+        report.files.push(path.display().to_string());
+    }
+
+    info!("[Secrets] Moved {} secret(s) out of {} file(s)", report.moved.len(), report.files.len());
+    Ok(report)
+}
+
 // ============ AI Configuration Commands ============
 
 /// Get official Provider list (preset templates)
 #[command]
-pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
+pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, AppError> {
     info!("[Official Provider] Getting official Provider preset list...");
 
     let providers = vec![
@@ -560,7 +922,7 @@ pub async fn get_official_providers() -> Result<Vec<OfficialProvider>, String> {
 
 /// Get AI configuration overview
 #[command]
-pub async fn get_ai_config() -> Result<AIConfigOverview, String> {
+pub async fn get_ai_config() -> Result<AIConfigOverview, AppError> {
     info!("[AI Config] Getting AI configuration overview...");
 
     let config_path = platform::get_config_file_path();
@@ -690,14 +1052,18 @@ pub async fn save_provider(
     api_key: Option<String>,
     api_type: String,
     models: Vec<ModelConfig>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     info!(
         "[Save Provider] Saving Provider: {} ({} models)",
         provider_name,
         models.len()
     );
+    // A literal key, or a ${env:NAME} / ${secret:NAME} reference resolved at gateway start
+    if let Some(key) = &api_key {
+        secret_refs::validate(key).map_err(AppError::invalid_input)?;
+    }
 
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
 
     // Ensure paths exist
     if config.get("models").is_none() {
@@ -765,8 +1131,9 @@ pub async fn save_provider(
     // Handle API Key: if a new non-empty key is provided, use it; otherwise preserve the existing one
     if let Some(key) = api_key {
         if !key.is_empty() {
-            // Use the newly provided API Key
-            provider_config["apiKey"] = json!(key);
+            // Use the newly provided API Key (kept in the secrets vault when there is one)
+            let secret_name = format!("{}_API_KEY", secrets_vault::to_secret_name(&provider_name));
+            provider_config["apiKey"] = json!(vault_secret_value(&secret_name, &key)?);
             info!("[Save Provider] Using new API Key");
         } else {
             // Empty string means no change, try to preserve the existing API Key
@@ -805,7 +1172,7 @@ pub async fn save_provider(
     }
     config["meta"]["lastTouchedAt"] = json!(now);
 
-    save_openclaw_config(&config)?;
+    config.commit("save_provider")?;
     info!("[Save Provider] Provider {} saved successfully", provider_name);
 
     Ok(format!("Provider {} saved", provider_name))
@@ -813,10 +1180,10 @@ pub async fn save_provider(
 
 /// Delete Provider
 #[command]
-pub async fn delete_provider(provider_name: String) -> Result<String, String> {
+pub async fn delete_provider(provider_name: String) -> Result<String, AppError> {
     info!("[Delete Provider] Deleting Provider: {}", provider_name);
 
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
 
     // Delete Provider configuration
     if let Some(providers) = config
@@ -852,7 +1219,7 @@ pub async fn delete_provider(provider_name: String) -> Result<String, String> {
         }
     }
 
-    save_openclaw_config(&config)?;
+    config.commit("delete_provider")?;
     info!("[Delete Provider] Provider {} deleted", provider_name);
 
     Ok(format!("Provider {} deleted", provider_name))
@@ -860,10 +1227,10 @@ pub async fn delete_provider(provider_name: String) -> Result<String, String> {
 
 /// Set primary model
 #[command]
-pub async fn set_primary_model(model_id: String) -> Result<String, String> {
+pub async fn set_primary_model(model_id: String) -> Result<String, AppError> {
     info!("[Set Primary Model] Setting primary model: {}", model_id);
 
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
 
     // Ensure paths exist
     if config.get("agents").is_none() {
@@ -879,7 +1246,7 @@ pub async fn set_primary_model(model_id: String) -> Result<String, String> {
     // Set primary model
     config["agents"]["defaults"]["model"]["primary"] = json!(model_id);
 
-    save_openclaw_config(&config)?;
+    config.commit("set_primary_model")?;
     info!("[Set Primary Model] Primary model set to: {}", model_id);
 
     Ok(format!("Primary model set to {}", model_id))
@@ -887,10 +1254,10 @@ pub async fn set_primary_model(model_id: String) -> Result<String, String> {
 
 /// Add model to available list
 #[command]
-pub async fn add_available_model(model_id: String) -> Result<String, String> {
+pub async fn add_available_model(model_id: String) -> Result<String, AppError> {
     info!("[Add Model] Adding model to available list: {}", model_id);
 
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
 
     // Ensure paths exist
     if config.get("agents").is_none() {
@@ -906,7 +1273,7 @@ pub async fn add_available_model(model_id: String) -> Result<String, String> {
     // Add model
     config["agents"]["defaults"]["models"][&model_id] = json!({});
 
-    save_openclaw_config(&config)?;
+    config.commit("add_available_model")?;
     info!("[Add Model] Model {} added", model_id);
 
     Ok(format!("Model {} added", model_id))
@@ -914,10 +1281,10 @@ pub async fn add_available_model(model_id: String) -> Result<String, String> {
 
 /// Remove model from available list
 #[command]
-pub async fn remove_available_model(model_id: String) -> Result<String, String> {
+pub async fn remove_available_model(model_id: String) -> Result<String, AppError> {
     info!("[Remove Model] Removing model from available list: {}", model_id);
 
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
 
     if let Some(models) = config
         .pointer_mut("/agents/defaults/models")
@@ -926,7 +1293,7 @@ pub async fn remove_available_model(model_id: String) -> Result<String, String>
         models.remove(&model_id);
     }
 
-    save_openclaw_config(&config)?;
+    config.commit("remove_available_model")?;
     info!("[Remove Model] Model {} removed", model_id);
 
     Ok(format!("Model {} removed", model_id))
@@ -935,7 +1302,7 @@ pub async fn remove_available_model(model_id: String) -> Result<String, String>
 // ============ MCP Configuration Commands ============
 
 /// Load MCP config from separate mcps.json file
-fn load_mcp_config_file() -> Result<HashMap<String, MCPConfig>, String> {
+fn load_mcp_config_file() -> Result<HashMap<String, MCPConfig>, AppError> {
     let config_path = platform::get_mcp_config_file_path();
     let path = std::path::Path::new(&config_path);
     
@@ -944,34 +1311,45 @@ fn load_mcp_config_file() -> Result<HashMap<String, MCPConfig>, String> {
     }
     
     let content = std::fs::read_to_string(path)
-        .map_err(|e| format!("Failed to read mcps.json: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::ConfigRead, "Failed to read mcps.json").with_detail(e))?;
     
     let configs: HashMap<String, MCPConfig> = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse mcps.json: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::ConfigParse, "Failed to parse mcps.json").with_detail(e))?;
     
     Ok(configs)
 }
 
-/// Save MCP config to separate mcps.json file AND sync to ~/.mcporter/mcporter.json
-fn save_mcp_config_file(configs: &HashMap<String, MCPConfig>) -> Result<(), String> {
+/// Save MCP config to separate mcps.json file AND sync to ~/.mcporter/mcporter.json.
+/// Returns the servers left out of mcporter.json because a reference could not be resolved.
+fn save_mcp_config_file(configs: &HashMap<String, MCPConfig>) -> Result<Vec<String>, AppError> {
     // 1. Save to Manager's private config (mcps.json)
     let config_path = platform::get_mcp_config_file_path();
     let content = serde_json::to_string_pretty(configs)
-        .map_err(|e| format!("Failed to serialize MCP config: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to serialize MCP config").with_detail(e))?;
     
-    std::fs::write(&config_path, content)
-        .map_err(|e| format!("Failed to write mcps.json: {}", e))?;
+    config_watcher::note_own_write(&config_path, content.as_bytes());
+    file::write_file(&config_path, &content)
+        .map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to write mcps.json").with_detail(e))?;
     
     // 2. Sync enabled servers to system mcporter config (~/.mcporter/mcporter.json)
-    if let Err(e) = sync_to_mcporter(configs) {
-        warn!("Failed to sync to mcporter: {}", e);
-        // Don't fail the whole save operation if sync fails
+    match sync_to_mcporter(configs) {
+        Ok(skipped) => {
+            for server in &skipped {
+                warn!("[MCP Config] Not synced to mcporter: {}", server);
+            }
+            Ok(skipped)
+        }
+        Err(e) => {
+            warn!("Failed to sync to mcporter: {}", e);
+            // Don't fail the whole save operation if sync fails
+            Ok(Vec::new())
+        }
     }
-    
-    Ok(())
 }
 
-fn sync_to_mcporter(configs: &HashMap<String, MCPConfig>) -> Result<(), String> {
+/// mcporter doesn't know `${env:NAME}` / `${secret:NAME}`, so references are written
+/// resolved; a server with a missing value is left out and reported
+fn sync_to_mcporter(configs: &HashMap<String, MCPConfig>) -> Result<Vec<String>, String> {
     let mcporter_path = platform::get_mcporter_config_file_path();
     let path = std::path::Path::new(&mcporter_path);
 
@@ -999,6 +1377,8 @@ fn sync_to_mcporter(configs: &HashMap<String, MCPConfig>) -> Result<(), String>
     }
 
     let mcp_servers_obj = root_val["mcpServers"].as_object_mut().unwrap();
+    let resolver = shell::reference_resolver();
+    let mut skipped = Vec::new();
 
     // Sync: Add/Update enabled servers from Manager
     for (name, config) in configs {
@@ -1011,6 +1391,12 @@ fn sync_to_mcporter(configs: &HashMap<String, MCPConfig>) -> Result<(), String>
             if let Some(obj) = server_val.as_object_mut() {
                 obj.remove("enabled");
             }
+
+            if let Err(unresolved) = resolver.resolve_value(&mut server_val) {
+                skipped.push(format!("{} ({})", name, secret_refs::format_unresolved(&unresolved).replace('\n', "; ")));
+                mcp_servers_obj.remove(name);
+                continue;
+            }
             
             mcp_servers_obj.insert(name.clone(), server_val);
         } else {
@@ -1026,15 +1412,17 @@ fn sync_to_mcporter(configs: &HashMap<String, MCPConfig>) -> Result<(), String>
     let new_content = serde_json::to_string_pretty(&root_val)
         .map_err(|e| format!("Failed to serialize mcporter config: {}", e))?;
     
-    std::fs::write(path, new_content)
+    // Resolved references put secret values in the file
+    config_watcher::note_own_write(&mcporter_path, new_content.as_bytes());
+    file::write_private_file(&mcporter_path, new_content.as_bytes())
         .map_err(|e| format!("Failed to write mcporter.json: {}", e))?;
 
-    Ok(())
+    Ok(skipped)
 }
 
 /// Get MCP configuration
 #[command]
-pub async fn get_mcp_config() -> Result<HashMap<String, MCPConfig>, String> {
+pub async fn get_mcp_config() -> Result<HashMap<String, MCPConfig>, AppError> {
     info!("[MCP Config] Getting MCP configuration...");
     
     let configs = load_mcp_config_file()?;
@@ -1048,12 +1436,15 @@ pub async fn get_mcp_config() -> Result<HashMap<String, MCPConfig>, String> {
 pub async fn save_mcp_config(
     name: String,
     config: Option<MCPConfig>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     info!("[Save MCP] Saving MCP configuration for: {}", name);
     
     let mut configs = load_mcp_config_file()?;
     
     if let Some(mcp) = config {
+        // String fields may hold literals or ${env:NAME} / ${secret:NAME} references
+        let value = serde_json::to_value(&mcp).map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to serialize MCP config").with_detail(e))?;
+        secret_refs::validate_value(&value).map_err(AppError::invalid_input)?;
         configs.insert(name.clone(), mcp);
         info!("[Save MCP] Updated configuration for {}", name);
     } else {
@@ -1061,13 +1452,21 @@ pub async fn save_mcp_config(
         info!("[Save MCP] Deleted configuration for {}", name);
     }
     
-    save_mcp_config_file(&configs)?;
-    Ok(format!("MCP configuration saved for {}", name))
+    let skipped = save_mcp_config_file(&configs)?;
+    if skipped.is_empty() {
+        Ok(format!("MCP configuration saved for {}", name))
+    } else {
+        Ok(format!(
+            "MCP configuration saved for {}; not synced to mcporter: {}",
+            name,
+            skipped.join(", ")
+        ))
+    }
 }
 
 /// Install MCP server from a Git repository URL
 #[command]
-pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
+pub async fn install_mcp_from_git(url: String) -> Result<String, AppError> {
     info!("[MCP Install] Installing MCP from: {}", url);
 
     // Extract repo name from URL (e.g. "excalidraw-mcp" from "https://github.com/excalidraw/excalidraw-mcp")
@@ -1080,7 +1479,7 @@ pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
         .to_string();
 
     if repo_name.is_empty() {
-        return Err("Could not extract repository name from URL".to_string());
+        return Err("Could not extract repository name from URL".into());
     }
 
     info!("[MCP Install] Repository name: {}", repo_name);
@@ -1088,7 +1487,7 @@ pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
     // Create mcps directory if it doesn't exist
     let mcps_dir = platform::get_mcp_install_dir();
     std::fs::create_dir_all(&mcps_dir)
-        .map_err(|e| format!("Failed to create mcps directory: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to create mcps directory").with_detail(e))?;
 
     let install_path = if platform::is_windows() {
         format!("{}\\{}", mcps_dir, repo_name)
@@ -1100,17 +1499,17 @@ pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
     if std::path::Path::new(&install_path).exists() {
         info!("[MCP Install] Removing existing installation at {}", install_path);
         std::fs::remove_dir_all(&install_path)
-            .map_err(|e| format!("Failed to remove existing directory: {}", e))?;
+            .map_err(|e| AppError::new(ErrorCode::Io, "Failed to remove existing directory").with_detail(e))?;
     }
 
     // Step 1: Clone the repository
     info!("[MCP Install] Cloning repository...");
     let clone_output = shell::run_command("git", &["clone", &url, &install_path])
-        .map_err(|e| format!("Failed to run git clone: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::CommandFailed, "Failed to run git clone").with_detail(e))?;
 
     if !clone_output.status.success() {
         let stderr = String::from_utf8_lossy(&clone_output.stderr);
-        return Err(format!("Git clone failed: {}", stderr));
+        return Err(AppError::command_failed("Git clone failed").with_detail(stderr));
     }
     info!("[MCP Install] Clone successful");
 
@@ -1128,11 +1527,11 @@ pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
     }
 
     let install_output = npm_install.output()
-        .map_err(|e| format!("Failed to run npm install: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::CommandFailed, "Failed to run npm install").with_detail(e))?;
 
     if !install_output.status.success() {
         let stderr = String::from_utf8_lossy(&install_output.stderr);
-        return Err(format!("npm install failed: {}", stderr));
+        return Err(AppError::command_failed("npm install failed").with_detail(stderr));
     }
     info!("[MCP Install] npm install successful");
 
@@ -1148,7 +1547,7 @@ pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
     }
 
     let build_output = npm_build.output()
-        .map_err(|e| format!("Failed to run npm run build: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::CommandFailed, "Failed to run npm run build").with_detail(e))?;
 
     if !build_output.status.success() {
         let stderr = String::from_utf8_lossy(&build_output.stderr);
@@ -1190,6 +1589,7 @@ pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
         env: HashMap::new(),
         url: String::new(),
         enabled: true,
+        extra: serde_json::Map::new(),
     });
 
     save_mcp_config_file(&configs)?;
@@ -1199,7 +1599,7 @@ pub async fn install_mcp_from_git(url: String) -> Result<String, String> {
 
 /// Uninstall an MCP server
 #[command]
-pub async fn uninstall_mcp(name: String) -> Result<String, String> {
+pub async fn uninstall_mcp(name: String) -> Result<String, AppError> {
     info!("[MCP Uninstall] Uninstalling MCP: {}", name);
 
     // Remove directory
@@ -1212,7 +1612,7 @@ pub async fn uninstall_mcp(name: String) -> Result<String, String> {
 
     if std::path::Path::new(&install_path).exists() {
         std::fs::remove_dir_all(&install_path)
-            .map_err(|e| format!("Failed to remove MCP directory: {}", e))?;
+            .map_err(|e| AppError::new(ErrorCode::Io, "Failed to remove MCP directory").with_detail(e))?;
         info!("[MCP Uninstall] Removed directory: {}", install_path);
     }
 
@@ -1227,7 +1627,7 @@ pub async fn uninstall_mcp(name: String) -> Result<String, String> {
 
 /// Check if mcporter is installed
 #[command]
-pub async fn check_mcporter_installed() -> Result<bool, String> {
+pub async fn check_mcporter_installed() -> Result<bool, AppError> {
     info!("[mcporter] Checking if mcporter is installed...");
     let installed = shell::command_exists("mcporter");
     info!("[mcporter] Installed: {}", installed);
@@ -1236,7 +1636,7 @@ pub async fn check_mcporter_installed() -> Result<bool, String> {
 
 /// Install mcporter via npm
 #[command]
-pub async fn install_mcporter() -> Result<String, String> {
+pub async fn install_mcporter() -> Result<String, AppError> {
     info!("[mcporter] Installing mcporter globally via npm...");
 
     let npm_cmd = if platform::is_windows() { "npm.cmd" } else { "npm" };
@@ -1251,11 +1651,11 @@ pub async fn install_mcporter() -> Result<String, String> {
     }
 
     let output = cmd.output()
-        .map_err(|e| format!("Failed to run npm install: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::CommandFailed, "Failed to run npm install").with_detail(e))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("npm install -g mcporter failed: {}", stderr));
+        return Err(AppError::command_failed("npm install -g mcporter failed").with_detail(stderr));
     }
 
     info!("[mcporter] Installation successful");
@@ -1264,7 +1664,7 @@ pub async fn install_mcporter() -> Result<String, String> {
 
 /// Uninstall Mcporter
 #[command]
-pub async fn uninstall_mcporter() -> Result<String, String> {
+pub async fn uninstall_mcporter() -> Result<String, AppError> {
     info!("Uninstalling mcporter globally via npm");
 
     #[cfg(target_os = "windows")]
@@ -1280,7 +1680,7 @@ pub async fn uninstall_mcporter() -> Result<String, String> {
     let output = std::process::Command::new(program)
         .args(args)
         .output()
-        .map_err(|e| format!("Failed to execute npm uninstall: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::CommandFailed, "Failed to execute npm uninstall").with_detail(e))?;
 
     if output.status.success() {
         info!("mcporter uninstalled successfully");
@@ -1288,17 +1688,17 @@ pub async fn uninstall_mcporter() -> Result<String, String> {
     } else {
         let error_msg = String::from_utf8_lossy(&output.stderr);
         error!("Failed to uninstall mcporter: {}", error_msg);
-        Err(format!("Failed to uninstall mcporter: {}", error_msg))
+        Err(AppError::command_failed("Failed to uninstall mcporter").with_detail(error_msg))
     }
 }
 
 /// Install MCP server as an OpenClaw plugin (using openclaw plugins install)
 #[command]
-pub async fn install_mcp_plugin(url: String) -> Result<String, String> {
+pub async fn install_mcp_plugin(url: String) -> Result<String, AppError> {
     info!("[MCP Plugin] Installing MCP plugin from: {}", url);
 
     let result = shell::run_openclaw(&["plugins", "install", &url])
-        .map_err(|e| format!("Failed to install plugin: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::CommandFailed, "Failed to install plugin").with_detail(e))?;
 
     info!("[MCP Plugin] Installation result: {}", result);
     Ok(format!("Successfully installed MCP plugin from: {}", url))
@@ -1306,11 +1706,11 @@ pub async fn install_mcp_plugin(url: String) -> Result<String, String> {
 
 /// Set openclaw config via CLI (openclaw config set <key> <value>)
 #[command]
-pub async fn openclaw_config_set(key: String, value: String) -> Result<String, String> {
+pub async fn openclaw_config_set(key: String, value: String) -> Result<String, AppError> {
     info!("[Config CLI] Setting config: {} = {}", key, value);
 
     let result = shell::run_openclaw(&["config", "set", &key, &value])
-        .map_err(|e| format!("Failed to set config: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::CommandFailed, "Failed to set config").with_detail(e))?;
 
     info!("[Config CLI] Set result: {}", result);
     Ok(format!("Set {} = {}", key, value))
@@ -1318,7 +1718,7 @@ pub async fn openclaw_config_set(key: String, value: String) -> Result<String, S
 
 /// Test an MCP server connectivity
 #[command]
-pub async fn test_mcp_server(server_type: String, target: String, command: Option<String>, args: Option<Vec<String>>) -> Result<String, String> {
+pub async fn test_mcp_server(server_type: String, target: String, command: Option<String>, args: Option<Vec<String>>) -> Result<String, AppError> {
     info!("[MCP Test] Testing MCP server: type={}, target={}", server_type, target);
 
     if server_type == "url" {
@@ -1367,10 +1767,10 @@ pub async fn test_mcp_server(server_type: String, target: String, command: Optio
                     }
                     Ok(format!("✅ Server reachable (HTTP {})", status_code))
                 } else {
-                    Err(format!("❌ Server returned HTTP {}", status_code))
+                    Err(AppError::new(ErrorCode::Network, format!("❌ Server returned HTTP {}", status_code)))
                 }
             }
-            Err(e) => Err(format!("Failed to test URL: {}", e))
+            Err(e) => Err(AppError::new(ErrorCode::Network, "Failed to test URL").with_detail(e))
         }
     } else {
         // Local stdio MCP: spawn the command directly with proper args
@@ -1433,7 +1833,7 @@ pub async fn test_mcp_server(server_type: String, target: String, command: Optio
                         if status.success() {
                             Ok("✅ Server process started and exited cleanly".to_string())
                         } else {
-                            Err(format!("❌ Server exited with {}\n{}", status, stderr.trim()))
+                            Err(AppError::command_failed(format!("❌ Server exited with {}", status)).with_detail(stderr.trim()))
                         }
                     }
                     Ok(None) => {
@@ -1443,12 +1843,13 @@ pub async fn test_mcp_server(server_type: String, target: String, command: Optio
                     }
                     Err(e) => {
                         let _ = child.kill();
-                        Err(format!("Failed to check process: {}", e))
+                        Err(AppError::command_failed("Failed to check process").with_detail(e))
                     }
                 }
             }
             Err(e) => {
-                Err(format!("❌ Failed to start server: {}\nCommand: {} {}", e, cmd_name, cmd_args.join(" ")))
+                Err(AppError::command_failed(format!("❌ Failed to start server: {}", e))
+                    .with_detail(format!("Command: {} {}", cmd_name, cmd_args.join(" "))))
             }
         }
     }
@@ -1458,7 +1859,7 @@ pub async fn test_mcp_server(server_type: String, target: String, command: Optio
 
 /// Get all supported AI Providers (legacy compatibility)
 #[command]
-pub async fn get_ai_providers() -> Result<Vec<crate::models::AIProviderOption>, String> {
+pub async fn get_ai_providers() -> Result<Vec<crate::models::AIProviderOption>, AppError> {
     info!("[AI Provider] Getting supported AI Provider list (legacy)...");
 
     let official = get_official_providers().await?;
@@ -1490,7 +1891,7 @@ pub async fn get_ai_providers() -> Result<Vec<crate::models::AIProviderOption>,
 
 /// Get channel configuration - read from openclaw.json and env file
 #[command]
-pub async fn get_channels_config() -> Result<Vec<ChannelConfig>, String> {
+pub async fn get_channels_config() -> Result<Vec<ChannelConfig>, AppError> {
     info!("[Channel Config] Getting channel configuration list...");
 
     let config = load_openclaw_config()?;
@@ -1570,13 +1971,13 @@ pub async fn get_channels_config() -> Result<Vec<ChannelConfig>, String> {
 
 /// Save channel configuration - save to openclaw.json
 #[command]
-pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, String> {
+pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, AppError> {
     info!(
         "[Save Channel Config] Saving channel configuration: {} ({})",
         channel.id, channel.channel_type
     );
 
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
     let env_path = platform::get_env_file_path();
     debug!("[Save Channel Config] Environment file path: {}", env_path);
 
@@ -1650,7 +2051,7 @@ pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, Strin
 
     // Save configuration
     info!("[Save Channel Config] Writing configuration file...");
-    match save_openclaw_config(&config) {
+    match config.commit("save_channel_config") {
         Ok(_) => {
             info!(
                 "[Save Channel Config] {} configuration saved successfully",
@@ -1667,10 +2068,10 @@ pub async fn save_channel_config(channel: ChannelConfig) -> Result<String, Strin
 
 /// Clear channel configuration - delete specified channel configuration from openclaw.json
 #[command]
-pub async fn clear_channel_config(channel_id: String) -> Result<String, String> {
+pub async fn clear_channel_config(channel_id: String) -> Result<String, AppError> {
     info!("[Clear Channel Config] Clearing channel configuration: {}", channel_id);
 
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
     let env_path = platform::get_env_file_path();
 
     // Delete channel from channels object
@@ -1702,7 +2103,7 @@ pub async fn clear_channel_config(channel_id: String) -> Result<String, String>
     }
 
     // Save configuration
-    match save_openclaw_config(&config) {
+    match config.commit("clear_channel_config") {
         Ok(_) => {
             info!("[Clear Channel Config] {} configuration cleared", channel_id);
             Ok(format!("{} configuration cleared", channel_id))
@@ -1736,84 +2137,68 @@ pub struct TelegramAccount {
     pub allow_from: Option<Vec<String>>,
 }
 
+/// Infer exclusive topics from group config.
+/// If a group has requireMention=true and specific topics have requireMention=false, those are exclusive topics.
+fn infer_exclusive_topics(groups: Option<&Value>) -> Option<Vec<String>> {
+    let mut inferred_topics = Vec::new();
+    if let Some(groups_map) = groups.and_then(|g| g.as_object()) {
+        for group_val in groups_map.values() {
+            // Check if group is muted (requireMention=true)
+            if group_val.get("requireMention").and_then(|v| v.as_bool()).unwrap_or(false) {
+                if let Some(topics_map) = group_val.get("topics").and_then(|t| t.as_object()) {
+                    for (tid, tval) in topics_map {
+                        // Check if topic is unmuted (requireMention=false)
+                        if !tval.get("requireMention").and_then(|v| v.as_bool()).unwrap_or(true) {
+                            inferred_topics.push(tid.clone());
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if inferred_topics.is_empty() { None } else { Some(inferred_topics) }
+}
+
+impl TelegramAccount {
+    fn from_config(id: String, acct: &TelegramAccountConfig, infer_topics: bool) -> Self {
+        TelegramAccount {
+            id,
+            bot_token: acct.bot_token.clone().unwrap_or_default(),
+            group_policy: acct.group_policy.clone(),
+            dm_policy: acct.dm_policy.clone(),
+            stream_mode: acct.stream_mode.clone(),
+            exclusive_topics: if infer_topics { infer_exclusive_topics(acct.groups.as_ref()) } else { None },
+            groups: acct.groups.clone(),
+            primary: None, // Set from manager.json by the caller
+            // User IDs may be stored as strings or numbers
+            allow_from: acct.allow_from.as_ref().map(|arr| arr.iter().filter_map(|v| {
+                if let Some(s) = v.as_str() { Some(s.to_string()) }
+                else { v.as_i64().map(|n| n.to_string()) }
+            }).collect()),
+        }
+    }
+}
+
 /// Get all Telegram bot accounts
 #[command]
-pub async fn get_telegram_accounts() -> Result<Vec<TelegramAccount>, String> {
+pub async fn get_telegram_accounts() -> Result<Vec<TelegramAccount>, AppError> {
     info!("[Telegram Accounts] Getting accounts...");
-    let config = load_openclaw_config()?;
+    let config = load_typed_config()?;
 
     let mut accounts = Vec::new();
 
-    // Check for multi-account structure: channels.telegram.accounts
-    if let Some(accts) = config.pointer("/channels/telegram/accounts").and_then(|v| v.as_object()) {
-        for (id, acct_val) in accts {
-            accounts.push(TelegramAccount {
-                id: id.to_lowercase().replace(' ', "-"),
-                bot_token: acct_val.get("botToken").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                group_policy: acct_val.get("groupPolicy").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                dm_policy: acct_val.get("dmPolicy").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                stream_mode: acct_val.get("streamMode").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                exclusive_topics: {
-                    // Re-infer exclusive topics from group config
-                    // Logic: If a group has requireMention=true and specific topics have requireMention=false, those are exclusive topics.
-                    let mut inferred_topics = Vec::new();
-                    if let Some(groups_map) = acct_val.get("groups").and_then(|g| g.as_object()) {
-                        for (_, group_val) in groups_map {
-                             // Check if group is muted (requireMention=true)
-                             if group_val.get("requireMention").and_then(|v| v.as_bool()).unwrap_or(false) {
-                                 if let Some(topics_map) = group_val.get("topics").and_then(|t| t.as_object()) {
-                                     for (tid, tval) in topics_map {
-                                         // Check if topic is unmuted (requireMention=false)
-                                         if !tval.get("requireMention").and_then(|v| v.as_bool()).unwrap_or(true) {
-                                             inferred_topics.push(tid.clone());
-                                         }
-                                     }
-                                 }
-                             }
-                        }
-                    }
-                    if inferred_topics.is_empty() { None } else { Some(inferred_topics) }
-                },
-                groups: acct_val.get("groups").cloned(),
-                primary: None, // Will be set below
-                allow_from: acct_val.get("allowFrom")
-                    .and_then(|v| v.as_array())
-                    .map(|arr| arr.iter().filter_map(|v| {
-                        if let Some(s) = v.as_str() { Some(s.to_string()) }
-                        else if let Some(n) = v.as_i64() { Some(n.to_string()) }
-                        else { None }
-                    }).collect()),
-            });
+    if let Some(telegram) = &config.channels.telegram {
+        // Multi-account structure: channels.telegram.accounts
+        for (id, acct) in telegram.accounts.iter().flatten() {
+            accounts.push(TelegramAccount::from_config(id.to_lowercase().replace(' ', "-"), acct, true));
         }
-    }
 
-    // Fallback: single-bot config (botToken at top level)
-    if accounts.is_empty() {
-        if let Some(token) = config.pointer("/channels/telegram/botToken").and_then(|v| v.as_str()) {
-            if !token.is_empty() {
-                accounts.push(TelegramAccount {
-                    id: "default".to_string(),
-                    bot_token: token.to_string(),
-                    group_policy: config.pointer("/channels/telegram/groupPolicy").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                    dm_policy: config.pointer("/channels/telegram/dmPolicy").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                    stream_mode: config.pointer("/channels/telegram/streamMode").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                    exclusive_topics: None,
-                    groups: config.pointer("/channels/telegram/groups").cloned(),
-                    primary: None,
-                    allow_from: config.pointer("/channels/telegram/allowFrom")
-                        .and_then(|v| v.as_array())
-                        .map(|arr| arr.iter().filter_map(|v| {
-                            if let Some(s) = v.as_str() { Some(s.to_string()) }
-                            else if let Some(n) = v.as_i64() { Some(n.to_string()) }
-                            else { None }
-                        }).collect()),
-                });
-            }
+        // Fallback: single-bot config (botToken at top level)
+        if accounts.is_empty() && telegram.bot.bot_token.as_deref().is_some_and(|t| !t.is_empty()) {
+            accounts.push(TelegramAccount::from_config("default".to_string(), &telegram.bot, false));
         }
     }
 
-
-
     // Load primary bot account from manager.json (safe from Core schema)
     let manager_config = load_manager_config().unwrap_or(json!({}));
     let primary_account_id = manager_config.pointer("/primaryBotAccount").and_then(|v: &Value| v.as_str());
@@ -1834,11 +2219,12 @@ pub async fn get_telegram_accounts() -> Result<Vec<TelegramAccount>, String> {
 
 /// Save a Telegram bot account
 #[command]
-pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, String> {
+pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, AppError> {
     // Normalize account ID to lowercase and replace spaces with dashes
     let account_id = account.id.to_lowercase().replace(' ', "-");
     info!("[Telegram Accounts] Saving account: {}", account_id);
-    let mut config = load_openclaw_config()?;
+    secret_refs::validate(&account.bot_token).map_err(AppError::invalid_input)?;
+    let mut config = ConfigTransaction::begin().await?;
 
     // Ensure channels.telegram exists
     if config.get("channels").is_none() {
@@ -1853,47 +2239,20 @@ pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, S
         config["channels"]["telegram"]["accounts"] = json!({});
     }
 
-    // Migrate single-bot to accounts if this is the first additional account
-    if let Some(top_token) = config["channels"]["telegram"].get("botToken").and_then(|v| v.as_str()).map(|s| s.to_string()) {
-        if !top_token.is_empty() {
-            // Move existing single-bot config to accounts["default"]
-            let mut existing = json!({
-                "botToken": top_token,
-                "groupPolicy": config["channels"]["telegram"].get("groupPolicy").cloned().unwrap_or(json!(null)),
-                "dmPolicy": config["channels"]["telegram"].get("dmPolicy").cloned().unwrap_or(json!(null)),
-                "streamMode": config["channels"]["telegram"].get("streamMode").cloned().unwrap_or(json!(null)),
-                "groups": config["channels"]["telegram"].get("groups").cloned().unwrap_or(json!(null)),
-            });
-
-            // Migrate allowList
-            if let Some(allow_from) = config["channels"]["telegram"].get("allowFrom").cloned() {
-                existing["allowFrom"] = allow_from;
-            }
-             if let Some(group_allow_from) = config["channels"]["telegram"].get("groupAllowFrom").cloned() {
-                existing["groupAllowFrom"] = group_allow_from;
-            }
-
-            config["channels"]["telegram"]["accounts"]["default"] = existing;
-            
-            // Remove top-level single-bot fields
-            if let Some(tg) = config["channels"]["telegram"].as_object_mut() {
-                tg.remove("botToken");
-                tg.remove("groupPolicy");
-                tg.remove("dmPolicy");
-                tg.remove("streamMode");
-                tg.remove("groups");
-                tg.remove("allowFrom");
-                tg.remove("groupAllowFrom");
-            }
-        }
+    // A legacy single-bot config (top-level botToken) would be moved to accounts.default
+    // by config_migrations on load; run it here too in case the file was edited since
+    let mut unused_manager = json!({});
+    for change in config_migrations::migrate_telegram_single_bot(&mut config, &mut unused_manager) {
+        info!("[Telegram Accounts] {}", change);
     }
 
     // If this account is set as primary, unset primary for all others
     // (This is now handled by only storing one ID in `meta`, so no need to iterate and clear others manually)
 
     // Build account object
+    let secret_name = format!("TELEGRAM_{}_BOT_TOKEN", secrets_vault::to_secret_name(&account_id));
     let mut acct_obj = json!({
-        "botToken": account.bot_token,
+        "botToken": vault_secret_value(&secret_name, &account.bot_token)?,
     });
     if let Some(gp) = &account.group_policy {
         acct_obj["groupPolicy"] = json!(gp);
@@ -2166,16 +2525,16 @@ pub async fn save_telegram_account(account: TelegramAccount) -> Result<String, S
         config["plugins"] = json!({ "allow": ["telegram"], "entries": { "telegram": { "enabled": true } } });
     }
 
-    save_openclaw_config(&config)?;
+    config.commit("save_telegram_account")?;
     Ok(format!("Account '{}' saved", account_id))
 }
 
 /// Delete a Telegram bot account
 #[command]
-pub async fn delete_telegram_account(account_id: String) -> Result<String, String> {
+pub async fn delete_telegram_account(account_id: String) -> Result<String, AppError> {
     let account_id = account_id.to_lowercase().replace(' ', "-");
     info!("[Telegram Accounts] Deleting account: {}", account_id);
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
 
     if let Some(accts) = config.pointer_mut("/channels/telegram/accounts").and_then(|v| v.as_object_mut()) {
         accts.remove(&account_id);
@@ -2186,7 +2545,7 @@ pub async fn delete_telegram_account(account_id: String) -> Result<String, Strin
         bindings.retain(|b| b.pointer("/match/accountId").and_then(|v| v.as_str()) != Some(&account_id));
     }
 
-    save_openclaw_config(&config)?;
+    config.commit("delete_telegram_account")?;
     Ok(format!("Account '{}' deleted", account_id))
 }
 
@@ -2202,7 +2561,7 @@ pub struct FeishuPluginStatus {
 
 /// Check if Feishu plugin is installed
 #[command]
-pub async fn check_feishu_plugin() -> Result<FeishuPluginStatus, String> {
+pub async fn check_feishu_plugin() -> Result<FeishuPluginStatus, AppError> {
     info!("[Feishu Plugin] Checking Feishu plugin installation status...");
 
     // Execute openclaw plugins list command
@@ -2258,7 +2617,7 @@ pub async fn check_feishu_plugin() -> Result<FeishuPluginStatus, String> {
 
 /// Install Feishu plugin
 #[command]
-pub async fn install_feishu_plugin() -> Result<String, String> {
+pub async fn install_feishu_plugin() -> Result<String, AppError> {
     info!("[Feishu Plugin] Starting Feishu plugin installation...");
 
     // First check if already installed
@@ -2282,24 +2641,98 @@ pub async fn install_feishu_plugin() -> Result<String, String> {
                 Ok(format!("Feishu plugin installed successfully: {}", verify_status.plugin_name.unwrap_or_default()))
             } else {
                 warn!("[Feishu Plugin] Installation command succeeded but plugin not found");
-                Err("Installation command succeeded but plugin not found, please check openclaw version".to_string())
+                Err(AppError::command_failed("Installation command succeeded but plugin not found")
+                    .with_hint("Check the openclaw version."))
             }
         }
         Err(e) => {
             error!("[Feishu Plugin] Installation failed: {}", e);
-            Err(format!("Failed to install Feishu plugin: {}\n\nPlease run manually: openclaw plugins install @m1heng-clawd/feishu", e))
+            Err(AppError::command_failed("Failed to install Feishu plugin")
+                .with_detail(e)
+                .with_hint("Run it manually: openclaw plugins install @m1heng-clawd/feishu"))
         }
     }
 }
 
 // ============ OpenClaw Home Directory ============
 
-/// Get the OpenClaw home directory path (~/.openclaw)
+/// Get the OpenClaw home directory path (~/.openclaw, or the active profile's directory)
 #[command]
-pub async fn get_openclaw_home_dir() -> Result<String, String> {
+pub async fn get_openclaw_home_dir() -> Result<String, AppError> {
     Ok(platform::get_config_dir())
 }
 
+// ============ Profiles ============
+
+/// Profiles plus what the active one resolves to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfilesOverview {
+    /// Active profile name ("default" for the implicit default profile)
+    pub active: String,
+    /// Config directory in use
+    pub config_dir: String,
+    /// OPENCLAW_HOME, if set (replaces ~/.openclaw for the default profile)
+    pub openclaw_home: Option<String>,
+    pub profiles: Vec<Profile>,
+}
+
+fn profiles_overview() -> ProfilesOverview {
+    let settings = platform::get_profile_settings();
+    ProfilesOverview {
+        active: settings
+            .active_profile()
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| profiles::DEFAULT_PROFILE.to_string()),
+        config_dir: platform::get_config_dir(),
+        openclaw_home: std::env::var(profiles::OPENCLAW_HOME_ENV).ok().filter(|v| !v.is_empty()),
+        profiles: settings.profiles,
+    }
+}
+
+/// List profiles
+#[command]
+pub async fn get_profiles() -> Result<ProfilesOverview, AppError> {
+    Ok(profiles_overview())
+}
+
+/// Add or update a profile
+#[command]
+pub async fn save_profile(profile: Profile) -> Result<ProfilesOverview, AppError> {
+    info!("[Profiles] Saving profile: {}", profile.name);
+    let mut settings = platform::get_profile_settings();
+    settings.upsert(profile)?;
+    platform::set_profile_settings(settings).map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to save profiles").with_detail(e))?;
+    Ok(profiles_overview())
+}
+
+/// Delete a profile (its config directory is left on disk)
+#[command]
+pub async fn delete_profile(name: String) -> Result<ProfilesOverview, AppError> {
+    info!("[Profiles] Deleting profile: {}", name);
+    let mut settings = platform::get_profile_settings();
+    settings.remove(&name)?;
+    platform::set_profile_settings(settings).map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to save profiles").with_detail(e))?;
+    Ok(profiles_overview())
+}
+
+/// Switch the active profile; every path and spawned process follows it from now on
+#[command]
+pub async fn switch_profile(name: String) -> Result<ProfilesOverview, AppError> {
+    info!("[Profiles] Switching to profile: {}", name);
+    let mut settings = platform::get_profile_settings();
+    settings.set_active(&name)?;
+    platform::set_profile_settings(settings).map_err(|e| AppError::new(ErrorCode::ConfigWrite, "Failed to save profiles").with_detail(e))?;
+
+    // The new profile's config may still use a legacy layout
+    if let Err(e) = migrate_config_files() {
+        warn!("[Profiles] Failed to migrate configuration of profile {}: {}", name, e);
+    }
+
+    let overview = profiles_overview();
+    info!("[Profiles] Active config directory: {}", overview.config_dir);
+    Ok(overview)
+}
+
 // ============ Multi-Agent Routing ============
 
 /// Agent configuration for the frontend
@@ -2360,164 +2793,151 @@ pub struct AgentsConfigResponse {
     pub subagent_defaults: SubagentDefaults,
 }
 
-/// Get multi-agent routing configuration
-#[command]
-pub async fn get_agents_config() -> Result<AgentsConfigResponse, String> {
-    info!("[Agents] Getting agents configuration...");
-    let config = load_openclaw_config()?;
-
-    let mut agents = Vec::new();
-    let mut bindings = Vec::new();
-
-    // Read agents.list — supports both array format (correct) and object format (legacy)
-    if let Some(list_arr) = config.pointer("/agents/list").and_then(|v| v.as_array()) {
-        // Correct format: array of { id, workspace, agentDir, model, ... }
-        for agent_val in list_arr {
-            agents.push(AgentInfo {
-                id: agent_val.get("id").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                name: agent_val.get("name").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                workspace: agent_val.get("workspace").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                agent_dir: agent_val.get("agentDir").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                model: agent_val.pointer("/model/primary").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                sandbox: agent_val.get("sandbox").and_then(|v| v.as_bool()),
-                heartbeat: agent_val.pointer("/heartbeat/every").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                default: agent_val.get("default").and_then(|v| v.as_bool()),
-                subagents: agent_val.get("subagents").and_then(|v| {
-                    let allow = v.get("allowAgents").and_then(|a| a.as_array()).map(|arr| {
-                        arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()
-                    });
-                    Some(SubagentConfig { allow_agents: allow })
-                }),
-            });
-        }
-    } else if let Some(list_obj) = config.pointer("/agents/list").and_then(|v| v.as_object()) {
-        // Legacy format: object with id as keys
-        for (id, agent_val) in list_obj {
-            agents.push(AgentInfo {
-                id: id.clone(),
-                name: agent_val.get("name").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                workspace: agent_val.get("workspace").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                agent_dir: agent_val.get("agentDir").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                model: agent_val.pointer("/model/primary").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                sandbox: agent_val.get("sandbox").and_then(|v| v.as_bool()),
-                heartbeat: agent_val.pointer("/heartbeat/every").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                default: agent_val.get("default").and_then(|v| v.as_bool()),
-                subagents: agent_val.get("subagents").and_then(|v| {
-                    let allow = v.get("allowAgents").and_then(|a| a.as_array()).map(|arr| {
-                        arr.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect()
-                    });
-                    Some(SubagentConfig { allow_agents: allow })
-                }),
-            });
+impl From<&AgentEntry> for AgentInfo {
+    fn from(entry: &AgentEntry) -> Self {
+        AgentInfo {
+            id: entry.id.clone(),
+            name: entry.name.clone(),
+            workspace: entry.workspace.clone(),
+            agent_dir: entry.agent_dir.clone(),
+            model: entry.primary_model().map(|s| s.to_string()),
+            sandbox: entry.sandbox.as_ref().and_then(|v| v.as_bool()),
+            heartbeat: entry.heartbeat.as_ref().and_then(|h| h.every.clone()),
+            default: entry.default,
+            subagents: entry.subagents.as_ref().map(|s| SubagentConfig {
+                allow_agents: s.allow_agents.clone(),
+            }),
         }
     }
+}
 
-    // Read bindings — check top-level first (correct), then agents.bindings (legacy)
-    let bindings_arr = config.get("bindings").and_then(|v| v.as_array())
-        .or_else(|| config.pointer("/agents/bindings").and_then(|v| v.as_array()));
-    
-    if let Some(bindings_arr) = bindings_arr {
-        for binding_val in bindings_arr {
-            let empty_match = json!({});
-            let match_obj = binding_val.get("match").unwrap_or(&empty_match);
-            
-            bindings.push(AgentBinding {
-                agent_id: binding_val.get("agentId").and_then(|v| v.as_str()).unwrap_or("").to_string(),
-                match_rule: MatchRule {
-                    channel: match_obj.get("channel").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                    account_id: match_obj.get("accountId").and_then(|v| v.as_str()).map(|s| s.to_string()),
-                    peer: match_obj.get("peer").cloned(),
-                }
-            });
+impl From<&BindingConfig> for AgentBinding {
+    fn from(binding: &BindingConfig) -> Self {
+        AgentBinding {
+            agent_id: binding.agent_id.clone(),
+            match_rule: MatchRule {
+                channel: binding.match_rule.channel.clone(),
+                account_id: binding.match_rule.account_id.clone(),
+                peer: binding.match_rule.peer.clone(),
+            },
         }
     }
+}
+
+/// Get multi-agent routing configuration
+#[command]
+pub async fn get_agents_config() -> Result<AgentsConfigResponse, AppError> {
+    info!("[Agents] Getting agents configuration...");
+    let config = load_typed_config()?;
+
+    // agents.list in the legacy object format is read as a list by the model
+    let agents: Vec<AgentInfo> = config.agents.list.iter().flatten().map(AgentInfo::from).collect();
+
+    // Top-level bindings (correct), falling back to agents.bindings (legacy)
+    let bindings: Vec<AgentBinding> = config.all_bindings().iter().map(AgentBinding::from).collect();
 
     // Read global subagent defaults from agents.defaults.subagents
-    let subagent_defaults = if let Some(sub_val) = config.pointer("/agents/defaults/subagents") {
-        SubagentDefaults {
-            max_spawn_depth: sub_val.get("maxSpawnDepth").and_then(|v| v.as_u64()).map(|v| v as u32),
-            max_children_per_agent: sub_val.get("maxChildrenPerAgent").and_then(|v| v.as_u64()).map(|v| v as u32),
-            max_concurrent: sub_val.get("maxConcurrent").and_then(|v| v.as_u64()).map(|v| v as u32),
-        }
-    } else {
-        SubagentDefaults::default()
-    };
+    let subagent_defaults = config.agents.defaults.subagents.as_ref()
+        .map(|sub| SubagentDefaults {
+            max_spawn_depth: sub.max_spawn_depth,
+            max_children_per_agent: sub.max_children_per_agent,
+            max_concurrent: sub.max_concurrent,
+        })
+        .unwrap_or_default();
 
     info!("[Agents] Found {} agents, {} bindings", agents.len(), bindings.len());
     Ok(AgentsConfigResponse { agents, bindings, subagent_defaults })
 }
 
-/// Save (add/update) an agent
-#[command]
-pub async fn save_agent(agent: AgentInfo) -> Result<String, String> {
-    info!("[Agents] Saving agent: {}", agent.id);
-    let mut config = load_openclaw_config()?;
+/// Create the agent and workspace directories (plus default SOUL.md / models.json) for an agent entry.
+/// Returns the agent directory and workspace paths actually used.
+fn ensure_agent_directories(agent_entry: &AgentEntry) -> (String, String) {
+    let openclaw_home = platform::get_config_dir();
 
-    // Ensure agents object exists
-    if config.get("agents").is_none() {
-        config["agents"] = json!({});
-    }
+    // 1. Agent Config Directory
+    // Use configured 'agentDir' or default to ~/.openclaw/agents/<id>/agent
+    // The CLI standard is to have the agent files inside an `agent` subdirectory
+    let agent_dir_path = if let Some(dir) = &agent_entry.agent_dir {
+         std::path::PathBuf::from(dir)
+    } else {
+         std::path::Path::new(&openclaw_home).join("agents").join(&agent_entry.id).join("agent")
+    };
 
-    // Build agent object (array element format with "id" field)
-    let mut agent_obj = json!({ "id": agent.id });
-    if let Some(name) = &agent.name {
-        if !name.is_empty() {
-            agent_obj["name"] = json!(name);
-        }
-    }
-    if let Some(workspace) = &agent.workspace {
-        if !workspace.is_empty() {
-            agent_obj["workspace"] = json!(workspace);
-        }
-    }
-    if let Some(agent_dir) = &agent.agent_dir {
-        if !agent_dir.is_empty() {
-            agent_obj["agentDir"] = json!(agent_dir);
-        }
-    }
-    if let Some(model) = &agent.model {
-        if !model.is_empty() {
-            agent_obj["model"] = json!({ "primary": model });
-        }
-    }
-    if let Some(sandbox) = agent.sandbox {
-        agent_obj["sandbox"] = json!(sandbox);
-    }
-    if let Some(heartbeat) = &agent.heartbeat {
-        if !heartbeat.is_empty() {
-            agent_obj["heartbeat"] = json!({ "every": heartbeat });
-        }
-    }
-    if let Some(is_default) = agent.default {
-        if is_default {
-            agent_obj["default"] = json!(true);
-        }
-    }
-    if let Some(sub) = &agent.subagents {
-        if let Some(allow) = &sub.allow_agents {
-            if !allow.is_empty() {
-                agent_obj["subagents"] = json!({ "allowAgents": allow });
+    if !agent_dir_path.exists() {
+         info!("[Agents] Creating agent directory: {:?}", agent_dir_path);
+         let _ = std::fs::create_dir_all(&agent_dir_path);
+    }
+
+    // SOUL.md
+    let soul_path = agent_dir_path.join("SOUL.md");
+    if !soul_path.exists() {
+         info!("[Agents] SOUL.md missing, creating default");
+         let name = agent_entry.name.as_deref().unwrap_or("agent");
+         let default_soul = format!("You are {}, a helpful AI assistant.", name);
+         let _ = file::write_file(&soul_path.to_string_lossy(), &default_soul);
+    }
+
+    // models.json
+    let models_path = agent_dir_path.join("models.json");
+    if !models_path.exists() {
+         info!("[Agents] models.json missing, creating default");
+         let default_models = json!({
+            "providers": {
+                "glm": {
+                    "baseUrl": "https://open.bigmodel.cn/api/paas/v4",
+                    "apiKey": "",
+                    "models": [ 
+                        {
+                            "id": "glm-4",
+                            "name": "GLM-4",
+                            "api": "openai-completions",
+                            "reasoning": false,
+                            "input": ["text", "image"],
+                            "contextWindow": 128000,
+                            "maxTokens": 8192
+                        }
+                    ]
+                }
             }
-        }
+         });
+         // Pretty print the JSON
+         if let Ok(content) = serde_json::to_string_pretty(&default_models) {
+             let _ = file::write_file(&models_path.to_string_lossy(), &content);
+         }
     }
 
-    // Migrate legacy object format to array if needed
-    let mut list = if let Some(arr) = config["agents"].get("list").and_then(|v| v.as_array()) {
-        arr.clone()
-    } else if let Some(obj) = config["agents"].get("list").and_then(|v| v.as_object()) {
-        // Convert legacy object to array
-        obj.iter().map(|(id, val)| {
-            let mut entry = val.clone();
-            entry["id"] = json!(id);
-            entry
-        }).collect()
+    // 2. Workspace Directory
+    // Use configured 'workspace' or default to ~/.openclaw/workspace-<id>
+    let workspace_path = if let Some(ws) = &agent_entry.workspace {
+         std::path::PathBuf::from(ws)
     } else {
-        Vec::new()
+         std::path::Path::new(&openclaw_home).join(format!("workspace-{}", agent_entry.id))
     };
 
+    if !workspace_path.exists() {
+         info!("[Agents] Creating workspace directory: {:?}", workspace_path);
+         let _ = std::fs::create_dir_all(&workspace_path);
+    }
+
+    // Return paths to update config if they were defaults
+    (agent_dir_path.to_string_lossy().to_string(), workspace_path.to_string_lossy().to_string())
+}
+
+/// Save (add/update) an agent
+#[command]
+pub async fn save_agent(agent: AgentInfo) -> Result<String, AppError> {
+    info!("[Agents] Saving agent: {}", agent.id);
+    let mut config = ConfigTransaction::begin().await?;
+    let mut typed = config.typed()?;
+
+    let non_empty = |v: &Option<String>| v.clone().filter(|s| !s.is_empty());
+    let allow_agents = agent.subagents.as_ref()
+        .and_then(|s| s.allow_agents.clone())
+        .filter(|a| !a.is_empty());
+
     // For NEW agents: use `openclaw agents add <id> --workspace <dir>` to create proper directory structure
     // The --workspace flag is required to make the CLI non-interactive
-    let is_new_agent = !list.iter().any(|a| a.get("id").and_then(|v| v.as_str()) == Some(&agent.id));
+    let is_new_agent = typed.agents.find_agent(&agent.id).is_none();
     let mut cli_error: Option<String> = None;
     let is_reserved_name = agent.id.eq_ignore_ascii_case("main"); // Check if name is "main" to bypass CLI
     
@@ -2532,6 +2952,9 @@ pub async fn save_agent(agent: AgentInfo) -> Result<String, String> {
                 std::path::Path::new(&openclaw_home).join(format!("workspace-{}", agent.id)).to_string_lossy().to_string()
             };
             
+            // Release the config lock while the CLI writes openclaw.json (run_openclaw takes it too)
+            drop(config);
+
             info!("[Agents] New agent '{}' — running `openclaw agents add --workspace {}`", agent.id, workspace_dir);
             match shell::run_openclaw(&["agents", "add", &agent.id, "--workspace", &workspace_dir]) {
                 Ok(output) => {
@@ -2541,158 +2964,59 @@ pub async fn save_agent(agent: AgentInfo) -> Result<String, String> {
                     // NOTE: The CLI may exit with code 1 due to TUI stdin issues in non-interactive mode,
                     // but it still writes the agent entry to openclaw.json successfully.
                     warn!("[Agents] openclaw agents add exited with error (may still have written config): {}", e);
-                    cli_error = Some(e);
+                    cli_error = Some(e.to_string());
                 }
             }
             
             // CRITICAL: Always reload config after CLI runs — it may have written the entry
-            config = load_openclaw_config()?;
-            list = if let Some(arr) = config["agents"].get("list").and_then(|v| v.as_array()) {
-                arr.clone()
-            } else if let Some(obj) = config["agents"].get("list").and_then(|v| v.as_object()) {
-                obj.iter().map(|(id, val)| {
-                    let mut entry = val.clone();
-                    entry["id"] = json!(id);
-                    entry
-                }).collect()
-            } else {
-                Vec::new()
-            };
+            config = ConfigTransaction::begin().await?;
+            typed = config.typed()?;
         } else {
              info!("[Agents] Skipping CLI for reserved name '{}', will create manually.", agent.id);
         }
     }
 
-    // Find agent in list (handle case-insensitive match if CLI normalized the ID, e.g. AgentTest -> agenttest)
-    let match_index = list.iter().position(|a| {
-        a.get("id").and_then(|v| v.as_str()) == Some(&agent.id)
-    }).or_else(|| {
-        list.iter().position(|a| {
-             a.get("id").and_then(|v| v.as_str()).map(|s| s.to_lowercase()) == Some(agent.id.to_lowercase())
-        })
-    });
+    // Writing the list back always uses the array format, which migrates the legacy object format
+    let list = typed.agents.list.get_or_insert_with(Vec::new);
 
-    // Helper closure to create agent directories
-    let ensure_directories = |agent_entry: &serde_json::Value| {
-        let openclaw_home = platform::get_config_dir();
-        
-        // 1. Agent Config Directory
-        // Use configured 'agentDir' or default to ~/.openclaw/agents/<id>/agent
-        // The CLI standard is to have the agent files inside an `agent` subdirectory
-        let agent_dir_path = if let Some(dir) = agent_entry.get("agentDir").and_then(|v| v.as_str()) {
-             std::path::PathBuf::from(dir)
-        } else {
-             let id = agent_entry.get("id").and_then(|v| v.as_str()).unwrap_or("unknown");
-             std::path::Path::new(&openclaw_home).join("agents").join(id).join("agent")
-        };
-        
-        if !agent_dir_path.exists() {
-             info!("[Agents] Creating agent directory: {:?}", agent_dir_path);
-             let _ = std::fs::create_dir_all(&agent_dir_path);
-        }
-        
-        // SOUL.md
-        let soul_path = agent_dir_path.join("SOUL.md");
-        if !soul_path.exists() {
-             info!("[Agents] SOUL.md missing, creating default");
-             let name = agent_entry.get("name").and_then(|v| v.as_str()).unwrap_or("agent");
-             let default_soul = format!("You are {}, a helpful AI assistant.", name);
-             let _ = std::fs::write(soul_path, default_soul);
-        }
-
-        // models.json
-        let models_path = agent_dir_path.join("models.json");
-        if !models_path.exists() {
-             info!("[Agents] models.json missing, creating default");
-             let default_models = json!({
-                "providers": {
-                    "glm": {
-                        "baseUrl": "https://open.bigmodel.cn/api/paas/v4",
-                        "apiKey": "",
-                        "models": [ 
-                            {
-                                "id": "glm-4",
-                                "name": "GLM-4",
-                                "api": "openai-completions",
-                                "reasoning": false,
-                                "input": ["text", "image"],
-                                "contextWindow": 128000,
-                                "maxTokens": 8192
-                            }
-                        ]
-                    }
-                }
-             });
-             // Pretty print the JSON
-             if let Ok(content) = serde_json::to_string_pretty(&default_models) {
-                 let _ = std::fs::write(models_path, content);
-             }
-        }
-        
-        // 2. Workspace Directory
-        // Use configured 'workspace' or default to ~/.openclaw/workspace-<id>
-        let workspace_path = if let Some(ws) = agent_entry.get("workspace").and_then(|v| v.as_str()) {
-             std::path::PathBuf::from(ws)
-        } else {
-             let id = agent_entry.get("id").and_then(|v| v.as_str()).unwrap_or("unknown");
-             std::path::Path::new(&openclaw_home).join(format!("workspace-{}", id))
-        };
-        
-        if !workspace_path.exists() {
-             info!("[Agents] Creating workspace directory: {:?}", workspace_path);
-             let _ = std::fs::create_dir_all(&workspace_path);
-        }
-        
-        // Return paths to update config if they were defaults
-        (agent_dir_path.to_string_lossy().to_string(), workspace_path.to_string_lossy().to_string())
-    };
+    // Find agent in list (handle case-insensitive match if CLI normalized the ID, e.g. AgentTest -> agenttest)
+    let match_index = list.iter().position(|a| a.id == agent.id)
+        .or_else(|| list.iter().position(|a| a.id.to_lowercase() == agent.id.to_lowercase()));
 
     // Update or add the agent
     if let Some(idx) = match_index {
         let existing = &mut list[idx];
         
         // Merge: only overwrite fields the user explicitly set (non-empty)
-        if let Some(name) = &agent.name {
-            if !name.is_empty() {
-                existing["name"] = json!(name);
-            }
+        if let Some(name) = non_empty(&agent.name) {
+            existing.name = Some(name);
         }
-        if let Some(model) = &agent.model {
-            if !model.is_empty() {
-                existing["model"] = json!({ "primary": model });
-            }
+        if let Some(model) = non_empty(&agent.model) {
+            existing.model.get_or_insert_with(AgentModelConfig::default).primary = Some(model);
         }
-        if let Some(is_default) = agent.default {
-            if is_default {
-                existing["default"] = json!(true);
-            }
+        if agent.default == Some(true) {
+            existing.default = Some(true);
         }
         
         // Enforce "Main" agent properties
         if agent.id.eq_ignore_ascii_case("main") {
             // "Main" should always be default unless user explicitly sets another default (which handles itself)
             // But to ensure fallback behavior, we mark it.
-            existing["default"] = json!(true);
+            existing.default = Some(true);
         }
 
-        if let Some(sub) = &agent.subagents {
-            if let Some(allow) = &sub.allow_agents {
-                if !allow.is_empty() {
-                    existing["subagents"] = json!({ "allowAgents": allow });
-                }
-            }
+        if let Some(allow) = allow_agents {
+            existing.subagents.get_or_insert_with(AgentSubagents::default).allow_agents = Some(allow);
         }
         if let Some(sandbox) = agent.sandbox {
-            existing["sandbox"] = json!(sandbox);
+            existing.sandbox = Some(json!(sandbox));
         }
-        if let Some(heartbeat) = &agent.heartbeat {
-            if !heartbeat.is_empty() {
-                existing["heartbeat"] = json!({ "every": heartbeat });
-            }
+        if let Some(every) = non_empty(&agent.heartbeat) {
+            existing.heartbeat.get_or_insert_with(HeartbeatSettings::default).every = Some(every);
         }
         
         // Repair directories for existing agent
-        let _ = ensure_directories(existing);
+        let _ = ensure_agent_directories(existing);
         
     } else {
         // Not found in config (New agent, manual addition)
@@ -2700,53 +3024,53 @@ pub async fn save_agent(agent: AgentInfo) -> Result<String, String> {
         // If we tried to create it via CLI and it's missing (and NOT reserved), that means CLI strictly failed.
         if let Some(err) = cli_error {
              if !is_reserved_name {
-                 return Err(format!("Failed to create agent via CLI: {}. Check logs or name uniqueness.", err));
+                 return Err(AppError::command_failed("Failed to create agent via CLI")
+                     .with_detail(err)
+                     .with_hint("Check the logs and that the agent name is unique."));
              }
         }
 
-        // Add to list
-        let mut new_entry = agent_obj.clone();
+        let mut new_entry = AgentEntry {
+            id: agent.id.clone(),
+            name: non_empty(&agent.name),
+            workspace: non_empty(&agent.workspace),
+            agent_dir: non_empty(&agent.agent_dir),
+            model: non_empty(&agent.model).map(|primary| AgentModelConfig { primary: Some(primary), ..Default::default() }),
+            sandbox: agent.sandbox.map(|s| json!(s)),
+            heartbeat: non_empty(&agent.heartbeat).map(|every| HeartbeatSettings { every: Some(every), ..Default::default() }),
+            default: if agent.default == Some(true) { Some(true) } else { None },
+            subagents: allow_agents.map(|allow| AgentSubagents { allow_agents: Some(allow), ..Default::default() }),
+            ..Default::default()
+        };
         
         // Ensure directories and get default paths if we need to explicitly save them
-        let (actual_agent_dir, actual_workspace) = ensure_directories(&new_entry);
+        let (actual_agent_dir, actual_workspace) = ensure_agent_directories(&new_entry);
         
         // If user didn't specify paths, save the defaults we just used/created
-        if new_entry.get("agentDir").is_none() {
-             new_entry["agentDir"] = json!(actual_agent_dir);
-        }
-        if new_entry.get("workspace").is_none() {
-             new_entry["workspace"] = json!(actual_workspace);
-        }
+        new_entry.agent_dir.get_or_insert(actual_agent_dir);
+        new_entry.workspace.get_or_insert(actual_workspace);
         
         list.push(new_entry);
     }
 
-    config["agents"]["list"] = json!(list);
-
     // Auto-create binding if a Telegram bot account is available and this agent has no binding yet
     let agent_id = agent.id.clone();
-    let available_accounts: Vec<String> = config.pointer("/channels/telegram/accounts")
-        .and_then(|v| v.as_object())
+    let available_accounts: Vec<String> = typed.channels.telegram.as_ref()
+        .and_then(|t| t.accounts.as_ref())
         .map(|accts| accts.keys().cloned().collect())
         .unwrap_or_default();
 
     if !available_accounts.is_empty() {
+        let bindings = typed.bindings.get_or_insert_with(Vec::new);
+
         // Check if this agent already has ANY binding
-        let has_existing_binding = config.get("bindings")
-            .and_then(|v| v.as_array())
-            .map(|bindings| bindings.iter().any(|b| {
-                b.get("agentId").and_then(|v| v.as_str()) == Some(&agent_id)
-            }))
-            .unwrap_or(false);
+        let has_existing_binding = bindings.iter().any(|b| b.agent_id == agent_id);
 
         if !has_existing_binding {
             // Find accounts already bound to other agents
-            let bound_accounts: Vec<String> = config.get("bindings")
-                .and_then(|v| v.as_array())
-                .map(|bindings| bindings.iter().filter_map(|b| {
-                    b.get("match").and_then(|m| m.get("accountId")).and_then(|v| v.as_str()).map(|s| s.to_string())
-                }).collect())
-                .unwrap_or_default();
+            let bound_accounts: Vec<String> = bindings.iter()
+                .filter_map(|b| b.match_rule.account_id.clone())
+                .collect();
 
             // Prefer: exact match > substring match > first unbound account > first account
             let best_account = available_accounts.iter()
@@ -2758,80 +3082,64 @@ pub async fn save_agent(agent: AgentInfo) -> Result<String, String> {
 
             if let Some(account_id) = best_account {
                 info!("[Agents] Auto-creating binding for agent '{}' → account '{}'", agent_id, account_id);
-                if config.get("bindings").is_none() {
-                    config["bindings"] = json!([]);
-                }
-                if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-                    bindings.push(json!({
-                        "agentId": agent_id,
-                        "match": { "channel": "telegram", "accountId": account_id }
-                    }));
-                }
+                bindings.push(BindingConfig {
+                    agent_id: agent_id.clone(),
+                    match_rule: BindingMatch {
+                        channel: Some("telegram".to_string()),
+                        account_id: Some(account_id),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                });
             }
         }
     }
 
-    save_openclaw_config(&config)?;
+    config.set_typed(&typed)?;
+    config.commit("save_agent")?;
     Ok(format!("Agent '{}' saved", agent.id))
 }
 
 /// Save global subagent defaults
 #[command]
-pub async fn save_subagent_defaults(defaults: SubagentDefaults) -> Result<String, String> {
+pub async fn save_subagent_defaults(defaults: SubagentDefaults) -> Result<String, AppError> {
     info!("[Agents] Saving subagent defaults");
-    let mut config = load_openclaw_config()?;
-
-    // Ensure agents.defaults exists
-    if config.get("agents").is_none() {
-        config["agents"] = json!({});
-    }
-    if config["agents"].get("defaults").is_none() {
-        config["agents"]["defaults"] = json!({});
-    }
-
-    let mut sub_obj = json!({});
-    if let Some(depth) = defaults.max_spawn_depth {
-        sub_obj["maxSpawnDepth"] = json!(depth);
-    }
-    if let Some(children) = defaults.max_children_per_agent {
-        sub_obj["maxChildrenPerAgent"] = json!(children);
-    }
-    if let Some(concurrent) = defaults.max_concurrent {
-        sub_obj["maxConcurrent"] = json!(concurrent);
-    }
+    let mut config = ConfigTransaction::begin().await?;
+    let mut typed = config.typed()?;
 
-    config["agents"]["defaults"]["subagents"] = sub_obj;
+    let sub = typed.agents.defaults.subagents.get_or_insert_with(SubagentSettings::default);
+    sub.max_spawn_depth = defaults.max_spawn_depth;
+    sub.max_children_per_agent = defaults.max_children_per_agent;
+    sub.max_concurrent = defaults.max_concurrent;
 
-    save_openclaw_config(&config)?;
+    config.set_typed(&typed)?;
+    config.commit("save_subagent_defaults")?;
     Ok("Subagent defaults saved".to_string())
 }
 
 /// Delete an agent
 #[command]
-pub async fn delete_agent(agent_id: String) -> Result<String, String> {
+pub async fn delete_agent(agent_id: String) -> Result<String, AppError> {
     info!("[Agents] Deleting agent: {}", agent_id);
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
+    let mut typed = config.typed()?;
 
     // 1. Find the agent to get its paths (before deleting from config)
     let mut agent_dir_to_delete: Option<String> = None;
     let mut workspace_to_delete: Option<String> = None;
 
-    if let Some(list) = config.pointer("/agents/list").and_then(|v| v.as_array()) {
-        if let Some(agent) = list.iter().find(|a| a.get("id").and_then(|v| v.as_str()) == Some(&agent_id)) {
-            // Get agent directory
-            if let Some(dir) = agent.get("agentDir").and_then(|v| v.as_str()) {
-                agent_dir_to_delete = Some(dir.to_string());
-            }
-            // Get workspace directory
-            if let Some(ws) = agent.get("workspace").and_then(|v| v.as_str()) {
-                workspace_to_delete = Some(ws.to_string());
-            } else {
-                // Fallback: deduce workspace path if default pattern was used
-                let openclaw_home = platform::get_config_dir();
-                let default_ws = std::path::Path::new(&openclaw_home).join(format!("workspace-{}", agent_id));
-                if default_ws.exists() {
-                    workspace_to_delete = Some(default_ws.to_string_lossy().to_string());
-                }
+    if let Some(agent) = typed.agents.find_agent(&agent_id) {
+        // Get agent directory
+        agent_dir_to_delete = agent.agent_dir.clone();
+        // Get workspace directory
+        if let Some(ws) = &agent.workspace {
+            workspace_to_delete = Some(ws.clone());
+        } else {
+            // Fallback: deduce workspace path if default pattern was used
+            let openclaw_home = platform::get_config_dir();
+            let default_ws = std::path::Path::new(&openclaw_home).join(format!("workspace-{}", agent_id));
+            if default_ws.exists() {
+                workspace_to_delete = Some(default_ws.to_string_lossy().to_string());
             }
         }
     }
@@ -2880,109 +3188,85 @@ pub async fn delete_agent(agent_id: String) -> Result<String, String> {
         }
     }
 
-    // 3. Remove from agents.list (array format)
-    if let Some(list) = config.pointer_mut("/agents/list").and_then(|v| v.as_array_mut()) {
-        list.retain(|a| a.get("id").and_then(|v| v.as_str()) != Some(&agent_id));
+    // 3. Remove from agents.list
+    if let Some(list) = typed.agents.list.as_mut() {
+        list.retain(|a| a.id != agent_id);
     }
 
     // Remove related bindings (top-level)
-    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-        bindings.retain(|b| b.get("agentId").and_then(|v| v.as_str()) != Some(&agent_id));
+    if let Some(bindings) = typed.bindings.as_mut() {
+        bindings.retain(|b| b.agent_id != agent_id);
     }
     // Also clean legacy agents.bindings
-    if let Some(bindings) = config.pointer_mut("/agents/bindings").and_then(|v| v.as_array_mut()) {
-        bindings.retain(|b| b.get("agentId").and_then(|v| v.as_str()) != Some(&agent_id));
+    if let Some(bindings) = typed.agents.bindings.as_mut() {
+        bindings.retain(|b| b.agent_id != agent_id);
     }
 
-    save_openclaw_config(&config)?;
+    config.set_typed(&typed)?;
+    config.commit("delete_agent")?;
     Ok(format!("Agent '{}' and its files were deleted", agent_id))
 }
 
 /// Save an agent binding rule
 #[command]
-
-pub async fn save_agent_binding(binding: AgentBinding) -> Result<String, String> {
+pub async fn save_agent_binding(binding: AgentBinding) -> Result<String, AppError> {
     info!("[Agents] Saving binding for agent: {}", binding.agent_id);
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
+    let mut typed = config.typed()?;
 
-    // Ensure top-level bindings array exists
-    if config.get("bindings").is_none() {
-        config["bindings"] = json!([]);
-    }
+    let bindings = typed.bindings.get_or_insert_with(Vec::new);
 
-    // Migrate legacy agents.bindings to top-level if present
-    if let Some(legacy) = config.pointer("/agents/bindings").and_then(|v| v.as_array()).map(|a| a.clone()) {
-        if let Some(top) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-            for b in legacy {
-                top.push(b);
-            }
-        }
-        // Remove legacy location
-        if let Some(agents) = config.get_mut("agents").and_then(|v| v.as_object_mut()) {
-            agents.remove("bindings");
-        }
+    // Migrate legacy agents.bindings to top-level if present (the file may have been edited since startup)
+    if let Some(legacy) = typed.agents.bindings.take() {
+        bindings.extend(legacy);
     }
 
-    let mut match_obj = json!({});
-    if let Some(ch) = &binding.match_rule.channel {
-        if !ch.is_empty() { match_obj["channel"] = json!(ch); }
-    }
-    if let Some(acc) = &binding.match_rule.account_id {
-        if !acc.is_empty() { match_obj["accountId"] = json!(acc); }
-    }
-    if let Some(peer) = &binding.match_rule.peer {
-        match_obj["peer"] = peer.clone();
-    }
-
-    let binding_obj = json!({
-        "agentId": binding.agent_id,
-        "match": match_obj
+    let non_empty = |v: &Option<String>| v.clone().filter(|s| !s.is_empty());
+    bindings.push(BindingConfig {
+        agent_id: binding.agent_id.clone(),
+        match_rule: BindingMatch {
+            channel: non_empty(&binding.match_rule.channel),
+            account_id: non_empty(&binding.match_rule.account_id),
+            peer: binding.match_rule.peer.clone(),
+            ..Default::default()
+        },
+        ..Default::default()
     });
 
-    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-        bindings.push(binding_obj);
-    }
-
-    save_openclaw_config(&config)?;
+    config.set_typed(&typed)?;
+    config.commit("save_agent_binding")?;
     Ok(format!("Binding for agent '{}' saved", binding.agent_id))
 }
 
 /// Delete an agent binding by index
 #[command]
-pub async fn delete_agent_binding(index: usize) -> Result<String, String> {
+pub async fn delete_agent_binding(index: usize) -> Result<String, AppError> {
     info!("[Agents] Deleting binding at index: {}", index);
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
+    let mut typed = config.typed()?;
+
+    // Top-level bindings first (correct location), then legacy agents.bindings
+    let bindings = match (typed.bindings.as_mut(), typed.agents.bindings.as_mut()) {
+        (Some(bindings), _) => bindings,
+        (None, Some(legacy)) => legacy,
+        (None, None) => return Err(AppError::not_found("No bindings found")),
+    };
 
-    // Try top-level bindings first (correct location)
-    if let Some(bindings) = config.get_mut("bindings").and_then(|v| v.as_array_mut()) {
-        if index < bindings.len() {
-            bindings.remove(index);
-            save_openclaw_config(&config)?;
-            return Ok(format!("Binding at index {} deleted", index));
-        } else {
-            return Err(format!("Binding index {} out of range", index));
-        }
+    if index >= bindings.len() {
+        return Err(AppError::invalid_input(format!("Binding index {} out of range", index)));
     }
+    bindings.remove(index);
 
-    // Fallback to legacy agents.bindings
-    if let Some(bindings) = config.pointer_mut("/agents/bindings").and_then(|v| v.as_array_mut()) {
-        if index < bindings.len() {
-            bindings.remove(index);
-            save_openclaw_config(&config)?;
-            return Ok(format!("Binding at index {} deleted", index));
-        } else {
-            return Err(format!("Binding index {} out of range", index));
-        }
-    }
-
-    Err("No bindings found".to_string())
+    config.set_typed(&typed)?;
+    config.commit("delete_agent_binding")?;
+    Ok(format!("Binding at index {} deleted", index))
 }
 
 // ============ Agent Soul / Personality ============
 
 /// Read the personality (SOUL.md) for an agent
 #[command]
-pub async fn get_agent_system_prompt(agent_id: String, workspace: Option<String>) -> Result<String, String> {
+pub async fn get_agent_system_prompt(agent_id: String, workspace: Option<String>) -> Result<String, AppError> {
     let base = workspace.unwrap_or_else(|| platform::get_config_dir());
     let sep = if cfg!(windows) { "\\" } else { "/" };
     
@@ -3013,7 +3297,7 @@ pub async fn get_agent_system_prompt(agent_id: String, workspace: Option<String>
         if std::path::Path::new(path).exists() {
             info!("[Agents] Found SOUL.md at: {}", path);
             return std::fs::read_to_string(path)
-                .map_err(|e| format!("Failed to read SOUL.md: {}", e));
+                .map_err(|e| AppError::new(ErrorCode::Io, "Failed to read SOUL.md").with_detail(e));
         }
     }
     
@@ -3022,7 +3306,7 @@ pub async fn get_agent_system_prompt(agent_id: String, workspace: Option<String>
 
 /// Save the personality (SOUL.md) for an agent
 #[command]
-pub async fn save_agent_system_prompt(agent_id: String, workspace: Option<String>, content: String) -> Result<String, String> {
+pub async fn save_agent_system_prompt(agent_id: String, workspace: Option<String>, content: String) -> Result<String, AppError> {
     let base = workspace.unwrap_or_else(|| platform::get_config_dir());
     let sep = if cfg!(windows) { "\\" } else { "/" };
     
@@ -3047,22 +3331,22 @@ pub async fn save_agent_system_prompt(agent_id: String, workspace: Option<String
 
     if let Some(parent) = std::path::Path::new(&path).parent() {
         if let Err(e) = std::fs::create_dir_all(parent) {
-            return Err(format!("Failed to create directory for {}: {}", path, e));
+            return Err(AppError::new(ErrorCode::Io, format!("Failed to create directory for {}", path)).with_detail(e));
         }
     }
     
-    match std::fs::write(&path, &content) {
+    match file::write_file(&path, &content) {
         Ok(_) => {
             info!("[Agents] Wrote SOUL.md to: {}", path);
             Ok(format!("Personality (SOUL.md) saved for agent '{}'", agent_id))
         },
-        Err(e) => Err(format!("Failed to save SOUL.md to {}: {}", path, e))
+        Err(e) => Err(AppError::new(ErrorCode::Io, format!("Failed to save SOUL.md to {}", path)).with_detail(e))
     }
 }
 
 /// Test agent routing: given an account ID, find which agent handles it
 #[command]
-pub async fn test_agent_routing(account_id: String) -> Result<serde_json::Value, String> {
+pub async fn test_agent_routing(account_id: String) -> Result<serde_json::Value, AppError> {
     let config = load_openclaw_config()?;
 
     // Walk through bindings to find a match
@@ -3153,68 +3437,52 @@ pub struct CompactionConfig {
 
 /// Get heartbeat configuration
 #[command]
-pub async fn get_heartbeat_config() -> Result<HeartbeatConfig, String> {
+pub async fn get_heartbeat_config() -> Result<HeartbeatConfig, AppError> {
     info!("[Heartbeat] Getting heartbeat config...");
-    let config = load_openclaw_config()?;
+    let config = load_typed_config()?;
 
-    let every = config.pointer("/agents/defaults/heartbeat/every")
-        .and_then(|v| v.as_str()).map(|s| s.to_string());
-    let target = config.pointer("/agents/defaults/heartbeat/target")
-        .and_then(|v| v.as_str()).map(|s| s.to_string());
-
-    Ok(HeartbeatConfig { every, target })
+    let heartbeat = config.agents.defaults.heartbeat.unwrap_or_default();
+    Ok(HeartbeatConfig { every: heartbeat.every, target: heartbeat.target })
 }
 
 /// Save heartbeat configuration
 #[command]
-pub async fn save_heartbeat_config(every: Option<String>, target: Option<String>) -> Result<String, String> {
+pub async fn save_heartbeat_config(every: Option<String>, target: Option<String>) -> Result<String, AppError> {
     info!("[Heartbeat] Saving heartbeat config: every={:?}, target={:?}", every, target);
-    let mut config = load_openclaw_config()?;
-
-    if config.get("agents").is_none() { config["agents"] = json!({}); }
-    if config["agents"].get("defaults").is_none() { config["agents"]["defaults"] = json!({}); }
+    let mut config = ConfigTransaction::begin().await?;
+    let mut typed = config.typed()?;
 
     if every.is_some() || target.is_some() {
-        let mut hb = json!({});
-        if let Some(e) = &every { hb["every"] = json!(e); }
-        if let Some(t) = &target { hb["target"] = json!(t); }
-        config["agents"]["defaults"]["heartbeat"] = hb;
+        let hb = typed.agents.defaults.heartbeat.get_or_insert_with(HeartbeatSettings::default);
+        hb.every = every;
+        hb.target = target;
     } else {
         // Remove heartbeat if both are None
-        if let Some(defaults) = config["agents"]["defaults"].as_object_mut() {
-            defaults.remove("heartbeat");
-        }
+        typed.agents.defaults.heartbeat = None;
     }
 
-    save_openclaw_config(&config)?;
+    config.set_typed(&typed)?;
+    config.commit("save_heartbeat_config")?;
     Ok("Heartbeat configuration saved".to_string())
 }
 
 /// Get compaction configuration
 #[command]
-pub async fn get_compaction_config() -> Result<CompactionConfig, String> {
+pub async fn get_compaction_config() -> Result<CompactionConfig, AppError> {
     info!("[Compaction] Getting compaction config...");
-    let config = load_openclaw_config()?;
-
-    let compaction_val = config.pointer("/agents/defaults/compaction");
-    let pruning_val = config.pointer("/agents/defaults/contextPruning");
-
-    let enabled = compaction_val.map(|v| {
-        // compaction can be true/false or an object with settings
-        v.as_bool().unwrap_or(true)
-    }).unwrap_or(false);
+    let config = load_typed_config()?;
+    let defaults = &config.agents.defaults;
 
-    let threshold = compaction_val
-        .and_then(|v| v.get("threshold"))
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u32);
+    // Both settings can be true/false or an object with settings
+    let enabled = defaults.compaction.as_ref().is_some_and(|c| c.is_enabled());
+    let threshold = defaults.compaction.as_ref()
+        .and_then(|c| c.settings())
+        .and_then(|s| s.threshold);
 
-    let context_pruning = pruning_val.map(|v| v.as_bool().unwrap_or(false)).unwrap_or(false);
-
-    let max_context_messages = pruning_val
-        .and_then(|v| v.get("maxMessages"))
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u32);
+    let context_pruning = defaults.context_pruning.as_ref().is_some_and(|p| p.is_enabled());
+    let max_context_messages = defaults.context_pruning.as_ref()
+        .and_then(|p| p.settings())
+        .and_then(|s| s.max_messages);
 
     Ok(CompactionConfig { enabled, threshold, context_pruning, max_context_messages })
 }
@@ -3226,36 +3494,37 @@ pub async fn save_compaction_config(
     threshold: Option<u32>,
     context_pruning: bool,
     max_context_messages: Option<u32>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     info!("[Compaction] Saving compaction config: enabled={}, pruning={}", enabled, context_pruning);
-    let mut config = load_openclaw_config()?;
-
-    if config.get("agents").is_none() { config["agents"] = json!({}); }
-    if config["agents"].get("defaults").is_none() { config["agents"]["defaults"] = json!({}); }
-
-    if enabled {
-        let mut comp = json!({});
-        if let Some(t) = threshold { comp["threshold"] = json!(t); }
-        config["agents"]["defaults"]["compaction"] = comp;
+    let mut config = ConfigTransaction::begin().await?;
+    let mut typed = config.typed()?;
+    let defaults = &mut typed.agents.defaults;
+
+    // Existing object settings are kept so options the Manager doesn't edit survive
+    defaults.compaction = if enabled {
+        let mut comp = defaults.compaction.as_ref()
+            .and_then(|c| c.settings().cloned())
+            .unwrap_or_default();
+        comp.threshold = threshold;
+        Some(FlagOrSettings::Settings(comp))
     } else {
-        if let Some(defaults) = config["agents"]["defaults"].as_object_mut() {
-            defaults.remove("compaction");
-        }
-    }
+        None
+    };
 
-    if context_pruning {
-        let mut pruning = json!(true);
-        if let Some(max) = max_context_messages {
-            pruning = json!({ "maxMessages": max });
-        }
-        config["agents"]["defaults"]["contextPruning"] = pruning;
+    defaults.context_pruning = if !context_pruning {
+        None
+    } else if let Some(max) = max_context_messages {
+        let mut pruning: ContextPruningSettings = defaults.context_pruning.as_ref()
+            .and_then(|p| p.settings().cloned())
+            .unwrap_or_default();
+        pruning.max_messages = Some(max);
+        Some(FlagOrSettings::Settings(pruning))
     } else {
-        if let Some(defaults) = config["agents"]["defaults"].as_object_mut() {
-            defaults.remove("contextPruning");
-        }
-    }
+        Some(FlagOrSettings::Flag(true))
+    };
 
-    save_openclaw_config(&config)?;
+    config.set_typed(&typed)?;
+    config.commit("save_compaction_config")?;
     Ok("Compaction configuration saved".to_string())
 }
 
@@ -3273,7 +3542,7 @@ pub struct WorkspaceConfig {
 
 /// Get workspace configuration
 #[command]
-pub async fn get_workspace_config() -> Result<WorkspaceConfig, String> {
+pub async fn get_workspace_config() -> Result<WorkspaceConfig, AppError> {
     info!("[Workspace] Getting workspace config...");
     let config = load_openclaw_config()?;
 
@@ -3299,9 +3568,9 @@ pub async fn save_workspace_config(
     time_format: Option<String>,
     skip_bootstrap: bool,
     bootstrap_max_chars: Option<u32>,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     info!("[Workspace] Saving workspace config...");
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
 
     if config.get("agents").is_none() { config["agents"] = json!({}); }
     if config["agents"].get("defaults").is_none() { config["agents"]["defaults"] = json!({}); }
@@ -3339,19 +3608,19 @@ pub async fn save_workspace_config(
         }
     }
 
-    save_openclaw_config(&config)?;
+    config.commit("save_workspace_config")?;
     Ok("Workspace configuration saved".to_string())
 }
 
 /// Get a personality file from the workspace directory
 #[command]
-pub async fn get_personality_file(filename: String) -> Result<String, String> {
+pub async fn get_personality_file(filename: String) -> Result<String, AppError> {
     info!("[Personality] Reading file: {}", filename);
 
     // Validate filename
     let allowed = ["AGENTS.md", "SOUL.md", "TOOLS.md"];
     if !allowed.contains(&filename.as_str()) {
-        return Err(format!("Invalid file: {}. Allowed: {:?}", filename, allowed));
+        return Err(AppError::invalid_input(format!("Invalid file: {}. Allowed: {:?}", filename, allowed)));
     }
 
     // Get workspace path from config, fallback to ~/.openclaw
@@ -3380,12 +3649,12 @@ pub async fn get_personality_file(filename: String) -> Result<String, String> {
 
 /// Save a personality file to the workspace directory
 #[command]
-pub async fn save_personality_file(filename: String, content: String) -> Result<String, String> {
+pub async fn save_personality_file(filename: String, content: String) -> Result<String, AppError> {
     info!("[Personality] Saving file: {}", filename);
 
     let allowed = ["AGENTS.md", "SOUL.md", "TOOLS.md"];
     if !allowed.contains(&filename.as_str()) {
-        return Err(format!("Invalid file: {}. Allowed: {:?}", filename, allowed));
+        return Err(AppError::invalid_input(format!("Invalid file: {}. Allowed: {:?}", filename, allowed)));
     }
 
     let config = load_openclaw_config()?;
@@ -3406,7 +3675,7 @@ pub async fn save_personality_file(filename: String, content: String) -> Result<
     };
 
     file::write_file(&filepath, &content)
-        .map_err(|e| format!("Failed to save {}: {}", filename, e))?;
+        .map_err(|e| AppError::new(ErrorCode::Io, format!("Failed to save {}", filename)).with_detail(e))?;
 
     Ok(format!("{} saved successfully", filename))
 }
@@ -3422,7 +3691,7 @@ pub struct BrowserConfig {
 
 /// Get browser configuration
 #[command]
-pub async fn get_browser_config() -> Result<BrowserConfig, String> {
+pub async fn get_browser_config() -> Result<BrowserConfig, AppError> {
     info!("[Browser] Getting browser config...");
     let config = load_openclaw_config()?;
 
@@ -3440,9 +3709,9 @@ pub async fn get_browser_config() -> Result<BrowserConfig, String> {
 
 /// Save browser configuration
 #[command]
-pub async fn save_browser_config(enabled: bool, color: Option<String>) -> Result<String, String> {
+pub async fn save_browser_config(enabled: bool, color: Option<String>) -> Result<String, AppError> {
     info!("[Browser] Saving browser config: enabled={}, color={:?}", enabled, color);
-    let mut config = load_openclaw_config()?;
+    let mut config = ConfigTransaction::begin().await?;
 
     // Store in meta.gui.browser to avoid polluting core config
     if config.get("meta").is_none() { config["meta"] = json!({}); }
@@ -3460,7 +3729,7 @@ pub async fn save_browser_config(enabled: bool, color: Option<String>) -> Result
 
     config["meta"]["gui"]["browser"] = browser_config;
 
-    save_openclaw_config(&config)?;
+    config.commit("save_browser_config")?;
     Ok("Browser configuration saved".to_string())
 }
 
@@ -3474,40 +3743,30 @@ pub struct WebConfig {
 
 /// Get web search configuration
 #[command]
-pub async fn get_web_config() -> Result<WebConfig, String> {
+pub async fn get_web_config() -> Result<WebConfig, AppError> {
     info!("[Web] Getting web search config...");
-    let config = load_openclaw_config()?;
+    let config = load_typed_config()?;
 
-    let brave_api_key = config.pointer("/web/braveApiKey")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+    let brave_api_key = config.web.and_then(|w| w.brave_api_key);
 
     Ok(WebConfig { brave_api_key })
 }
 
 /// Save web search configuration
 #[command]
-pub async fn save_web_config(brave_api_key: Option<String>) -> Result<String, String> {
+pub async fn save_web_config(brave_api_key: Option<String>) -> Result<String, AppError> {
     info!("[Web] Saving web search config...");
-    let mut config = load_openclaw_config()?;
-
-    if config.get("web").is_none() {
-        config["web"] = json!({});
-    }
-
-    match brave_api_key {
-        Some(key) if !key.is_empty() => {
-            config["web"]["braveApiKey"] = json!(key);
-        }
-        _ => {
-            if let Some(web) = config.get_mut("web").and_then(|v| v.as_object_mut()) {
-                web.remove("braveApiKey");
-            }
-        }
-    }
+    let mut config = ConfigTransaction::begin().await?;
+    let mut typed = config.typed()?;
 
+    let brave_api_key = match brave_api_key.filter(|key| !key.is_empty()) {
+        Some(key) => Some(vault_secret_value("BRAVE_API_KEY", &key)?),
+        None => None,
+    };
+    typed.web.get_or_insert_with(WebToolsConfig::default).brave_api_key = brave_api_key;
 
-    save_openclaw_config(&config)?;
+    config.set_typed(&typed)?;
+    config.commit("save_web_config")?;
     Ok("Web search configuration saved".to_string())
 }
 
@@ -3522,86 +3781,304 @@ pub struct GatewayConfig {
 
 /// Get gateway configuration
 #[command]
-pub async fn get_gateway_config() -> Result<GatewayConfig, String> {
+pub async fn get_gateway_config() -> Result<GatewayConfig, AppError> {
     info!("[Gateway] Getting gateway config...");
-    let config = load_openclaw_config()?;
+    let config = load_typed_config()?;
 
-    let port = config.pointer("/gateway/port")
-        .and_then(|v| v.as_u64())
-        .map(|v| v as u16)
-        .unwrap_or(3000);
+    let port = config.gateway.port.filter(|p| *p != 0).unwrap_or_else(shell::get_gateway_port);
 
-    let log_level = config.pointer("/manager/log_level")
-        .and_then(|v| v.as_str())
-        .or_else(|| config.pointer("/gateway/logLevel").and_then(|v| v.as_str())) // Legacy fallback
-        .map(|s| s.to_string())
+    let log_level = config.manager.as_ref().and_then(|m| m.log_level.clone())
+        .or_else(|| config.gateway.extra.get("logLevel").and_then(|v| v.as_str()).map(|s| s.to_string())) // Legacy fallback
         .unwrap_or_else(|| "info".to_string());
 
     Ok(GatewayConfig { port, log_level })
 }
 
+/// Result of saving the gateway configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewaySaveResult {
+    pub message: String,
+    /// The gateway is running on the old port and must be restarted to use the new one
+    pub restart_required: bool,
+    pub previous_port: Option<u16>,
+}
+
 /// Save gateway configuration
 #[command]
-pub async fn save_gateway_config(port: u16, log_level: String) -> Result<String, String> {
+pub async fn save_gateway_config(port: u16, log_level: String) -> Result<GatewaySaveResult, AppError> {
     info!("[Gateway] Saving gateway config: port={}, level={}", port, log_level);
-    let mut config = load_openclaw_config()?;
-
-    if config.get("gateway").is_none() {
-        config["gateway"] = json!({});
+    if port == 0 {
+        return Err(AppError::invalid_input("Gateway port must be between 1 and 65535"));
     }
+    let previous_port = shell::get_gateway_port();
+    let mut config = ConfigTransaction::begin().await?;
+    let mut typed = config.typed()?;
 
-    if let Some(gateway) = config.get_mut("gateway").and_then(|v| v.as_object_mut()) {
-        gateway.insert("port".to_string(), json!(port));
-        // Remove legacy logLevel if exists
-        gateway.remove("logLevel");
-        gateway.remove("log_level");
-    }
+    typed.gateway.port = Some(port);
+    // Remove legacy logLevel if exists
+    typed.gateway.extra.remove("logLevel");
+    typed.gateway.extra.remove("log_level");
 
-    if config.get("manager").is_none() {
-        config["manager"] = json!({});
-    }
+    typed.manager.get_or_insert_with(ManagerSettings::default).log_level = Some(log_level);
+    
+    config.set_typed(&typed)?;
+    config.commit("save_gateway_config")?;
 
-    if let Some(manager) = config.get_mut("manager").and_then(|v| v.as_object_mut()) {
-        manager.insert("log_level".to_string(), json!(log_level));
+    let restart_required = previous_port != port && service::is_port_listening(previous_port);
+    if restart_required {
+        info!("[Gateway] Port changed {} -> {} while the gateway is running", previous_port, port);
+        service::note_port_change(previous_port);
     }
-    
-    save_openclaw_config(&config)?;
-    Ok("Gateway configuration saved".to_string())
+
+    Ok(GatewaySaveResult {
+        message: "Gateway configuration saved".to_string(),
+        restart_required,
+        previous_port: (previous_port != port).then_some(previous_port),
+    })
 }
 
 // ============ Configuration Management ============
 
 /// Export configuration
 #[command]
-pub async fn export_config(path: String) -> Result<String, String> {
+pub async fn export_config(path: String) -> Result<String, AppError> {
     info!("[Config] Exporting config to: {}", path);
     let config = load_openclaw_config()?;
     
     let content = serde_json::to_string_pretty(&config)
-        .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to serialize config").with_detail(e))?;
 
     file::write_file(&path, &content)
-        .map_err(|e| format!("Failed to write export file: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to write export file").with_detail(e))?;
+
+    // Exports can carry plaintext keys; the secret scan checks the files listed here
+    let mut manager = load_manager_config()?;
+    let mut exported: Vec<String> = manager
+        .get(secret_scan::EXPORTED_CONFIGS_KEY)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    exported.retain(|p| *p != path);
+    exported.push(path.clone());
+    let excess = exported.len().saturating_sub(secret_scan::MAX_EXPORTED_CONFIGS);
+    exported.drain(..excess);
+    manager[secret_scan::EXPORTED_CONFIGS_KEY] = json!(exported);
+    if let Err(e) = save_manager_config(&manager) {
+        warn!("[Config] Failed to record exported config path: {}", e);
+    }
 
     Ok(format!("Configuration exported to {}", path))
 }
 
 /// Import configuration
 #[command]
-pub async fn import_config(path: String) -> Result<String, String> {
+pub async fn import_config(path: String) -> Result<String, AppError> {
     info!("[Config] Importing config from: {}", path);
 
     let content = file::read_file(&path)
-        .map_err(|e| format!("Failed to read import file: {}", e))?;
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to read import file").with_detail(e))?;
 
-    let new_config: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| format!("Invalid JSON file: {}", e))?;
+    let mut new_config: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| AppError::new(ErrorCode::ConfigParse, "Invalid JSON file").with_detail(e))?;
 
     if !new_config.is_object() {
-        return Err("Imported file is not a valid configuration object".to_string());
+        return Err(AppError::invalid_input("Imported file is not a valid configuration object"));
     }
 
-    save_openclaw_config(&new_config)?;
+    // Exports from older versions may still use a legacy layout
+    let mut manager_config = load_manager_config()?;
+    let report = config_migrations::run(&mut new_config, &mut manager_config);
+    log_migration_report("import_config", &report);
+
+    replace_openclaw_config(&new_config, "import_config").await?;
+    if report.manager_changed {
+        save_manager_config(&manager_config)?;
+    }
 
     Ok("Configuration imported successfully".to_string())
 }
+
+// ============ Configuration Migrations ============
+
+fn log_migration_report(source: &str, report: &MigrationReport) {
+    for migration in &report.migrations {
+        for change in &migration.changes {
+            info!("[Config Migration] {} ({}): {}", migration.id, source, change);
+        }
+    }
+}
+
+/// Run pending migrations on openclaw.json and manager.json.
+/// Called once at startup; configs already in the current layout are left untouched.
+pub fn migrate_config_files() -> Result<MigrationReport, AppError> {
+    if !file::file_exists(&platform::get_config_file_path()) {
+        return Ok(MigrationReport {
+            from_version: None,
+            to_version: None,
+            migrations: Vec::new(),
+            config_changed: false,
+            manager_changed: false,
+        });
+    }
+
+    let mut config = ConfigTransaction::begin_blocking()?;
+    let mut manager_config = load_manager_config()?;
+    let report = config_migrations::run(&mut config, &mut manager_config);
+    log_migration_report("startup", &report);
+
+    // manager.json first: if openclaw.json then fails to save, the moved values
+    // are still in their old place and the migration simply runs again
+    if report.manager_changed {
+        save_manager_config(&manager_config)?;
+    }
+    if report.config_changed {
+        config.commit("config_migration")?;
+    }
+    Ok(report)
+}
+
+/// Dry run: which migrations are pending and what each would change
+#[command]
+pub async fn get_config_migration_report() -> Result<MigrationReport, AppError> {
+    info!("[Config Migration] Building dry-run report...");
+    let config = load_openclaw_config()?;
+    let manager_config = load_manager_config()?;
+    Ok(config_migrations::dry_run(&config, &manager_config))
+}
+
+/// Run pending migrations now
+#[command]
+pub async fn run_config_migrations() -> Result<MigrationReport, AppError> {
+    info!("[Config Migration] Running pending migrations...");
+    migrate_config_files()
+}
+
+// ============ Configuration History ============
+
+/// List openclaw.json snapshots, newest first
+#[command]
+pub async fn list_config_snapshots() -> Result<Vec<ConfigSnapshot>, AppError> {
+    info!("[Config History] Listing snapshots...");
+    config_history::list_snapshots(&platform::get_config_history_dir())
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to read configuration history").with_detail(e))
+}
+
+/// Load a snapshot (or the live config for "current") as JSON
+fn load_snapshot_value(id: &str) -> Result<Value, AppError> {
+    if id == "current" {
+        return load_openclaw_config();
+    }
+    let content = config_history::read_snapshot(&platform::get_config_history_dir(), id)
+        .map_err(|e| AppError::not_found(format!("Failed to read snapshot {}", id)).with_detail(e))?;
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
+    serde_json::from_str(content)
+        .map_err(|e| AppError::new(ErrorCode::ConfigParse, format!("Snapshot {} is not valid JSON", id)).with_detail(e))
+}
+
+/// Diff two snapshots; either ID may be "current" for the live openclaw.json
+#[command]
+pub async fn diff_config_snapshots(from: String, to: String) -> Result<Vec<ConfigDiffEntry>, AppError> {
+    info!("[Config History] Diffing {} -> {}", from, to);
+    let before = load_snapshot_value(&from)?;
+    let after = load_snapshot_value(&to)?;
+    Ok(config_history::diff(&before, &after))
+}
+
+/// Rewrite openclaw.json from an edit of its current text (moving a leaked secret).
+/// Goes through the usual save path: locked, validated and snapshotted.
+pub async fn edit_openclaw_config_text(
+    command: &str,
+    edit: impl FnOnce(&str) -> Result<String, AppError>,
+) -> Result<(), AppError> {
+    let mut tx = ConfigTransaction::begin().await?;
+    let current = file::read_file(&platform::get_config_file_path())
+        .map_err(|e| AppError::new(ErrorCode::ConfigRead, "Failed to read configuration file").with_detail(e))?;
+    let updated = edit(&current)?;
+    *tx = serde_json::from_str(updated.trim_start_matches('\u{FEFF}'))
+        .map_err(|e| AppError::new(ErrorCode::ConfigParse, "Edited configuration is not valid JSON").with_detail(e))?;
+    tx.commit(command)
+}
+
+/// Rewrite mcps.json from an edit of its current text; mcporter.json is synced as on
+/// any MCP save
+pub fn edit_mcp_config_text(edit: impl FnOnce(&str) -> Result<String, AppError>) -> Result<(), AppError> {
+    let current = file::read_file(&platform::get_mcp_config_file_path())
+        .map_err(|e| AppError::new(ErrorCode::ConfigRead, "Failed to read mcps.json").with_detail(e))?;
+    let updated = edit(&current)?;
+    let configs: HashMap<String, MCPConfig> = serde_json::from_str(updated.trim_start_matches('\u{FEFF}'))
+        .map_err(|e| AppError::new(ErrorCode::ConfigParse, "Edited mcps.json is not valid").with_detail(e))?;
+    save_mcp_config_file(&configs)?;
+    Ok(())
+}
+
+/// Replace a secret that moved into the vault or env file with its reference in every
+/// config snapshot, so the history doesn't keep a plaintext copy
+pub fn redact_config_history(secret: &str, reference: &str) {
+    match config_history::redact(&platform::get_config_history_dir(), secret, reference) {
+        Ok(0) => {}
+        Ok(n) => info!("[Config History] Replaced a moved secret with {} in {} snapshot(s)", reference, n),
+        Err(e) => warn!("[Config History] Failed to redact snapshots: {}", e),
+    }
+}
+
+/// Restore openclaw.json from a snapshot.
+/// The config being replaced is snapshotted too, so a restore can itself be undone.
+#[command]
+pub async fn restore_config_snapshot(id: String) -> Result<String, AppError> {
+    info!("[Config History] Restoring snapshot {}...", id);
+    let content = config_history::read_snapshot(&platform::get_config_history_dir(), &id)
+        .map_err(|e| AppError::new(ErrorCode::NotFound, format!("Failed to read snapshot {}", id)).with_detail(e))?;
+
+    // Restored content is written byte for byte, but only if it would pass a normal save
+    let restored: Value = serde_json::from_str(content.strip_prefix('\u{FEFF}').unwrap_or(&content)).map_err(|e| {
+        AppError::new(ErrorCode::ConfigParse, format!("Snapshot {} is not valid JSON", id)).with_detail(e)
+    })?;
+    let issues = config_validation::validate(&restored);
+    if !issues.is_empty() {
+        warn!("[Config History] Refusing to restore invalid snapshot {}: {}", id, config_validation::format_issues(&issues));
+        return Err(invalid_config_error(&issues));
+    }
+
+    let _lock = lock_openclaw_config().await?;
+    write_openclaw_config_content(&content, "restore_config_snapshot")?;
+
+    info!("[Config History] Snapshot {} restored", id);
+    Ok(format!("Configuration restored from snapshot {}", id))
+}
+
+// ============ Configuration Watcher ============
+
+/// Watch openclaw.json, mcps.json and mcporter.json in the background and emit
+/// `config-changed` whenever one of them changes, including edits made by the
+/// openclaw CLI or a text editor
+pub fn start_config_watcher(app: AppHandle) {
+    let watch_active_profile = || {
+        let mut watcher = ConfigWatcher::new();
+        watcher.watch("openclaw", &platform::get_config_file_path());
+        watcher.watch("mcps", &platform::get_mcp_config_file_path());
+        watcher.watch("mcporter", &platform::get_mcporter_config_file_path());
+        (platform::get_config_dir(), watcher)
+    };
+    let (mut watched_dir, mut watcher) = watch_active_profile();
+
+    let spawned = std::thread::Builder::new().name("config-watcher".to_string()).spawn(move || loop {
+        std::thread::sleep(config_watcher::POLL_INTERVAL);
+        // Follow profile switches; the switch itself is not reported as a change
+        if platform::get_config_dir() != watched_dir {
+            (watched_dir, watcher) = watch_active_profile();
+            info!("[Config Watcher] Now watching {}", watched_dir);
+        }
+        for event in watcher.poll(std::time::Instant::now()) {
+            match &event.error {
+                Some(e) => warn!("[Config Watcher] {} no longer parses: {}", event.file, e),
+                None => info!("[Config Watcher] {} changed: {}", event.file, event.sections.join(", ")),
+            }
+            if let Err(e) = app.emit(config_watcher::CONFIG_CHANGED_EVENT, &event) {
+                error!("[Config Watcher] Failed to emit change event: {}", e);
+            }
+        }
+    });
+
+    if let Err(e) = spawned {
+        error!("[Config Watcher] Failed to start: {}", e);
+    }
+}
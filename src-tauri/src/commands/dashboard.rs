@@ -0,0 +1,397 @@
+use crate::commands::config;
+use crate::models::{AppError, ErrorCode};
+use crate::utils::dashboard_access::{self, DashboardAccess, DashboardScope, DashboardToken, TokenStatus};
+use crate::utils::dashboard_proxy::{self, ClientFrame, Frame, RequestHead, COOKIE_NAME, MAX_HEAD_BYTES};
+use crate::utils::{secrets_vault, shell};
+use chrono::Utc;
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::command;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+/// How long a client may take to send its request head
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often an open dashboard WebSocket re-checks that its token is still valid
+const RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+// The running proxy, replaced whenever its settings change
+static PROXY: Lazy<Mutex<Option<JoinHandle<()>>>> = Lazy::new(|| Mutex::new(None));
+
+/// A dashboard token as listed in Settings (the token itself only goes into links)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardTokenInfo {
+    pub id: String,
+    pub name: String,
+    pub scope: DashboardScope,
+    pub created_at: String,
+    pub expires_at: String,
+    pub revoked_at: Option<String>,
+    pub status: TokenStatus,
+}
+
+/// Proxy settings and the tokens handed out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardAccessOverview {
+    pub enabled: bool,
+    pub bind: String,
+    pub port: u16,
+    pub public_url: Option<String>,
+    pub running: bool,
+    pub tokens: Vec<DashboardTokenInfo>,
+}
+
+/// A newly minted token and its link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardLink {
+    pub token: DashboardTokenInfo,
+    pub url: String,
+}
+
+fn info_of(token: &DashboardToken) -> DashboardTokenInfo {
+    DashboardTokenInfo {
+        id: token.id.clone(),
+        name: token.name.clone(),
+        scope: token.scope,
+        created_at: token.created_at.clone(),
+        expires_at: token.expires_at.clone(),
+        revoked_at: token.revoked_at.clone(),
+        status: token.status(Utc::now()),
+    }
+}
+
+fn load_access() -> Result<DashboardAccess, AppError> {
+    let manager = config::load_manager_config()?;
+    Ok(DashboardAccess::from_manager_config(&manager))
+}
+
+fn save_access(access: &DashboardAccess) -> Result<(), AppError> {
+    let mut manager = config::load_manager_config()?;
+    if !manager.is_object() {
+        manager = serde_json::json!({});
+    }
+    manager[dashboard_access::MANAGER_KEY] = serde_json::to_value(access)
+        .map_err(|e| AppError::new(ErrorCode::Internal, "Failed to serialize dashboard access").with_detail(e))?;
+    config::save_manager_config(&manager)
+}
+
+fn overview(access: &DashboardAccess) -> DashboardAccessOverview {
+    let running = PROXY
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .is_some_and(|h| !h.inner().is_finished());
+    DashboardAccessOverview {
+        enabled: access.enabled,
+        bind: access.bind.clone(),
+        port: access.port,
+        public_url: access.public_url.clone(),
+        running,
+        tokens: access.tokens.iter().map(info_of).collect(),
+    }
+}
+
+/// Get the dashboard proxy settings and the tokens handed out
+#[command]
+pub async fn get_dashboard_access() -> Result<DashboardAccessOverview, AppError> {
+    Ok(overview(&load_access()?))
+}
+
+/// Save the proxy settings (tokens are kept) and restart the proxy
+#[command]
+pub async fn save_dashboard_proxy(
+    enabled: bool,
+    bind: String,
+    port: u16,
+    public_url: Option<String>,
+) -> Result<DashboardAccessOverview, AppError> {
+    let mut access = load_access()?;
+    access.enabled = enabled;
+    access.bind = bind.trim().to_string();
+    access.port = port;
+    access.public_url = public_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    access.validate().map_err(AppError::invalid_input)?;
+    save_access(&access)?;
+    info!(
+        "[Dashboard Access] Proxy {} on {}:{}",
+        if enabled { "enabled" } else { "disabled" },
+        access.bind,
+        access.port
+    );
+    restart_proxy(&access).await?;
+    Ok(overview(&access))
+}
+
+/// Mint a named, expiring dashboard token and return its link
+#[command]
+pub async fn create_dashboard_token(name: String, scope: DashboardScope, expires_in_hours: u32) -> Result<DashboardLink, AppError> {
+    let mut access = load_access()?;
+    let now = Utc::now();
+    access.prune(now);
+    let token = access.mint(&name, scope, expires_in_hours, now).map_err(AppError::invalid_input)?.clone();
+    save_access(&access)?;
+    info!("[Dashboard Access] Created {:?} token {} for '{}'", scope, token.id, token.name);
+    Ok(DashboardLink { url: access.link(&token), token: info_of(&token) })
+}
+
+/// Link of an existing token
+#[command]
+pub async fn get_dashboard_token_link(id: String) -> Result<String, AppError> {
+    let access = load_access()?;
+    let token = access
+        .tokens
+        .iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| AppError::not_found(format!("Dashboard token {} does not exist", id)))?;
+    Ok(access.link(token))
+}
+
+/// Revoke a token; open dashboards using it are disconnected within `RECHECK_INTERVAL`
+/// and plain HTTP requests are refused right away
+#[command]
+pub async fn revoke_dashboard_token(id: String) -> Result<DashboardAccessOverview, AppError> {
+    let mut access = load_access()?;
+    if !access.revoke(&id, Utc::now()) {
+        return Err(AppError::not_found(format!("Dashboard token {} does not exist", id)));
+    }
+    save_access(&access)?;
+    info!("[Dashboard Access] Revoked token {}", id);
+    Ok(overview(&access))
+}
+
+/// Start the dashboard proxy if it is enabled (called at startup)
+pub fn start_dashboard_proxy() {
+    tauri::async_runtime::spawn(async {
+        match load_access() {
+            Ok(access) => {
+                if let Err(e) = restart_proxy(&access).await {
+                    warn!("[Dashboard Access] {}", e);
+                }
+            }
+            Err(e) => warn!("[Dashboard Access] Failed to load settings: {}", e),
+        }
+    });
+}
+
+async fn restart_proxy(access: &DashboardAccess) -> Result<(), AppError> {
+    if let Some(handle) = PROXY.lock().unwrap_or_else(|e| e.into_inner()).take() {
+        handle.abort();
+    }
+    if !access.enabled {
+        return Ok(());
+    }
+    let address = format!("{}:{}", access.bind, access.port);
+    let listener = TcpListener::bind(&address).await.map_err(|e| {
+        AppError::new(ErrorCode::PortInUse, format!("Dashboard proxy cannot listen on {}", address)).with_detail(e)
+    })?;
+    info!("[Dashboard Access] Proxy listening on {}", address);
+    let handle = tauri::async_runtime::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, peer.ip()).await {
+                            debug!("[Dashboard Access] Connection from {} ended: {}", peer, e);
+                        }
+                    });
+                }
+                Err(e) => warn!("[Dashboard Access] Accept failed: {}", e),
+            }
+        }
+    });
+    *PROXY.lock().unwrap_or_else(|e| e.into_inner()) = Some(handle);
+    Ok(())
+}
+
+/// Read until the end of an HTTP head; returns the head and any bytes after it
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        if let Some(end) = dashboard_proxy::head_end(&buf) {
+            let rest = buf.split_off(end);
+            return Ok(Some((buf, rest)));
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Ok(None);
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+/// Check the dashboard token on every request and relay it to the gateway on loopback
+async fn handle_connection(mut client: TcpStream, client_ip: IpAddr) -> std::io::Result<()> {
+    let Ok(Ok(Some((head, body)))) = tokio::time::timeout(HEAD_TIMEOUT, read_head(&mut client)).await else {
+        return client.write_all(&dashboard_proxy::plain_response(400, "Bad Request", "Bad request")).await;
+    };
+    let Some(mut request) = RequestHead::parse(&head) else {
+        return client.write_all(&dashboard_proxy::plain_response(400, "Bad Request", "Bad request")).await;
+    };
+
+    let Some((presented, from_link)) = request.presented_token() else {
+        let body = "Open the dashboard with the link you were given";
+        return client.write_all(&dashboard_proxy::plain_response(401, "Unauthorized", body)).await;
+    };
+    let access = load_access().unwrap_or_default();
+    let token = match access.authenticate(&presented, Utc::now()) {
+        Ok(token) => token.clone(),
+        Err(denied) => {
+            return client.write_all(&dashboard_proxy::plain_response(403, "Forbidden", denied.message())).await;
+        }
+    };
+    if request.is_websocket() && !request.same_origin() {
+        return client.write_all(&dashboard_proxy::plain_response(403, "Forbidden", "Cross-origin request")).await;
+    }
+    let read_only = token.scope == DashboardScope::ReadOnly;
+    if read_only && !request.is_websocket() && !request.is_read() {
+        let body = "This dashboard link is read-only";
+        return client.write_all(&dashboard_proxy::plain_response(403, "Forbidden", body)).await;
+    }
+    // Frames sent before the 101 would reach the gateway unfiltered
+    if read_only && request.is_websocket() && !body.is_empty() {
+        let body = "Data sent before the WebSocket handshake completed";
+        return client.write_all(&dashboard_proxy::plain_response(400, "Bad Request", body)).await;
+    }
+    let Some(gateway_token) = shell::read_gateway_token() else {
+        let body = "The gateway has no auth token configured";
+        return client.write_all(&dashboard_proxy::plain_response(502, "Bad Gateway", body)).await;
+    };
+
+    let port = shell::get_gateway_port();
+    let mut upstream = match TcpStream::connect(("127.0.0.1", port)).await {
+        Ok(stream) => stream,
+        Err(_) => {
+            let body = "The OpenClaw gateway is not running";
+            return client.write_all(&dashboard_proxy::plain_response(502, "Bad Gateway", body)).await;
+        }
+    };
+    let websocket = request.is_websocket();
+    request.prepare_upstream(port, client_ip);
+    upstream.write_all(&request.to_bytes()).await?;
+    // A read carries no body; anything after its head could be a pipelined write
+    if !read_only {
+        upstream.write_all(&body).await?;
+    }
+
+    let Some((mut response, rest)) = read_head(&mut upstream).await? else {
+        return Ok(());
+    };
+    let switched = dashboard_proxy::response_status(&response) == Some(101);
+    if from_link {
+        // Later requests (assets, the WebSocket) carry the cookie instead of ?token=
+        let cookie = format!(
+            "Set-Cookie: {}={}; Path=/; HttpOnly; SameSite=Strict; Max-Age={}",
+            COOKIE_NAME,
+            token.token,
+            token.remaining_secs(Utc::now())
+        );
+        response = dashboard_proxy::insert_response_header(&response, &cookie);
+    }
+    client.write_all(&response).await?;
+    client.write_all(&rest).await?;
+
+    if websocket && switched {
+        relay_websocket(client, upstream, token, gateway_token).await
+    } else if read_only {
+        // Only the response flows; further client bytes never reach the gateway
+        tokio::io::copy(&mut upstream, &mut client).await.map(|_| ())
+    } else {
+        tokio::io::copy_bidirectional(&mut client, &mut upstream).await.map(|_| ())
+    }
+}
+
+/// Relay WebSocket frames, applying the token's scope to everything the dashboard sends
+async fn relay_websocket(
+    client: TcpStream,
+    upstream: TcpStream,
+    token: DashboardToken,
+    gateway_token: String,
+) -> std::io::Result<()> {
+    let (mut client_read, mut client_write) = client.into_split();
+    let (mut upstream_read, mut upstream_write) = upstream.into_split();
+
+    // Frames to the dashboard come from the gateway and from the proxy's own replies
+    let (to_client, mut outbox) = mpsc::channel::<Frame>(64);
+    let writer = tokio::spawn(async move {
+        while let Some(frame) = outbox.recv().await {
+            if client_write.write_all(&frame.encode(None)).await.is_err() {
+                break;
+            }
+        }
+    });
+    let from_gateway = to_client.clone();
+    let reader = tokio::spawn(async move {
+        while let Ok(Some(frame)) = Frame::read(&mut upstream_read).await {
+            if from_gateway.send(frame).await.is_err() {
+                break;
+            }
+        }
+    });
+    // Frame::read isn't cancel safe, so client frames are read in their own task and
+    // the loop below can wake up for the token re-check without a frame arriving
+    let (client_frames, mut inbox) = mpsc::channel::<std::io::Result<Option<Frame>>>(16);
+    let client_reader = tokio::spawn(async move {
+        loop {
+            let frame = Frame::read(&mut client_read).await;
+            let last = !matches!(frame, Ok(Some(_)));
+            if client_frames.send(frame).await.is_err() || last {
+                break;
+            }
+        }
+    });
+
+    let mut recheck = tokio::time::interval(RECHECK_INTERVAL);
+    recheck.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    recheck.tick().await;
+    let result = loop {
+        let frame = tokio::select! {
+            received = inbox.recv() => match received {
+                Some(Ok(Some(frame))) => frame,
+                Some(Ok(None)) | None => break Ok(()),
+                Some(Err(e)) => break Err(e),
+            },
+            _ = recheck.tick() => {
+                let access = load_access().unwrap_or_default();
+                if let Err(denied) = access.authenticate(&token.token, Utc::now()) {
+                    info!("[Dashboard Access] Closing dashboard of '{}': {}", token.name, denied.message());
+                    let _ = to_client.send(Frame::policy_close(denied.message())).await;
+                    break Ok(());
+                }
+                continue;
+            }
+        };
+        match dashboard_proxy::filter_client_frame(frame, token.scope, &gateway_token) {
+            ClientFrame::Forward(frame) => {
+                let mask = secrets_vault::random_bytes::<4>();
+                if let Err(e) = upstream_write.write_all(&frame.encode(Some(mask))).await {
+                    break Err(e);
+                }
+            }
+            ClientFrame::Reply(frame) => {
+                if to_client.send(frame).await.is_err() {
+                    break Ok(());
+                }
+            }
+            ClientFrame::Close(reason) => {
+                let _ = to_client.send(Frame::policy_close(reason)).await;
+                break Ok(());
+            }
+        }
+    };
+
+    reader.abort();
+    client_reader.abort();
+    drop(to_client);
+    let _ = writer.await;
+    result
+}
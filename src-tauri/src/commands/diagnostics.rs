@@ -1,4 +1,4 @@
-use crate::models::{AITestResult, ChannelTestResult, DiagnosticResult, SystemInfo};
+use crate::models::{AITestResult, AppError, ChannelTestResult, DiagnosticResult, SystemInfo};
 use crate::utils::{log_sanitizer, platform, shell};
 use tauri::command;
 use log::{info, warn, debug};
@@ -83,7 +83,7 @@ fn extract_json_from_output(output: &str) -> Option<String> {
 
 /// Run diagnostics
 #[command]
-pub async fn run_doctor() -> Result<Vec<DiagnosticResult>, String> {
+pub async fn run_doctor() -> Result<Vec<DiagnosticResult>, AppError> {
     info!("[Diagnostics] Starting system diagnostics...");
     let mut results = Vec::new();
 
@@ -163,7 +163,7 @@ pub async fn run_doctor() -> Result<Vec<DiagnosticResult>, String> {
         results.push(DiagnosticResult {
             name: "OpenClaw Doctor".to_string(),
             passed: doctor_result.is_ok() && !doctor_result.as_ref().unwrap().contains("invalid"),
-            message: doctor_result.unwrap_or_else(|e| e),
+            message: doctor_result.unwrap_or_else(|e| e.to_string()),
             suggestion: None,
         });
     }
@@ -173,7 +173,7 @@ pub async fn run_doctor() -> Result<Vec<DiagnosticResult>, String> {
 
 /// Test AI connection
 #[command]
-pub async fn test_ai_connection() -> Result<AITestResult, String> {
+pub async fn test_ai_connection() -> Result<AITestResult, AppError> {
     info!("[AI Test] Starting AI connection test...");
 
     // Get current configured provider
@@ -220,7 +220,7 @@ pub async fn test_ai_connection() -> Result<AITestResult, String> {
             provider: "current".to_string(),
             model: "default".to_string(),
             response: None,
-            error: Some(e),
+            error: Some(e.to_string()),
             latency_ms: Some(latency),
         }),
     }
@@ -283,7 +283,7 @@ fn parse_channel_status_text(output: &str, channel_type: &str) -> Option<(bool,
 
 /// Test channel connection (check status and send test message)
 #[command]
-pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, String> {
+pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, AppError> {
     info!("[Channel Test] Testing channel: {}", channel_type);
     let channel_lower = channel_type.to_lowercase();
 
@@ -455,7 +455,7 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
                     success: false,
                     channel: channel_type.clone(),
                     message: format!("{} message send failed", channel_type),
-                    error: Some(e),
+                    error: Some(e.to_string()),
                 })
             }
         }
@@ -481,7 +481,7 @@ pub async fn test_channel(channel_type: String) -> Result<ChannelTestResult, Str
 
 /// Send test message to channel
 #[command]
-pub async fn send_test_message(channel_type: String, target: String) -> Result<ChannelTestResult, String> {
+pub async fn send_test_message(channel_type: String, target: String) -> Result<ChannelTestResult, AppError> {
     let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
     let message = format!("🤖 OpenClaw Test Message\n\n✅ Connection successful!\n⏰ {}", timestamp);
 
@@ -521,14 +521,14 @@ pub async fn send_test_message(channel_type: String, target: String) -> Result<C
             success: false,
             channel: channel_type,
             message: "Send failed".to_string(),
-            error: Some(e),
+            error: Some(e.to_string()),
         }),
     }
 }
 
 /// Get system information
 #[command]
-pub async fn get_system_info() -> Result<SystemInfo, String> {
+pub async fn get_system_info() -> Result<SystemInfo, AppError> {
     info!("[System Info] Getting system information...");
     let os = platform::get_os();
     let arch = platform::get_arch();
@@ -567,7 +567,7 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
 
 /// Start channel login (e.g., WhatsApp QR code scan)
 #[command]
-pub async fn start_channel_login(channel_type: String) -> Result<String, String> {
+pub async fn start_channel_login(channel_type: String) -> Result<String, AppError> {
     info!("[Channel Login] Starting channel login flow: {}", channel_type);
 
     match channel_type.as_str() {
@@ -640,7 +640,7 @@ echo "Step 2/3: Restarting Gateway to apply plugin..."
 openclaw gateway stop 2>/dev/null || true
 sleep 2
 # Start gateway service
-openclaw gateway start 2>/dev/null || openclaw gateway --port 18789 &
+openclaw gateway start 2>/dev/null || openclaw gateway --port {} &
 sleep 3
 echo "✅ Gateway restarted"
 echo ""
@@ -655,7 +655,8 @@ echo "Login complete!"
 echo ""
 read -p "Press Enter to close this window..."
 "#,
-                    env_path
+                    env_path,
+                    shell::get_gateway_port()
                 );
 
                 let script_path = "/tmp/openclaw_whatsapp_login.command";
@@ -717,7 +718,7 @@ read -p "Press Enter to close..."
                 }
 
                 if !launched {
-                    return Err("Unable to launch terminal, please run manually: openclaw channels login --channel whatsapp".to_string());
+                    return Err("Unable to launch terminal, please run manually: openclaw channels login --channel whatsapp".into());
                 }
             }
 
@@ -729,6 +730,6 @@ read -p "Press Enter to close..."
             #[cfg(not(target_os = "windows"))]
             Ok("WhatsApp login started in new terminal window, please check the popup terminal window and scan the QR code".to_string())
         }
-        _ => Err(format!("Login wizard not supported for {}", channel_type)),
+        _ => Err(AppError::invalid_input(format!("Login wizard not supported for {}", channel_type))),
     }
 }
@@ -1,4 +1,11 @@
-use crate::utils::{log_sanitizer, platform, shell};
+use crate::commands::{config, service};
+use crate::models::{AppError, ErrorCode};
+use crate::utils::secret_refs;
+use crate::utils::stop_policy::StopPolicy;
+use crate::utils::supervisor::BackoffPolicy;
+use crate::utils::systemd_unit::{self, UnitSpec, UnitStatus, UNIT_NAME};
+use crate::utils::{file, log_sanitizer, platform, shell};
+use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 use tauri::command;
 use log::{info, warn, error, debug};
@@ -49,7 +56,7 @@ pub struct InstallResult {
 
 /// Check environment status
 #[command]
-pub async fn check_environment() -> Result<EnvironmentStatus, String> {
+pub async fn check_environment() -> Result<EnvironmentStatus, AppError> {
     info!("[Environment Check] Starting system environment check...");
 
     let os = platform::get_os();
@@ -336,6 +343,9 @@ fn check_node_version_requirement(version: &Option<String>) -> bool {
 
 /// Check if gateway service is installed
 fn check_gateway_installed() -> bool {
+    if platform::get_os() == "linux" && gateway_unit_status().is_enabled() {
+        return true;
+    }
     match shell::run_openclaw(&["gateway", "status"]) {
         Ok(output) => {
             let lower = output.to_lowercase();
@@ -347,7 +357,7 @@ fn check_gateway_installed() -> bool {
             true
         }
         Err(e) => {
-            let lower = e.to_lowercase();
+            let lower = e.to_string().to_lowercase();
             // Some versions return error when not installed
             if lower.contains("not installed") || lower.contains("not found") {
                 return false;
@@ -361,16 +371,16 @@ fn check_gateway_installed() -> bool {
 
 /// Install gateway service (opens elevated terminal)
 #[command]
-pub async fn install_gateway_service() -> Result<String, String> {
+pub async fn install_gateway_service() -> Result<String, AppError> {
     info!("[Gateway Install] Starting gateway service installation...");
     let os = platform::get_os();
     info!("[Gateway Install] Detected operating system: {}", os);
 
     match os.as_str() {
-        "windows" => install_gateway_windows().await,
-        "macos" => install_gateway_macos().await,
-        "linux" => install_gateway_linux().await,
-        _ => Err(format!("Unsupported operating system: {}", os)),
+        "windows" => install_gateway_windows().await.map_err(AppError::from),
+        "macos" => install_gateway_macos().await.map_err(AppError::from),
+        "linux" => install_gateway_linux(),
+        _ => Err(AppError::invalid_input(format!("Unsupported operating system: {}", os))),
     }
 }
 
@@ -463,62 +473,201 @@ read -p "Press Enter to close this window..."
     Ok("Gateway install terminal opened. Please enter your password when prompted and click Refresh after completion.".to_string())
 }
 
-/// Install gateway service on Linux (terminal with sudo)
-async fn install_gateway_linux() -> Result<String, String> {
-    info!("[Gateway Install] Opening terminal for gateway install on Linux...");
+/// Install gateway service on Linux: a `systemd --user` unit generated by the Manager
+/// (no terminal or sudo needed, works headless and on Wayland-only desktops)
+fn install_gateway_linux() -> Result<String, AppError> {
+    let status = enable_unit()?;
+    Ok(status
+        .message
+        .unwrap_or_else(|| format!("Gateway service {} enabled", UNIT_NAME)))
+}
 
-    let script_content = r#"#!/bin/bash
-clear
-echo "========================================"
-echo "  OpenClaw Gateway Service Installer"
-echo "========================================"
-echo ""
-echo "Installing OpenClaw Gateway as a system service..."
-echo "You may be prompted for your password."
-echo ""
+/// Run `systemctl --user <args>`
+fn systemctl_user(args: &[&str]) -> Result<String, AppError> {
+    let mut full = vec!["--user"];
+    full.extend_from_slice(args);
+    shell::run_command_output("systemctl", &full)
+}
 
-sudo openclaw gateway install
+fn ensure_linux() -> Result<(), AppError> {
+    if platform::get_os() != "linux" {
+        return Err(AppError::invalid_input("systemd user units are only available on Linux"));
+    }
+    Ok(())
+}
 
-echo ""
-if [ $? -eq 0 ]; then
-    echo "✅ Gateway service installed successfully!"
-else
-    echo "❌ Installation failed. Please check the error above."
-fi
-echo ""
-echo "You can close this window and click Refresh in OpenClaw Manager."
-read -p "Press Enter to close this window..."
-"#;
+/// `~/.config/systemd/user/openclaw-gateway.service`
+fn unit_file_path() -> Result<PathBuf, AppError> {
+    let xdg_config_home = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from);
+    systemd_unit::user_unit_dir(xdg_config_home.as_deref(), dirs::home_dir().as_deref())
+        .map(|dir| dir.join(UNIT_NAME))
+        .ok_or_else(|| AppError::new(ErrorCode::Io, "Cannot determine the systemd user unit directory"))
+}
 
-    let script_path = "/tmp/openclaw_gateway_install.sh";
-    std::fs::write(script_path, script_content)
-        .map_err(|e| format!("Failed to create script: {}", e))?;
+/// Unit settings from the Manager's current configuration: port, env, supervisor backoff and stop grace period
+fn gateway_unit_spec() -> Result<UnitSpec, AppError> {
+    let mut openclaw_path = shell::get_openclaw_path().ok_or_else(AppError::openclaw_not_installed)?;
+    // systemd needs an absolute ExecStart path
+    if !openclaw_path.starts_with('/') {
+        openclaw_path = shell::run_command_output("which", &[openclaw_path.as_str()])?;
+    }
+    let port = shell::get_gateway_port();
+    let backoff = BackoffPolicy::default();
+    let stop = config::load_manager_config()
+        .map(|manager| StopPolicy::from_manager_config(&manager))
+        .unwrap_or_default();
+
+    Ok(UnitSpec {
+        openclaw_path,
+        port,
+        environment_file: platform::get_gateway_env_file_path(),
+        environment: vec![
+            ("PATH".to_string(), shell::get_extended_path()),
+            ("OPENCLAW_STATE_DIR".to_string(), platform::get_config_dir()),
+            ("OPENCLAW_CONFIG_PATH".to_string(), platform::get_config_file_path()),
+            ("OPENCLAW_GATEWAY_PORT".to_string(), port.to_string()),
+        ],
+        restart_sec: backoff.initial_delay.as_secs().max(1),
+        start_limit_burst: backoff.crash_loop_threshold,
+        start_limit_interval_sec: backoff.crash_window.as_secs(),
+        stop_timeout_sec: (stop.grace_period_ms / 1000).max(1),
+    })
+}
 
-    std::process::Command::new("chmod")
-        .args(["+x", script_path])
-        .output()
-        .map_err(|e| format!("Failed to set permissions: {}", e))?;
+/// Write the unit file and the env file it reads; both are regenerated on every enable
+fn write_gateway_unit() -> Result<PathBuf, AppError> {
+    let spec = gateway_unit_spec()?;
 
-    // Try different terminal emulators
-    let terminals = ["gnome-terminal", "xfce4-terminal", "konsole", "xterm"];
-    for term in terminals {
-        if std::process::Command::new(term)
-            .args(["--", script_path])
-            .spawn()
-            .is_ok()
-        {
-            info!("[Gateway Install] Terminal '{}' launched successfully on Linux", term);
-            return Ok("Gateway install terminal opened. Please enter your password when prompted and click Refresh after completion.".to_string());
+    let env_content = file::read_file(&platform::get_env_file_path()).unwrap_or_default();
+    // The env file holds API keys
+    file::write_private_file(
+        &spec.environment_file,
+        systemd_unit::render_environment_file(&env_content).as_bytes(),
+    )?;
+
+    let path = unit_file_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(&path, systemd_unit::render_unit(&spec))?;
+    info!("[Gateway Unit] Wrote {}", path.display());
+    Ok(path)
+}
+
+fn gateway_unit_status() -> UnitStatus {
+    let mut status = UnitStatus::default();
+    if let Ok(path) = unit_file_path() {
+        status.installed = path.exists();
+        status.unit_path = Some(path.display().to_string());
+    }
+    if platform::get_os() != "linux" {
+        status.message = Some("systemd user units are only available on Linux".to_string());
+        return status;
+    }
+
+    match systemctl_user(&["show", UNIT_NAME, "-p", "LoadState,ActiveState,SubState,UnitFileState,MainPID"]) {
+        Ok(output) => {
+            status.systemd_available = true;
+            systemd_unit::apply_show_output(&mut status, &output);
+        }
+        Err(e) => {
+            debug!("[Gateway Unit] systemctl --user show failed: {}", e);
+            status.message = Some(format!("systemctl --user is not available: {}", e.message));
         }
     }
 
-    warn!("[Gateway Install] No terminal emulator found on Linux");
-    Err("Unable to launch terminal. Please open a terminal and run: sudo openclaw gateway install".to_string())
+    if let Ok(user) = std::env::var("USER") {
+        status.linger = shell::run_command_output("loginctl", &["show-user", user.as_str(), "-p", "Linger", "--value"])
+            .ok()
+            .map(|v| v.trim() == "yes");
+    }
+    status
+}
+
+/// Write, reload and enable the unit; start it unless a gateway already holds the port
+fn enable_unit() -> Result<UnitStatus, AppError> {
+    ensure_linux()?;
+    // systemd starts the gateway without the Manager, so nothing could unlock the vault
+    if file::file_exists(&platform::get_secrets_vault_file_path()) {
+        return Err(AppError::invalid_input(
+            "The systemd unit cannot read secrets from the encrypted vault. Start the gateway from the Manager instead.",
+        ));
+    }
+    if secret_refs::uses_prefixed_references(&shell::read_openclaw_config_value()) {
+        return Err(AppError::invalid_input(
+            "openclaw.json uses ${env:NAME} / ${secret:NAME} references, which only a gateway started by the Manager can resolve. Use plain ${NAME} references with the env file instead.",
+        ));
+    }
+    write_gateway_unit()?;
+    systemctl_user(&["daemon-reload"])?;
+
+    let port = shell::get_gateway_port();
+    let started = if service::is_port_listening(port) {
+        info!("[Gateway Unit] Port {} is in use, enabling without starting", port);
+        systemctl_user(&["enable", UNIT_NAME])?;
+        false
+    } else {
+        systemctl_user(&["enable", "--now", UNIT_NAME])?;
+        true
+    };
+
+    let mut status = gateway_unit_status();
+    if !started {
+        status.message = Some(format!(
+            "Enabled. A gateway is already running on port {}; stop it and start the unit to switch over.",
+            port
+        ));
+    } else if !status.is_active() {
+        status.message = Some(format!(
+            "Enabled, but the unit is {}. See `journalctl --user -u {}`.",
+            status.active_state.as_deref().unwrap_or("not running"),
+            UNIT_NAME
+        ));
+    } else if status.linger == Some(false) {
+        status.message = Some("Enabled. Run `loginctl enable-linger` to keep the gateway running after you log out.".to_string());
+    }
+    info!("[Gateway Unit] Enabled {} (started: {})", UNIT_NAME, started);
+    Ok(status)
+}
+
+/// Status of the Manager's systemd user unit for the gateway
+#[command]
+pub async fn get_gateway_unit_status() -> Result<UnitStatus, AppError> {
+    Ok(gateway_unit_status())
+}
+
+/// Generate the systemd user unit from the current settings, enable and start it
+#[command]
+pub async fn enable_gateway_unit() -> Result<UnitStatus, AppError> {
+    enable_unit()
+}
+
+/// Stop and disable the systemd user unit; `remove` also deletes the generated files
+#[command]
+pub async fn disable_gateway_unit(remove: Option<bool>) -> Result<UnitStatus, AppError> {
+    ensure_linux()?;
+    let path = unit_file_path()?;
+    if !path.exists() {
+        return Err(AppError::not_found(format!("{} is not installed", UNIT_NAME)));
+    }
+
+    systemctl_user(&["disable", "--now", UNIT_NAME])?;
+    if remove.unwrap_or(false) {
+        std::fs::remove_file(&path)?;
+        let env_file = platform::get_gateway_env_file_path();
+        if file::file_exists(&env_file) {
+            std::fs::remove_file(&env_file)?;
+        }
+        systemctl_user(&["daemon-reload"])?;
+        info!("[Gateway Unit] Removed {}", path.display());
+    }
+    info!("[Gateway Unit] Disabled {}", UNIT_NAME);
+    Ok(gateway_unit_status())
 }
 
 /// Install Node.js
 #[command]
-pub async fn install_nodejs() -> Result<InstallResult, String> {
+pub async fn install_nodejs() -> Result<InstallResult, AppError> {
     info!("[Install Node.js] Starting Node.js installation...");
     let os = platform::get_os();
     info!("[Install Node.js] Detected operating system: {}", os);
@@ -552,7 +701,7 @@ pub async fn install_nodejs() -> Result<InstallResult, String> {
         Err(e) => error!("[Install Node.js] Installation error: {}", e),
     }
 
-    result
+    result.map_err(AppError::from)
 }
 
 /// Install Node.js on Windows
@@ -624,7 +773,7 @@ if ($nodeVersion) {
         Err(e) => Ok(InstallResult {
             success: false,
             message: "Node.js installation failed".to_string(),
-            error: Some(e),
+            error: Some(e.to_string()),
         }),
     }
 }
@@ -663,7 +812,7 @@ node --version
         Err(e) => Ok(InstallResult {
             success: false,
             message: "Node.js installation failed".to_string(),
-            error: Some(e),
+            error: Some(e.to_string()),
         }),
     }
 }
@@ -706,14 +855,14 @@ node --version
         Err(e) => Ok(InstallResult {
             success: false,
             message: "Node.js installation failed".to_string(),
-            error: Some(e),
+            error: Some(e.to_string()),
         }),
     }
 }
 
 /// Install OpenClaw
 #[command]
-pub async fn install_openclaw() -> Result<InstallResult, String> {
+pub async fn install_openclaw() -> Result<InstallResult, AppError> {
     info!("[Install OpenClaw] Starting OpenClaw installation...");
     let os = platform::get_os();
     info!("[Install OpenClaw] Detected operating system: {}", os);
@@ -735,7 +884,7 @@ pub async fn install_openclaw() -> Result<InstallResult, String> {
         Err(e) => error!("[Install OpenClaw] Installation error: {}", e),
     }
 
-    result
+    result.map_err(AppError::from)
 }
 
 /// Install OpenClaw on Windows
@@ -783,7 +932,7 @@ if ($openclawVersion) {
         Err(e) => Ok(InstallResult {
             success: false,
             message: "OpenClaw installation failed".to_string(),
-            error: Some(e),
+            error: Some(e.to_string()),
         }),
     }
 }
@@ -813,14 +962,14 @@ openclaw --version
         Err(e) => Ok(InstallResult {
             success: false,
             message: "OpenClaw installation failed".to_string(),
-            error: Some(e),
+            error: Some(e.to_string()),
         }),
     }
 }
 
 /// Initialize OpenClaw configuration
 #[command]
-pub async fn init_openclaw_config() -> Result<InstallResult, String> {
+pub async fn init_openclaw_config() -> Result<InstallResult, AppError> {
     info!("[Init Config] Starting OpenClaw configuration initialization...");
 
     let config_dir = platform::get_config_dir();
@@ -892,7 +1041,7 @@ pub async fn init_openclaw_config() -> Result<InstallResult, String> {
             Ok(InstallResult {
                 success: false,
                 message: "Configuration initialization failed".to_string(),
-                error: Some(e),
+                error: Some(e.to_string()),
             })
         },
     }
@@ -900,11 +1049,11 @@ pub async fn init_openclaw_config() -> Result<InstallResult, String> {
 
 /// Open terminal to execute installation script (for scenarios requiring administrator privileges)
 #[command]
-pub async fn open_install_terminal(install_type: String) -> Result<String, String> {
+pub async fn open_install_terminal(install_type: String) -> Result<String, AppError> {
     match install_type.as_str() {
-        "nodejs" => open_nodejs_install_terminal().await,
-        "openclaw" => open_openclaw_install_terminal().await,
-        _ => Err(format!("Unknown installation type: {}", install_type)),
+        "nodejs" => open_nodejs_install_terminal().await.map_err(AppError::from),
+        "openclaw" => open_openclaw_install_terminal().await.map_err(AppError::from),
+        _ => Err(AppError::invalid_input(format!("Unknown installation type: {}", install_type))),
     }
 }
 
@@ -1112,7 +1261,7 @@ read -p "Press Enter to close..."
 
 /// Uninstall OpenClaw
 #[command]
-pub async fn uninstall_openclaw() -> Result<InstallResult, String> {
+pub async fn uninstall_openclaw() -> Result<InstallResult, AppError> {
     info!("[Uninstall OpenClaw] Starting OpenClaw uninstallation...");
     let os = platform::get_os();
     info!("[Uninstall OpenClaw] Detected operating system: {}", os);
@@ -1155,7 +1304,7 @@ pub async fn uninstall_openclaw() -> Result<InstallResult, String> {
         Err(e) => error!("[Uninstall OpenClaw] Uninstallation error: {}", e),
     }
 
-    result
+    result.map_err(AppError::from)
 }
 
 /// Uninstall OpenClaw on Windows
@@ -1188,7 +1337,7 @@ async fn uninstall_openclaw_windows() -> Result<InstallResult, String> {
             Ok(InstallResult {
                 success: false,
                 message: "OpenClaw uninstallation failed".to_string(),
-                error: Some(e),
+                error: Some(e.to_string()),
             })
         }
     }
@@ -1219,7 +1368,7 @@ fi
         Err(e) => Ok(InstallResult {
             success: false,
             message: "OpenClaw uninstallation failed".to_string(),
-            error: Some(e),
+            error: Some(e.to_string()),
         }),
     }
 }
@@ -1239,7 +1388,7 @@ pub struct UpdateInfo {
 
 /// Check for OpenClaw updates
 #[command]
-pub async fn check_openclaw_update() -> Result<UpdateInfo, String> {
+pub async fn check_openclaw_update() -> Result<UpdateInfo, AppError> {
     info!("[Version Check] Starting OpenClaw update check...");
 
     // Get current version
@@ -1344,7 +1493,7 @@ fn compare_versions(current: &str, latest: &str) -> bool {
 
 /// Update OpenClaw
 #[command]
-pub async fn update_openclaw() -> Result<InstallResult, String> {
+pub async fn update_openclaw() -> Result<InstallResult, AppError> {
     info!("[Update OpenClaw] Starting OpenClaw update...");
     let os = platform::get_os();
 
@@ -1370,7 +1519,7 @@ pub async fn update_openclaw() -> Result<InstallResult, String> {
         Err(e) => error!("[Update OpenClaw] Update error: {}", e),
     }
 
-    result
+    result.map_err(AppError::from)
 }
 
 /// Update OpenClaw on Windows
@@ -1395,7 +1544,7 @@ async fn update_openclaw_windows() -> Result<InstallResult, String> {
             Ok(InstallResult {
                 success: false,
                 message: "OpenClaw update failed".to_string(),
-                error: Some(e),
+                error: Some(e.to_string()),
             })
         }
     }
@@ -1420,7 +1569,7 @@ openclaw --version
         Err(e) => Ok(InstallResult {
             success: false,
             message: "OpenClaw update failed".to_string(),
-            error: Some(e),
+            error: Some(e.to_string()),
         }),
     }
 }
@@ -0,0 +1,240 @@
+use crate::commands::{config, installer, service};
+use crate::models::{AppError, ErrorCode};
+use crate::utils::scheduler::{
+    self, CronSchedule, JobRun, MaintenanceAction, MaintenanceJob, MaintenanceSettings, RunHistory, RunOutcome,
+    RunTrigger,
+};
+use crate::utils::{platform, shell};
+use chrono::{Local, NaiveDateTime};
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{command, AppHandle, Emitter};
+
+/// Event carrying each finished (or skipped) maintenance run
+pub const MAINTENANCE_RUN_EVENT: &str = "maintenance-run";
+
+/// How often the scheduler looks for due jobs
+const SCHEDULER_TICK: Duration = Duration::from_secs(20);
+
+// One maintenance job at a time; a job that finds it taken is skipped, not queued
+static JOB_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+// Serializes read-modify-write of maintenance-history.json
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Next scheduled run of a job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextRun {
+    pub job_id: String,
+    /// RFC 3339, None when the job is disabled or never fires
+    pub at: Option<String>,
+}
+
+/// Maintenance settings with the upcoming run of each job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceOverview {
+    pub settings: MaintenanceSettings,
+    pub next_runs: Vec<NextRun>,
+}
+
+fn load_settings() -> Result<MaintenanceSettings, AppError> {
+    let manager = config::load_manager_config()?;
+    Ok(MaintenanceSettings::from_manager_config(&manager))
+}
+
+fn to_rfc3339(t: NaiveDateTime) -> Option<String> {
+    t.and_local_timezone(Local).earliest().map(|t| t.to_rfc3339())
+}
+
+fn overview(settings: MaintenanceSettings) -> MaintenanceOverview {
+    let now = Local::now().naive_local();
+    let next_runs = settings
+        .jobs
+        .iter()
+        .map(|job| NextRun {
+            job_id: job.id.clone(),
+            at: CronSchedule::parse(&job.schedule)
+                .ok()
+                .filter(|_| settings.enabled && job.enabled)
+                .and_then(|schedule| schedule.next_after(now))
+                .and_then(to_rfc3339),
+        })
+        .collect();
+    MaintenanceOverview { settings, next_runs }
+}
+
+/// Get the maintenance schedule
+#[command]
+pub async fn get_maintenance_settings() -> Result<MaintenanceOverview, AppError> {
+    Ok(overview(load_settings()?))
+}
+
+/// Save the maintenance schedule to manager.json
+#[command]
+pub async fn save_maintenance_settings(settings: MaintenanceSettings) -> Result<MaintenanceOverview, AppError> {
+    settings.validate().map_err(AppError::invalid_input)?;
+    let mut manager = config::load_manager_config()?;
+    if !manager.is_object() {
+        manager = serde_json::json!({});
+    }
+    manager[scheduler::MANAGER_KEY] = serde_json::to_value(&settings)
+        .map_err(|e| AppError::new(ErrorCode::Internal, "Failed to serialize maintenance settings").with_detail(e))?;
+    config::save_manager_config(&manager)?;
+    info!(
+        "[Maintenance] Schedule saved: {} job(s), scheduler {}",
+        settings.jobs.len(),
+        if settings.enabled { "enabled" } else { "disabled" }
+    );
+    Ok(overview(settings))
+}
+
+/// Run a job now, ignoring quiet hours and the maintenance window
+#[command]
+pub async fn run_maintenance_job(app: AppHandle, id: String) -> Result<JobRun, AppError> {
+    let settings = load_settings()?;
+    let job = settings
+        .job(&id)
+        .cloned()
+        .ok_or_else(|| AppError::not_found(format!("Maintenance job '{}' does not exist", id)))?;
+    Ok(run_job(&app, &settings, &job, RunTrigger::Manual).await)
+}
+
+/// Get the maintenance run history (oldest first)
+#[command]
+pub async fn get_maintenance_history() -> Result<RunHistory, AppError> {
+    scheduler::load(&platform::get_maintenance_history_file_path())
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to read maintenance history").with_detail(e))
+}
+
+/// Start the background scheduler; it re-reads the schedule from manager.json on every tick
+pub fn start_maintenance_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        info!("[Maintenance] Scheduler started");
+        let mut last_tick = Local::now().naive_local();
+        loop {
+            tokio::time::sleep(SCHEDULER_TICK).await;
+            let now = Local::now().naive_local();
+            // Clock moved backwards (DST, manual change): start over from here
+            if now <= last_tick {
+                last_tick = now;
+                continue;
+            }
+
+            let settings = match load_settings() {
+                Ok(settings) => settings,
+                Err(e) => {
+                    warn!("[Maintenance] Failed to load schedule: {}", e);
+                    last_tick = now;
+                    continue;
+                }
+            };
+            let due: Vec<MaintenanceJob> = settings.due_jobs(last_tick, now).into_iter().cloned().collect();
+            last_tick = now;
+            for job in due {
+                run_job(&app, &settings, &job, RunTrigger::Scheduled).await;
+            }
+        }
+    });
+}
+
+/// Run `job`, record the outcome in the history and emit it
+async fn run_job(app: &AppHandle, settings: &MaintenanceSettings, job: &MaintenanceJob, trigger: RunTrigger) -> JobRun {
+    let started_at = Local::now();
+    let started = Instant::now();
+
+    let result = match JOB_LOCK.try_lock() {
+        Err(_) => Err("Another maintenance job is running".to_string()),
+        Ok(_guard) => match skip_reason(settings, job, trigger, started_at.naive_local()) {
+            Some(reason) => Err(reason),
+            None => {
+                info!("[Maintenance] Running '{}' ({:?}, {:?})", job.name, job.action, trigger);
+                Ok(execute(app, job.action).await)
+            }
+        },
+    };
+
+    let (outcome, message) = match result {
+        Err(reason) => (RunOutcome::Skipped, reason),
+        Ok(Ok(message)) => (RunOutcome::Succeeded, message),
+        Ok(Err(e)) => (RunOutcome::Failed, e.message),
+    };
+    match outcome {
+        RunOutcome::Failed => error!("[Maintenance] '{}' failed: {}", job.name, message),
+        _ => info!("[Maintenance] '{}' {:?}: {}", job.name, outcome, message),
+    }
+
+    let run = JobRun {
+        job_id: job.id.clone(),
+        job_name: job.name.clone(),
+        action: job.action,
+        trigger,
+        started_at: started_at.to_rfc3339(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        outcome,
+        message: Some(message),
+    };
+    record_run(run.clone());
+    if let Err(e) = app.emit(MAINTENANCE_RUN_EVENT, &run) {
+        warn!("[Maintenance] Failed to emit {}: {}", MAINTENANCE_RUN_EVENT, e);
+    }
+    run
+}
+
+/// Manual runs are never skipped; scheduled ones honour quiet hours, the
+/// maintenance window and do not restart a gateway the user stopped
+fn skip_reason(settings: &MaintenanceSettings, job: &MaintenanceJob, trigger: RunTrigger, now: NaiveDateTime) -> Option<String> {
+    if trigger == RunTrigger::Manual {
+        return None;
+    }
+    if let Some(reason) = settings.skip_reason(job, now.time()) {
+        return Some(reason);
+    }
+    if job.action == MaintenanceAction::Restart && !service::is_port_listening(shell::get_gateway_port()) {
+        return Some("Gateway is not running".to_string());
+    }
+    None
+}
+
+async fn execute(app: &AppHandle, action: MaintenanceAction) -> Result<String, AppError> {
+    match action {
+        MaintenanceAction::Restart => service::restart_service(app.clone()).await,
+        MaintenanceAction::Update => {
+            let was_running = service::is_port_listening(shell::get_gateway_port());
+            let result = installer::update_openclaw().await?;
+            if !result.success {
+                let error = AppError::new(ErrorCode::CommandFailed, result.message);
+                return Err(match result.error {
+                    Some(detail) => error.with_detail(detail),
+                    None => error,
+                });
+            }
+            // The update stops the gateway; bring it back if it was up
+            if was_running {
+                service::restart_service(app.clone()).await?;
+            }
+            Ok(result.message)
+        }
+        MaintenanceAction::RepairDeviceToken => {
+            let repaired = config::repair_device_token().await?;
+            service::restart_service(app.clone()).await?;
+            Ok(format!("{} Gateway restarted.", repaired))
+        }
+    }
+}
+
+/// Append to maintenance-history.json
+fn record_run(run: JobRun) {
+    let _guard = HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = platform::get_maintenance_history_file_path();
+    let mut history = scheduler::load(&path).unwrap_or_else(|e| {
+        warn!("[Maintenance] Failed to read run history, starting a new one: {}", e);
+        RunHistory::default()
+    });
+    history.push(run);
+    if let Err(e) = scheduler::save(&path, &history) {
+        warn!("[Maintenance] Failed to save run history: {}", e);
+    }
+}
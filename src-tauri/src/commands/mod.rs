@@ -1,6 +1,9 @@
 pub mod config;
+pub mod dashboard;
 pub mod diagnostics;
 pub mod installer;
+pub mod maintenance;
 pub mod process;
+pub mod secrets;
 pub mod service;
 pub mod skills;
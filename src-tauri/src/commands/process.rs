@@ -1,10 +1,12 @@
+use crate::commands::service;
+use crate::models::{AppError, HealthState};
 use crate::utils::shell;
 use tauri::command;
 use log::{info, debug};
 
 /// Check if OpenClaw is installed
 #[command]
-pub async fn check_openclaw_installed() -> Result<bool, String> {
+pub async fn check_openclaw_installed() -> Result<bool, AppError> {
     info!("[Process Check] Checking if OpenClaw is installed...");
     // Use get_openclaw_path to check, because command_exists may be unreliable on Windows
     let installed = shell::get_openclaw_path().is_some();
@@ -14,7 +16,7 @@ pub async fn check_openclaw_installed() -> Result<bool, String> {
 
 /// Get OpenClaw version
 #[command]
-pub async fn get_openclaw_version() -> Result<Option<String>, String> {
+pub async fn get_openclaw_version() -> Result<Option<String>, AppError> {
     info!("[Process Check] Getting OpenClaw version...");
     // Use run_openclaw to get the version
     match shell::run_openclaw(&["--version"]) {
@@ -32,21 +34,19 @@ pub async fn get_openclaw_version() -> Result<Option<String>, String> {
 
 /// Check if port is in use (by attempting to connect to openclaw gateway)
 #[command]
-pub async fn check_port_in_use(port: u16) -> Result<bool, String> {
+pub async fn check_port_in_use(port: u16) -> Result<bool, AppError> {
     info!("[Process Check] Checking if port {} is in use...", port);
 
-    // Use openclaw health to check if gateway is running
-    // If port is the default 18789, use openclaw health directly
-    if port == 18789 {
-        debug!("[Process Check] Using openclaw health to check port 18789...");
-        let result = shell::run_openclaw(&["health", "--timeout", "2000"]);
-        // If health command succeeds, the port is occupied by gateway
-        let in_use = result.is_ok();
-        info!("[Process Check] Port 18789 status: {}", if in_use { "in use" } else { "available" });
+    // For the configured gateway port, probe the gateway health endpoint
+    if port == shell::get_gateway_port() {
+        debug!("[Process Check] Probing gateway health on port {}...", port);
+        // Any HTTP answer (even degraded) means the gateway occupies the port
+        let in_use = service::probe_gateway(port).state != HealthState::Down;
+        info!("[Process Check] Port {} status: {}", port, if in_use { "in use" } else { "available" });
         return Ok(in_use);
     }
 
-    // For non-default ports, try using TCP connection check
+    // For other ports, try using TCP connection check
     debug!("[Process Check] Using TCP connection to check port {}...", port);
     use std::net::TcpStream;
     use std::time::Duration;
@@ -72,7 +72,7 @@ pub struct SecureVersionInfo {
 
 /// Check if current OpenClaw version is secure (>= 2026.1.29)
 #[command]
-pub async fn check_secure_version() -> Result<SecureVersionInfo, String> {
+pub async fn check_secure_version() -> Result<SecureVersionInfo, AppError> {
     info!("[Process Check] Checking OpenClaw version security...");
     match shell::run_openclaw(&["--version"]) {
         Ok(version) => {
@@ -96,7 +96,7 @@ pub async fn check_secure_version() -> Result<SecureVersionInfo, String> {
 
 /// Get Node.js version
 #[command]
-pub async fn get_node_version() -> Result<Option<String>, String> {
+pub async fn get_node_version() -> Result<Option<String>, AppError> {
     info!("[Process Check] Getting Node.js version...");
     if !shell::command_exists("node") {
         info!("[Process Check] Node.js is not installed");
@@ -0,0 +1,300 @@
+use crate::commands::config;
+use crate::models::{AppError, ErrorCode};
+use crate::utils::secret_scan::{self, LeakFinding, MAX_FILE_BYTES};
+use crate::utils::secrets_vault::{self, VaultStatus};
+use crate::utils::{file, platform, shell};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+/// Shortest passphrase accepted for a new vault
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+fn vault_error(message: &str, e: String) -> AppError {
+    AppError::new(ErrorCode::Io, message).with_detail(e)
+}
+
+/// Get the secrets vault state of the active profile (names only, never values)
+#[command]
+pub async fn get_vault_status() -> Result<VaultStatus, AppError> {
+    secrets_vault::status().map_err(|e| vault_error("Failed to read the secrets vault", e))
+}
+
+/// Create the secrets vault. With a passphrase the key is derived from it (Argon2id);
+/// without one a random key is kept in a local key file.
+#[command]
+pub async fn create_vault(passphrase: Option<String>) -> Result<VaultStatus, AppError> {
+    let passphrase = passphrase.filter(|p| !p.is_empty());
+    if passphrase.as_ref().is_some_and(|p| p.chars().count() < MIN_PASSPHRASE_LEN) {
+        return Err(AppError::invalid_input(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_LEN
+        )));
+    }
+    secrets_vault::create(passphrase.as_deref(), secrets_vault::Secrets::new())
+        .map_err(|e| vault_error("Failed to create the secrets vault", e))?;
+    info!(
+        "[Secrets] Vault created ({})",
+        if passphrase.is_some() { "passphrase" } else { "key file" }
+    );
+    get_vault_status().await
+}
+
+/// Unlock a passphrase-protected vault for this session
+#[command]
+pub async fn unlock_vault(passphrase: String) -> Result<VaultStatus, AppError> {
+    secrets_vault::unlock(Some(&passphrase)).map_err(|e| {
+        AppError::new(ErrorCode::InvalidInput, "Failed to unlock the secrets vault").with_detail(e)
+    })?;
+    info!("[Secrets] Vault unlocked");
+    get_vault_status().await
+}
+
+/// Forget the vault key until it is unlocked again
+#[command]
+pub async fn lock_vault() -> Result<VaultStatus, AppError> {
+    secrets_vault::lock();
+    info!("[Secrets] Vault locked");
+    get_vault_status().await
+}
+
+/// Add or replace a secret; reference it from the config as `${NAME}`
+#[command]
+pub async fn set_secret(name: String, value: String) -> Result<VaultStatus, AppError> {
+    if !secrets_vault::is_valid_name(&name) {
+        return Err(AppError::invalid_input(
+            "Secret names are upper-case env var names (A-Z, 0-9, _), e.g. OPENAI_API_KEY",
+        ));
+    }
+    if value.is_empty() {
+        return Err(AppError::invalid_input("Secret value is empty"));
+    }
+    secrets_vault::update(|secrets| {
+        secrets.insert(name.clone(), value);
+    })
+    .map_err(|e| vault_error("Failed to save the secret", e))?;
+    info!("[Secrets] Saved secret {}", name);
+    get_vault_status().await
+}
+
+/// Remove a secret from the vault
+#[command]
+pub async fn delete_secret(name: String) -> Result<VaultStatus, AppError> {
+    let mut found = false;
+    secrets_vault::update(|secrets| found = secrets.remove(&name).is_some())
+        .map_err(|e| vault_error("Failed to delete the secret", e))?;
+    if !found {
+        return Err(AppError::not_found(format!("Secret {} does not exist", name)));
+    }
+    info!("[Secrets] Deleted secret {}", name);
+    get_vault_status().await
+}
+
+/// Where a leaked value is moved to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeakTarget {
+    Vault,
+    EnvFile,
+}
+
+/// Result of a leak scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeakScanReport {
+    pub findings: Vec<LeakFinding>,
+    pub files_scanned: usize,
+    /// More than `MAX_FINDINGS` were found; only the first are listed
+    pub truncated: bool,
+}
+
+/// Deepest directory level walked below a scan root
+const MAX_SCAN_DEPTH: usize = 8;
+
+fn walk(dir: &Path, depth: usize, skip_dirs: &[PathBuf], excluded: &[String], files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        // Symlinks are not followed, so a link to $HOME can't widen the scan
+        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+            continue;
+        };
+        if meta.is_dir() {
+            let name = entry.file_name();
+            let hidden_or_deps = name == "node_modules" || name == ".git" || name == "logs";
+            if depth < MAX_SCAN_DEPTH && !hidden_or_deps && !skip_dirs.contains(&path) {
+                walk(&path, depth + 1, skip_dirs, excluded, files);
+            }
+        } else if meta.is_file() && meta.len() <= MAX_FILE_BYTES && secret_scan::is_scannable(&path, excluded) {
+            files.push(path);
+        }
+    }
+}
+
+/// Files the leak scan reads: the OpenClaw home (workspace notes, per-agent models.json,
+/// sessions), agent workspaces configured elsewhere, `/tmp/openclaw_*` helper scripts
+/// configs written by Export Config and config history snapshots. The env file and the
+/// vault are left out: they are where secrets are supposed to live.
+fn leak_scan_files() -> Vec<PathBuf> {
+    let config_dir = PathBuf::from(platform::get_config_dir());
+    let excluded = vec![
+        platform::get_env_file_path(),
+        platform::get_gateway_env_file_path(),
+        platform::get_secrets_vault_file_path(),
+        platform::get_secrets_key_file_path(),
+        platform::get_runtime_config_file_path(),
+    ];
+    let skip_dirs = vec![PathBuf::from(platform::get_mcp_install_dir())];
+
+    let mut files = Vec::new();
+    walk(&config_dir, 0, &skip_dirs, &excluded, &mut files);
+
+    let openclaw = shell::read_openclaw_config_value();
+    let agent_workspaces = openclaw
+        .pointer("/agents/list")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|agent| agent.get("workspace"))
+        .chain(openclaw.pointer("/agents/defaults/workspace"))
+        .filter_map(|v| v.as_str())
+        .map(PathBuf::from)
+        .filter(|p| !p.starts_with(&config_dir))
+        .collect::<Vec<_>>();
+    for workspace in agent_workspaces {
+        walk(&workspace, 0, &skip_dirs, &excluded, &mut files);
+    }
+
+    if let Ok(entries) = std::fs::read_dir(std::env::temp_dir()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let helper = entry.file_name().to_string_lossy().starts_with("openclaw_");
+            if helper && path.is_file() && secret_scan::is_scannable(&path, &excluded) {
+                files.push(path);
+            }
+        }
+    }
+
+    let exported = config::load_manager_config()
+        .ok()
+        .and_then(|m| m.get(secret_scan::EXPORTED_CONFIGS_KEY).cloned())
+        .and_then(|v| serde_json::from_value::<Vec<String>>(v).ok())
+        .unwrap_or_default();
+    files.extend(exported.into_iter().map(PathBuf::from).filter(|p| p.is_file()));
+
+    files.sort();
+    files.dedup();
+    files
+}
+
+fn scan_leaks() -> LeakScanReport {
+    let files = leak_scan_files();
+    let vault_names = secrets_vault::session_secrets().ok().flatten().unwrap_or_default();
+    let mut findings = Vec::new();
+    let mut truncated = false;
+    for path in &files {
+        if truncated {
+            break;
+        }
+        let Ok(content) = std::fs::read_to_string(path) else {
+            continue;
+        };
+        let file = path.to_string_lossy();
+        for text_match in secret_scan::scan_text(&content) {
+            if findings.len() == secret_scan::MAX_FINDINGS {
+                truncated = true;
+                break;
+            }
+            let mut finding = text_match.to_finding(&file);
+            finding.suggested_name = secret_scan::unique_name(&finding.suggested_name, &vault_names);
+            findings.push(finding);
+        }
+    }
+    LeakScanReport { findings, files_scanned: files.len(), truncated }
+}
+
+/// Look for API keys and tokens pasted into files under the OpenClaw home, agent
+/// workspaces, temporary helper scripts and exported configs. Values are masked.
+#[command]
+pub async fn scan_for_leaked_secrets() -> Result<LeakScanReport, AppError> {
+    info!("[Secrets] Scanning for leaked secrets...");
+    let report = tokio::task::spawn_blocking(scan_leaks)
+        .await
+        .map_err(|e| AppError::new(ErrorCode::Io, "Secret scan failed").with_detail(e))?;
+    info!(
+        "[Secrets] Scanned {} file(s), {} finding(s)",
+        report.files_scanned,
+        report.findings.len()
+    );
+    Ok(report)
+}
+
+/// Move a value found by the scan into the vault or the env file and leave a
+/// `${NAME}` reference in its place
+#[command]
+pub async fn move_leaked_secret(
+    file: String,
+    line: usize,
+    fingerprint: String,
+    name: String,
+    target: LeakTarget,
+) -> Result<String, AppError> {
+    if !secrets_vault::is_valid_name(&name) {
+        return Err(AppError::invalid_input(
+            "Secret names are upper-case env var names (A-Z, 0-9, _), e.g. OPENAI_API_KEY",
+        ));
+    }
+    // Only files the scan itself would read can be rewritten
+    let path = PathBuf::from(&file);
+    if !leak_scan_files().contains(&path) {
+        return Err(AppError::invalid_input(format!("{} is not part of the secret scan", file)));
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to read the file").with_detail(e))?;
+    let value = secret_scan::scan_text(&content)
+        .into_iter()
+        .find(|m| m.line == line && m.movable && secret_scan::fingerprint(&m.value) == fingerprint)
+        .map(|m| m.value)
+        .ok_or_else(|| {
+            AppError::not_found("The value is no longer on that line").with_hint("Run the scan again.")
+        })?;
+
+    match target {
+        LeakTarget::Vault => secrets_vault::update(|secrets| {
+            secrets.insert(name.clone(), value.clone());
+        })
+        .map(|_| ())
+        .map_err(|e| vault_error("Failed to store the secret in the vault", e))?,
+        LeakTarget::EnvFile => file::set_env_value(&platform::get_env_file_path(), &name, &value)
+            .map_err(|e| AppError::new(ErrorCode::Io, "Failed to write the env file").with_detail(e))?,
+    }
+
+    let reference = secrets_vault::reference(&name);
+    let replace = |text: &str| {
+        secret_scan::replace_on_line(text, line, &value, &reference)
+            .ok_or_else(|| AppError::not_found("The value is no longer on that line"))
+    };
+    // Config files are saved like any other edit: locked, validated and snapshotted
+    if path == Path::new(&platform::get_config_file_path()) {
+        config::edit_openclaw_config_text("move_leaked_secret", replace).await?;
+    } else if path == Path::new(&platform::get_mcp_config_file_path()) {
+        config::edit_mcp_config_text(replace)?;
+    } else {
+        file::write_file(&file, &replace(&content)?)
+            .map_err(|e| AppError::new(ErrorCode::Io, "Failed to update the file").with_detail(e))?;
+    }
+    config::redact_config_history(&value, &reference);
+    info!("[Secrets] Moved a value from {}:{} to {} ({:?})", file, line, name, target);
+    Ok(format!(
+        "Moved to {} as {}; {}:{} now references it",
+        match target {
+            LeakTarget::Vault => "the vault",
+            LeakTarget::EnvFile => "the env file",
+        },
+        name,
+        file,
+        line
+    ))
+}
@@ -1,413 +1,1192 @@
-use crate::models::ServiceStatus;
-use crate::utils::shell;
-use tauri::command;
-use std::process::Command;
+use crate::models::{AppError, ErrorCode, GatewayState, HealthReport, HealthState, ServiceStatus};
+use crate::utils::supervisor::{
+    self, BackoffPolicy, CrashDecision, CrashTracker, RestartHistory, RestartOutcome, RestartRecord,
+};
+use crate::utils::gateway_logs::{self, GatewayLogEntry, GatewayLogPage, LogLevel, LogTail};
+use crate::commands::config;
+use crate::utils::gateway_process::{self, PidRecord, StopSignal};
+use crate::utils::health_probe;
+use crate::utils::port_scan;
+use crate::utils::proc_stats::{self, ResourceSample, ResourceSeries};
+use crate::utils::service_state::{
+    ActiveOperation, ServiceMachine, ServiceOperation, ServicePhase, ServiceProgress, TransitionError,
+};
+use crate::utils::stop_policy::{self, StopPolicy, StopStep};
+use crate::utils::secret_refs;
+use crate::utils::{platform, shell};
+use tauri::{command, AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::Child;
 use log::{info, warn, debug, error};
-use std::sync::atomic::{AtomicBool, Ordering};
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-// Track if service stop was intentional (manual stop) vs unexpected (crash/restart command)
-static INTENTIONAL_STOP: AtomicBool = AtomicBool::new(false);
+/// Event emitted when the supervisor gives up on a crash-looping gateway
+pub const CRASH_LOOP_EVENT: &str = "service-crash-loop";
 
-#[cfg(windows)]
-use std::os::windows::process::CommandExt;
+/// Event carrying each captured gateway log line
+pub const GATEWAY_LOG_EVENT: &str = "gateway-log";
 
-/// Windows CREATE_NO_WINDOW flag to hide console window
-#[cfg(windows)]
-const CREATE_NO_WINDOW: u32 = 0x08000000;
+/// How often gateway.log is checked for new output
+const LOG_TAIL_INTERVAL: Duration = Duration::from_millis(250);
 
-const SERVICE_PORT: u16 = 18789;
+/// Event carrying start/stop/restart progress
+pub const SERVICE_PROGRESS_EVENT: &str = "service-progress";
 
-/// Check if a service is listening on the port, return PID
-/// Simple and direct: port in use = service running
-fn check_port_listening(port: u16) -> Option<u32> {
-    #[cfg(unix)]
-    {
-        let output = Command::new("lsof")
-            .args(["-ti", &format!(":{}", port)])
-            .output()
-            .ok()?;
-        
-        if output.status.success() {
-            String::from_utf8_lossy(&output.stdout)
-                .lines()
-                .next()
-                .and_then(|line| line.trim().parse::<u32>().ok())
-        } else {
-            None
+/// How often a waiting service operation checks for cancellation
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often the supervisor checks the gateway port
+const SUPERVISOR_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Timeout of one HTTP health probe
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+// Bumped on every Start/Stop/Restart; a supervisor thread exits once its generation is stale,
+// so a manual stop is never mistaken for a crash and at most one supervisor is active
+static SUPERVISOR_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+// Held for the whole of a Start/Stop/Restart so concurrent requests run one after another
+static SERVICE_OPERATION_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+// Tracks the operation holding SERVICE_OPERATION_LOCK and its cancel requests
+static SERVICE_MACHINE: Lazy<Mutex<ServiceMachine>> = Lazy::new(|| Mutex::new(ServiceMachine::default()));
+
+// Serializes read-modify-write of restart-history.json
+static RESTART_HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Gateway process started by the Manager
+struct OwnedGateway {
+    pid: u32,
+    port: u16,
+    /// None when adopted from the PID file after the Manager restarted
+    child: Option<Child>,
+    exited: bool,
+    exit_status: Option<String>,
+}
+
+impl OwnedGateway {
+    /// Refresh the exit state; true while the process is alive
+    fn is_alive(&mut self) -> bool {
+        if self.exited {
+            return false;
         }
-    }
-    
-    #[cfg(windows)]
-    {
-        let mut cmd = Command::new("netstat");
-        cmd.args(["-ano"]);
-        cmd.creation_flags(CREATE_NO_WINDOW);
-        
-        let output = cmd.output().ok()?;
-        
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.lines() {
-                if line.contains(&format!(":{}", port)) && line.contains("LISTENING") {
-                    if let Some(pid_str) = line.split_whitespace().last() {
-                        if let Ok(pid) = pid_str.parse::<u32>() {
-                            return Some(pid);
-                        }
-                    }
+        match self.child.as_mut() {
+            Some(child) => match child.try_wait() {
+                Ok(Some(status)) => {
+                    self.exited = true;
+                    self.exit_status = Some(status.to_string());
                 }
-            }
+                Ok(None) => {}
+                Err(e) => warn!("[Service] Failed to query gateway PID {} exit status: {}", self.pid, e),
+            },
+            None => self.exited = !gateway_process::is_process_alive(self.pid),
+        }
+        if self.exited {
+            info!(
+                "[Service] Gateway PID {} exited ({})",
+                self.pid,
+                self.exit_status.as_deref().unwrap_or("exit status unknown")
+            );
+            gateway_process::remove_pid_file(&platform::get_gateway_pid_file_path());
         }
-        None
+        !self.exited
     }
 }
 
-/// Find ALL PIDs using a given port (not just the first one)
+/// What the Manager knows about the gateway it started
+enum OwnedState {
+    Alive(u32),
+    Exited(Option<String>),
+    Missing,
+}
+
+// The gateway this Manager started; adopted from the PID file after a Manager restart
+static OWNED_GATEWAY: Mutex<Option<OwnedGateway>> = Mutex::new(None);
+
+/// Minimum time between resource samples; faster status polls reuse the last one
+const RESOURCE_SAMPLE_INTERVAL_MS: i64 = 5000;
+
+/// Resource series of the gateway process plus the CPU reading of the previous sample
+#[derive(Default)]
+struct ResourceSampler {
+    series: ResourceSeries,
+    prev_cpu: Option<(u32, u64, Instant)>,
+}
+
+static RESOURCE_SAMPLER: Lazy<Mutex<ResourceSampler>> = Lazy::new(|| Mutex::new(ResourceSampler::default()));
+
+static CLOCK_TICKS: Lazy<u64> = Lazy::new(proc_stats::ticks_per_second);
+
+/// Port the gateway was listening on before `save_gateway_config` changed it.
+/// The next stop/restart also stops whatever still listens there.
+static PREVIOUS_PORT: Mutex<Option<u16>> = Mutex::new(None);
+
+/// Remember that the gateway is still running on `old_port` after a port change
+pub fn note_port_change(old_port: u16) {
+    *PREVIOUS_PORT.lock().unwrap_or_else(|e| e.into_inner()) = Some(old_port);
+}
+
+fn take_previous_port() -> Option<u16> {
+    PREVIOUS_PORT.lock().unwrap_or_else(|e| e.into_inner()).take()
+}
+
+/// Whether anything is listening on `port`
+pub fn is_port_listening(port: u16) -> bool {
+    check_port_listening(port).is_some()
+}
+
+/// PID listening on the port, if any
+fn check_port_listening(port: u16) -> Option<u32> {
+    port_scan::listening_pids(port).into_iter().next()
+}
+
+/// Probe the gateway's health endpoint
+pub fn probe_gateway(port: u16) -> HealthReport {
+    probe_listener(port, port_state(port).state)
+}
+
+/// Probe the health endpoint. The configured token is only sent to the gateway the
+/// Manager started, never to a foreign process listening on the port.
+fn probe_listener(port: u16, state: GatewayState) -> HealthReport {
+    let token = match state {
+        GatewayState::Ours => shell::read_gateway_token(),
+        _ => None,
+    };
+    health_probe::probe(port, token.as_deref(), HEALTH_PROBE_TIMEOUT)
+}
+
+/// Find ALL PIDs with a socket bound to the port (listener and accepted connections)
 fn find_all_port_pids(port: u16) -> Vec<u32> {
-    let mut pids = Vec::new();
+    port_scan::bound_pids(port)
+}
 
-    #[cfg(unix)]
-    {
-        if let Ok(output) = Command::new("lsof")
-            .args(["-ti", &format!(":{}", port)])
-            .output()
-        {
-            if output.status.success() {
-                for line in String::from_utf8_lossy(&output.stdout).lines() {
-                    if let Ok(pid) = line.trim().parse::<u32>() {
-                        if pid > 0 && !pids.contains(&pid) {
-                            pids.push(pid);
-                        }
+/// Track a freshly spawned gateway and record its PID file
+fn track_gateway(child: Child, port: u16) {
+    let pid = child.id();
+    let record = PidRecord { pid, port, started: gateway_process::process_start_time(pid) };
+    if let Err(e) = gateway_process::write_pid_file(&platform::get_gateway_pid_file_path(), &record) {
+        warn!("[Service] Failed to write gateway PID file: {}", e);
+    }
+    let mut owned = OWNED_GATEWAY.lock().unwrap_or_else(|e| e.into_inner());
+    *owned = Some(OwnedGateway {
+        pid,
+        port,
+        child: Some(child),
+        exited: false,
+        exit_status: None,
+    });
+}
+
+/// Follow gateway.log in the background: emit new lines as `gateway-log` events and
+/// rotate the file when it grows too large
+pub fn start_gateway_log_tail(app: AppHandle) {
+    let mut tail = LogTail::at_end(platform::get_gateway_log_file_path());
+    let spawned = thread::Builder::new().name("gateway-log-tail".to_string()).spawn(move || loop {
+        thread::sleep(LOG_TAIL_INTERVAL);
+        // The active profile (and so the log path) can change
+        let path = platform::get_gateway_log_file_path();
+        if tail.path() != Path::new(&path) {
+            tail = LogTail::at_end(path);
+        }
+        match tail.read_lines() {
+            Ok(lines) => {
+                for line in lines {
+                    let mut entry = GatewayLogEntry::parse(&line);
+                    if entry.timestamp.is_empty() {
+                        entry.timestamp = chrono::Local::now().to_rfc3339();
                     }
+                    let _ = app.emit(GATEWAY_LOG_EVENT, &entry);
                 }
             }
+            Err(e) => {
+                warn!("[Service] Failed to read gateway log {}: {}", tail.path().display(), e);
+                continue;
+            }
+        }
+        let _rotation = gateway_logs::LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        match gateway_logs::rotate_if_needed(tail.path(), gateway_logs::MAX_LOG_BYTES, gateway_logs::MAX_LOG_FILES) {
+            Ok(true) => debug!("[Service] Rotated {}", tail.path().display()),
+            Ok(false) => {}
+            Err(e) => warn!("[Service] Failed to rotate gateway log {}: {}", tail.path().display(), e),
         }
+    });
+    if let Err(e) = spawned {
+        error!("[Service] Failed to start gateway log tail: {}", e);
     }
+}
 
-    #[cfg(windows)]
-    {
-        let mut cmd = Command::new("netstat");
-        cmd.args(["-ano"]);
-        cmd.creation_flags(CREATE_NO_WINDOW);
-
-        if let Ok(output) = cmd.output() {
-            if output.status.success() {
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                for line in stdout.lines() {
-                    if line.contains(&format!(":{}", port)) {
-                        if let Some(pid_str) = line.split_whitespace().last() {
-                            if let Ok(pid) = pid_str.parse::<u32>() {
-                                if pid > 0 && !pids.contains(&pid) {
-                                    pids.push(pid);
-                                }
-                            }
-                        }
-                    }
-                }
+/// A page of captured gateway log entries, see `gateway_logs::read_page`
+fn read_captured_logs(skip: usize, limit: usize, level: Option<LogLevel>) -> Result<GatewayLogPage, AppError> {
+    let path = PathBuf::from(platform::get_gateway_log_file_path());
+    let _rotation = gateway_logs::LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    gateway_logs::read_page(&path, gateway_logs::MAX_LOG_FILES, skip, limit, level)
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to read gateway logs").with_detail(e))
+}
+
+/// Whether the PID file still describes a running gateway of ours: the PID was not
+/// reused by another process and it (or its child behind the Windows shim) still
+/// listens on the recorded port
+fn is_recorded_gateway(record: &PidRecord) -> bool {
+    if !gateway_process::is_process_alive(record.pid) {
+        return false;
+    }
+    let started = gateway_process::process_start_time(record.pid);
+    let command = gateway_process::process_command(record.pid);
+    if !record.matches(started.as_deref(), command.as_deref()) {
+        return false;
+    }
+    match check_port_listening(record.port) {
+        Some(listener) => listener == record.pid || gateway_process::parent_pid(listener) == Some(record.pid),
+        None => false,
+    }
+}
+
+/// State of the gateway we started; `take_exited` hands an exited gateway to the caller
+fn owned_gateway_state(take_exited: bool) -> OwnedState {
+    let mut owned = OWNED_GATEWAY.lock().unwrap_or_else(|e| e.into_inner());
+    if owned.is_none() {
+        // Gateway started by an earlier Manager run
+        let pid_file = platform::get_gateway_pid_file_path();
+        if let Some(record) = gateway_process::read_pid_file(&pid_file) {
+            if is_recorded_gateway(&record) {
+                info!("[Service] Adopting gateway PID {} (port {}) from PID file", record.pid, record.port);
+                *owned = Some(OwnedGateway {
+                    pid: record.pid,
+                    port: record.port,
+                    child: None,
+                    exited: false,
+                    exit_status: None,
+                });
+            } else {
+                info!("[Service] PID file no longer matches a running gateway, removing it");
+                gateway_process::remove_pid_file(&pid_file);
             }
         }
     }
 
-    pids
+    let Some(gateway) = owned.as_mut() else {
+        return OwnedState::Missing;
+    };
+    if gateway.is_alive() {
+        return OwnedState::Alive(gateway.pid);
+    }
+    if take_exited {
+        let exit_status = gateway.exit_status.clone();
+        *owned = None;
+        OwnedState::Exited(exit_status)
+    } else {
+        OwnedState::Exited(gateway.exit_status.clone())
+    }
 }
 
-/// Get service status (simple version: directly check port usage)
-#[command]
-pub async fn get_service_status() -> Result<ServiceStatus, String> {
-    // Simple and direct: check if port is in use
-    let pid = check_port_listening(SERVICE_PORT);
-    let running = pid.is_some();
-    
-    Ok(ServiceStatus {
-        running,
+/// PID of our gateway while it is alive
+fn owned_gateway_pid() -> Option<u32> {
+    match owned_gateway_state(false) {
+        OwnedState::Alive(pid) => Some(pid),
+        _ => None,
+    }
+}
+
+/// Stop tracking our gateway after it was stopped
+fn forget_owned_gateway() {
+    let taken = OWNED_GATEWAY.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some(mut gateway) = taken {
+        // Reap the child so it does not linger as a zombie
+        if let Some(child) = gateway.child.as_mut() {
+            let _ = child.try_wait();
+        }
+        debug!("[Service] No longer tracking gateway PID {} (port {})", gateway.pid, gateway.port);
+    }
+    gateway_process::remove_pid_file(&platform::get_gateway_pid_file_path());
+}
+
+/// Signal our gateway's process group (the node process behind the Windows .cmd shim included)
+fn signal_owned_gateway(pid: u32, signal: StopSignal) -> bool {
+    let sent = gateway_process::send_signal(pid, signal, true);
+    if !sent {
+        warn!("[Service] Failed to send {:?} to gateway PID {}", signal, pid);
+    }
+    sent
+}
+
+/// Force-kill our gateway and reap it so it does not linger as a zombie
+fn kill_owned_gateway(pid: u32) {
+    signal_owned_gateway(pid, StopSignal::Kill);
+    let mut owned = OWNED_GATEWAY.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(child) = owned.as_mut().filter(|g| g.pid == pid).and_then(|g| g.child.as_mut()) {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+/// Whether `pid` still runs; our own gateway is checked through its wait status
+fn is_pid_running(pid: u32) -> bool {
+    let mut owned = OWNED_GATEWAY.lock().unwrap_or_else(|e| e.into_inner());
+    match owned.as_mut().filter(|g| g.pid == pid) {
+        Some(gateway) => gateway.is_alive(),
+        None => gateway_process::is_process_alive(pid),
+    }
+}
+
+/// Sample CPU, memory and uptime of `pid` into the resource series
+fn sample_resources(pid: u32) -> Option<ResourceSample> {
+    let mut sampler = RESOURCE_SAMPLER.lock().unwrap_or_else(|e| e.into_inner());
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    if let Some(last) = sampler.series.last() {
+        if last.pid == pid && now_ms - last.timestamp_ms < RESOURCE_SAMPLE_INTERVAL_MS {
+            return Some(last.clone());
+        }
+    }
+
+    let ticks = *CLOCK_TICKS;
+    let snapshot = proc_stats::read_process(pid, ticks)?;
+    let now = Instant::now();
+    let cpu_percent = match sampler.prev_cpu {
+        Some((prev_pid, prev_ticks, at)) if prev_pid == pid => Some(proc_stats::cpu_percent(
+            prev_ticks,
+            snapshot.cpu_ticks,
+            now.duration_since(at).as_secs_f64(),
+            ticks,
+        )),
+        // First sample: average over the process lifetime
+        _ => snapshot
+            .uptime_seconds
+            .filter(|uptime| *uptime > 0)
+            .map(|uptime| proc_stats::cpu_percent(0, snapshot.cpu_ticks, uptime as f64, ticks)),
+    };
+    sampler.prev_cpu = Some((pid, snapshot.cpu_ticks, now));
+
+    let sample = ResourceSample {
+        timestamp_ms: now_ms,
         pid,
-        port: SERVICE_PORT,
-        uptime_seconds: None,
-        memory_mb: None,
-        cpu_percent: None,
-    })
+        cpu_percent: cpu_percent.map(|c| (c * 10.0).round() / 10.0),
+        memory_mb: snapshot.memory_mb.map(|m| (m * 10.0).round() / 10.0),
+        uptime_seconds: snapshot.uptime_seconds,
+    };
+    sampler.series.push(sample.clone());
+    Some(sample)
+}
+
+/// Who holds the gateway port
+struct PortState {
+    state: GatewayState,
+    owned: Option<u32>,
+    listener: Option<u32>,
+}
+
+fn port_state(port: u16) -> PortState {
+    let listener = check_port_listening(port);
+    let owned = owned_gateway_pid();
+    let listener_parent = match (owned, listener) {
+        (Some(owned), Some(listener)) if owned != listener => gateway_process::parent_pid(listener),
+        _ => None,
+    };
+    PortState {
+        state: gateway_process::classify(owned, listener, listener_parent),
+        owned,
+        listener,
+    }
+}
+
+/// Service status: our gateway, a foreign process on the port, or stopped (blocking)
+fn service_status() -> ServiceStatus {
+    let port = shell::get_gateway_port();
+    let PortState { state, owned, listener } = port_state(port);
+    let pid = match state {
+        GatewayState::Ours => owned,
+        _ => listener,
+    };
+    // Measure the process actually serving the port (node behind the Windows shim)
+    let sample = listener.or(pid).and_then(sample_resources);
+    let health = listener.map(|_| probe_listener(port, state));
+
+    ServiceStatus {
+        running: listener.is_some(),
+        state,
+        pid,
+        port,
+        uptime_seconds: sample.as_ref().and_then(|s| s.uptime_seconds),
+        memory_mb: sample.as_ref().and_then(|s| s.memory_mb),
+        cpu_percent: sample.as_ref().and_then(|s| s.cpu_percent),
+        health,
+    }
+}
+
+/// Run blocking work (CLI calls, port scans, health probes) off the async workers
+async fn blocking<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Result<T, AppError> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| AppError::new(ErrorCode::Internal, "Background task failed").with_detail(e))
+}
+
+/// Get service status: our gateway, a foreign process on the port, or stopped
+#[command]
+pub async fn get_service_status() -> Result<ServiceStatus, AppError> {
+    blocking(service_status).await
+}
+
+/// Probe the gateway health endpoint now
+#[command]
+pub async fn probe_gateway_health() -> Result<HealthReport, AppError> {
+    let port = shell::get_gateway_port();
+    let report = blocking(move || probe_gateway(port)).await?;
+    debug!("[Service] Health probe on port {}: {:?} ({:?})", port, report.state, report.message);
+    Ok(report)
+}
+
+fn service_machine() -> std::sync::MutexGuard<'static, ServiceMachine> {
+    SERVICE_MACHINE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The start/stop/restart currently holding the service, reported to the UI and
+/// checked for cancellation at every step
+#[derive(Clone)]
+struct ServiceOp {
+    app: AppHandle,
+    id: u64,
+    operation: ServiceOperation,
+    started: Instant,
+}
+
+impl ServiceOp {
+    fn begin(app: &AppHandle, operation: ServiceOperation) -> Self {
+        let op = Self {
+            app: app.clone(),
+            id: service_machine().begin(operation),
+            operation,
+            started: Instant::now(),
+        };
+        op.emit(ServicePhase::Queued, None);
+        op
+    }
+
+    fn emit(&self, phase: ServicePhase, message: Option<String>) {
+        let progress = ServiceProgress {
+            operation_id: self.id,
+            operation: self.operation,
+            phase,
+            message,
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+        };
+        if let Err(e) = self.app.emit(SERVICE_PROGRESS_EVENT, &progress) {
+            debug!("[Service] Failed to emit progress event: {}", e);
+        }
+    }
+
+    fn cancelled_error(&self) -> AppError {
+        AppError::cancelled(format!("{:?} was cancelled", self.operation))
+    }
+
+    /// Enter `phase`, failing with `Cancelled` once cancellation was requested
+    fn advance(&self, phase: ServicePhase, message: Option<String>) -> Result<(), AppError> {
+        match service_machine().advance(self.id, phase) {
+            Ok(()) => {}
+            Err(TransitionError::Backwards) => {
+                warn!("[Service] Ignoring backwards transition to {:?}", phase);
+                return Ok(());
+            }
+            Err(_) => return Err(self.cancelled_error()),
+        }
+        self.emit(phase, message);
+        Ok(())
+    }
+
+    fn check_cancelled(&self) -> Result<(), AppError> {
+        if service_machine().is_cancelled(self.id) {
+            return Err(self.cancelled_error());
+        }
+        Ok(())
+    }
+
+    /// Sleep without holding a worker, waking early when cancelled
+    async fn sleep(&self, duration: Duration) -> Result<(), AppError> {
+        let deadline = tokio::time::Instant::now() + duration;
+        loop {
+            self.check_cancelled()?;
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return Ok(());
+            }
+            tokio::time::sleep((deadline - now).min(CANCEL_POLL_INTERVAL)).await;
+        }
+    }
+
+    /// Emit the terminal phase for `result` and release the state machine
+    fn finish<T>(&self, result: &Result<T, AppError>) {
+        let (phase, message) = match result {
+            Ok(_) if self.operation == ServiceOperation::Stop => (ServicePhase::Stopped, None),
+            Ok(_) => (ServicePhase::Healthy, None),
+            Err(e) if e.is(ErrorCode::Cancelled) => (ServicePhase::Cancelled, Some(e.message.clone())),
+            Err(e) => (ServicePhase::Failed, Some(e.to_string())),
+        };
+        service_machine().finish(self.id);
+        self.emit(phase, message);
+    }
+}
+
+/// Run `body` as the only service operation. Stop and Restart supersede an in-flight
+/// Start/Restart; everything else waits its turn.
+async fn run_service_operation<F, Fut>(app: &AppHandle, operation: ServiceOperation, body: F) -> Result<String, AppError>
+where
+    F: FnOnce(ServiceOp) -> Fut,
+    Fut: Future<Output = Result<String, AppError>>,
+{
+    if operation != ServiceOperation::Start {
+        if let Some(active) = service_machine().request_cancel(&[ServiceOperation::Start, ServiceOperation::Restart]) {
+            info!("[Service] {:?} supersedes in-flight {:?} (#{})", operation, active.operation, active.id);
+        }
+    }
+
+    let _guard = SERVICE_OPERATION_LOCK.lock().await;
+    let op = ServiceOp::begin(app, operation);
+    let result = body(op.clone()).await;
+    op.finish(&result);
+    result
 }
 
 /// Start service
 #[command]
-pub async fn start_service() -> Result<String, String> {
+pub async fn start_service(app: AppHandle) -> Result<String, AppError> {
+    run_service_operation(&app, ServiceOperation::Start, start_gateway).await
+}
+
+async fn start_gateway(op: ServiceOp) -> Result<String, AppError> {
     info!("[Service] Starting service...");
 
     // Check if already running
-    let status = get_service_status().await?;
-    if status.running {
-        info!("[Service] Service is already running");
-        return Err("Service is already running".to_string());
+    let status = blocking(service_status).await?;
+    match status.state {
+        GatewayState::Ours => {
+            info!("[Service] Service is already running");
+            return Err(AppError::new(ErrorCode::ServiceFailed, "Service is already running"));
+        }
+        GatewayState::Foreign => {
+            info!("[Service] Port {} is held by PID {:?}, which the Manager did not start", status.port, status.pid);
+            return Err(AppError::port_in_use(status.port)
+                .with_detail(format!("PID {} was not started by the Manager", status.pid.unwrap_or_default())));
+        }
+        GatewayState::Stopped => {}
     }
 
     // Check if openclaw command exists
-    let openclaw_path = shell::get_openclaw_path();
+    let openclaw_path = blocking(shell::get_openclaw_path).await?;
     if openclaw_path.is_none() {
         info!("[Service] openclaw command not found");
-        return Err("openclaw command not found, please install it via npm install -g openclaw".to_string());
+        return Err(AppError::openclaw_not_installed());
     }
     info!("[Service] openclaw path: {:?}", openclaw_path);
 
-    // Start gateway in background directly (do not wait for doctor, avoid blocking)
+    let port = status.port;
+    let pid = spawn_gateway(&op, port)?;
+
+    // Poll and wait for the gateway to answer its health endpoint (max 15 seconds)
+    info!("[Service] Waiting for gateway on port {} to become ready...", port);
+    wait_for_gateway_start(&op, port, pid).await?;
+
+    // A manual start ends any previous crash loop
+    reset_crash_loop();
+    spawn_supervisor(op.app.clone(), port);
+
+    Ok(format!("Service started, PID: {}", pid))
+}
+
+/// Launch the gateway in the background and start tracking it
+fn spawn_gateway(op: &ServiceOp, port: u16) -> Result<u32, AppError> {
+    op.advance(ServicePhase::Spawning, None)?;
+    // A missing ${env:..} / ${secret:..} value would only show up as a start timeout
+    if let Err(unresolved) = shell::plan_gateway_launch() {
+        return Err(AppError::new(
+            ErrorCode::ConfigInvalid,
+            format!("openclaw.json has {} unresolved reference(s)", unresolved.len()),
+        )
+        .with_detail(secret_refs::format_unresolved(&unresolved))
+        .with_hint("Add the missing values to the secrets vault or the env file, or unlock the vault in Settings."));
+    }
     info!("[Service] Starting gateway in background...");
-    shell::spawn_openclaw_gateway()
-        .map_err(|e| format!("Failed to start service: {}", e))?;
+    let child = shell::spawn_openclaw_gateway()
+        .map_err(|e| AppError::new(ErrorCode::ServiceFailed, "Failed to start service").with_detail(e))?;
+    let pid = child.id();
+    track_gateway(child, port);
+    Ok(pid)
+}
 
-    // Poll and wait for port to start listening (max 15 seconds)
-    info!("[Service] Waiting for port {} to start listening...", SERVICE_PORT);
-    let mut started = false;
+/// Wait up to 15s for our freshly spawned gateway to report ready on `port`.
+/// A cancelled start kills the half-started gateway.
+async fn wait_for_gateway_start(op: &ServiceOp, port: u16, pid: u32) -> Result<(), AppError> {
+    let result = poll_gateway_start(op, port).await;
+    if matches!(&result, Err(e) if e.is(ErrorCode::Cancelled)) {
+        info!("[Service] Start cancelled, killing gateway PID {}", pid);
+        blocking(move || {
+            kill_owned_gateway(pid);
+            forget_owned_gateway();
+        })
+        .await?;
+    }
+    result
+}
+
+async fn poll_gateway_start(op: &ServiceOp, port: u16) -> Result<(), AppError> {
+    op.advance(ServicePhase::WaitingForPort, Some(format!("Waiting for port {}", port)))?;
+    let mut answering = false;
+    let mut last_report = None;
     for i in 1..=15 {
-        std::thread::sleep(std::time::Duration::from_secs(1));
-        if let Some(pid) = check_port_listening(SERVICE_PORT) {
-            info!("[Service] Successfully started ({}s), PID: {}", i, pid);
-            started = true;
-            break;
+        op.sleep(Duration::from_secs(1)).await?;
+        let report = blocking(move || probe_gateway(port)).await?;
+        if report.ready {
+            info!("[Service] Successfully started ({}s, {:?})", i, report.state);
+            return Ok(());
+        }
+        if !answering && report.state != HealthState::Down {
+            answering = true;
+            op.advance(ServicePhase::WaitingForHealth, report.message.clone())?;
+        }
+        last_report = Some(report);
+        // Fail fast when the gateway dies during startup (bad config, missing key, ...)
+        if let OwnedState::Exited(exit_status) = owned_gateway_state(true) {
+            let exit_status = exit_status.unwrap_or_else(|| "exit status unknown".to_string());
+            info!("[Service] Gateway exited during startup ({})", exit_status);
+            return Err(AppError::new(ErrorCode::ServiceFailed, "Gateway exited during startup, please check openclaw logs")
+                .with_detail(exit_status));
         }
         if i % 3 == 0 {
             debug!("[Service] Waiting... ({}s)", i);
         }
     }
 
-    if !started {
-        info!("[Service] Wait timeout, port still not listening");
-        return Err("Service start timeout (15s), please check openclaw logs".to_string());
-    }
+    let detail = last_report.and_then(|r| r.message).unwrap_or_default();
+    info!("[Service] Wait timeout, gateway still not ready: {}", detail);
+    Err(AppError::new(ErrorCode::ServiceTimeout, "Service start timeout (15s), please check openclaw logs").with_detail(detail))
+}
 
-    // Reset stop flag
-    INTENTIONAL_STOP.store(false, Ordering::Relaxed);
+/// Stop service
+#[command]
+pub async fn stop_service(app: AppHandle) -> Result<String, AppError> {
+    run_service_operation(&app, ServiceOperation::Stop, |op| async move { stop_gateway(&op).await }).await
+}
 
-    // Spawn supervisor thread
-    thread::spawn(|| {
-        info!("[Service Supervisor] Thread started");
-        loop {
-            thread::sleep(Duration::from_secs(5));
+async fn stop_gateway(op: &ServiceOp) -> Result<String, AppError> {
+    info!("[Service] Stopping service...");
+    op.advance(ServicePhase::Stopping, None)?;
 
-            // If stop was intentional, exit supervisor
-            if INTENTIONAL_STOP.load(Ordering::Relaxed) {
-                info!("[Service Supervisor] Intentional stop detected, exiting thread");
-                break;
-            }
+    // Retire the supervisor so it does not restart the gateway, and clear any crash loop
+    SUPERVISOR_GENERATION.fetch_add(1, Ordering::SeqCst);
+    reset_crash_loop();
 
-            // Check if service is running
-            if check_port_listening(SERVICE_PORT).is_none() {
-                warn!("[Service Supervisor] Service stopped unexpectedly! Restarting...");
-                
-                // Double check flag just in case
-                if INTENTIONAL_STOP.load(Ordering::Relaxed) { break; }
+    // After a port change the old gateway still listens on the previous port
+    if let Some(old_port) = take_previous_port() {
+        if blocking(move || check_port_listening(old_port).is_some()).await? {
+            info!("[Service] Stopping gateway still listening on previous port {}...", old_port);
+            blocking(|| shell::run_openclaw(&["gateway", "stop"])).await?.ok();
+            op.sleep(Duration::from_millis(1000)).await?;
+            let grace = Duration::from_millis(load_stop_policy().grace_period_ms);
+            let (killed, failed, skipped) = blocking(move || kill_port_gateways(old_port, grace)).await?;
+            if killed + failed > 0 {
+                info!("[Service] Previous port {}: killed {}, failed {}", old_port, killed, failed);
+            }
+            if !skipped.is_empty() {
+                warn!("[Service] Previous port {}: left {} alone", old_port, skipped.join(", "));
+                op.advance(
+                    ServicePhase::Stopping,
+                    Some(format!("Left {} on previous port {} running: not node/openclaw", skipped.join(", "), old_port)),
+                )?;
+            }
+        }
+    }
 
-                if let Err(e) = shell::spawn_openclaw_gateway() {
-                    error!("[Service Supervisor] Failed to restart service: {}", e);
+    let policy = load_stop_policy();
+    let mut status = blocking(service_status).await?;
+    for step in policy.steps() {
+        if status.state == GatewayState::Stopped {
+            break;
+        }
+        match step {
+            StopStep::CliStop => {
+                op.advance(ServicePhase::Stopping, Some("Asking openclaw to stop the gateway".to_string()))?;
+                blocking(|| shell::run_openclaw(&["gateway", "stop"])).await?.ok();
+                status = wait_until_stopped(op, Duration::from_millis(policy.cli_timeout_ms)).await?;
+            }
+            StopStep::Terminate | StopStep::ForceKill => {
+                // Signals only go to our own gateway; a foreign process is left to Kill All
+                if status.state == GatewayState::Foreign {
+                    info!("[Service] Port {} is held by PID {:?}, which the Manager did not start", status.port, status.pid);
+                    return Err(AppError::port_in_use(status.port)
+                        .with_detail(format!("PID {} was not started by the Manager", status.pid.unwrap_or_default())));
+                }
+                let Some(pid) = status.pid else {
+                    break;
+                };
+                if step == StopStep::Terminate {
+                    let grace = Duration::from_millis(policy.grace_period_ms);
+                    op.advance(
+                        ServicePhase::Stopping,
+                        Some(format!("Sent SIGTERM to PID {}, waiting up to {}s", pid, grace.as_secs())),
+                    )?;
+                    info!("[Service] Sending SIGTERM to gateway process group {}...", pid);
+                    blocking(move || signal_owned_gateway(pid, StopSignal::Terminate)).await?;
+                    status = wait_until_stopped(op, grace).await?;
                 } else {
-                    info!("[Service Supervisor] Restart command sent");
-                    // Wait for it to come up so we don't spam restarts
-                    thread::sleep(Duration::from_secs(10));
+                    op.advance(ServicePhase::Stopping, Some(format!("Sending SIGKILL to PID {}", pid)))?;
+                    info!("[Service] Gateway still running, sending SIGKILL to process group {}...", pid);
+                    blocking(move || kill_owned_gateway(pid)).await?;
+                    status = wait_until_stopped(op, Duration::from_millis(1000)).await?;
                 }
             }
         }
-    });
+    }
 
-    if let Some(pid) = check_port_listening(SERVICE_PORT) {
-        Ok(format!("Service started, PID: {}", pid))
-    } else {
-        Ok("Service started (pid unknown)".to_string())
+    match status.state {
+        GatewayState::Stopped => {
+            info!("[Service] Successfully stopped");
+            forget_owned_gateway();
+            Ok("Service stopped".to_string())
+        }
+        GatewayState::Foreign => {
+            info!("[Service] Port {} is held by PID {:?}, which the Manager did not start", status.port, status.pid);
+            Err(AppError::port_in_use(status.port)
+                .with_detail(format!("PID {} was not started by the Manager", status.pid.unwrap_or_default())))
+        }
+        GatewayState::Ours => Err(AppError::new(ErrorCode::ServiceFailed, "Failed to stop service after all attempts")
+            .with_hint("Enable more steps in the stop policy, or use Kill All.")),
     }
 }
 
-/// Stop service
-/// Stop service
-#[command]
-pub async fn stop_service() -> Result<String, String> {
-    info!("[Service] Stopping service...");
-
-    // Set flag so supervisor knows this is intentional
-    INTENTIONAL_STOP.store(true, Ordering::Relaxed);
-
-    // 1. Try graceful stop
-    let _ = shell::run_openclaw(&["gateway", "stop"]);
-    
-    // Wait a bit
-    for _ in 0..5 {
-        std::thread::sleep(std::time::Duration::from_millis(500));
-        let status = get_service_status().await?;
-        if !status.running {
-            info!("[Service] Successfully stopped (graceful)");
-            return Ok("Service stopped".to_string());
-        }
-    }
-
-    // 2. Try force stop via CLI
-    info!("[Service] Graceful stop failed, trying CLI force stop...");
-    let _ = shell::run_openclaw(&["gateway", "stop", "--force"]);
-    std::thread::sleep(std::time::Duration::from_millis(1000));
-
-    let status = get_service_status().await?;
-    if !status.running {
-        info!("[Service] Successfully stopped (CLI force)");
-        return Ok("Service stopped".to_string());
-    }
-
-    // 3. Last resort: Kill process by PID
-    if let Some(pid) = status.pid {
-        info!("[Service] CLI force stop failed, killing PID {}...", pid);
-        
-        #[cfg(windows)]
-        {
-            let mut cmd = Command::new("taskkill");
-            cmd.args(["/F", "/PID", &pid.to_string()]);
-            cmd.creation_flags(CREATE_NO_WINDOW);
-            if let Ok(output) = cmd.output() {
-                if !output.status.success() {
-                     let stderr = String::from_utf8_lossy(&output.stderr);
-                     warn!("[Service] Failed to taskkill PID {}: {}", pid, stderr);
-                }
-            }
+/// Poll until the gateway is stopped or `timeout` passes; returns the last status
+async fn wait_until_stopped(op: &ServiceOp, timeout: Duration) -> Result<ServiceStatus, AppError> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        op.sleep(Duration::from_millis(500).min(deadline.saturating_duration_since(Instant::now()))).await?;
+        let status = blocking(service_status).await?;
+        if status.state == GatewayState::Stopped || Instant::now() >= deadline {
+            return Ok(status);
         }
+    }
+}
 
-        #[cfg(unix)]
-        {
-            let _ = Command::new("kill").args(["-9", &pid.to_string()]).output();
-        }
-        
-        std::thread::sleep(std::time::Duration::from_millis(1000));
-        
-        let final_status = get_service_status().await?;
-        if !final_status.running {
-             info!("[Service] Successfully killed process");
-             return Ok("Service stopped (killed)".to_string());
+fn load_stop_policy() -> StopPolicy {
+    match config::load_manager_config() {
+        Ok(manager) => StopPolicy::from_manager_config(&manager),
+        Err(e) => {
+            warn!("[Service] Failed to read stop policy, using defaults: {}", e);
+            StopPolicy::default()
         }
     }
+}
 
-    Err("Failed to stop service after all attempts".to_string())
+/// Get the stop policy from manager.json
+#[command]
+pub async fn get_stop_policy() -> Result<StopPolicy, AppError> {
+    Ok(load_stop_policy())
+}
+
+/// Save the stop policy to manager.json
+#[command]
+pub async fn save_stop_policy(policy: StopPolicy) -> Result<StopPolicy, AppError> {
+    let policy = policy.clamped();
+    if policy.steps().is_empty() {
+        return Err(AppError::invalid_input("Enable at least one stop step"));
+    }
+    let mut manager = config::load_manager_config()?;
+    if !manager.is_object() {
+        manager = serde_json::json!({});
+    }
+    manager[stop_policy::MANAGER_KEY] = serde_json::to_value(policy)
+        .map_err(|e| AppError::new(ErrorCode::Internal, "Failed to serialize stop policy").with_detail(e))?;
+    config::save_manager_config(&manager)?;
+    info!("[Service] Stop policy saved: {:?}", policy);
+    Ok(policy)
 }
 
 /// Restart service
 #[command]
-pub async fn restart_service() -> Result<String, String> {
+pub async fn restart_service(app: AppHandle) -> Result<String, AppError> {
+    run_service_operation(&app, ServiceOperation::Restart, restart_gateway).await
+}
+
+async fn restart_gateway(op: ServiceOp) -> Result<String, AppError> {
     info!("[Service] Restarting service...");
 
     // Step 1: Stop the service if it's running
-    // Step 1: Stop the service if it's running
-    match stop_service().await {
+    match stop_gateway(&op).await {
         Ok(_) => {
             info!("[Service] Service stopped successfully");
             // Wait a bit to ensure port is freed
-            std::thread::sleep(std::time::Duration::from_millis(1000));
+            op.sleep(Duration::from_millis(1000)).await?;
         }
+        Err(e) if e.is(ErrorCode::Cancelled) => return Err(e),
         Err(e) => {
             info!("[Service] Failed to stop service: {}, trying to continue anyway...", e);
         }
     }
-    
+
     // Double check port is free
-    if check_port_listening(SERVICE_PORT).is_some() {
-         return Err(format!("Port {} is still in use after stop attempt", SERVICE_PORT));
+    let port = shell::get_gateway_port();
+    if blocking(move || check_port_listening(port).is_some()).await? {
+         return Err(AppError::new(ErrorCode::PortInUse, format!("Port {} is still in use after stop attempt", port)));
     }
 
     // Step 2: Start the service
-    info!("[Service] Starting gateway in background...");
-    shell::spawn_openclaw_gateway()
-        .map_err(|e| format!("Failed to start service: {}", e))?;
+    let pid = spawn_gateway(&op, port)?;
 
-    // Step 3: Poll and wait for port to start listening (max 15 seconds)
-    info!("[Service] Waiting for port {} to start listening...", SERVICE_PORT);
-    for i in 1..=15 {
-        std::thread::sleep(std::time::Duration::from_secs(1));
-        if let Some(pid) = check_port_listening(SERVICE_PORT) {
-            info!("[Service] Successfully restarted ({}s), PID: {}", i, pid);
-            return Ok(format!("Service restarted, PID: {}", pid));
+    // Step 3: Poll and wait for the gateway to become ready (max 15 seconds)
+    info!("[Service] Waiting for gateway on port {} to become ready...", port);
+    wait_for_gateway_start(&op, port, pid).await?;
+    spawn_supervisor(op.app.clone(), port);
+    Ok(format!("Service restarted, PID: {}", pid))
+}
+
+/// Cancel an in-flight start or restart; a running stop is not interrupted
+#[command]
+pub async fn cancel_service_operation() -> Result<bool, AppError> {
+    let cancelled = service_machine().request_cancel(&[ServiceOperation::Start, ServiceOperation::Restart]);
+    match cancelled {
+        Some(active) => info!("[Service] Cancel requested for {:?} (#{})", active.operation, active.id),
+        None => debug!("[Service] Cancel requested but no start/restart is in flight"),
+    }
+    Ok(cancelled.is_some())
+}
+
+/// The service operation in progress, if any (lets the UI resume its progress display)
+#[command]
+pub async fn get_service_operation() -> Result<Option<ActiveOperation>, AppError> {
+    Ok(service_machine().active())
+}
+
+/// Start a supervisor for the gateway listening on `port`, retiring any previous one
+fn spawn_supervisor(app: AppHandle, port: u16) {
+    let generation = SUPERVISOR_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let spawned = thread::Builder::new()
+        .name("gateway-supervisor".to_string())
+        .spawn(move || supervise(app, port, generation));
+    if let Err(e) = spawned {
+        error!("[Service Supervisor] Failed to start supervisor thread: {}", e);
+    }
+}
+
+fn is_current_supervisor(generation: u64) -> bool {
+    SUPERVISOR_GENERATION.load(Ordering::SeqCst) == generation
+}
+
+/// Sleep for `duration`, returning false as soon as the supervisor is retired
+fn sleep_while_current(duration: Duration, generation: u64) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if !is_current_supervisor(generation) {
+            return false;
         }
-        if i % 3 == 0 {
-            debug!("[Service] Waiting... ({}s)", i);
+        thread::sleep(Duration::from_millis(250).min(deadline - Instant::now()));
+    }
+    is_current_supervisor(generation)
+}
+
+/// Restart the gateway with exponential backoff until it crash-loops or the user stops it
+fn supervise(app: AppHandle, port: u16, generation: u64) {
+    info!("[Service Supervisor] Thread started (port {})", port);
+    let mut tracker = CrashTracker::new(BackoffPolicy::default());
+    let mut started_at = Instant::now();
+
+    loop {
+        if !sleep_while_current(SUPERVISOR_POLL_INTERVAL, generation) {
+            info!("[Service Supervisor] Intentional stop detected, exiting thread");
+            break;
+        }
+        // Our gateway's wait status decides; without a tracked process fall back to the port
+        let exit_status = match owned_gateway_state(true) {
+            OwnedState::Alive(_) => continue,
+            OwnedState::Exited(exit_status) => exit_status,
+            OwnedState::Missing if check_port_listening(port).is_some() => continue,
+            OwnedState::Missing => None,
+        };
+        // Stop may have closed the port between the two checks
+        if !is_current_supervisor(generation) {
+            break;
+        }
+
+        let now = Instant::now();
+        let uptime_seconds = now.duration_since(started_at).as_secs();
+        warn!(
+            "[Service Supervisor] Service stopped unexpectedly after {}s ({})",
+            uptime_seconds,
+            exit_status.as_deref().unwrap_or("exit status unknown")
+        );
+
+        match tracker.on_crash(now, started_at) {
+            CrashDecision::GiveUp { crashes } => {
+                error!("[Service Supervisor] Crash loop detected ({} crashes), giving up", crashes);
+                let history = record_restart(RestartRecord {
+                    timestamp: chrono::Local::now().to_rfc3339(),
+                    attempt: 0,
+                    exit_status,
+                    uptime_seconds,
+                    delay_ms: 0,
+                    outcome: RestartOutcome::GaveUp,
+                    message: Some(format!("Gave up after {} crashes", crashes)),
+                });
+                notify_crash_loop(&app, crashes, &history);
+                break;
+            }
+            CrashDecision::Restart { attempt, delay } => {
+                info!("[Service Supervisor] Restarting in {:?} (attempt {})", delay, attempt);
+                if !sleep_while_current(delay, generation) {
+                    info!("[Service Supervisor] Stopped while waiting to restart, exiting thread");
+                    break;
+                }
+
+                let (outcome, message) = match supervised_restart(&app, port, generation) {
+                    None => {
+                        info!("[Service Supervisor] Superseded by a manual operation, exiting thread");
+                        break;
+                    }
+                    Some(Ok(pid)) => {
+                        info!("[Service Supervisor] Gateway is ready again, PID: {}", pid);
+                        (RestartOutcome::Restarted, None)
+                    }
+                    Some(Err(e)) if e.is(ErrorCode::Cancelled) => {
+                        info!("[Service Supervisor] Restart cancelled, exiting thread");
+                        break;
+                    }
+                    Some(Err(e)) if e.is(ErrorCode::ServiceTimeout) => {
+                        warn!("[Service Supervisor] Gateway did not become ready: {}", e);
+                        (RestartOutcome::StartTimeout, None)
+                    }
+                    Some(Err(e)) => {
+                        error!("[Service Supervisor] Failed to restart service: {}", e);
+                        (RestartOutcome::SpawnFailed, Some(e.to_string()))
+                    }
+                };
+                started_at = Instant::now();
+
+                record_restart(RestartRecord {
+                    timestamp: chrono::Local::now().to_rfc3339(),
+                    attempt,
+                    exit_status,
+                    uptime_seconds,
+                    delay_ms: delay.as_millis() as u64,
+                    outcome,
+                    message,
+                });
+            }
         }
     }
+}
+
+/// Restart a crashed gateway as a Restart operation: it waits behind a manual
+/// Start/Stop/Restart, reports progress like one and can be cancelled or superseded.
+/// None when a manual operation retired this supervisor while it waited.
+fn supervised_restart(app: &AppHandle, port: u16, generation: u64) -> Option<Result<u32, AppError>> {
+    tauri::async_runtime::block_on(async {
+        let _guard = SERVICE_OPERATION_LOCK.lock().await;
+        if !is_current_supervisor(generation) {
+            return None;
+        }
+        let op = ServiceOp::begin(app, ServiceOperation::Restart);
+        let result = async {
+            let pid = spawn_gateway(&op, port)?;
+            wait_for_gateway_start(&op, port, pid).await?;
+            Ok(pid)
+        }
+        .await;
+        op.finish(&result);
+        Some(result)
+    })
+}
+
+/// Append to restart-history.json and return the updated history
+fn record_restart(record: RestartRecord) -> RestartHistory {
+    update_restart_history(|history| history.push(record))
+}
 
-    info!("[Service] Restart timeout, port still not listening");
-    Err("Service restart timeout (15s), please check openclaw logs".to_string())
+/// Clear the crash-loop flag after a manual Start/Stop; records are kept
+fn reset_crash_loop() {
+    update_restart_history(|history| history.crash_loop = false);
+}
+
+fn update_restart_history(change: impl FnOnce(&mut RestartHistory)) -> RestartHistory {
+    let _guard = RESTART_HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = platform::get_restart_history_file_path();
+    let mut history = supervisor::load(&path).unwrap_or_else(|e| {
+        warn!("[Service Supervisor] Failed to read restart history, starting a new one: {}", e);
+        RestartHistory::default()
+    });
+    let before = history.clone();
+    change(&mut history);
+    if history != before {
+        if let Err(e) = supervisor::save(&path, &history) {
+            warn!("[Service Supervisor] Failed to save restart history: {}", e);
+        }
+    }
+    history
+}
+
+/// Tell the user the gateway is crash-looping: desktop notification plus an event for the UI
+fn notify_crash_loop(app: &AppHandle, crashes: usize, history: &RestartHistory) {
+    let body = format!(
+        "The gateway crashed {} times in a short period, automatic restarts are paused. Check the logs, then start it again.",
+        crashes
+    );
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title("OpenClaw gateway keeps crashing")
+        .body(body)
+        .show()
+    {
+        warn!("[Service Supervisor] Failed to show notification: {}", e);
+    }
+    if let Err(e) = app.emit(CRASH_LOOP_EVENT, history) {
+        warn!("[Service Supervisor] Failed to emit {}: {}", CRASH_LOOP_EVENT, e);
+    }
+}
+
+/// Get the recent CPU / memory series of the gateway process (oldest first)
+#[command]
+pub async fn get_resource_history() -> Result<Vec<ResourceSample>, AppError> {
+    let sampler = RESOURCE_SAMPLER.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(sampler.series.to_vec())
+}
+
+/// Get the supervisor's restart history (oldest first)
+#[command]
+pub async fn get_restart_history() -> Result<RestartHistory, AppError> {
+    let _guard = RESTART_HISTORY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    supervisor::load(&platform::get_restart_history_file_path())
+        .map_err(|e| AppError::new(ErrorCode::Io, "Failed to read restart history").with_detail(e))
 }
 
 /// Get logs
 #[command]
-pub async fn get_logs(lines: Option<u32>) -> Result<Vec<String>, String> {
+pub async fn get_logs(lines: Option<u32>) -> Result<Vec<String>, AppError> {
     let n = lines.unwrap_or(100);
 
+    // Prefer the output captured from the gateway the Manager started
+    if PathBuf::from(platform::get_gateway_log_file_path()).exists() {
+        let page = read_captured_logs(0, n as usize, None)?;
+        return Ok(page.entries.iter().map(|e| e.to_line()).collect());
+    }
+
     match shell::run_openclaw(&["logs", "--lines", &n.to_string()]) {
         Ok(output) => {
             Ok(output.lines().map(|s| s.to_string()).collect())
         }
-        Err(e) => Err(format!("Failed to read logs: {}", e))
+        Err(e) => Err(AppError::command_failed("Failed to read logs").with_detail(e))
     }
 }
 
-/// Kill ALL processes using port 18789
+/// Read a page of captured gateway logs.
+/// `skip` counts the newest entries at or above `level` to leave out; omit it to get the newest page.
 #[command]
-pub async fn kill_all_port_processes() -> Result<String, String> {
-    info!("[Service] Kill All: Finding all processes on port {}...", SERVICE_PORT);
+pub async fn read_gateway_logs(
+    skip: Option<usize>,
+    limit: Option<usize>,
+    level: Option<LogLevel>,
+) -> Result<GatewayLogPage, AppError> {
+    let limit = limit.unwrap_or(200).clamp(1, 5000);
+    blocking(move || read_captured_logs(skip.unwrap_or(0), limit, level)).await?
+}
 
-    let pids = find_all_port_pids(SERVICE_PORT);
+/// Kill ALL processes using the gateway port. Processes that are not node/openclaw
+/// are only killed with `confirm`; otherwise a `confirmation_required` error lists them.
+#[command]
+pub async fn kill_all_port_processes(confirm: Option<bool>) -> Result<String, AppError> {
+    let port = shell::get_gateway_port();
+    info!("[Service] Kill All: Finding all processes on port {}...", port);
 
-    if pids.is_empty() {
-        info!("[Service] Kill All: No processes found on port {}", SERVICE_PORT);
-        return Ok("No processes found on port 18789".to_string());
+    let processes = blocking(move || {
+        find_all_port_pids(port)
+            .into_iter()
+            .map(|pid| (pid, gateway_process::process_command(pid).unwrap_or_default()))
+            .collect::<Vec<_>>()
+    })
+    .await?;
+
+    if processes.is_empty() {
+        info!("[Service] Kill All: No processes found on port {}", port);
+        return Ok(format!("No processes found on port {}", port));
     }
 
-    info!("[Service] Kill All: Found {} process(es): {:?}", pids.len(), pids);
+    let others: Vec<String> = processes
+        .iter()
+        .filter(|(_, command)| !gateway_process::is_gateway_command(command))
+        .map(|(pid, command)| format!("PID {} ({})", pid, if command.is_empty() { "unknown" } else { command }))
+        .collect();
+    if !others.is_empty() && !confirm.unwrap_or(false) {
+        info!("[Service] Kill All: Needs confirmation for {}", others.join(", "));
+        return Err(AppError::new(
+            ErrorCode::ConfirmationRequired,
+            format!("{} process(es) on port {} are not node/openclaw", others.len(), port),
+        )
+        .with_detail(others.join("\n"))
+        .with_hint("Confirm to kill them anyway."));
+    }
 
-    let mut killed = 0u32;
-    let mut failed = 0u32;
+    let pids: Vec<u32> = processes.iter().map(|(pid, _)| *pid).collect();
+    let grace = Duration::from_millis(load_stop_policy().grace_period_ms);
+    let (killed, failed) = blocking(move || terminate_pids(&pids, grace)).await?;
 
-    for pid in &pids {
-        info!("[Service] Kill All: Killing PID {}...", pid);
+    let msg = if failed == 0 {
+        format!("Killed {} process(es) on port {}", killed, port)
+    } else {
+        format!("Killed {}, failed to kill {} process(es) on port {}", killed, failed, port)
+    };
 
-        #[cfg(windows)]
-        {
-            let mut cmd = Command::new("taskkill");
-            cmd.args(["/F", "/PID", &pid.to_string()]);
-            cmd.creation_flags(CREATE_NO_WINDOW);
+    info!("[Service] Kill All: {}", msg);
+    Ok(msg)
+}
 
-            match cmd.output() {
-                Ok(output) if output.status.success() => {
-                    info!("[Service] Kill All: Successfully killed PID {}", pid);
-                    killed += 1;
-                }
-                Ok(output) => {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    warn!("[Service] Kill All: Failed to kill PID {}: {}", pid, stderr.trim());
-                    failed += 1;
-                }
-                Err(e) => {
-                    warn!("[Service] Kill All: Error killing PID {}: {}", pid, e);
-                    failed += 1;
-                }
-            }
+/// Stop the node/openclaw processes with a socket on `port`; anything else may have
+/// taken the port since and is left running. Returns (killed, failed, skipped).
+fn kill_port_gateways(port: u16, grace: Duration) -> (u32, u32, Vec<String>) {
+    let mut pids = Vec::new();
+    let mut skipped = Vec::new();
+    for pid in find_all_port_pids(port) {
+        match gateway_process::process_command(pid) {
+            Some(command) if gateway_process::is_gateway_command(&command) => pids.push(pid),
+            command => skipped.push(format!("PID {} ({})", pid, command.as_deref().unwrap_or("unknown"))),
         }
+    }
+    if pids.is_empty() {
+        return (0, 0, skipped);
+    }
 
-        #[cfg(unix)]
-        {
-            match Command::new("kill").args(["-9", &pid.to_string()]).output() {
-                Ok(output) if output.status.success() => {
-                    info!("[Service] Kill All: Successfully killed PID {}", pid);
-                    killed += 1;
-                }
-                Ok(output) => {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    warn!("[Service] Kill All: Failed to kill PID {}: {}", pid, stderr.trim());
-                    failed += 1;
-                }
-                Err(e) => {
-                    warn!("[Service] Kill All: Error killing PID {}: {}", pid, e);
-                    failed += 1;
-                }
-            }
+    info!("[Service] Kill All: Found {} process(es): {:?}", pids.len(), pids);
+    let (killed, failed) = terminate_pids(&pids, grace);
+    (killed, failed, skipped)
+}
+
+/// SIGTERM each PID, give them `grace` to exit, then SIGKILL the rest; returns (killed, failed)
+fn terminate_pids(pids: &[u32], grace: Duration) -> (u32, u32) {
+    for pid in pids {
+        info!("[Service] Kill All: Sending SIGTERM to PID {}...", pid);
+        gateway_process::send_signal(*pid, StopSignal::Terminate, false);
+    }
+
+    let deadline = Instant::now() + grace;
+    let mut remaining = pids.to_vec();
+    loop {
+        remaining.retain(|pid| is_pid_running(*pid));
+        if remaining.is_empty() || Instant::now() >= deadline {
+            break;
         }
+        thread::sleep(Duration::from_millis(250));
     }
 
-    let msg = if failed == 0 {
-        format!("Killed {} process(es) on port 18789", killed)
-    } else {
-        format!("Killed {}, failed to kill {} process(es) on port 18789", killed, failed)
-    };
+    let mut killed = (pids.len() - remaining.len()) as u32;
+    let mut failed = 0u32;
+    for pid in remaining {
+        info!("[Service] Kill All: PID {} survived SIGTERM, sending SIGKILL...", pid);
+        if gateway_process::send_signal(pid, StopSignal::Kill, false) {
+            info!("[Service] Kill All: Successfully killed PID {}", pid);
+            killed += 1;
+        } else {
+            warn!("[Service] Kill All: Failed to kill PID {}", pid);
+            failed += 1;
+        }
+    }
 
-    info!("[Service] Kill All: {}", msg);
-    Ok(msg)
+    (killed, failed)
 }
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
+use crate::models::AppError;
+use crate::utils::platform;
 use std::fs;
+use std::path::PathBuf;
 use std::process::Command;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
@@ -21,11 +24,10 @@ struct SkillFrontmatter {
 }
 
 #[command]
-pub async fn get_skills() -> Result<Vec<Skill>, String> {
+pub async fn get_skills() -> Result<Vec<Skill>, AppError> {
     info!("Executing get_skills command");
     let mut skills = Vec::new();
-    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
-    let skills_dir = home_dir.join(".openclaw").join("skills");
+    let skills_dir = PathBuf::from(platform::get_config_dir()).join("skills");
     info!("Using skills directory: {:?}", skills_dir);
 
     if !skills_dir.exists() {
@@ -82,7 +84,7 @@ fn create_command(program: &str) -> Command {
 }
 
 #[command]
-pub async fn check_clawhub_installed() -> Result<bool, String> {
+pub async fn check_clawhub_installed() -> Result<bool, AppError> {
     info!("Checking if clawhub is installed");
     
     // Method 1: Check if 'clawhub' command exists
@@ -133,7 +135,7 @@ pub async fn check_clawhub_installed() -> Result<bool, String> {
 }
 
 #[command]
-pub async fn install_clawhub() -> Result<String, String> {
+pub async fn install_clawhub() -> Result<String, AppError> {
     info!("Installing clawhub globally via npm");
 
     #[cfg(target_os = "windows")]
@@ -157,16 +159,15 @@ pub async fn install_clawhub() -> Result<String, String> {
     } else {
         let error_msg = String::from_utf8_lossy(&output.stderr);
         error!("Failed to install clawhub: {}", error_msg);
-        Err(format!("Failed to install clawhub: {}", error_msg))
+        Err(format!("Failed to install clawhub: {}", error_msg).into())
     }
 }
 
 #[command]
-pub async fn install_skill(skill_name: String) -> Result<String, String> {
+pub async fn install_skill(skill_name: String) -> Result<String, AppError> {
     info!("Installing skill: {}", skill_name);
     
-    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
-    let openclaw_dir = home_dir.join(".openclaw");
+    let openclaw_dir = PathBuf::from(platform::get_config_dir());
     
     // Ensure .openclaw directory exists
     if !openclaw_dir.exists() {
@@ -198,19 +199,18 @@ pub async fn install_skill(skill_name: String) -> Result<String, String> {
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
         error!("Failed to install skill: {}", stderr);
-        Err(format!("Failed to install skill: {}", stderr))
+        Err(format!("Failed to install skill: {}", stderr).into())
     }
 }
 
 #[command]
-pub async fn uninstall_skill(skill_id: String) -> Result<String, String> {
+pub async fn uninstall_skill(skill_id: String) -> Result<String, AppError> {
     info!("Uninstalling skill: {}", skill_id);
     
-    let home_dir = dirs::home_dir().ok_or("Could not find home directory")?;
-    let skill_path = home_dir.join(".openclaw").join("skills").join(&skill_id);
+    let skill_path = PathBuf::from(platform::get_config_dir()).join("skills").join(&skill_id);
     
     if !skill_path.exists() {
-        return Err(format!("Skill directory not found: {:?}", skill_path));
+        return Err(AppError::not_found(format!("Skill directory not found: {:?}", skill_path)));
     }
 
     info!("Removing directory: {:?}", skill_path);
@@ -222,7 +222,7 @@ pub async fn uninstall_skill(skill_id: String) -> Result<String, String> {
 }
 
 #[command]
-pub async fn uninstall_clawhub() -> Result<String, String> {
+pub async fn uninstall_clawhub() -> Result<String, AppError> {
     info!("Uninstalling clawhub globally via npm");
 
     #[cfg(target_os = "windows")]
@@ -246,6 +246,6 @@ pub async fn uninstall_clawhub() -> Result<String, String> {
     } else {
         let error_msg = String::from_utf8_lossy(&output.stderr);
         error!("Failed to uninstall clawhub: {}", error_msg);
-        Err(format!("Failed to uninstall clawhub: {}", error_msg))
+        Err(format!("Failed to uninstall clawhub: {}", error_msg).into())
     }
 }
@@ -8,7 +8,7 @@ mod commands;
 mod models;
 mod utils;
 
-use commands::{config, diagnostics, installer, process, service, skills};
+use commands::{config, dashboard, diagnostics, installer, maintenance, process, secrets, service, skills};
 use utils::log_sanitizer;
 use std::io::Write;
 
@@ -29,6 +29,25 @@ fn main() {
         .setup(|app| {
             #[cfg(desktop)]
             app.handle().plugin(tauri_plugin_updater::Builder::new().build())?;
+
+            // Bring legacy config layouts up to date before any command reads them
+            match config::migrate_config_files() {
+                Ok(report) if report.config_changed || report.manager_changed => {
+                    log::info!("[Config Migration] Applied {} migration(s)", report.migrations.len())
+                }
+                Ok(_) => {}
+                Err(e) => log::error!("[Config Migration] Failed to migrate configuration: {}", e),
+            }
+
+            // Push external edits of config files to the frontend
+            config::start_config_watcher(app.handle().clone());
+
+            // Timed restarts, updates and repairs from the maintenance schedule
+            maintenance::start_maintenance_scheduler(app.handle().clone());
+            dashboard::start_dashboard_proxy();
+
+            // Live gateway output, read back from the log file the gateway writes
+            service::start_gateway_log_tail(app.handle().clone());
             Ok(())
         })
         .plugin(tauri_plugin_shell::init())
@@ -39,11 +58,19 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             // Service management
             service::get_service_status,
+            service::get_resource_history,
+            service::probe_gateway_health,
             service::start_service,
             service::stop_service,
             service::restart_service,
+            service::cancel_service_operation,
+            service::get_service_operation,
             service::get_logs,
+            service::read_gateway_logs,
             service::kill_all_port_processes,
+            service::get_stop_policy,
+            service::save_stop_policy,
+            service::get_restart_history,
             // Process management
             process::check_openclaw_installed,
             process::get_openclaw_version,
@@ -51,6 +78,7 @@ fn main() {
             process::check_port_in_use,
             // Configuration management
             config::get_config,
+            config::validate_config,
             config::save_config,
             config::get_env_value,
             config::save_env_value,
@@ -61,6 +89,14 @@ fn main() {
             // Gateway Token
             config::get_or_create_gateway_token,
             config::get_dashboard_url,
+            config::get_gateway_token_state,
+            config::rotate_gateway_token,
+            // Shared dashboard links
+            dashboard::get_dashboard_access,
+            dashboard::save_dashboard_proxy,
+            dashboard::create_dashboard_token,
+            dashboard::get_dashboard_token_link,
+            dashboard::revoke_dashboard_token,
             config::repair_device_token,
             // AI configuration management
             config::get_official_providers,
@@ -98,9 +134,27 @@ fn main() {
             installer::open_install_terminal,
             installer::uninstall_openclaw,
             installer::install_gateway_service,
+            installer::get_gateway_unit_status,
+            installer::enable_gateway_unit,
+            installer::disable_gateway_unit,
             // Version update
             installer::check_openclaw_update,
             installer::update_openclaw,
+            // Secrets vault
+            secrets::get_vault_status,
+            secrets::create_vault,
+            secrets::unlock_vault,
+            secrets::lock_vault,
+            secrets::set_secret,
+            secrets::delete_secret,
+            secrets::scan_for_leaked_secrets,
+            secrets::move_leaked_secret,
+            config::migrate_secrets_to_vault,
+            // Scheduled maintenance
+            maintenance::get_maintenance_settings,
+            maintenance::save_maintenance_settings,
+            maintenance::run_maintenance_job,
+            maintenance::get_maintenance_history,
             // Skills management
             skills::get_skills,
             skills::check_clawhub_installed,
@@ -111,6 +165,11 @@ fn main() {
             skills::uninstall_clawhub,
             // Multi-Agent Routing
             config::get_openclaw_home_dir,
+            // Profiles
+            config::get_profiles,
+            config::save_profile,
+            config::delete_profile,
+            config::switch_profile,
             config::get_agents_config,
             config::save_agent,
             config::save_subagent_defaults,
@@ -146,6 +205,11 @@ fn main() {
             // Configuration Management
             config::export_config,
             config::import_config,
+            config::list_config_snapshots,
+            config::diff_config_snapshots,
+            config::restore_config_snapshot,
+            config::get_config_migration_report,
+            config::run_config_migrations,
         ])
         .run(tauri::generate_context!())
         .expect("Error occurred while running Tauri application");
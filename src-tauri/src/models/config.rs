@@ -1,125 +1,751 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::utils::config_history;
+use crate::utils::config_lock::escape_pointer_token;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
 
 /// OpenClaw complete configuration - corresponds to openclaw.json structure
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+///
+/// Sections the Manager edits are typed; sections it only passes through are
+/// kept as raw JSON. Keys the model doesn't declare are kept in the `extra`
+/// maps so they survive a load/save round trip, and are reported as unknown by
+/// `utils::config_validation` because Core rejects unknown fields.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct OpenClawConfig {
     /// Agent configuration
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub agents: AgentsConfig,
     /// Model configuration
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub models: ModelsConfig,
     /// Gateway configuration
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub gateway: GatewayConfig,
     /// Channel configuration
-    #[serde(default)]
-    pub channels: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub channels: ChannelsConfig,
     /// Plugin configuration
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub plugins: PluginsConfig,
     /// MCP configuration
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub mcp: HashMap<String, MCPConfig>,
     /// Metadata
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub meta: MetaConfig,
+    /// Agent routing bindings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bindings: Option<Vec<BindingConfig>>,
+    /// Web tools (search API keys)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web: Option<WebToolsConfig>,
+    /// Manager GUI settings (log level, time format)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manager: Option<ManagerSettings>,
+    // Core sections the Manager does not edit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wizard: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logging: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub browser: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub broadcast: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audio: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub messages: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commands: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approvals: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discovery: Option<Value>,
+    #[serde(rename = "canvasHost", default, skip_serializing_if = "Option::is_none")]
+    pub canvas_host: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub talk: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skills: Option<Value>,
+    /// Keys not declared above
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl OpenClawConfig {
+    /// Parse a raw openclaw.json value
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+        serde_path_to_error::deserialize(value).map_err(|e| {
+            format!("Failed to parse configuration at {}: {}", e.path(), e.inner())
+        })
+    }
+
+    /// Parse a raw openclaw.json value, leaving out top-level sections that don't match the
+    /// model, so a bad value in one section doesn't block edits to the others.
+    /// Returns the model and the keys of the sections left out.
+    pub fn from_value_lenient(value: &Value) -> Result<(Self, Vec<String>), String> {
+        let first_error = match Self::from_value(value) {
+            Ok(config) => return Ok((config, Vec::new())),
+            Err(e) => e,
+        };
+        let Some(sections) = value.as_object() else {
+            return Err(first_error);
+        };
+        let skipped: Vec<String> = sections
+            .iter()
+            .filter(|(key, section)| {
+                let alone = Value::Object(Map::from_iter([((*key).clone(), (*section).clone())]));
+                Self::from_value(&alone).is_err()
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        let mut rest = sections.clone();
+        rest.retain(|key, _| !skipped.contains(key));
+        match Self::from_value(&Value::Object(rest)) {
+            Ok(config) if !skipped.is_empty() => Ok((config, skipped)),
+            _ => Err(first_error),
+        }
+    }
+
+    /// Convert back to a raw value; unknown keys are written back unchanged
+    pub fn to_value(&self) -> Result<Value, String> {
+        serde_json::to_value(self).map_err(|e| format!("Failed to serialize configuration: {}", e))
+    }
+
+    /// Write the edits made to this model into `original`, the raw config it was parsed from.
+    /// Only values that differ from `original`'s own typed form are touched, so empty sections,
+    /// key order and sections left out by `from_value_lenient` stay as they were written.
+    pub fn patch_into(&self, original: &mut Value) -> Result<(), String> {
+        let (before, skipped) = Self::from_value_lenient(original)?;
+        let changes = config_history::diff(&before.to_value()?, &self.to_value()?);
+        for change in &changes {
+            let section = unescape_pointer_token(change.path.split('/').nth(1).unwrap_or_default());
+            if skipped.contains(&section) {
+                return Err(format!("'{}' does not match the configuration schema and can't be edited", section));
+            }
+        }
+        if !original.is_object() {
+            *original = Value::Object(Map::new());
+        }
+        for change in changes {
+            set_pointer(original, &change.path, change.after);
+        }
+        Ok(())
+    }
+
+    /// JSON pointers of all keys kept in `extra` maps, i.e. not declared by the model
+    pub fn unknown_fields(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        self.collect_unknown("", &mut out);
+        out
+    }
+
+    /// Top-level bindings, including any still stored at the legacy `agents.bindings`
+    pub fn all_bindings(&self) -> Vec<BindingConfig> {
+        let mut bindings = self.bindings.clone().unwrap_or_default();
+        if bindings.is_empty() {
+            bindings = self.agents.bindings.clone().unwrap_or_default();
+        }
+        bindings
+    }
+}
+
+fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+/// Set (or with `None`, remove) the value at an object-only JSON pointer, creating parents
+fn set_pointer(root: &mut Value, pointer: &str, value: Option<Value>) {
+    let tokens: Vec<String> = pointer.split('/').skip(1).map(unescape_pointer_token).collect();
+    let Some((last, parents)) = tokens.split_last() else {
+        return;
+    };
+    let mut node = root;
+    for token in parents {
+        if !node.is_object() {
+            if value.is_none() {
+                return;
+            }
+            *node = Value::Object(Map::new());
+        }
+        node = node
+            .as_object_mut()
+            .expect("just made an object")
+            .entry(token.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+    let Some(map) = node.as_object_mut() else {
+        return;
+    };
+    match value {
+        Some(value) => {
+            map.insert(last.clone(), value);
+        }
+        None => {
+            map.remove(last);
+        }
+    }
+}
+
+/// Walks the typed tree collecting the keys left in `extra` maps
+trait CollectUnknown {
+    fn collect_unknown(&self, pointer: &str, out: &mut Vec<String>);
+}
+
+fn push_extra(pointer: &str, extra: &Map<String, Value>, out: &mut Vec<String>) {
+    for key in extra.keys() {
+        out.push(format!("{}/{}", pointer, escape_pointer_token(key)));
+    }
+}
+
+fn child(pointer: &str, key: &str) -> String {
+    format!("{}/{}", pointer, escape_pointer_token(key))
+}
+
+impl CollectUnknown for OpenClawConfig {
+    fn collect_unknown(&self, pointer: &str, out: &mut Vec<String>) {
+        self.agents.collect_unknown(&child(pointer, "agents"), out);
+        self.models.collect_unknown(&child(pointer, "models"), out);
+        self.gateway.collect_unknown(&child(pointer, "gateway"), out);
+        // channels are not checked: Core's per-channel schemas are too broad to mirror here
+        push_extra(&child(pointer, "plugins"), &self.plugins.extra, out);
+        for (name, mcp) in &self.mcp {
+            push_extra(&child(&child(pointer, "mcp"), name), &mcp.extra, out);
+        }
+        push_extra(&child(pointer, "meta"), &self.meta.extra, out);
+        for (i, binding) in self.bindings.iter().flatten().enumerate() {
+            binding.collect_unknown(&child(&child(pointer, "bindings"), &i.to_string()), out);
+        }
+        if let Some(web) = &self.web {
+            push_extra(&child(pointer, "web"), &web.extra, out);
+        }
+        if let Some(manager) = &self.manager {
+            push_extra(&child(pointer, "manager"), &manager.extra, out);
+        }
+        push_extra(pointer, &self.extra, out);
+    }
+}
+
+/// Returns true if the value equals its type's default (used to skip empty sections)
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// A setting that is either a plain on/off flag or an object with options,
+/// e.g. `"compaction": true` or `"compaction": { "threshold": 80 }`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum FlagOrSettings<T> {
+    Flag(bool),
+    Settings(T),
+}
+
+impl<T> FlagOrSettings<T> {
+    /// An object counts as enabled; a flag is taken as is
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            FlagOrSettings::Flag(enabled) => *enabled,
+            FlagOrSettings::Settings(_) => true,
+        }
+    }
+
+    pub fn settings(&self) -> Option<&T> {
+        match self {
+            FlagOrSettings::Flag(_) => None,
+            FlagOrSettings::Settings(settings) => Some(settings),
+        }
+    }
 }
 
 /// Agent configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct AgentsConfig {
     /// Default configuration
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub defaults: AgentDefaults,
+    /// Agent list (the legacy object form keyed by ID is read as a list)
+    #[serde(default, deserialize_with = "deserialize_agent_list", skip_serializing_if = "Option::is_none")]
+    pub list: Option<Vec<AgentEntry>>,
+    /// Legacy bindings location (moved to top-level `bindings`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bindings: Option<Vec<BindingConfig>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl AgentsConfig {
+    pub fn find_agent(&self, id: &str) -> Option<&AgentEntry> {
+        self.list.as_ref()?.iter().find(|a| a.id == id)
+    }
+}
+
+impl CollectUnknown for AgentsConfig {
+    fn collect_unknown(&self, pointer: &str, out: &mut Vec<String>) {
+        self.defaults.collect_unknown(&child(pointer, "defaults"), out);
+        for (i, agent) in self.list.iter().flatten().enumerate() {
+            agent.collect_unknown(&child(&child(pointer, "list"), &i.to_string()), out);
+        }
+        for (i, binding) in self.bindings.iter().flatten().enumerate() {
+            binding.collect_unknown(&child(&child(pointer, "bindings"), &i.to_string()), out);
+        }
+        push_extra(pointer, &self.extra, out);
+    }
+}
+
+/// Accept `agents.list` both as an array (current) and as an object keyed by agent ID (legacy)
+fn deserialize_agent_list<'de, D>(deserializer: D) -> Result<Option<Vec<AgentEntry>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AgentList {
+        List(Vec<AgentEntry>),
+        Legacy(BTreeMap<String, Value>),
+    }
+
+    match Option::<AgentList>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(AgentList::List(list)) => Ok(Some(list)),
+        Some(AgentList::Legacy(map)) => map
+            .into_iter()
+            .map(|(id, mut value)| {
+                if let Some(obj) = value.as_object_mut() {
+                    obj.insert("id".to_string(), Value::String(id));
+                }
+                serde_json::from_value(value).map_err(serde::de::Error::custom)
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some),
+    }
 }
 
 /// Agent default configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct AgentDefaults {
     /// Model configuration
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "is_default")]
     pub model: AgentModelConfig,
     /// Available model list (provider/model -> {})
-    #[serde(default)]
-    pub models: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub models: BTreeMap<String, Value>,
     /// Compression configuration
-    #[serde(default)]
-    pub compaction: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compaction: Option<FlagOrSettings<CompactionSettings>>,
     /// Context pruning
-    #[serde(rename = "contextPruning", default)]
-    pub context_pruning: Option<serde_json::Value>,
+    #[serde(rename = "contextPruning", default, skip_serializing_if = "Option::is_none")]
+    pub context_pruning: Option<FlagOrSettings<ContextPruningSettings>>,
     /// Heartbeat configuration
-    #[serde(default)]
-    pub heartbeat: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat: Option<HeartbeatSettings>,
     /// Maximum concurrency
-    #[serde(rename = "maxConcurrent", default)]
+    #[serde(rename = "maxConcurrent", default, skip_serializing_if = "Option::is_none")]
     pub max_concurrent: Option<u32>,
     /// Sub-agent configuration
-    #[serde(default)]
-    pub subagents: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subagents: Option<SubagentSettings>,
+    /// Default workspace directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    /// Maximum characters injected from bootstrap files
+    #[serde(rename = "bootstrapMaxChars", default, skip_serializing_if = "Option::is_none")]
+    pub bootstrap_max_chars: Option<u64>,
+    /// Skip creating bootstrap files in the workspace
+    #[serde(rename = "skipBootstrap", default, skip_serializing_if = "Option::is_none")]
+    pub skip_bootstrap: Option<bool>,
+    // Core settings the Manager does not edit
+    #[serde(rename = "imageModel", default, skip_serializing_if = "Option::is_none")]
+    pub image_model: Option<Value>,
+    #[serde(rename = "userTimezone", default, skip_serializing_if = "Option::is_none")]
+    pub user_timezone: Option<Value>,
+    #[serde(rename = "timeFormat", default, skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<Value>,
+    #[serde(rename = "thinkingDefault", default, skip_serializing_if = "Option::is_none")]
+    pub thinking_default: Option<Value>,
+    #[serde(rename = "verboseDefault", default, skip_serializing_if = "Option::is_none")]
+    pub verbose_default: Option<Value>,
+    #[serde(rename = "elevatedDefault", default, skip_serializing_if = "Option::is_none")]
+    pub elevated_default: Option<Value>,
+    #[serde(rename = "timeoutSeconds", default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<Value>,
+    #[serde(rename = "mediaMaxMb", default, skip_serializing_if = "Option::is_none")]
+    pub media_max_mb: Option<Value>,
+    #[serde(rename = "typingMode", default, skip_serializing_if = "Option::is_none")]
+    pub typing_mode: Option<Value>,
+    #[serde(rename = "typingIntervalSeconds", default, skip_serializing_if = "Option::is_none")]
+    pub typing_interval_seconds: Option<Value>,
+    #[serde(rename = "blockStreamingDefault", default, skip_serializing_if = "Option::is_none")]
+    pub block_streaming_default: Option<Value>,
+    #[serde(rename = "blockStreamingBreak", default, skip_serializing_if = "Option::is_none")]
+    pub block_streaming_break: Option<Value>,
+    #[serde(rename = "blockStreamingChunk", default, skip_serializing_if = "Option::is_none")]
+    pub block_streaming_chunk: Option<Value>,
+    #[serde(rename = "humanDelay", default, skip_serializing_if = "Option::is_none")]
+    pub human_delay: Option<Value>,
+    #[serde(rename = "memorySearch", default, skip_serializing_if = "Option::is_none")]
+    pub memory_search: Option<Value>,
+    #[serde(rename = "cliBackends", default, skip_serializing_if = "Option::is_none")]
+    pub cli_backends: Option<Value>,
+    #[serde(rename = "repoRoot", default, skip_serializing_if = "Option::is_none")]
+    pub repo_root: Option<Value>,
+    #[serde(rename = "contextTokens", default, skip_serializing_if = "Option::is_none")]
+    pub context_tokens: Option<Value>,
+    #[serde(rename = "envelopeTimezone", default, skip_serializing_if = "Option::is_none")]
+    pub envelope_timezone: Option<Value>,
+    #[serde(rename = "envelopeTimestamp", default, skip_serializing_if = "Option::is_none")]
+    pub envelope_timestamp: Option<Value>,
+    #[serde(rename = "envelopeElapsed", default, skip_serializing_if = "Option::is_none")]
+    pub envelope_elapsed: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl CollectUnknown for AgentDefaults {
+    fn collect_unknown(&self, pointer: &str, out: &mut Vec<String>) {
+        push_extra(&child(pointer, "model"), &self.model.extra, out);
+        if let Some(settings) = self.compaction.as_ref().and_then(|c| c.settings()) {
+            push_extra(&child(pointer, "compaction"), &settings.extra, out);
+        }
+        if let Some(settings) = self.context_pruning.as_ref().and_then(|c| c.settings()) {
+            push_extra(&child(pointer, "contextPruning"), &settings.extra, out);
+        }
+        if let Some(heartbeat) = &self.heartbeat {
+            push_extra(&child(pointer, "heartbeat"), &heartbeat.extra, out);
+        }
+        if let Some(subagents) = &self.subagents {
+            push_extra(&child(pointer, "subagents"), &subagents.extra, out);
+        }
+        push_extra(pointer, &self.extra, out);
+    }
 }
 
 /// Agent model configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct AgentModelConfig {
     /// Primary model (format: provider/model-id)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub primary: Option<String>,
+    /// Fallback models, tried in order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallbacks: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Heartbeat settings (agents.defaults.heartbeat or per agent)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct HeartbeatSettings {
+    /// Interval, e.g. "30m"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every: Option<String>,
+    /// Where heartbeat messages are delivered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Compaction settings (agents.defaults.compaction)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct CompactionSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Context pruning settings (agents.defaults.contextPruning)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ContextPruningSettings {
+    #[serde(rename = "maxMessages", default, skip_serializing_if = "Option::is_none")]
+    pub max_messages: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Global sub-agent limits (agents.defaults.subagents)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct SubagentSettings {
+    #[serde(rename = "maxSpawnDepth", default, skip_serializing_if = "Option::is_none")]
+    pub max_spawn_depth: Option<u32>,
+    #[serde(rename = "maxChildrenPerAgent", default, skip_serializing_if = "Option::is_none")]
+    pub max_children_per_agent: Option<u32>,
+    #[serde(rename = "maxConcurrent", default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent: Option<u32>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// One entry of agents.list
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct AgentEntry {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<String>,
+    #[serde(rename = "agentDir", default, skip_serializing_if = "Option::is_none")]
+    pub agent_dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<AgentModelConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat: Option<HeartbeatSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subagents: Option<AgentSubagents>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl AgentEntry {
+    pub fn primary_model(&self) -> Option<&str> {
+        self.model.as_ref()?.primary.as_deref()
+    }
+}
+
+impl CollectUnknown for AgentEntry {
+    fn collect_unknown(&self, pointer: &str, out: &mut Vec<String>) {
+        if let Some(model) = &self.model {
+            push_extra(&child(pointer, "model"), &model.extra, out);
+        }
+        if let Some(heartbeat) = &self.heartbeat {
+            push_extra(&child(pointer, "heartbeat"), &heartbeat.extra, out);
+        }
+        if let Some(subagents) = &self.subagents {
+            push_extra(&child(pointer, "subagents"), &subagents.extra, out);
+        }
+        push_extra(pointer, &self.extra, out);
+    }
+}
+
+/// Per-agent sub-agent permissions
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct AgentSubagents {
+    #[serde(rename = "allowAgents", default, skip_serializing_if = "Option::is_none")]
+    pub allow_agents: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Routing rule: messages matching `match` go to `agentId`
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct BindingConfig {
+    #[serde(rename = "agentId", default)]
+    pub agent_id: String,
+    #[serde(rename = "match", default)]
+    pub match_rule: BindingMatch,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl CollectUnknown for BindingConfig {
+    fn collect_unknown(&self, pointer: &str, out: &mut Vec<String>) {
+        push_extra(&child(pointer, "match"), &self.match_rule.extra, out);
+        push_extra(pointer, &self.extra, out);
+    }
+}
+
+/// Binding match criteria
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct BindingMatch {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(rename = "accountId", default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer: Option<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Channel configuration; only Telegram is typed, other channels pass through.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ChannelsConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub telegram: Option<TelegramChannelConfig>,
+    /// Other channels (discord, feishu, ...) by name
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// channels.telegram
+///
+/// Single-bot configs keep `botToken` and the policies at this level;
+/// multi-bot configs use `accounts`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct TelegramChannelConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(flatten)]
+    pub bot: TelegramAccountConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accounts: Option<BTreeMap<String, TelegramAccountConfig>>,
+}
+
+/// A Telegram bot (channels.telegram.accounts.<id>)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct TelegramAccountConfig {
+    #[serde(rename = "botToken", default, skip_serializing_if = "Option::is_none")]
+    pub bot_token: Option<String>,
+    #[serde(rename = "groupPolicy", default, skip_serializing_if = "Option::is_none")]
+    pub group_policy: Option<String>,
+    #[serde(rename = "dmPolicy", default, skip_serializing_if = "Option::is_none")]
+    pub dm_policy: Option<String>,
+    #[serde(rename = "streamMode", default, skip_serializing_if = "Option::is_none")]
+    pub stream_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Value>,
+    /// Allowed user IDs (strings or numbers)
+    #[serde(rename = "allowFrom", default, skip_serializing_if = "Option::is_none")]
+    pub allow_from: Option<Vec<Value>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Web tools configuration (openclaw.json `web`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct WebToolsConfig {
+    #[serde(rename = "braveApiKey", default, skip_serializing_if = "Option::is_none")]
+    pub brave_api_key: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Manager GUI settings stored in openclaw.json `manager` (snake_case keys)
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ManagerSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Model configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct ModelsConfig {
     /// Provider configuration mapping
-    #[serde(default)]
-    pub providers: HashMap<String, ProviderConfig>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub providers: BTreeMap<String, ProviderConfig>,
+    /// How custom providers combine with built-in ones (merge / replace)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl CollectUnknown for ModelsConfig {
+    fn collect_unknown(&self, pointer: &str, out: &mut Vec<String>) {
+        for (name, provider) in &self.providers {
+            let provider_pointer = child(&child(pointer, "providers"), name);
+            for (i, model) in provider.models.iter().enumerate() {
+                let model_pointer = child(&child(&provider_pointer, "models"), &i.to_string());
+                if let Some(cost) = &model.cost {
+                    push_extra(&child(&model_pointer, "cost"), &cost.extra, out);
+                }
+                push_extra(&model_pointer, &model.extra, out);
+            }
+            push_extra(&provider_pointer, &provider.extra, out);
+        }
+        push_extra(pointer, &self.extra, out);
+    }
 }
 
 /// Provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ProviderConfig {
     /// API URL
     #[serde(rename = "baseUrl")]
     pub base_url: String,
     /// API Key
-    #[serde(rename = "apiKey")]
+    #[serde(rename = "apiKey", default, skip_serializing_if = "Option::is_none")]
     pub api_key: Option<String>,
+    /// Default API type for the provider's models
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api: Option<String>,
+    /// Authentication scheme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Value>,
+    /// Extra request headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, String>>,
+    /// Send the API key in the Authorization header
+    #[serde(rename = "authHeader", default, skip_serializing_if = "Option::is_none")]
+    pub auth_header: Option<bool>,
     /// Model list
     #[serde(default)]
     pub models: Vec<ModelConfig>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Model configuration details
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ModelConfig {
     /// Model ID
     pub id: String,
     /// Display name
     pub name: String,
     /// API type (anthropic-messages / openai-completions)
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub api: Option<String>,
     /// Supported input types
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub input: Vec<String>,
     /// Context window size
-    #[serde(rename = "contextWindow", default)]
+    #[serde(rename = "contextWindow", default, skip_serializing_if = "Option::is_none")]
     pub context_window: Option<u32>,
     /// Maximum output tokens
-    #[serde(rename = "maxTokens", default)]
+    #[serde(rename = "maxTokens", default, skip_serializing_if = "Option::is_none")]
     pub max_tokens: Option<u32>,
     /// Whether reasoning mode is supported
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reasoning: Option<bool>,
     /// Cost configuration
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cost: Option<ModelCostConfig>,
+    /// Extra request headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headers: Option<BTreeMap<String, String>>,
+    /// Provider compatibility flags
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compat: Option<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Model cost configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct ModelCostConfig {
     #[serde(default)]
     pub input: f64,
@@ -129,41 +755,109 @@ pub struct ModelCostConfig {
     pub cache_read: f64,
     #[serde(rename = "cacheWrite", default)]
     pub cache_write: f64,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Gateway configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct GatewayConfig {
     /// Mode: local or cloud
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mode: Option<String>,
     /// Authentication configuration
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub auth: Option<GatewayAuthConfig>,
+    /// Listening port
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// Bind address policy (loopback / lan / ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bind: Option<String>,
+    /// Control UI settings
+    #[serde(rename = "controlUi", default, skip_serializing_if = "Option::is_none")]
+    pub control_ui: Option<GatewayControlUiConfig>,
+    // Core settings the Manager does not edit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reload: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tailscale: Option<Value>,
+    #[serde(rename = "trustedProxies", default, skip_serializing_if = "Option::is_none")]
+    pub trusted_proxies: Option<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl CollectUnknown for GatewayConfig {
+    fn collect_unknown(&self, pointer: &str, out: &mut Vec<String>) {
+        if let Some(auth) = &self.auth {
+            push_extra(&child(pointer, "auth"), &auth.extra, out);
+        }
+        if let Some(control_ui) = &self.control_ui {
+            push_extra(&child(pointer, "controlUi"), &control_ui.extra, out);
+        }
+        push_extra(pointer, &self.extra, out);
+    }
 }
 
 /// Gateway authentication configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct GatewayAuthConfig {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mode: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(rename = "allowTailscale", default, skip_serializing_if = "Option::is_none")]
+    pub allow_tailscale: Option<bool>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Gateway Control UI configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct GatewayControlUiConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(rename = "basePath", default, skip_serializing_if = "Option::is_none")]
+    pub base_path: Option<String>,
+    /// Allow token-only auth without device pairing
+    #[serde(rename = "allowInsecureAuth", default, skip_serializing_if = "Option::is_none")]
+    pub allow_insecure_auth: Option<bool>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// Plugin configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct PluginsConfig {
-    #[serde(default)]
-    pub allow: Vec<String>,
-    #[serde(default)]
-    pub entries: HashMap<String, serde_json::Value>,
-    #[serde(default)]
-    pub installs: HashMap<String, serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deny: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slots: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entries: Option<BTreeMap<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installs: Option<BTreeMap<String, Value>>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 /// MCP configuration (supports both stdio and HTTP modes)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MCPConfig {
     /// Command to run (for stdio servers)
     #[serde(default, skip_serializing_if = "String::is_empty")]
@@ -180,6 +874,8 @@ pub struct MCPConfig {
     /// Whether enabled
     #[serde(default = "default_mcp_enabled")]
     pub enabled: bool,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 fn default_mcp_enabled() -> bool {
@@ -187,12 +883,17 @@ fn default_mcp_enabled() -> bool {
 }
 
 /// Metadata configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct MetaConfig {
-    #[serde(rename = "lastTouchedAt", default)]
+    #[serde(rename = "lastTouchedAt", default, skip_serializing_if = "Option::is_none")]
     pub last_touched_at: Option<String>,
-    #[serde(rename = "lastTouchedVersion", default)]
+    #[serde(rename = "lastTouchedVersion", default, skip_serializing_if = "Option::is_none")]
     pub last_touched_version: Option<String>,
+    /// Manager GUI state (browser control settings)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gui: Option<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 // ============ Data structures for frontend display ============
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config::{FlagOrSettings, OpenClawConfig};
+    use serde_json::json;
+
+    fn sample_config() -> serde_json::Value {
+        json!({
+            "agents": {
+                "defaults": {
+                    "model": {"primary": "anthropic/claude-sonnet-4", "fallbacks": ["openai/gpt-4o"]},
+                    "models": {"anthropic/claude-sonnet-4": {}},
+                    "heartbeat": {"every": "30m", "target": "last", "futureOption": 1},
+                    "compaction": {"threshold": 80, "mode": "safeguard"},
+                    "contextPruning": true,
+                    "workspace": "/home/u/.openclaw/workspace"
+                },
+                "list": [
+                    {"id": "main", "default": true, "model": {"primary": "anthropic/claude-sonnet-4"}, "tools": {"deny": ["exec"]}},
+                    {"id": "coder", "workspace": "/w/coder", "subagents": {"allowAgents": ["main"]}}
+                ]
+            },
+            "bindings": [{"agentId": "coder", "match": {"channel": "telegram", "accountId": "bot2"}}],
+            "channels": {
+                "telegram": {
+                    "enabled": true,
+                    "replyToMode": "first",
+                    "accounts": {"bot2": {"botToken": "123:abc", "allowFrom": ["42", 43], "linkPreview": false}}
+                },
+                "discord": {"token": "x"}
+            },
+            "gateway": {"mode": "local", "port": 18789, "auth": {"mode": "token", "token": "t"}, "controlUi": {"allowInsecureAuth": true}},
+            "web": {"braveApiKey": "k"},
+            "manager": {"log_level": "debug"},
+            "meta": {"lastTouchedVersion": "2026.1.29", "gui": {"browser": {"enabled": true}}},
+            "somethingNew": {"a": 1}
+        })
+    }
+
+    #[test]
+    fn test_round_trip_is_lossless() {
+        let value = sample_config();
+        let typed = OpenClawConfig::from_value(&value).unwrap();
+        assert_eq!(typed.to_value().unwrap(), value);
+    }
+
+    #[test]
+    fn test_typed_accessors() {
+        let typed = OpenClawConfig::from_value(&sample_config()).unwrap();
+
+        assert_eq!(typed.agents.find_agent("main").unwrap().primary_model(), Some("anthropic/claude-sonnet-4"));
+        assert_eq!(typed.agents.defaults.heartbeat.as_ref().unwrap().every.as_deref(), Some("30m"));
+        assert_eq!(typed.agents.defaults.context_pruning, Some(FlagOrSettings::Flag(true)));
+        assert_eq!(
+            typed.agents.defaults.compaction.as_ref().and_then(|c| c.settings()).and_then(|s| s.threshold),
+            Some(80)
+        );
+        let telegram = typed.channels.telegram.as_ref().unwrap();
+        assert_eq!(telegram.accounts.as_ref().unwrap()["bot2"].bot_token.as_deref(), Some("123:abc"));
+        assert_eq!(typed.all_bindings()[0].match_rule.account_id.as_deref(), Some("bot2"));
+        assert_eq!(typed.manager.as_ref().unwrap().log_level.as_deref(), Some("debug"));
+    }
+
+    #[test]
+    fn test_edits_keep_unknown_fields() {
+        let mut typed = OpenClawConfig::from_value(&sample_config()).unwrap();
+        typed.agents.defaults.heartbeat.as_mut().unwrap().every = Some("1h".to_string());
+        typed.gateway.port = Some(18790);
+
+        let value = typed.to_value().unwrap();
+        assert_eq!(value["agents"]["defaults"]["heartbeat"], json!({"every": "1h", "target": "last", "futureOption": 1}));
+        assert_eq!(value["agents"]["list"][0]["tools"], json!({"deny": ["exec"]}));
+        assert_eq!(value["channels"]["telegram"]["replyToMode"], json!("first"));
+        assert_eq!(value["somethingNew"], json!({"a": 1}));
+        assert_eq!(value["gateway"]["port"], json!(18790));
+    }
+
+    #[test]
+    fn test_legacy_agent_list_object_is_read_as_list() {
+        let value = json!({"agents": {"list": {"main": {"workspace": "/w"}}}});
+        let typed = OpenClawConfig::from_value(&value).unwrap();
+
+        assert_eq!(typed.agents.find_agent("main").unwrap().workspace.as_deref(), Some("/w"));
+        assert_eq!(typed.to_value().unwrap(), json!({"agents": {"list": [{"id": "main", "workspace": "/w"}]}}));
+    }
+
+    #[test]
+    fn test_unknown_fields_are_listed() {
+        let typed = OpenClawConfig::from_value(&sample_config()).unwrap();
+        let mut unknown = typed.unknown_fields();
+        unknown.sort();
+        assert_eq!(
+            unknown,
+            vec![
+                "/agents/defaults/heartbeat/futureOption",
+                "/agents/list/0/tools",
+                "/somethingNew",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_patch_keeps_untouched_sections() {
+        let original = json!({
+            "agents": {"defaults": {"workspace": "/w"}, "list": {"main": {"workspace": "/w"}}},
+            "models": {},
+            "gateway": {"port": 18789, "auth": {}},
+            "plugins": {"entries": {}}
+        });
+        let mut typed = OpenClawConfig::from_value(&original).unwrap();
+        typed.agents.defaults.workspace = Some("/w2".to_string());
+
+        let mut patched = original.clone();
+        typed.patch_into(&mut patched).unwrap();
+        let mut expected = original.clone();
+        expected["agents"]["defaults"]["workspace"] = json!("/w2");
+        // Empty sections and the legacy list stay as written
+        assert_eq!(patched, expected);
+    }
+
+    #[test]
+    fn test_lenient_parse_skips_mismatched_sections() {
+        let original = json!({
+            "agents": {"defaults": {"workspace": "/w"}},
+            "gateway": {"port": "not a port"}
+        });
+        assert!(OpenClawConfig::from_value(&original).is_err());
+
+        let (mut typed, skipped) = OpenClawConfig::from_value_lenient(&original).unwrap();
+        assert_eq!(skipped, vec!["gateway".to_string()]);
+        typed.agents.defaults.workspace = Some("/w2".to_string());
+        let mut patched = original.clone();
+        typed.patch_into(&mut patched).unwrap();
+        assert_eq!(patched["agents"]["defaults"]["workspace"], json!("/w2"));
+        assert_eq!(patched["gateway"], json!({"port": "not a port"}));
+
+        typed.gateway.port = Some(18790);
+        assert!(typed.patch_into(&mut original.clone()).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Stable error codes the frontend can switch on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// openclaw CLI not found
+    OpenclawNotInstalled,
+    /// An external command ran but failed
+    CommandFailed,
+    /// Config file could not be read
+    ConfigRead,
+    /// Config file is not valid JSON / does not match the model
+    ConfigParse,
+    /// Config would not pass schema validation
+    ConfigInvalid,
+    /// Config was changed by another process
+    ConfigConflict,
+    /// Another writer holds the config lock
+    ConfigLocked,
+    /// Config file could not be written
+    ConfigWrite,
+    /// Gateway port is taken
+    PortInUse,
+    /// Gateway did not start / stop in time
+    ServiceTimeout,
+    /// Gateway could not be started or stopped
+    ServiceFailed,
+    /// Operation was cancelled by the user or superseded by a newer one
+    Cancelled,
+    /// Operation needs explicit user confirmation before it runs
+    ConfirmationRequired,
+    /// Requested item (agent, snapshot, profile, ...) does not exist
+    NotFound,
+    /// Bad argument from the caller
+    InvalidInput,
+    /// Filesystem error
+    Io,
+    /// HTTP / network error
+    Network,
+    /// Anything not classified yet
+    Internal,
+}
+
+impl ErrorCode {
+    /// Default remediation hint shown when an error doesn't carry its own
+    fn default_hint(self) -> Option<&'static str> {
+        match self {
+            ErrorCode::OpenclawNotInstalled => Some("Install OpenClaw with `npm install -g openclaw`, then restart the Manager."),
+            ErrorCode::ConfigParse => Some("Fix the JSON syntax in openclaw.json or restore a snapshot from Settings → History."),
+            ErrorCode::ConfigInvalid => Some("Remove or correct the listed fields; run `openclaw doctor` for details."),
+            ErrorCode::ConfigConflict => Some("Reload the page to pick up the latest configuration, then apply your change again."),
+            ErrorCode::ConfigLocked => Some("Another Manager window or command is saving the configuration; try again in a moment."),
+            ErrorCode::PortInUse => Some("Stop the process using the gateway port (Kill All) or choose another port in Settings."),
+            ErrorCode::ServiceTimeout => Some("Check the gateway logs for startup errors."),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned by every Tauri command.
+///
+/// Serialized as `{ code, message, hint, detail }`; `message` is what the UI
+/// shows, `detail` carries the underlying cause (stderr, serde error, ...).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub hint: Option<String>,
+    pub detail: Option<String>,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            hint: code.default_hint().map(|h| h.to_string()),
+            detail: None,
+        }
+    }
+
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl fmt::Display) -> Self {
+        self.detail = Some(detail.to_string());
+        self
+    }
+
+    pub fn openclaw_not_installed() -> Self {
+        Self::new(
+            ErrorCode::OpenclawNotInstalled,
+            "Cannot find openclaw command, please ensure it is installed via npm install -g openclaw",
+        )
+    }
+
+    pub fn command_failed(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::CommandFailed, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+
+    pub fn cancelled(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Cancelled, message)
+    }
+
+    pub fn port_in_use(port: u16) -> Self {
+        Self::new(ErrorCode::PortInUse, format!("Port {} is already in use", port))
+    }
+
+    pub fn is(&self, code: ErrorCode) -> bool {
+        self.code == code
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.detail {
+            Some(detail) if !self.message.contains(detail.as_str()) => write!(f, "{}: {}", self.message, detail),
+            _ => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Plain-text errors from code that hasn't been classified keep working with `?`
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        Self::new(ErrorCode::Io, e.to_string())
+    }
+}
+
+/// Lets String-returning helpers call functions that return `AppError`
+impl From<AppError> for String {
+    fn from(e: AppError) -> Self {
+        e.to_string()
+    }
+}
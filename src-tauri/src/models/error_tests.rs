@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use super::super::error::{AppError, ErrorCode};
+    use serde_json::json;
+
+    #[test]
+    fn test_serializes_with_stable_code() {
+        let err = AppError::port_in_use(18789).with_detail("pid 42");
+        assert_eq!(
+            serde_json::to_value(&err).unwrap(),
+            json!({
+                "code": "port_in_use",
+                "message": "Port 18789 is already in use",
+                "hint": "Stop the process using the gateway port (Kill All) or choose another port in Settings.",
+                "detail": "pid 42"
+            })
+        );
+    }
+
+    #[test]
+    fn test_string_conversions() {
+        let err: AppError = format!("Agent '{}' not found", "coder").into();
+        assert!(err.is(ErrorCode::Internal));
+        assert_eq!(err.hint, None);
+
+        let text: String = AppError::new(ErrorCode::ConfigParse, "Failed to parse configuration file")
+            .with_detail("expected value at line 1")
+            .into();
+        assert_eq!(text, "Failed to parse configuration file: expected value at line 1");
+    }
+
+    #[test]
+    fn test_question_mark_works_both_ways() {
+        fn legacy() -> Result<(), String> {
+            Err("legacy failure".to_string())
+        }
+        fn typed() -> Result<(), AppError> {
+            Err(AppError::openclaw_not_installed())
+        }
+        fn command() -> Result<(), AppError> {
+            legacy()?;
+            Ok(())
+        }
+        fn helper() -> Result<(), String> {
+            typed()?;
+            Ok(())
+        }
+
+        assert_eq!(command().unwrap_err().message, "legacy failure");
+        assert!(helper().unwrap_err().contains("npm install -g openclaw"));
+        assert!(typed().unwrap_err().hint.is_some());
+    }
+}
@@ -1,5 +1,12 @@
 pub mod config;
+pub mod error;
 pub mod status;
 
 pub use config::*;
+pub use error::*;
 pub use status::*;
+
+#[cfg(test)]
+mod config_tests;
+#[cfg(test)]
+mod error_tests;
@@ -1,11 +1,64 @@
 use serde::{Deserialize, Serialize};
 
+/// Who the process on the gateway port belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayState {
+    /// Gateway process started (and tracked) by the Manager
+    Ours,
+    /// Port is held by a process the Manager did not start
+    Foreign,
+    /// Nothing on the port and no tracked gateway process
+    #[default]
+    Stopped,
+}
+
+/// Tri-state gateway health
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthState {
+    /// Health endpoint answered OK in time
+    Healthy,
+    /// Gateway answers HTTP but reports a problem, rejects the token or is slow
+    Degraded,
+    /// No HTTP answer on the gateway port
+    #[default]
+    Down,
+}
+
+/// Result of one health probe
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub state: HealthState,
+    /// Gateway accepts requests (start/restart wait for this)
+    pub ready: bool,
+    pub version: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub http_status: Option<u16>,
+    pub message: Option<String>,
+}
+
+impl HealthReport {
+    pub fn down(message: impl Into<String>) -> Self {
+        Self {
+            state: HealthState::Down,
+            ready: false,
+            version: None,
+            latency_ms: None,
+            http_status: None,
+            message: Some(message.into()),
+        }
+    }
+}
+
 /// Service running status
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceStatus {
-    /// Whether it is running
+    /// Whether the gateway port is being served
     pub running: bool,
-    /// Process ID
+    /// Ownership of the gateway process
+    pub state: GatewayState,
+    /// Process ID (our gateway, or the foreign process on the port)
     pub pid: Option<u32>,
     /// Listening port
     pub port: u16,
@@ -15,17 +68,21 @@ pub struct ServiceStatus {
     pub memory_mb: Option<f64>,
     /// CPU usage percentage
     pub cpu_percent: Option<f64>,
+    /// Result of the HTTP health probe (None when nothing listens on the port)
+    pub health: Option<HealthReport>,
 }
 
 impl Default for ServiceStatus {
     fn default() -> Self {
         Self {
             running: false,
+            state: GatewayState::Stopped,
             pid: None,
             port: 18789,
             uptime_seconds: None,
             memory_mb: None,
             cpu_percent: None,
+            health: None,
         }
     }
 }
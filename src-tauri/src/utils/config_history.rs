@@ -0,0 +1,245 @@
+use crate::utils::config_lock::escape_pointer_token;
+use crate::utils::file;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Number of snapshots kept; older ones are pruned on every new snapshot
+pub const MAX_SNAPSHOTS: usize = 50;
+
+/// A saved copy of openclaw.json taken right before it was overwritten
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    /// Snapshot ID (file stem in the history directory)
+    pub id: String,
+    /// When the snapshot was taken (RFC 3339)
+    pub timestamp: String,
+    /// Command whose save replaced this content (e.g. save_agent)
+    pub command: String,
+    /// Size of the saved content in bytes
+    pub size: usize,
+}
+
+/// On-disk format of a snapshot. The content is kept as raw text so that
+/// even a config that no longer parses can be restored byte for byte.
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile {
+    timestamp: String,
+    command: String,
+    content: String,
+}
+
+/// One difference between two configs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDiffEntry {
+    /// JSON pointer of the changed value
+    pub path: String,
+    /// added / removed / changed
+    pub kind: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Store `content` as a new snapshot and prune the oldest beyond `MAX_SNAPSHOTS`
+pub fn save_snapshot(history_dir: &str, content: &str, command: &str) -> io::Result<ConfigSnapshot> {
+    fs::create_dir_all(history_dir)?;
+
+    let now = chrono::Utc::now();
+    let command = sanitize_command(command);
+    // The sequence number keeps IDs unique and in save order within one millisecond
+    let stamp = now.format("%Y%m%dT%H%M%S%3fZ").to_string();
+    let taken = snapshot_ids(history_dir)?.into_iter().filter(|id| id.starts_with(&stamp)).count();
+    let id = format!("{}-{:03}-{}", stamp, taken, command);
+
+    let snapshot = SnapshotFile {
+        timestamp: now.to_rfc3339(),
+        command: command.clone(),
+        content: content.to_string(),
+    };
+    write_snapshot_file(history_dir, &id, &snapshot)?;
+
+    prune(history_dir, MAX_SNAPSHOTS)?;
+    restrict_permissions(history_dir);
+
+    Ok(ConfigSnapshot {
+        id,
+        timestamp: snapshot.timestamp,
+        command,
+        size: content.len(),
+    })
+}
+
+/// Replace `secret` with `replacement` in every snapshot, so a value moved into the vault
+/// or env file doesn't stay behind in the history. Returns the number of snapshots rewritten.
+pub fn redact(history_dir: &str, secret: &str, replacement: &str) -> io::Result<usize> {
+    if secret.is_empty() {
+        return Ok(0);
+    }
+    let mut rewritten = 0;
+    for id in snapshot_ids(history_dir)? {
+        let Ok(mut snapshot) = read_snapshot_file(history_dir, &id) else {
+            continue;
+        };
+        if snapshot.content.contains(secret) {
+            snapshot.content = snapshot.content.replace(secret, replacement);
+            write_snapshot_file(history_dir, &id, &snapshot)?;
+            rewritten += 1;
+        }
+    }
+    Ok(rewritten)
+}
+
+/// List snapshots, newest first
+pub fn list_snapshots(history_dir: &str) -> io::Result<Vec<ConfigSnapshot>> {
+    let mut snapshots = Vec::new();
+    for id in snapshot_ids(history_dir)?.into_iter().rev() {
+        match read_snapshot_file(history_dir, &id) {
+            Ok(s) => snapshots.push(ConfigSnapshot {
+                id,
+                timestamp: s.timestamp,
+                command: s.command,
+                size: s.content.len(),
+            }),
+            // A damaged snapshot shouldn't hide the rest of the history
+            Err(_) => continue,
+        }
+    }
+    Ok(snapshots)
+}
+
+/// Raw openclaw.json content stored in a snapshot
+pub fn read_snapshot(history_dir: &str, id: &str) -> io::Result<String> {
+    read_snapshot_file(history_dir, id).map(|s| s.content)
+}
+
+/// Differences from `before` to `after`, keyed by JSON pointer.
+/// Objects are compared key by key; arrays and scalars are compared whole.
+pub fn diff(before: &Value, after: &Value) -> Vec<ConfigDiffEntry> {
+    let mut entries = Vec::new();
+    diff_at("", before, after, &mut entries);
+    entries
+}
+
+fn diff_at(pointer: &str, before: &Value, after: &Value, entries: &mut Vec<ConfigDiffEntry>) {
+    if before == after {
+        return;
+    }
+
+    if let (Value::Object(b), Value::Object(a)) = (before, after) {
+        let keys: BTreeSet<&String> = b.keys().chain(a.keys()).collect();
+        for key in keys {
+            let child = format!("{}/{}", pointer, escape_pointer_token(key));
+            match (b.get(key), a.get(key)) {
+                (Some(bv), Some(av)) => diff_at(&child, bv, av, entries),
+                (Some(bv), None) => entries.push(ConfigDiffEntry {
+                    path: child,
+                    kind: "removed".to_string(),
+                    before: Some(bv.clone()),
+                    after: None,
+                }),
+                (None, Some(av)) => entries.push(ConfigDiffEntry {
+                    path: child,
+                    kind: "added".to_string(),
+                    before: None,
+                    after: Some(av.clone()),
+                }),
+                (None, None) => {}
+            }
+        }
+        return;
+    }
+
+    entries.push(ConfigDiffEntry {
+        path: if pointer.is_empty() { "/".to_string() } else { pointer.to_string() },
+        kind: "changed".to_string(),
+        before: Some(before.clone()),
+        after: Some(after.clone()),
+    });
+}
+
+/// Snapshots hold full copies of openclaw.json, secrets included, so they are private
+fn write_snapshot_file(history_dir: &str, id: &str, snapshot: &SnapshotFile) -> io::Result<()> {
+    let body = serde_json::to_string(snapshot).map_err(io::Error::other)?;
+    file::write_private_file(&snapshot_path(history_dir, id).to_string_lossy(), body.as_bytes())
+}
+
+fn read_snapshot_file(history_dir: &str, id: &str) -> io::Result<SnapshotFile> {
+    if !is_valid_id(id) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid snapshot id: {}", id)));
+    }
+    let content = fs::read_to_string(snapshot_path(history_dir, id))?;
+    serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Snapshot IDs sorted oldest first (IDs start with a sortable UTC timestamp)
+fn snapshot_ids(history_dir: &str) -> io::Result<Vec<String>> {
+    let entries = match fs::read_dir(history_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.strip_suffix(".json").filter(|stem| is_valid_id(stem)).map(|s| s.to_string())
+        })
+        .collect();
+    ids.sort();
+    Ok(ids)
+}
+
+fn prune(history_dir: &str, keep: usize) -> io::Result<()> {
+    let ids = snapshot_ids(history_dir)?;
+    if ids.len() > keep {
+        for id in &ids[..ids.len() - keep] {
+            let _ = fs::remove_file(snapshot_path(history_dir, id));
+        }
+    }
+    Ok(())
+}
+
+/// Make snapshots written before they were private readable by the owner only
+fn restrict_permissions(history_dir: &str) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        for id in snapshot_ids(history_dir).unwrap_or_default() {
+            let path = snapshot_path(history_dir, &id);
+            if fs::metadata(&path).is_ok_and(|m| m.permissions().mode() & 0o077 != 0) {
+                let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = history_dir;
+    }
+}
+
+fn snapshot_path(history_dir: &str, id: &str) -> PathBuf {
+    Path::new(history_dir).join(format!("{}.json", id))
+}
+
+/// IDs come from the frontend, so only allow what `save_snapshot` generates
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with('.')
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+fn sanitize_command(command: &str) -> String {
+    let cleaned: String = command
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if cleaned.is_empty() {
+        "unknown".to_string()
+    } else {
+        cleaned
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config_history::{diff, list_snapshots, read_snapshot, redact, save_snapshot, MAX_SNAPSHOTS};
+    use super::super::test_dir;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn test_snapshot_roundtrip_and_order() {
+        let dir = test_dir("roundtrip");
+        let dir_str = dir.to_str().unwrap();
+
+        let first = save_snapshot(dir_str, "{\"a\":1}", "save_agent").unwrap();
+        let second = save_snapshot(dir_str, "{\"a\":2}", "delete_provider").unwrap();
+
+        let list = list_snapshots(dir_str).unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].id, second.id);
+        assert_eq!(list[0].command, "delete_provider");
+        assert_eq!(list[1].id, first.id);
+        assert_eq!(read_snapshot(dir_str, &first.id).unwrap(), "{\"a\":1}");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let dir = test_dir("bounded");
+        let dir_str = dir.to_str().unwrap();
+
+        let mut ids = Vec::new();
+        for i in 0..MAX_SNAPSHOTS + 5 {
+            ids.push(save_snapshot(dir_str, &format!("{{\"n\":{}}}", i), "save_config").unwrap().id);
+        }
+
+        let list = list_snapshots(dir_str).unwrap();
+        assert_eq!(list.len(), MAX_SNAPSHOTS);
+        assert_eq!(list[0].id, *ids.last().unwrap());
+        assert!(read_snapshot(dir_str, &ids[0]).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_snapshots_are_private_and_redactable() {
+        let dir = test_dir("redact");
+        let dir_str = dir.to_str().unwrap();
+
+        let keyed = save_snapshot(dir_str, "{\"apiKey\":\"sk-secret-123\"}", "save_provider").unwrap();
+        let other = save_snapshot(dir_str, "{\"a\":1}", "save_agent").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir.join(format!("{}.json", keyed.id))).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        assert_eq!(redact(dir_str, "sk-secret-123", "${OPENAI_API_KEY}").unwrap(), 1);
+        assert_eq!(read_snapshot(dir_str, &keyed.id).unwrap(), "{\"apiKey\":\"${OPENAI_API_KEY}\"}");
+        assert_eq!(read_snapshot(dir_str, &other.id).unwrap(), "{\"a\":1}");
+        assert_eq!(redact(dir_str, "sk-secret-123", "${OPENAI_API_KEY}").unwrap(), 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rejects_path_traversal_ids() {
+        let dir = test_dir("traversal");
+        let dir_str = dir.to_str().unwrap();
+
+        assert!(read_snapshot(dir_str, "../openclaw").is_err());
+        assert!(read_snapshot(dir_str, "").is_err());
+    }
+
+    #[test]
+    fn test_diff_reports_pointers() {
+        let before = json!({
+            "gateway": {"port": 18789},
+            "bindings": [{"agentId": "main"}],
+            "channels": {"telegram": {"enabled": true}}
+        });
+        let after = json!({
+            "gateway": {"port": 18790, "mode": "local"},
+            "channels": {"telegram": {"enabled": true}}
+        });
+
+        let entries = diff(&before, &after);
+        let summary: Vec<(&str, &str)> = entries.iter().map(|e| (e.path.as_str(), e.kind.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![("/bindings", "removed"), ("/gateway/mode", "added"), ("/gateway/port", "changed")]
+        );
+        assert_eq!(entries[2].before, Some(json!(18789)));
+        assert_eq!(entries[2].after, Some(json!(18790)));
+        assert!(diff(&after, &after).is_empty());
+    }
+}
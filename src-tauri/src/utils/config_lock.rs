@@ -0,0 +1,177 @@
+use serde_json::{Map, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait for another writer before giving up
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Advisory lock on a config file, held through a `<file>.lock` sidecar.
+/// The sidecar (not the config itself) is locked so the atomic rename in
+/// `file::write_file` keeps working on Windows. Released on drop.
+pub struct ConfigLock {
+    file: File,
+}
+
+impl ConfigLock {
+    /// Acquire the exclusive lock for `config_path`, waiting up to 10s.
+    /// On a tokio worker the wait is handed to `block_in_place`, so other tasks keep running;
+    /// async callers should prefer `acquire_async`.
+    pub fn acquire(config_path: &str) -> io::Result<Self> {
+        let file = open_lock_file(config_path)?;
+        let wait = move || {
+            let started = Instant::now();
+            loop {
+                if try_lock(&file, config_path, started)? {
+                    return Ok(Self { file });
+                }
+                thread::sleep(LOCK_RETRY_INTERVAL);
+            }
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(wait)
+            }
+            _ => wait(),
+        }
+    }
+
+    /// Acquire the exclusive lock for `config_path` without blocking the executor, waiting up to 10s
+    pub async fn acquire_async(config_path: &str) -> io::Result<Self> {
+        let file = open_lock_file(config_path)?;
+        let started = Instant::now();
+        loop {
+            if try_lock(&file, config_path, started)? {
+                return Ok(Self { file });
+            }
+            tokio::time::sleep(LOCK_RETRY_INTERVAL).await;
+        }
+    }
+}
+
+fn open_lock_file(config_path: &str) -> io::Result<File> {
+    let lock_path = format!("{}.lock", config_path);
+    if let Some(parent) = Path::new(&lock_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&lock_path)
+}
+
+/// One attempt at the lock; false while another writer holds it and the timeout hasn't passed
+fn try_lock(file: &File, config_path: &str, started: Instant) -> io::Result<bool> {
+    match file.try_lock() {
+        Ok(()) => Ok(true),
+        Err(TryLockError::WouldBlock) if started.elapsed() >= LOCK_TIMEOUT => Err(io::Error::new(
+            io::ErrorKind::WouldBlock,
+            format!("{} is locked by another process", config_path),
+        )),
+        Err(TryLockError::WouldBlock) => Ok(false),
+        Err(TryLockError::Error(e)) => Err(e),
+    }
+}
+
+impl Drop for ConfigLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// Revision of a file's current content, `None` if it does not exist.
+/// Content-based rather than mtime-based, because mtime resolution is too
+/// coarse to catch two writes within the same second.
+pub fn file_revision(path: &str) -> Option<String> {
+    fs::read(path).ok().map(|bytes| content_revision(&bytes))
+}
+
+/// Revision string for raw file content
+pub fn content_revision(bytes: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    format!("{:016x}-{}", hasher.finish(), bytes.len())
+}
+
+/// Three-way merge of JSON objects.
+///
+/// `base` is what we originally loaded, `ours` is our edited copy and `theirs`
+/// is what is on disk now. Keys changed on only one side are taken from that
+/// side; objects changed on both sides are merged recursively. Returns the
+/// JSON pointers of values changed differently on both sides as the error.
+pub fn three_way_merge(base: &Value, ours: &Value, theirs: &Value) -> Result<Value, Vec<String>> {
+    let mut conflicts = Vec::new();
+    let merged = merge_at("", base, ours, theirs, &mut conflicts);
+    if conflicts.is_empty() {
+        Ok(merged)
+    } else {
+        Err(conflicts)
+    }
+}
+
+fn merge_at(pointer: &str, base: &Value, ours: &Value, theirs: &Value, conflicts: &mut Vec<String>) -> Value {
+    if ours == theirs || theirs == base {
+        return ours.clone();
+    }
+    if ours == base {
+        return theirs.clone();
+    }
+
+    match (base, ours, theirs) {
+        (Value::Object(b), Value::Object(o), Value::Object(t)) => {
+            let mut merged = Map::new();
+            let keys: BTreeSet<&String> = o.keys().chain(t.keys()).chain(b.keys()).collect();
+
+            for key in keys {
+                let child_pointer = format!("{}/{}", pointer, escape_pointer_token(key));
+                let (bv, ov, tv) = (b.get(key), o.get(key), t.get(key));
+
+                let value = match (bv, ov, tv) {
+                    (_, Some(ov), Some(tv)) => {
+                        let bv = bv.cloned().unwrap_or(Value::Null);
+                        Some(merge_at(&child_pointer, &bv, ov, tv, conflicts))
+                    }
+                    // Removed by them, untouched by us -> removed
+                    (Some(bv), Some(ov), None) if bv == ov => None,
+                    // Removed by us, untouched by them -> removed
+                    (Some(bv), None, Some(tv)) if bv == tv => None,
+                    // Added or kept on one side only
+                    (None, Some(ov), None) => Some(ov.clone()),
+                    (None, None, Some(tv)) => Some(tv.clone()),
+                    (_, None, None) => None,
+                    // Edited on one side, removed on the other
+                    (Some(_), Some(ov), None) => {
+                        conflicts.push(child_pointer);
+                        Some(ov.clone())
+                    }
+                    (Some(_), None, Some(_)) => {
+                        conflicts.push(child_pointer);
+                        None
+                    }
+                };
+
+                if let Some(v) = value {
+                    merged.insert(key.clone(), v);
+                }
+            }
+            Value::Object(merged)
+        }
+        _ => {
+            conflicts.push(if pointer.is_empty() { "/".to_string() } else { pointer.to_string() });
+            ours.clone()
+        }
+    }
+}
+
+/// Escape a key for use in a JSON pointer (RFC 6901)
+pub fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
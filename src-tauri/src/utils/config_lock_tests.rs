@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config_lock::{content_revision, file_revision, three_way_merge, ConfigLock};
+    use super::super::test_dir;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn test_merge_disjoint_edits() {
+        let base = json!({"gateway": {"port": 18789}, "agents": {"list": []}});
+        let ours = json!({"gateway": {"port": 18790}, "agents": {"list": []}});
+        let theirs = json!({"gateway": {"port": 18789}, "agents": {"list": [{"id": "main"}]}});
+
+        let merged = three_way_merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged, json!({"gateway": {"port": 18790}, "agents": {"list": [{"id": "main"}]}}));
+    }
+
+    #[test]
+    fn test_merge_keeps_their_additions_and_our_removals() {
+        let base = json!({"channels": {"telegram": {}, "discord": {}}});
+        let ours = json!({"channels": {"telegram": {}}});
+        let theirs = json!({"channels": {"telegram": {}, "discord": {}, "slack": {"enabled": true}}});
+
+        let merged = three_way_merge(&base, &ours, &theirs).unwrap();
+        assert_eq!(merged, json!({"channels": {"telegram": {}, "slack": {"enabled": true}}}));
+    }
+
+    #[test]
+    fn test_merge_reports_conflict_pointer() {
+        let base = json!({"gateway": {"auth": {"token": "a"}}});
+        let ours = json!({"gateway": {"auth": {"token": "b"}}});
+        let theirs = json!({"gateway": {"auth": {"token": "c"}}});
+
+        let conflicts = three_way_merge(&base, &ours, &theirs).unwrap_err();
+        assert_eq!(conflicts, vec!["/gateway/auth/token".to_string()]);
+    }
+
+    #[test]
+    fn test_merge_escapes_pointer_tokens() {
+        let base = json!({"models": {"anthropic/claude": 1}});
+        let ours = json!({"models": {"anthropic/claude": 2}});
+        let theirs = json!({"models": {}});
+
+        let conflicts = three_way_merge(&base, &ours, &theirs).unwrap_err();
+        assert_eq!(conflicts, vec!["/models/anthropic~1claude".to_string()]);
+    }
+
+    #[test]
+    fn test_revision_tracks_content() {
+        let dir = test_dir("revision");
+        let path = dir.join("openclaw.json");
+        let path_str = path.to_str().unwrap();
+
+        assert_eq!(file_revision(path_str), None);
+        fs::write(&path, "{\"a\":1}").unwrap();
+        let first = file_revision(path_str).unwrap();
+        assert_eq!(first, content_revision(b"{\"a\":1}"));
+        fs::write(&path, "{\"a\":2}").unwrap();
+        assert_ne!(file_revision(path_str).unwrap(), first);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_lock_is_released_on_drop() {
+        let dir = test_dir("lock");
+        let path = dir.join("openclaw.json");
+        let path_str = path.to_str().unwrap();
+
+        let lock = ConfigLock::acquire(path_str).unwrap();
+        assert!(dir.join("openclaw.json.lock").exists());
+        drop(lock);
+        // Re-acquiring must not wait for the timeout
+        let started = std::time::Instant::now();
+        let _lock = ConfigLock::acquire(path_str).unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_async_acquire_waits_for_release() {
+        let dir = test_dir("async");
+        let path = dir.join("openclaw.json");
+        let path_str = path.to_str().unwrap().to_string();
+
+        let held = ConfigLock::acquire(&path_str).unwrap();
+        let releaser = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            drop(held);
+        });
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+        let started = std::time::Instant::now();
+        let lock = runtime.block_on(ConfigLock::acquire_async(&path_str)).unwrap();
+        assert!(started.elapsed() >= std::time::Duration::from_millis(150));
+        drop(lock);
+        releaser.join().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
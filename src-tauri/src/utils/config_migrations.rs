@@ -0,0 +1,293 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::cmp::Ordering;
+
+/// Core release whose config layout the Manager writes (see `process::check_secure_version`).
+/// Recorded in `meta.lastTouchedVersion` after migrating, unless the file already has a newer stamp.
+pub const LAYOUT_VERSION: &str = "2026.1.29";
+
+/// A single layout fix. `apply` must be idempotent: running it on an already
+/// migrated config changes nothing and returns no changes. Migrations carry no
+/// version of their own; whether one is pending is decided by detecting the legacy
+/// layout, not by `meta.lastTouchedVersion`: the openclaw CLI stamps that field too,
+/// so a legacy layout can carry a newer stamp. The stamp only records that the
+/// Manager brought the file to `LAYOUT_VERSION`.
+struct Migration {
+    id: &'static str,
+    description: &'static str,
+    /// Mutates openclaw.json and manager.json, returning a line per change made
+    apply: fn(&mut Value, &mut Value) -> Vec<String>,
+}
+
+/// Ordered: later migrations may rely on the layout produced by earlier ones
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        id: "agents-list-array",
+        description: "Convert agents.list from an object keyed by ID to an array",
+        apply: migrate_agents_list_to_array,
+    },
+    Migration {
+        id: "top-level-bindings",
+        description: "Move agents.bindings to top-level bindings",
+        apply: migrate_agents_bindings,
+    },
+    Migration {
+        id: "telegram-accounts",
+        description: "Move single-bot Telegram settings to channels.telegram.accounts.default",
+        apply: migrate_telegram_single_bot,
+    },
+    Migration {
+        id: "manager-log-level",
+        description: "Move gateway.logLevel to manager.log_level",
+        apply: migrate_gateway_log_level,
+    },
+    Migration {
+        id: "primary-bot-to-manager-json",
+        description: "Move meta.primaryBotAccount to manager.json",
+        apply: migrate_primary_bot_account,
+    },
+];
+
+/// What one migration changed (or would change, for a dry run)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationResult {
+    pub id: String,
+    pub description: String,
+    pub changes: Vec<String>,
+}
+
+/// Outcome of running the pending migrations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationReport {
+    /// meta.lastTouchedVersion before migrating
+    pub from_version: Option<String>,
+    /// Version recorded in meta.lastTouchedVersion afterwards
+    pub to_version: Option<String>,
+    /// Migrations that changed something, in the order they ran
+    pub migrations: Vec<MigrationResult>,
+    /// Whether openclaw.json changed
+    pub config_changed: bool,
+    /// Whether manager.json changed
+    pub manager_changed: bool,
+}
+
+/// Run every migration (each one only changes a legacy layout it finds), then record
+/// the layout version if anything changed. `config` is openclaw.json, `manager` is manager.json.
+pub fn run(config: &mut Value, manager: &mut Value) -> MigrationReport {
+    let original_config = config.clone();
+    let original_manager = manager.clone();
+    let from_version = last_touched_version(config);
+
+    let mut migrations = Vec::new();
+    for migration in MIGRATIONS {
+        let changes = (migration.apply)(config, manager);
+        if !changes.is_empty() {
+            migrations.push(MigrationResult {
+                id: migration.id.to_string(),
+                description: migration.description.to_string(),
+                changes,
+            });
+        }
+    }
+
+    let target = (!migrations.is_empty()).then_some(LAYOUT_VERSION);
+    // Never downgrade a newer stamp written by the CLI
+    let to_version = match target {
+        Some(target) if from_version.as_deref().is_none_or(|v| compare_versions(v, target) == Ordering::Less) => {
+            if !config.is_object() {
+                *config = json!({});
+            }
+            let meta = ensure_object(config, "meta");
+            meta.insert("lastTouchedVersion".to_string(), json!(target));
+            Some(target.to_string())
+        }
+        _ => from_version.clone(),
+    };
+
+    MigrationReport {
+        from_version,
+        to_version,
+        config_changed: *config != original_config,
+        manager_changed: *manager != original_manager,
+        migrations,
+    }
+}
+
+/// Report what `run` would do without touching the inputs
+pub fn dry_run(config: &Value, manager: &Value) -> MigrationReport {
+    run(&mut config.clone(), &mut manager.clone())
+}
+
+fn last_touched_version(config: &Value) -> Option<String> {
+    config.pointer("/meta/lastTouchedVersion").and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// Compare dotted versions numerically ("2026.1.9" < "2026.1.29").
+/// Non-numeric suffixes such as "-beta.1" are ignored.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split(['.', '-'])
+            .map_while(|part| part.parse::<u64>().ok())
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+    for i in 0..a.len().max(b.len()) {
+        match a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+/// Get `value[key]` as an object, creating it (or replacing a non-object) if needed
+fn ensure_object<'a>(value: &'a mut Value, key: &str) -> &'a mut Map<String, Value> {
+    let obj = value.as_object_mut().expect("ensure_object called on a non-object");
+    let entry = obj.entry(key.to_string()).or_insert_with(|| json!({}));
+    if !entry.is_object() {
+        *entry = json!({});
+    }
+    entry.as_object_mut().expect("just ensured an object")
+}
+
+// ============ Migrations ============
+
+fn migrate_agents_list_to_array(config: &mut Value, _manager: &mut Value) -> Vec<String> {
+    let Some(agents) = config.get_mut("agents").and_then(|v| v.as_object_mut()) else {
+        return Vec::new();
+    };
+    let Some(Value::Object(legacy)) = agents.get("list") else {
+        return Vec::new();
+    };
+
+    let list: Vec<Value> = legacy
+        .iter()
+        .map(|(id, val)| {
+            let mut entry = val.clone();
+            if let Some(obj) = entry.as_object_mut() {
+                obj.insert("id".to_string(), json!(id));
+            }
+            entry
+        })
+        .collect();
+    let count = list.len();
+    agents.insert("list".to_string(), Value::Array(list));
+
+    vec![format!("Converted {} agent(s) in /agents/list to array entries", count)]
+}
+
+fn migrate_agents_bindings(config: &mut Value, _manager: &mut Value) -> Vec<String> {
+    let legacy = match config.get_mut("agents").and_then(|v| v.as_object_mut()) {
+        Some(agents) => match agents.remove("bindings") {
+            Some(legacy) => legacy,
+            None => return Vec::new(),
+        },
+        None => return Vec::new(),
+    };
+
+    let legacy = match legacy {
+        Value::Array(items) => items,
+        other => {
+            // Not something Core could have used; drop it but say so
+            return vec![format!("Removed /agents/bindings (not an array: {})", other)];
+        }
+    };
+
+    let count = legacy.len();
+    let obj = config.as_object_mut().expect("agents exists, so config is an object");
+    let top = obj.entry("bindings".to_string()).or_insert_with(|| json!([]));
+    if !top.is_array() {
+        *top = json!([]);
+    }
+    let top = top.as_array_mut().expect("just ensured an array");
+    for binding in legacy {
+        if !top.contains(&binding) {
+            top.push(binding);
+        }
+    }
+
+    vec![format!("Moved {} binding(s) from /agents/bindings to /bindings", count)]
+}
+
+/// Top-level Telegram keys that belong to a single bot
+const TELEGRAM_BOT_KEYS: &[&str] = &["botToken", "groupPolicy", "dmPolicy", "streamMode", "groups", "allowFrom", "groupAllowFrom"];
+
+/// Also run by `save_telegram_account` before adding an account, in case the
+/// file was edited back to the single-bot layout after startup
+pub fn migrate_telegram_single_bot(config: &mut Value, _manager: &mut Value) -> Vec<String> {
+    let Some(telegram) = config.pointer_mut("/channels/telegram").and_then(|v| v.as_object_mut()) else {
+        return Vec::new();
+    };
+    let has_token = telegram.get("botToken").and_then(|v| v.as_str()).is_some_and(|t| !t.is_empty());
+    if !has_token {
+        return Vec::new();
+    }
+
+    let mut bot = Map::new();
+    for key in TELEGRAM_BOT_KEYS {
+        if let Some(value) = telegram.remove(*key) {
+            if !value.is_null() {
+                bot.insert(key.to_string(), value);
+            }
+        }
+    }
+
+    let accounts = telegram.entry("accounts".to_string()).or_insert_with(|| json!({}));
+    if !accounts.is_object() {
+        *accounts = json!({});
+    }
+    let accounts = accounts.as_object_mut().expect("just ensured an object");
+
+    // An explicit accounts.default wins; the top-level bot is kept under another ID
+    let id = if accounts.contains_key("default") { "legacy" } else { "default" };
+    let moved: Vec<String> = bot.keys().cloned().collect();
+    accounts.insert(id.to_string(), Value::Object(bot));
+
+    vec![format!(
+        "Moved /channels/telegram/{{{}}} to /channels/telegram/accounts/{}",
+        moved.join(","),
+        id
+    )]
+}
+
+fn migrate_gateway_log_level(config: &mut Value, _manager: &mut Value) -> Vec<String> {
+    let Some(gateway) = config.get_mut("gateway").and_then(|v| v.as_object_mut()) else {
+        return Vec::new();
+    };
+    let camel = gateway.remove("logLevel");
+    let snake = gateway.remove("log_level");
+    let Some(level) = camel.or(snake) else {
+        return Vec::new();
+    };
+
+    let manager_settings = ensure_object(config, "manager");
+    if manager_settings.contains_key("log_level") {
+        return vec![format!("Removed /gateway/logLevel ({}); /manager/log_level is already set", level)];
+    }
+    manager_settings.insert("log_level".to_string(), level.clone());
+    vec![format!("Moved /gateway/logLevel ({}) to /manager/log_level", level)]
+}
+
+fn migrate_primary_bot_account(config: &mut Value, manager: &mut Value) -> Vec<String> {
+    let Some(primary) = config
+        .get_mut("meta")
+        .and_then(|v| v.as_object_mut())
+        .and_then(|meta| meta.remove("primaryBotAccount"))
+    else {
+        return Vec::new();
+    };
+
+    if !manager.is_object() {
+        *manager = json!({});
+    }
+    let manager_obj = manager.as_object_mut().expect("just ensured an object");
+    if manager_obj.contains_key("primaryBotAccount") {
+        return vec![format!(
+            "Removed /meta/primaryBotAccount ({}); manager.json already has a primary bot",
+            primary
+        )];
+    }
+    manager_obj.insert("primaryBotAccount".to_string(), primary.clone());
+    vec![format!("Moved /meta/primaryBotAccount ({}) to manager.json", primary)]
+}
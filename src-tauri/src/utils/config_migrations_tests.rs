@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config_migrations::{compare_versions, dry_run, run, LAYOUT_VERSION};
+    use serde_json::{json, Value};
+    use std::cmp::Ordering;
+
+    fn legacy_config() -> Value {
+        json!({
+            "agents": {
+                "list": {"coder": {"name": "Coder"}},
+                "bindings": [{"agentId": "coder", "match": {"channel": "telegram"}}]
+            },
+            "channels": {"telegram": {"enabled": true, "botToken": "123:abc", "dmPolicy": "pairing", "allowFrom": [42]}},
+            "gateway": {"port": 18789, "logLevel": "debug"},
+            "meta": {"lastTouchedVersion": "2026.1.5", "primaryBotAccount": "default"}
+        })
+    }
+
+    #[test]
+    fn test_migrations_move_legacy_layout() {
+        let mut config = legacy_config();
+        let mut manager = json!({});
+        let report = run(&mut config, &mut manager);
+
+        assert_eq!(
+            config,
+            json!({
+                "agents": {"list": [{"id": "coder", "name": "Coder"}]},
+                "bindings": [{"agentId": "coder", "match": {"channel": "telegram"}}],
+                "channels": {"telegram": {
+                    "enabled": true,
+                    "accounts": {"default": {"botToken": "123:abc", "dmPolicy": "pairing", "allowFrom": [42]}}
+                }},
+                "gateway": {"port": 18789},
+                "manager": {"log_level": "debug"},
+                "meta": {"lastTouchedVersion": LAYOUT_VERSION}
+            })
+        );
+        assert_eq!(manager, json!({"primaryBotAccount": "default"}));
+        assert!(report.config_changed && report.manager_changed);
+        assert_eq!(report.from_version.as_deref(), Some("2026.1.5"));
+        assert_eq!(report.to_version.as_deref(), Some(LAYOUT_VERSION));
+        assert!(report.migrations.iter().all(|m| m.changes.len() == 1), "{:?}", report.migrations);
+    }
+
+    #[test]
+    fn test_migrations_are_idempotent() {
+        let mut config = legacy_config();
+        let mut manager = json!({});
+        run(&mut config, &mut manager);
+        let (migrated, migrated_manager) = (config.clone(), manager.clone());
+
+        // Force the migrations to run again despite the recorded version
+        config["meta"].as_object_mut().unwrap().remove("lastTouchedVersion");
+        let report = run(&mut config, &mut manager);
+
+        assert_eq!(manager, migrated_manager);
+        assert!(report.migrations.is_empty());
+        assert!(!report.config_changed);
+        assert_eq!(config, {
+            let mut expected = migrated;
+            expected["meta"].as_object_mut().unwrap().remove("lastTouchedVersion");
+            expected
+        });
+    }
+
+    #[test]
+    fn test_existing_targets_are_not_overwritten() {
+        let mut config = json!({
+            "channels": {"telegram": {"botToken": "old", "accounts": {"default": {"botToken": "new"}}}},
+            "gateway": {"logLevel": "debug"},
+            "manager": {"log_level": "warn"},
+            "meta": {"primaryBotAccount": "old"}
+        });
+        let mut manager = json!({"primaryBotAccount": "new"});
+        run(&mut config, &mut manager);
+
+        assert_eq!(config["channels"]["telegram"]["accounts"]["default"]["botToken"], "new");
+        assert_eq!(config["channels"]["telegram"]["accounts"]["legacy"]["botToken"], "old");
+        assert_eq!(config["manager"]["log_level"], "warn");
+        assert!(config["gateway"].get("logLevel").is_none());
+        assert!(config["meta"].get("primaryBotAccount").is_none());
+        assert_eq!(manager["primaryBotAccount"], "new");
+    }
+
+    #[test]
+    fn test_dry_run_reports_without_changing_inputs() {
+        let config = legacy_config();
+        let manager = json!({});
+        let report = dry_run(&config, &manager);
+
+        assert_eq!(config, legacy_config());
+        assert_eq!(manager, json!({}));
+        assert!(report.config_changed);
+        let ids: Vec<&str> = report.migrations.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["agents-list-array", "top-level-bindings", "telegram-accounts", "manager-log-level", "primary-bot-to-manager-json"]
+        );
+    }
+
+    #[test]
+    fn test_legacy_layout_with_newer_stamp_is_migrated() {
+        // The openclaw CLI stamps lastTouchedVersion too, so the stamp can't rule out a legacy layout
+        let mut config = legacy_config();
+        config["meta"]["lastTouchedVersion"] = json!("2026.2.1");
+        let mut manager = json!({});
+        let report = run(&mut config, &mut manager);
+
+        assert_eq!(report.migrations.len(), 5);
+        assert!(config["agents"]["list"].is_array());
+        assert!(config["gateway"].get("logLevel").is_none());
+        assert_eq!(manager, json!({"primaryBotAccount": "default"}));
+        // The newer stamp is kept
+        assert_eq!(config["meta"]["lastTouchedVersion"], "2026.2.1");
+        assert_eq!(report.to_version.as_deref(), Some("2026.2.1"));
+    }
+
+    #[test]
+    fn test_compare_versions_is_numeric() {
+        assert_eq!(compare_versions("2026.1.9", "2026.1.29"), Ordering::Less);
+        assert_eq!(compare_versions("2026.2.0", "2026.1.29"), Ordering::Greater);
+        assert_eq!(compare_versions("2026.1.29", "v2026.1.29"), Ordering::Equal);
+        assert_eq!(compare_versions("2026.1", "2026.1.0"), Ordering::Equal);
+        assert_eq!(compare_versions("2026.1.29-beta.1", "2026.1.29"), Ordering::Equal);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::models::OpenClawConfig;
+use crate::utils::config_lock::escape_pointer_token;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A problem found in openclaw.json, located by JSON pointer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigValidationIssue {
+    /// JSON pointer of the offending value ("/" for the document root)
+    pub path: String,
+    /// unknown_field / invalid_value
+    pub kind: String,
+    pub message: String,
+}
+
+/// Validate a config against the schema defined by `models::OpenClawConfig`.
+///
+/// Every key the typed model does not declare (i.e. ends up in an `extra` map)
+/// is reported as `unknown_field`. Type mismatches and missing required fields
+/// are reported as `invalid_value`; serde stops at the first one, so at most
+/// one is returned per call and unknown fields are only known once it parses.
+pub fn validate(config: &Value) -> Vec<ConfigValidationIssue> {
+    if !config.is_object() {
+        return vec![ConfigValidationIssue {
+            path: "/".to_string(),
+            kind: "invalid_value".to_string(),
+            message: "Configuration must be a JSON object".to_string(),
+        }];
+    }
+
+    match serde_path_to_error::deserialize::<_, OpenClawConfig>(config) {
+        Ok(typed) => typed
+            .unknown_fields()
+            .into_iter()
+            .map(|path| ConfigValidationIssue {
+                message: format!("Unknown field '{}'", path.rsplit('/').next().unwrap_or_default()),
+                path,
+                kind: "unknown_field".to_string(),
+            })
+            .collect(),
+        Err(e) => vec![ConfigValidationIssue {
+            path: error_path_to_pointer(e.path()),
+            kind: "invalid_value".to_string(),
+            message: e.inner().to_string(),
+        }],
+    }
+}
+
+/// Issues in `after` that `before` did not already have.
+/// Lets a command save its own change even if the file already had unrelated problems.
+pub fn new_issues(before: &Value, after: &Value) -> Vec<ConfigValidationIssue> {
+    let existing = validate(before);
+    validate(after).into_iter().filter(|issue| !existing.contains(issue)).collect()
+}
+
+/// One-line summary for command errors, e.g. "/gateway/prot: Unknown field 'prot'"
+pub fn format_issues(issues: &[ConfigValidationIssue]) -> String {
+    issues
+        .iter()
+        .map(|i| format!("{}: {}", i.path, i.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn error_path_to_pointer(path: &serde_path_to_error::Path) -> String {
+    use serde_path_to_error::Segment;
+
+    let pointer: String = path
+        .iter()
+        .map(|segment| match segment {
+            Segment::Seq { index } => format!("/{}", index),
+            Segment::Map { key } => format!("/{}", escape_pointer_token(key)),
+            Segment::Enum { variant } => format!("/{}", escape_pointer_token(variant)),
+            Segment::Unknown => "/?".to_string(),
+        })
+        .collect();
+
+    if pointer.is_empty() {
+        "/".to_string()
+    } else {
+        pointer
+    }
+}
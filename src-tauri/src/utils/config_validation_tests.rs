@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config_validation::{format_issues, new_issues, validate};
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_config_has_no_issues() {
+        let config = json!({
+            "agents": {
+                "defaults": {"model": {"primary": "anthropic/claude-sonnet-4"}, "workspace": "~/.openclaw/workspace"},
+                "list": [{"id": "main"}]
+            },
+            "models": {"providers": {"anthropic": {"baseUrl": "https://api.anthropic.com", "apiKey": "sk", "models": []}}},
+            "gateway": {"mode": "local", "port": 18789, "auth": {"mode": "token", "token": "abc"}, "controlUi": {"allowInsecureAuth": true}},
+            "channels": {"telegram": {"enabled": true, "anything": {"goes": 1}}},
+            "bindings": [{"agentId": "main", "match": {"channel": "telegram"}}],
+            "meta": {"lastTouchedVersion": "2026.1.1", "gui": {"browser": {"enabled": true}}}
+        });
+
+        assert_eq!(validate(&config), vec![]);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_reported_with_pointers() {
+        let config = json!({
+            "gateway": {"prot": 18789, "controlUi": {"allowInsecure": true}},
+            "meta": {"primaryBotAccount": "bot1"},
+            "models": {"providers": {"my/provider": {"baseUrl": "http://x", "apiKey": null, "models": [{"id": "m", "name": "M", "ctx": 1}]}}}
+        });
+
+        let mut paths: Vec<String> = validate(&config)
+            .into_iter()
+            .filter(|i| i.kind == "unknown_field")
+            .map(|i| i.path)
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                "/gateway/controlUi/allowInsecure",
+                "/gateway/prot",
+                "/meta/primaryBotAccount",
+                "/models/providers/my~1provider/models/0/ctx",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_errors_are_reported_with_pointers() {
+        let config = json!({"gateway": {"port": "18789"}});
+
+        let issues = validate(&config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "/gateway/port");
+        assert_eq!(issues[0].kind, "invalid_value");
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        let config = json!({"models": {"providers": {"openai": {"apiKey": "sk"}}}});
+
+        let issues = validate(&config);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "/models/providers/openai");
+        assert!(issues[0].message.contains("baseUrl"));
+    }
+
+    #[test]
+    fn test_non_object_root() {
+        let issues = validate(&json!([1, 2]));
+        assert_eq!(issues[0].path, "/");
+        assert_eq!(format_issues(&issues), "/: Configuration must be a JSON object");
+    }
+
+    #[test]
+    fn test_new_issues_ignores_existing_problems() {
+        let before = json!({"meta": {"primaryBotAccount": "bot1"}});
+        let after = json!({"meta": {"primaryBotAccount": "bot1"}, "gateway": {"port": 18790}});
+        assert!(new_issues(&before, &after).is_empty());
+
+        let broken = json!({"meta": {"primaryBotAccount": "bot1"}, "gateway": {"logLevel": "debug"}});
+        let issues = new_issues(&before, &broken);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path, "/gateway/logLevel");
+    }
+}
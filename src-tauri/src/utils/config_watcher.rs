@@ -0,0 +1,186 @@
+use crate::utils::config_lock;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How often watched files are checked
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A change is reported once the file has stayed the same for this long,
+/// so editors that write in several steps produce a single event
+pub const DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// Event sent to the frontend when a watched config file changes
+pub const CONFIG_CHANGED_EVENT: &str = "config-changed";
+
+/// Own writes remembered per file; older ones are dropped (e.g. writes that failed)
+const MAX_OWN_WRITES: usize = 16;
+
+/// Revisions the Manager itself wrote, by path
+static OWN_WRITES: Lazy<Mutex<HashMap<String, Vec<String>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Record content the Manager is about to write to a watched file, so the watcher
+/// takes it as the new baseline instead of reporting it as an outside change.
+/// Call before writing, so the watcher can't see the file first.
+pub fn note_own_write(path: &str, content: &[u8]) {
+    let mut writes = OWN_WRITES.lock().unwrap_or_else(|e| e.into_inner());
+    let revisions = writes.entry(path.to_string()).or_default();
+    revisions.push(config_lock::content_revision(content));
+    if revisions.len() > MAX_OWN_WRITES {
+        revisions.remove(0);
+    }
+}
+
+fn take_own_write(path: &str, revision: &str) -> bool {
+    let mut writes = OWN_WRITES.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(revisions) = writes.get_mut(path) else {
+        return false;
+    };
+    match revisions.iter().position(|r| r == revision) {
+        Some(index) => {
+            // Anything noted before it was superseded on disk
+            revisions.drain(..=index);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Payload of `CONFIG_CHANGED_EVENT`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChangeEvent {
+    /// openclaw / mcps / mcporter
+    pub file: String,
+    pub path: String,
+    /// Top-level keys whose value changed (empty when the file does not parse)
+    pub sections: Vec<String>,
+    /// Parse error if the new content is not valid JSON
+    pub error: Option<String>,
+    /// Content revision after the change (None if the file was deleted)
+    pub revision: Option<String>,
+}
+
+struct WatchedFile {
+    name: &'static str,
+    path: String,
+    /// Revision last reported (or seen at startup)
+    revision: Option<String>,
+    /// Last content that parsed, used to work out which sections changed
+    value: Value,
+    /// A new revision waiting out the debounce: (revision, first seen)
+    pending: Option<(Option<String>, Instant)>,
+}
+
+/// Polls config files for changes made outside the Manager (CLI, text editors)
+pub struct ConfigWatcher {
+    files: Vec<WatchedFile>,
+}
+
+impl ConfigWatcher {
+    pub fn new() -> Self {
+        Self { files: Vec::new() }
+    }
+
+    /// Start watching `path`; its current content is the baseline
+    pub fn watch(&mut self, name: &'static str, path: &str) {
+        let revision = config_lock::file_revision(path);
+        let value = read_json(path).unwrap_or_else(|_| Value::Object(Default::default()));
+        self.files.push(WatchedFile {
+            name,
+            path: path.to_string(),
+            revision,
+            value,
+            pending: None,
+        });
+    }
+
+    /// Check all files; returns the changes whose debounce elapsed by `now`
+    pub fn poll(&mut self, now: Instant) -> Vec<ConfigChangeEvent> {
+        let mut events = Vec::new();
+        for file in &mut self.files {
+            let current = config_lock::file_revision(&file.path);
+
+            if current == file.revision {
+                // Changed and changed back before the debounce elapsed
+                file.pending = None;
+                continue;
+            }
+
+            match &file.pending {
+                Some((pending, since)) if *pending == current => {
+                    if now.duration_since(*since) < DEBOUNCE {
+                        continue;
+                    }
+                }
+                _ => {
+                    file.pending = Some((current, now));
+                    continue;
+                }
+            }
+
+            file.pending = None;
+            file.revision = current.clone();
+            if current.as_deref().is_some_and(|r| take_own_write(&file.path, r)) {
+                if let Ok(value) = read_json(&file.path) {
+                    file.value = value;
+                }
+                continue;
+            }
+            let event = match read_json(&file.path) {
+                Ok(value) => {
+                    let sections = changed_sections(&file.value, &value);
+                    file.value = value;
+                    ConfigChangeEvent {
+                        file: file.name.to_string(),
+                        path: file.path.clone(),
+                        sections,
+                        error: None,
+                        revision: current,
+                    }
+                }
+                Err(e) => ConfigChangeEvent {
+                    file: file.name.to_string(),
+                    path: file.path.clone(),
+                    sections: Vec::new(),
+                    error: Some(e),
+                    revision: current,
+                },
+            };
+            events.push(event);
+        }
+        events
+    }
+}
+
+impl Default for ConfigWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Top-level keys whose value differs between two configs
+pub fn changed_sections(before: &Value, after: &Value) -> Vec<String> {
+    let empty = serde_json::Map::new();
+    let before = before.as_object().unwrap_or(&empty);
+    let after = after.as_object().unwrap_or(&empty);
+    let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    keys.into_iter()
+        .filter(|key| before.get(*key) != after.get(*key))
+        .map(|key| key.to_string())
+        .collect()
+}
+
+/// Parse a watched file; a missing file reads as an empty object
+fn read_json(path: &str) -> Result<Value, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Value::Object(Default::default())),
+        Err(e) => return Err(format!("Failed to read {}: {}", path, e)),
+    };
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
+    serde_json::from_str(content).map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use super::super::config_watcher::{changed_sections, note_own_write, ConfigWatcher, DEBOUNCE};
+    use serde_json::json;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::Instant;
+
+    fn test_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("openclaw-watcher-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("openclaw.json")
+    }
+
+    #[test]
+    fn test_change_is_reported_once_after_debounce() {
+        let path = test_file("debounce");
+        fs::write(&path, r#"{"gateway":{"port":1},"agents":{}}"#).unwrap();
+        let mut watcher = ConfigWatcher::new();
+        watcher.watch("openclaw", path.to_str().unwrap());
+
+        let start = Instant::now();
+        assert!(watcher.poll(start).is_empty());
+
+        fs::write(&path, r#"{"gateway":{"port":2},"agents":{}}"#).unwrap();
+        // First sighting only starts the debounce
+        assert!(watcher.poll(start).is_empty());
+        let events = watcher.poll(start + DEBOUNCE);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].file, "openclaw");
+        assert_eq!(events[0].sections, vec!["gateway"]);
+        assert_eq!(events[0].error, None);
+
+        // Nothing new afterwards
+        assert!(watcher.poll(start + DEBOUNCE * 2).is_empty());
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_own_writes_are_not_reported() {
+        let path = test_file("own");
+        let path_str = path.to_str().unwrap();
+        fs::write(&path, r#"{"gateway":{"port":1}}"#).unwrap();
+        let mut watcher = ConfigWatcher::new();
+        watcher.watch("openclaw", path_str);
+        let start = Instant::now();
+
+        let ours = r#"{"gateway":{"port":2}}"#;
+        note_own_write(path_str, ours.as_bytes());
+        fs::write(&path, ours).unwrap();
+        assert!(watcher.poll(start).is_empty());
+        assert!(watcher.poll(start + DEBOUNCE).is_empty());
+
+        // An outside edit afterwards is still reported, against our write as the baseline
+        fs::write(&path, r#"{"gateway":{"port":2},"agents":{}}"#).unwrap();
+        assert!(watcher.poll(start + DEBOUNCE).is_empty());
+        let events = watcher.poll(start + DEBOUNCE * 2);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].sections, vec!["agents"]);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_rapid_writes_restart_debounce() {
+        let path = test_file("rapid");
+        fs::write(&path, r#"{"a":1}"#).unwrap();
+        let mut watcher = ConfigWatcher::new();
+        watcher.watch("openclaw", path.to_str().unwrap());
+
+        let start = Instant::now();
+        fs::write(&path, r#"{"a":2}"#).unwrap();
+        assert!(watcher.poll(start).is_empty());
+        fs::write(&path, r#"{"a":3,"b":1}"#).unwrap();
+        // A different revision than the pending one restarts the wait
+        assert!(watcher.poll(start + DEBOUNCE).is_empty());
+        let events = watcher.poll(start + DEBOUNCE * 2);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].sections, vec!["a", "b"]);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_parse_errors_are_flagged_and_recovered() {
+        let path = test_file("parse");
+        fs::write(&path, r#"{"a":1,"b":1}"#).unwrap();
+        let mut watcher = ConfigWatcher::new();
+        watcher.watch("openclaw", path.to_str().unwrap());
+
+        let start = Instant::now();
+        fs::write(&path, r#"{"a":"#).unwrap();
+        watcher.poll(start);
+        let events = watcher.poll(start + DEBOUNCE);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].error.as_deref().unwrap().starts_with("Failed to parse"));
+        assert!(events[0].sections.is_empty());
+
+        // Sections are compared against the last content that parsed
+        fs::write(&path, r#"{"a":1,"b":2}"#).unwrap();
+        watcher.poll(start + DEBOUNCE * 2);
+        let events = watcher.poll(start + DEBOUNCE * 3);
+        assert_eq!(events[0].error, None);
+        assert_eq!(events[0].sections, vec!["b"]);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_changed_sections() {
+        let before = json!({"a": 1, "b": {"x": 1}, "c": 3});
+        let after = json!({"a": 1, "b": {"x": 2}, "d": 4});
+        assert_eq!(changed_sections(&before, &after), vec!["b", "c", "d"]);
+        assert!(changed_sections(&before, &before).is_empty());
+    }
+}
@@ -0,0 +1,255 @@
+use crate::utils::gateway_token;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// manager.json key for shared dashboard access
+pub const MANAGER_KEY: &str = "dashboardAccess";
+/// Port of the dashboard proxy unless configured otherwise
+pub const DEFAULT_PROXY_PORT: u16 = 18799;
+/// Longest lifetime of a shared link
+pub const MAX_TOKEN_HOURS: u32 = 90 * 24;
+/// Expired and revoked tokens are listed for this long, then dropped
+pub const KEEP_INACTIVE_DAYS: i64 = 30;
+/// Scoped tokens start with this, so they're never mistaken for the gateway token
+pub const TOKEN_PREFIX: &str = "ocd_";
+
+/// What a dashboard token may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DashboardScope {
+    /// Status, lists and history; no RPC that changes anything
+    ReadOnly,
+    /// Everything the gateway token allows
+    Admin,
+}
+
+/// Gateway RPC methods a read-only link may call. Anything not listed is refused,
+/// including config and log reads: they run with the gateway token and could reveal it.
+const READ_ONLY_METHODS: &[&str] = &[
+    "connect",
+    "health",
+    "status",
+    "last-heartbeat",
+    "system-presence",
+    "sessions.list",
+    "sessions.preview",
+    "sessions.usage",
+    "chat.history",
+    "agents.list",
+    "models.list",
+    "channels.status",
+    "nodes.list",
+    "cron.list",
+    "cron.status",
+    "cron.runs",
+    "skills.status",
+    "usage.status",
+    "usage.cost",
+];
+
+impl DashboardScope {
+    /// Whether a gateway RPC method may be called with this scope
+    pub fn allows(&self, method: &str) -> bool {
+        match self {
+            DashboardScope::Admin => true,
+            DashboardScope::ReadOnly => READ_ONLY_METHODS.contains(&method),
+        }
+    }
+}
+
+/// A named dashboard token handed to one person
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardToken {
+    pub id: String,
+    /// Who the link is for
+    pub name: String,
+    pub token: String,
+    pub scope: DashboardScope,
+    /// RFC 3339
+    pub created_at: String,
+    pub expires_at: String,
+    #[serde(default)]
+    pub revoked_at: Option<String>,
+}
+
+/// Lifecycle state of a token at a given time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenStatus {
+    Active,
+    Expired,
+    Revoked,
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
+impl DashboardToken {
+    pub fn status(&self, now: DateTime<Utc>) -> TokenStatus {
+        if self.revoked_at.is_some() {
+            TokenStatus::Revoked
+        } else if parse_time(&self.expires_at).is_none_or(|expires| expires <= now) {
+            TokenStatus::Expired
+        } else {
+            TokenStatus::Active
+        }
+    }
+
+    /// Seconds until expiry (for the proxy's cookie), zero once expired
+    pub fn remaining_secs(&self, now: DateTime<Utc>) -> i64 {
+        parse_time(&self.expires_at).map_or(0, |expires| (expires - now).num_seconds().max(0))
+    }
+}
+
+/// Why a presented token was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Denied {
+    Unknown,
+    Expired,
+    Revoked,
+}
+
+impl Denied {
+    pub fn message(&self) -> &'static str {
+        match self {
+            Denied::Unknown => "This dashboard link is not valid",
+            Denied::Expired => "This dashboard link has expired",
+            Denied::Revoked => "This dashboard link has been revoked",
+        }
+    }
+}
+
+/// Compare without stopping at the first differing byte
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Shared dashboard access stored in manager.json under "dashboardAccess"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DashboardAccess {
+    /// Run the dashboard proxy; links only work while it is on
+    pub enabled: bool,
+    /// Address the proxy listens on; 0.0.0.0 to share on the network
+    pub bind: String,
+    pub port: u16,
+    /// Base URL teammates reach the proxy at (e.g. a LAN or tailnet name); defaults to bind:port
+    pub public_url: Option<String>,
+    pub tokens: Vec<DashboardToken>,
+}
+
+impl Default for DashboardAccess {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: "127.0.0.1".to_string(),
+            port: DEFAULT_PROXY_PORT,
+            public_url: None,
+            tokens: Vec::new(),
+        }
+    }
+}
+
+impl DashboardAccess {
+    pub fn from_manager_config(manager: &serde_json::Value) -> Self {
+        manager
+            .get(MANAGER_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Check proxy settings before saving
+    pub fn validate(&self) -> Result<(), String> {
+        self.bind
+            .parse::<std::net::IpAddr>()
+            .map_err(|_| format!("'{}' is not an IP address", self.bind))?;
+        if self.port == 0 {
+            return Err("Proxy port must not be 0".to_string());
+        }
+        if let Some(url) = &self.public_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err("Public URL must start with http:// or https://".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a token for `name` valid for `hours`
+    pub fn mint(&mut self, name: &str, scope: DashboardScope, hours: u32, now: DateTime<Utc>) -> Result<&DashboardToken, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Give the link a name, e.g. who it is for".to_string());
+        }
+        if hours == 0 || hours > MAX_TOKEN_HOURS {
+            return Err(format!("Expiry must be between 1 hour and {} days", MAX_TOKEN_HOURS / 24));
+        }
+        let secret = gateway_token::generate();
+        let token = DashboardToken {
+            id: secret[..8].to_string(),
+            name: name.to_string(),
+            token: format!("{}{}", TOKEN_PREFIX, secret),
+            scope,
+            created_at: now.to_rfc3339(),
+            expires_at: (now + Duration::hours(hours as i64)).to_rfc3339(),
+            revoked_at: None,
+        };
+        self.tokens.push(token);
+        Ok(self.tokens.last().expect("just pushed"))
+    }
+
+    /// Revoke a token; returns false if there is no such token
+    pub fn revoke(&mut self, id: &str, now: DateTime<Utc>) -> bool {
+        match self.tokens.iter_mut().find(|t| t.id == id) {
+            Some(token) => {
+                token.revoked_at.get_or_insert_with(|| now.to_rfc3339());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Drop tokens that expired or were revoked more than `KEEP_INACTIVE_DAYS` ago
+    pub fn prune(&mut self, now: DateTime<Utc>) {
+        let cutoff = now - Duration::days(KEEP_INACTIVE_DAYS);
+        self.tokens.retain(|t| {
+            let ended = t.revoked_at.as_deref().or(Some(t.expires_at.as_str())).and_then(parse_time);
+            t.status(now) == TokenStatus::Active || ended.is_some_and(|ended| ended > cutoff)
+        });
+    }
+
+    /// The token matching `presented`, if it may be used at `now`
+    pub fn authenticate(&self, presented: &str, now: DateTime<Utc>) -> Result<&DashboardToken, Denied> {
+        let token = self
+            .tokens
+            .iter()
+            .find(|t| constant_time_eq(&t.token, presented))
+            .ok_or(Denied::Unknown)?;
+        match token.status(now) {
+            TokenStatus::Active => Ok(token),
+            TokenStatus::Expired => Err(Denied::Expired),
+            TokenStatus::Revoked => Err(Denied::Revoked),
+        }
+    }
+
+    /// Base URL of shared links
+    pub fn base_url(&self) -> String {
+        match &self.public_url {
+            Some(url) => url.trim_end_matches('/').to_string(),
+            None => {
+                let host = match self.bind.as_str() {
+                    "0.0.0.0" | "::" => "localhost".to_string(),
+                    ip if ip.contains(':') => format!("[{}]", ip),
+                    ip => ip.to_string(),
+                };
+                format!("http://{}:{}", host, self.port)
+            }
+        }
+    }
+
+    /// Dashboard link for one token
+    pub fn link(&self, token: &DashboardToken) -> String {
+        format!("{}/?token={}", self.base_url(), token.token)
+    }
+}
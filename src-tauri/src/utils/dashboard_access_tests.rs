@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use super::super::dashboard_access::{DashboardAccess, DashboardScope, Denied, TokenStatus, TOKEN_PREFIX};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_mint_authenticate_expire_and_revoke() {
+        let now = Utc::now();
+        let mut access = DashboardAccess::default();
+        assert!(access.mint("  ", DashboardScope::Admin, 24, now).is_err());
+        assert!(access.mint("alice", DashboardScope::Admin, 0, now).is_err());
+
+        let alice = access.mint("alice", DashboardScope::ReadOnly, 24, now).unwrap().clone();
+        let bob = access.mint("bob", DashboardScope::Admin, 1, now).unwrap().clone();
+        assert!(alice.token.starts_with(TOKEN_PREFIX));
+        assert_ne!(alice.token, bob.token);
+
+        assert_eq!(access.authenticate(&alice.token, now).unwrap().name, "alice");
+        assert_eq!(access.authenticate("ocd_nope", now).unwrap_err(), Denied::Unknown);
+
+        let later = now + Duration::hours(2);
+        assert_eq!(access.authenticate(&bob.token, later).unwrap_err(), Denied::Expired);
+        assert_eq!(bob.remaining_secs(later), 0);
+
+        assert!(access.revoke(&alice.id, now));
+        assert!(!access.revoke("missing", now));
+        assert_eq!(access.authenticate(&alice.token, now).unwrap_err(), Denied::Revoked);
+        assert_eq!(access.tokens[0].status(now), TokenStatus::Revoked);
+
+        // Inactive tokens stay listed for a while, then are dropped
+        access.prune(later);
+        assert_eq!(access.tokens.len(), 2);
+        access.prune(now + Duration::days(31));
+        assert!(access.tokens.is_empty());
+    }
+
+    #[test]
+    fn test_scope_and_links() {
+        assert!(DashboardScope::ReadOnly.allows("connect"));
+        assert!(DashboardScope::ReadOnly.allows("sessions.list"));
+        assert!(DashboardScope::ReadOnly.allows("health"));
+        assert!(!DashboardScope::ReadOnly.allows("config.apply"));
+        assert!(!DashboardScope::ReadOnly.allows("chat.send"));
+        assert!(!DashboardScope::ReadOnly.allows("config.get"));
+        assert!(!DashboardScope::ReadOnly.allows("logs.tail"));
+        assert!(!DashboardScope::ReadOnly.allows("anything.read"));
+        assert!(DashboardScope::Admin.allows("config.apply"));
+
+        let mut access = DashboardAccess { bind: "0.0.0.0".to_string(), ..Default::default() };
+        assert!(access.validate().is_ok());
+        assert_eq!(access.base_url(), "http://localhost:18799");
+        access.public_url = Some("https://box.tailnet.ts.net/".to_string());
+        let token = access.mint("carol", DashboardScope::Admin, 1, Utc::now()).unwrap().clone();
+        assert_eq!(access.link(&token), format!("https://box.tailnet.ts.net/?token={}", token.token));
+
+        access.public_url = Some("box:18799".to_string());
+        assert!(access.validate().is_err());
+        access.public_url = None;
+        access.bind = "localhost".to_string();
+        assert!(access.validate().is_err());
+    }
+}
@@ -0,0 +1,331 @@
+use crate::utils::dashboard_access::DashboardScope;
+use serde_json::{json, Value};
+use std::io;
+use std::net::IpAddr;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Cookie the proxy sets after a link is opened, so the dashboard's asset and
+/// WebSocket requests are authenticated without the token in every URL
+pub const COOKIE_NAME: &str = "ocm_dashboard";
+/// Largest request or response head accepted
+pub const MAX_HEAD_BYTES: usize = 16 * 1024;
+/// Largest WebSocket frame relayed
+const MAX_FRAME_BYTES: u64 = 16 * 1024 * 1024;
+
+pub const OPCODE_CONTINUATION: u8 = 0x0;
+pub const OPCODE_TEXT: u8 = 0x1;
+pub const OPCODE_CLOSE: u8 = 0x8;
+pub const OPCODE_PING: u8 = 0x9;
+pub const OPCODE_PONG: u8 = 0xA;
+
+/// Index just past the `\r\n\r\n` ending an HTTP head
+pub fn head_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n").map(|i| i + 4)
+}
+
+/// HTTP/1.1 request line and headers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestHead {
+    pub method: String,
+    pub target: String,
+    pub version: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl RequestHead {
+    pub fn parse(head: &[u8]) -> Option<Self> {
+        let text = std::str::from_utf8(head).ok()?;
+        let mut lines = text.split("\r\n");
+        let mut request_line = lines.next()?.split(' ');
+        let (method, target, version) = (request_line.next()?, request_line.next()?, request_line.next()?);
+        if !version.starts_with("HTTP/1.") {
+            return None;
+        }
+        let headers = lines
+            .filter(|l| !l.is_empty())
+            .map(|l| l.split_once(':').map(|(k, v)| (k.trim().to_string(), v.trim().to_string())))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { method: method.to_string(), target: target.to_string(), version: version.to_string(), headers })
+    }
+
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+
+    pub fn remove_header(&mut self, name: &str) {
+        self.headers.retain(|(k, _)| !k.eq_ignore_ascii_case(name));
+    }
+
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        self.remove_header(name);
+        self.headers.push((name.to_string(), value.to_string()));
+    }
+
+    pub fn query_param(&self, name: &str) -> Option<String> {
+        let (_, query) = self.target.split_once('?')?;
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.to_string())
+    }
+
+    pub fn remove_query_param(&mut self, name: &str) {
+        let Some((path, query)) = self.target.split_once('?') else {
+            return;
+        };
+        let kept: Vec<&str> = query
+            .split('&')
+            .filter(|pair| pair.split_once('=').map_or(*pair, |(k, _)| k) != name)
+            .collect();
+        self.target = if kept.is_empty() { path.to_string() } else { format!("{}?{}", path, kept.join("&")) };
+    }
+
+    pub fn cookie(&self, name: &str) -> Option<String> {
+        self.header("Cookie")?
+            .split(';')
+            .filter_map(|c| c.trim().split_once('='))
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.to_string())
+    }
+
+    pub fn is_websocket(&self) -> bool {
+        self.header("Upgrade").is_some_and(|v| v.eq_ignore_ascii_case("websocket"))
+    }
+
+    /// A browser WebSocket from another site would carry the cookie too; only the
+    /// dashboard's own origin may open one
+    pub fn same_origin(&self) -> bool {
+        match (self.header("Origin"), self.header("Host")) {
+            (None, _) => true,
+            (Some(origin), Some(host)) => origin.split_once("://").is_some_and(|(_, h)| h.trim_end_matches('/') == host),
+            (Some(_), None) => false,
+        }
+    }
+
+    /// The dashboard token presented with the request: `?token=` from a shared link,
+    /// otherwise the proxy's cookie. The flag is true for the query parameter.
+    pub fn presented_token(&self) -> Option<(String, bool)> {
+        self.query_param("token")
+            .map(|t| (t, true))
+            .or_else(|| self.cookie(COOKIE_NAME).map(|t| (t, false)))
+    }
+
+    /// Whether a plain HTTP request only reads, as required on a read-only link
+    pub fn is_read(&self) -> bool {
+        matches!(self.method.as_str(), "GET" | "HEAD")
+    }
+
+    /// Rewrite the request for the gateway on loopback: drop the dashboard token and
+    /// cookie, present the gateway's own host and origin, ask for one request per
+    /// connection (so every request is checked) and no WebSocket compression (so
+    /// frames can be read). `X-Forwarded-*` tell the gateway who the request came from;
+    /// the client's own forwarding headers are dropped so it can't pose as local.
+    pub fn prepare_upstream(&mut self, gateway_port: u16, client_ip: IpAddr) {
+        self.remove_query_param("token");
+        self.headers.retain(|(k, _)| {
+            let name = k.to_ascii_lowercase();
+            name != "forwarded" && !name.starts_with("x-forwarded-")
+        });
+        self.set_header("X-Forwarded-For", &client_ip.to_string());
+        self.set_header("X-Forwarded-Proto", "http");
+        if let Some(host) = self.header("Host").map(str::to_string) {
+            self.set_header("X-Forwarded-Host", &host);
+        }
+        let gateway = format!("127.0.0.1:{}", gateway_port);
+        self.set_header("Host", &gateway);
+        if self.header("Origin").is_some() {
+            self.set_header("Origin", &format!("http://{}", gateway));
+        }
+        if let Some(cookies) = self.header("Cookie") {
+            let kept: Vec<&str> = cookies
+                .split(';')
+                .map(str::trim)
+                .filter(|c| !c.starts_with(&format!("{}=", COOKIE_NAME)))
+                .collect();
+            if kept.is_empty() {
+                self.remove_header("Cookie");
+            } else {
+                self.set_header("Cookie", &kept.join("; "));
+            }
+        }
+        if self.is_websocket() {
+            self.remove_header("Sec-WebSocket-Extensions");
+        } else {
+            self.set_header("Connection", "close");
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = format!("{} {} {}\r\n", self.method, self.target, self.version);
+        for (name, value) in &self.headers {
+            out.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        out.push_str("\r\n");
+        out.into_bytes()
+    }
+}
+
+/// Insert a header line right after the status line of a response head
+pub fn insert_response_header(head: &[u8], line: &str) -> Vec<u8> {
+    match head.windows(2).position(|w| w == b"\r\n") {
+        Some(end) => [&head[..end + 2], line.as_bytes(), b"\r\n", &head[end + 2..]].concat(),
+        None => head.to_vec(),
+    }
+}
+
+/// Status code of a response head
+pub fn response_status(head: &[u8]) -> Option<u16> {
+    std::str::from_utf8(head).ok()?.split(' ').nth(1)?.parse().ok()
+}
+
+/// Small plain-text response from the proxy itself
+pub fn plain_response(status: u16, reason: &str, body: &str) -> Vec<u8> {
+    format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )
+    .into_bytes()
+}
+
+/// One WebSocket frame, payload unmasked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub fin: bool,
+    pub rsv: u8,
+    pub opcode: u8,
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    pub fn text(text: &str) -> Self {
+        Self { fin: true, rsv: 0, opcode: OPCODE_TEXT, payload: text.as_bytes().to_vec() }
+    }
+
+    /// Close frame with status 1008 (policy violation)
+    pub fn policy_close(reason: &str) -> Self {
+        let mut payload = 1008u16.to_be_bytes().to_vec();
+        payload.extend_from_slice(&reason.as_bytes()[..reason.len().min(120)]);
+        Self { fin: true, rsv: 0, opcode: OPCODE_CLOSE, payload }
+    }
+
+    /// Wire form; client-to-server frames must be masked
+    pub fn encode(&self, mask: Option<[u8; 4]>) -> Vec<u8> {
+        let mut out = vec![(if self.fin { 0x80 } else { 0 }) | (self.rsv << 4) | self.opcode];
+        let mask_bit = if mask.is_some() { 0x80 } else { 0 };
+        let len = self.payload.len();
+        if len < 126 {
+            out.push(mask_bit | len as u8);
+        } else if len <= u16::MAX as usize {
+            out.push(mask_bit | 126);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        } else {
+            out.push(mask_bit | 127);
+            out.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+        match mask {
+            Some(key) => {
+                out.extend_from_slice(&key);
+                out.extend(self.payload.iter().enumerate().map(|(i, b)| b ^ key[i % 4]));
+            }
+            None => out.extend_from_slice(&self.payload),
+        }
+        out
+    }
+
+    /// Read one frame; None on a clean end of stream between frames
+    pub async fn read<R: AsyncRead + Unpin>(reader: &mut R) -> io::Result<Option<Self>> {
+        let mut header = [0u8; 2];
+        match reader.read_exact(&mut header).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+        let len = match header[1] & 0x7f {
+            126 => reader.read_u16().await? as u64,
+            127 => reader.read_u64().await?,
+            len => len as u64,
+        };
+        if len > MAX_FRAME_BYTES {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "WebSocket frame too large"));
+        }
+        let mask = if header[1] & 0x80 != 0 {
+            let mut key = [0u8; 4];
+            reader.read_exact(&mut key).await?;
+            Some(key)
+        } else {
+            None
+        };
+        let mut payload = vec![0u8; len as usize];
+        reader.read_exact(&mut payload).await?;
+        if let Some(key) = mask {
+            payload.iter_mut().enumerate().for_each(|(i, b)| *b ^= key[i % 4]);
+        }
+        Ok(Some(Self { fin: header[0] & 0x80 != 0, rsv: (header[0] >> 4) & 0x7, opcode: header[0] & 0x0f, payload }))
+    }
+}
+
+/// What to do with a frame from the dashboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientFrame {
+    /// Send on to the gateway
+    Forward(Frame),
+    /// Answer the dashboard directly; nothing reaches the gateway
+    Reply(Frame),
+    /// End the connection
+    Close(&'static str),
+}
+
+/// Apply a token's scope to a frame from the dashboard. The `connect` request gets the
+/// gateway token in place of the dashboard token. A read-only scope fails closed: any
+/// other request must be a read, and anything that can't be checked (binary or
+/// fragmented frames, text that isn't a `req` message) ends the connection.
+pub fn filter_client_frame(frame: Frame, scope: DashboardScope, gateway_token: &str) -> ClientFrame {
+    let read_only = scope == DashboardScope::ReadOnly;
+    if matches!(frame.opcode, OPCODE_CLOSE | OPCODE_PING | OPCODE_PONG) {
+        return ClientFrame::Forward(frame);
+    }
+    let fragmented = !frame.fin || frame.opcode == OPCODE_CONTINUATION;
+    if frame.opcode != OPCODE_TEXT || fragmented {
+        if read_only {
+            return ClientFrame::Close("Only complete text messages are allowed on a read-only link");
+        }
+        return ClientFrame::Forward(frame);
+    }
+    let request = serde_json::from_slice::<Value>(&frame.payload)
+        .ok()
+        .filter(|m| m.get("type").and_then(|t| t.as_str()) == Some("req"));
+    let Some(mut message) = request else {
+        if read_only {
+            return ClientFrame::Close("Unrecognized message on a read-only link");
+        }
+        return ClientFrame::Forward(frame);
+    };
+    let method = message.get("method").and_then(|m| m.as_str()).unwrap_or_default().to_string();
+    if method == "connect" {
+        if !message["params"].is_object() {
+            message["params"] = json!({});
+        }
+        if !message["params"]["auth"].is_object() {
+            message["params"]["auth"] = json!({});
+        }
+        message["params"]["auth"]["token"] = json!(gateway_token);
+        return ClientFrame::Forward(Frame::text(&message.to_string()));
+    }
+    if scope.allows(&method) {
+        return ClientFrame::Forward(frame);
+    }
+    let error = json!({
+        "type": "res",
+        "id": message.get("id").cloned().unwrap_or(Value::Null),
+        "ok": false,
+        "error": {
+            "code": "FORBIDDEN",
+            "message": format!("This dashboard link is read-only; {} is not allowed", method),
+        },
+    });
+    ClientFrame::Reply(Frame::text(&error.to_string()))
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use super::super::dashboard_access::DashboardScope;
+    use super::super::dashboard_proxy::{
+        filter_client_frame, ClientFrame, Frame, RequestHead, OPCODE_CONTINUATION, OPCODE_PING,
+    };
+    use serde_json::Value;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    #[test]
+    fn test_request_head_is_rewritten_for_the_gateway() {
+        let raw = b"GET /chat?token=ocd_abc&tab=1 HTTP/1.1\r\nHost: box:18799\r\nOrigin: http://box:18799\r\n\
+Cookie: theme=dark; ocm_dashboard=ocd_old\r\nUpgrade: websocket\r\nSec-WebSocket-Extensions: permessage-deflate\r\n\r\n";
+        let mut head = RequestHead::parse(raw).unwrap();
+        assert_eq!(head.presented_token(), Some(("ocd_abc".to_string(), true)));
+        assert!(head.is_websocket());
+        assert!(head.same_origin());
+
+        head.prepare_upstream(18789, IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20)));
+        assert_eq!(head.target, "/chat?tab=1");
+        assert_eq!(head.header("host"), Some("127.0.0.1:18789"));
+        assert_eq!(head.header("Origin"), Some("http://127.0.0.1:18789"));
+        assert_eq!(head.header("Cookie"), Some("theme=dark"));
+        assert_eq!(head.header("Sec-WebSocket-Extensions"), None);
+        assert_eq!(head.presented_token(), None);
+        assert_eq!(head.header("X-Forwarded-For"), Some("192.168.1.20"));
+        assert_eq!(head.header("X-Forwarded-Proto"), Some("http"));
+        assert_eq!(head.header("X-Forwarded-Host"), Some("box:18799"));
+        assert!(head.is_read());
+
+        // Forwarding headers from the client are replaced, never trusted
+        let spoofed = b"POST /api HTTP/1.1\r\nHost: box\r\nX-Forwarded-For: 127.0.0.1\r\nx-forwarded-proto: https\r\n\
+Forwarded: for=127.0.0.1\r\nX-Forwarded-Port: 443\r\n\r\n";
+        let mut proxied = RequestHead::parse(spoofed).unwrap();
+        assert!(!proxied.is_read());
+        proxied.prepare_upstream(18789, IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2)));
+        assert_eq!(proxied.header("X-Forwarded-For"), Some("10.0.0.2"));
+        assert_eq!(proxied.header("X-Forwarded-Proto"), Some("http"));
+        assert_eq!(proxied.header("Forwarded"), None);
+        assert_eq!(proxied.header("X-Forwarded-Port"), None);
+        assert_eq!(proxied.headers.iter().filter(|(k, _)| k.eq_ignore_ascii_case("X-Forwarded-For")).count(), 1);
+
+        let cookie_only = RequestHead::parse(b"GET /app.js HTTP/1.1\r\nHost: box\r\nCookie: ocm_dashboard=ocd_x\r\n\r\n").unwrap();
+        assert_eq!(cookie_only.presented_token(), Some(("ocd_x".to_string(), false)));
+        let cross = RequestHead::parse(b"GET / HTTP/1.1\r\nHost: box\r\nOrigin: https://evil.example\r\n\r\n").unwrap();
+        assert!(!cross.same_origin());
+    }
+
+    #[test]
+    fn test_frames_roundtrip_and_scope_filter() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let frame = Frame::text(&"x".repeat(300));
+        let wire = frame.encode(Some([1, 2, 3, 4]));
+        let decoded = runtime.block_on(Frame::read(&mut wire.as_slice())).unwrap();
+        assert_eq!(decoded, Some(frame));
+        assert_eq!(runtime.block_on(Frame::read(&mut &b""[..])).unwrap(), None);
+
+        let connect = Frame::text(r#"{"type":"req","id":"1","method":"connect","params":{"auth":{"token":"ocd_abc"}}}"#);
+        let ClientFrame::Forward(forwarded) = filter_client_frame(connect, DashboardScope::ReadOnly, "master") else {
+            panic!("connect must be forwarded");
+        };
+        let message: Value = serde_json::from_slice(&forwarded.payload).unwrap();
+        assert_eq!(message["params"]["auth"]["token"], "master");
+
+        let read = Frame::text(r#"{"type":"req","id":"2","method":"sessions.list"}"#);
+        assert_eq!(filter_client_frame(read.clone(), DashboardScope::ReadOnly, "master"), ClientFrame::Forward(read));
+
+        let write = Frame::text(r#"{"type":"req","id":"3","method":"config.apply","params":{}}"#);
+        let ClientFrame::Reply(reply) = filter_client_frame(write.clone(), DashboardScope::ReadOnly, "master") else {
+            panic!("writes must be refused on a read-only link");
+        };
+        let message: Value = serde_json::from_slice(&reply.payload).unwrap();
+        assert_eq!(message["id"], "3");
+        assert_eq!(message["ok"], false);
+        assert_eq!(filter_client_frame(write.clone(), DashboardScope::Admin, "master"), ClientFrame::Forward(write));
+
+        let fragment = Frame { fin: false, rsv: 0, opcode: OPCODE_CONTINUATION, payload: vec![] };
+        assert!(matches!(filter_client_frame(fragment, DashboardScope::ReadOnly, "master"), ClientFrame::Close(_)));
+
+        // Read-only links close on anything that can't be classified; admin links pass it through
+        let binary = Frame { fin: true, rsv: 0, opcode: 0x2, payload: vec![1, 2, 3] };
+        let unrecognized = [
+            binary,
+            Frame::text("not json"),
+            Frame::text(r#"{"type":"event","method":"config.apply"}"#),
+            Frame::text(r#"[{"type":"req","method":"config.apply"}]"#),
+        ];
+        for frame in unrecognized {
+            assert!(matches!(filter_client_frame(frame.clone(), DashboardScope::ReadOnly, "master"), ClientFrame::Close(_)));
+            assert_eq!(filter_client_frame(frame.clone(), DashboardScope::Admin, "master"), ClientFrame::Forward(frame));
+        }
+        let ping = Frame { fin: true, rsv: 0, opcode: OPCODE_PING, payload: vec![] };
+        assert_eq!(filter_client_frame(ping.clone(), DashboardScope::ReadOnly, "master"), ClientFrame::Forward(ping));
+    }
+}
@@ -1,6 +1,9 @@
-use std::fs;
-use std::io::{self, BufRead, BufReader};
-use std::path::Path;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// 读取文件内容
 pub fn read_file(path: &str) -> io::Result<String> {
@@ -8,19 +11,124 @@ pub fn read_file(path: &str) -> io::Result<String> {
 }
 
 /// 写入文件内容
+///
+/// Crash-safe: content goes to a temp file in the same directory, is fsynced,
+/// then renamed over the target. On any failure the original file is untouched.
 pub fn write_file(path: &str, content: &str) -> io::Result<()> {
+    write_file_atomic_with(Path::new(path), |f| f.write_all(content.as_bytes()))
+}
+
+/// Atomically write a file only the current user may read (0600 on Unix).
+/// Used for anything holding secrets: the vault, env files, config snapshots.
+pub fn write_private_file(path: &str, content: &[u8]) -> io::Result<()> {
+    write_atomic(Path::new(path), true, |f| f.write_all(content))
+}
+
+/// Atomic write path shared by every config save.
+/// `write_body` fills the temp file; the target is only replaced once it succeeds.
+pub(crate) fn write_file_atomic_with<F>(path: &Path, write_body: F) -> io::Result<()>
+where
+    F: FnOnce(&mut fs::File) -> io::Result<()>,
+{
+    write_atomic(path, false, write_body)
+}
+
+/// With `private`, the temp file is created 0600 so the content is never readable by
+/// others, not even between create and rename; otherwise it keeps the target's permissions.
+fn write_atomic<F>(path: &Path, private: bool, write_body: F) -> io::Result<()>
+where
+    F: FnOnce(&mut fs::File) -> io::Result<()>,
+{
     // 确保父目录存在
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    fs::create_dir_all(&parent)?;
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Path has no file name"))?;
+    let tmp_path = parent.join(temp_file_name(&file_name));
+
+    let result = (|| {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        if private {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut tmp = options.open(&tmp_path)?;
+
+        // Keep the original file's permissions (e.g. 0600 on the env file)
+        if !private {
+            if let Ok(meta) = fs::metadata(path) {
+                tmp.set_permissions(meta.permissions())?;
+            }
+        }
+
+        write_body(&mut tmp)?;
+        tmp.sync_all()?;
+        drop(tmp);
+
+        fs::rename(&tmp_path, path)?;
+        sync_dir(&parent);
+        Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Temp file name used while writing `file_name`
+fn temp_file_name(file_name: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or_default();
+    format!(".{}.tmp.{}.{}", file_name, std::process::id(), nanos)
+}
+
+/// Persist the rename itself by fsyncing the parent directory (no-op on Windows)
+fn sync_dir(dir: &Path) {
+    #[cfg(unix)]
+    {
+        if let Ok(d) = fs::File::open(dir) {
+            let _ = d.sync_all();
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = dir;
+    }
+}
+
+/// Read a JSON file the Manager keeps for itself (history, profiles, ...).
+/// A missing file gives the default value; a UTF-8 BOM is ignored.
+pub fn load_json<T: DeserializeOwned + Default>(path: &str) -> io::Result<T> {
+    if !file_exists(path) {
+        return Ok(T::default());
+    }
+    let content = read_file(path)?;
+    let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
+    serde_json::from_str(content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Write `value` as pretty JSON, creating the parent directory if needed
+pub fn save_json<T: Serialize>(path: &str, value: &T) -> io::Result<()> {
     if let Some(parent) = Path::new(path).parent() {
         fs::create_dir_all(parent)?;
     }
-    fs::write(path, content)
+    let content = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    write_file(path, &content)
 }
 
 /// 追加文件内容
 pub fn append_file(path: &str, content: &str) -> io::Result<()> {
-    use std::fs::OpenOptions;
-    use std::io::Write;
-    
     let mut file = OpenOptions::new()
         .create(true)
         .append(true)
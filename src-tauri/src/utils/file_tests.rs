@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use super::super::file::{
+        load_json, read_env_value, read_file, save_json, set_env_value, write_file, write_file_atomic_with,
+    };
+    use super::super::test_dir;
+    use std::fs;
+    use std::io::{self, Write};
+    use std::path::PathBuf;
+
+    fn leftover_temp_files(dir: &PathBuf) -> Vec<String> {
+        fs::read_dir(dir)
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|n| n.contains(".tmp."))
+            .collect()
+    }
+
+    #[test]
+    fn test_write_creates_file_and_parent_dirs() {
+        let dir = test_dir("create");
+        let path = dir.join("nested").join("openclaw.json");
+
+        write_file(path.to_str().unwrap(), "{\"a\":1}").unwrap();
+
+        assert_eq!(read_file(path.to_str().unwrap()).unwrap(), "{\"a\":1}");
+        assert!(leftover_temp_files(&dir.join("nested")).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_write_replaces_existing_content() {
+        let dir = test_dir("replace");
+        let path = dir.join("manager.json");
+        fs::write(&path, "old content that is longer than the new one").unwrap();
+
+        write_file(path.to_str().unwrap(), "new").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert!(leftover_temp_files(&dir).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_interrupted_write_keeps_original() {
+        let dir = test_dir("interrupted");
+        let path = dir.join("openclaw.json");
+        fs::write(&path, "{\"gateway\":{\"port\":18789}}").unwrap();
+
+        // Simulate a crash / full disk halfway through writing the body
+        let result = write_file_atomic_with(&path, |f| {
+            f.write_all(b"{\"gateway\":")?;
+            Err(io::Error::other("simulated disk full"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"gateway\":{\"port\":18789}}");
+        assert!(leftover_temp_files(&dir).is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_stale_temp_file_from_crash_is_harmless() {
+        let dir = test_dir("stale");
+        let path = dir.join("mcps.json");
+        fs::write(&path, "{}").unwrap();
+        // A previous process died between writing the temp file and renaming it
+        fs::write(dir.join(".mcps.json.tmp.1.1"), "{\"half\":").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+        write_file(path.to_str().unwrap(), "{\"ok\":true}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"ok\":true}");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = test_dir("perms");
+        let path = dir.join("env");
+        fs::write(&path, "export A=\"1\"").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+
+        set_env_value(path.to_str().unwrap(), "B", "2").unwrap();
+
+        let mode = fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+        assert_eq!(read_env_value(path.to_str().unwrap(), "A").as_deref(), Some("1"));
+        assert_eq!(read_env_value(path.to_str().unwrap(), "B").as_deref(), Some("2"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_json_round_trip_defaults_and_bom() {
+        let dir = test_dir("json");
+        let path = dir.join("state").join("history.json");
+        let path = path.to_str().unwrap();
+
+        let missing: Vec<u32> = load_json(path).unwrap();
+        assert!(missing.is_empty());
+
+        save_json(path, &vec![1u32, 2, 3]).unwrap();
+        assert_eq!(load_json::<Vec<u32>>(path).unwrap(), vec![1, 2, 3]);
+
+        fs::write(path, "\u{FEFF}[4]").unwrap();
+        assert_eq!(load_json::<Vec<u32>>(path).unwrap(), vec![4]);
+
+        fs::write(path, "not json").unwrap();
+        assert_eq!(load_json::<Vec<u32>>(path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::utils::log_sanitizer;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+
+/// Size at which gateway.log is rotated
+pub const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Rotated files kept next to gateway.log (gateway.log.1 ... gateway.log.N)
+pub const MAX_LOG_FILES: usize = 5;
+
+/// Bytes read per step when walking a log file backwards
+const READ_CHUNK: u64 = 64 * 1024;
+
+/// Held while a line is appended to gateway.log or the file is rotated or paged, so
+/// none of them sees the file half rotated
+pub static LOG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Output stream a gateway log line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+impl LogStream {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogStream::Stdout => "stdout",
+            LogStream::Stderr => "stderr",
+        }
+    }
+}
+
+/// Severity of a gateway log line, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// One captured gateway log line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GatewayLogEntry {
+    /// RFC 3339 time the Manager received the line; empty for lines the gateway wrote
+    /// to gateway.log itself while no Manager was reading
+    pub timestamp: String,
+    pub stream: LogStream,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+impl GatewayLogEntry {
+    pub fn new(timestamp: String, stream: LogStream, message: String) -> Self {
+        let level = detect_level(&message, stream);
+        Self { timestamp, stream, level, message }
+    }
+
+    /// Entry for a raw line of gateway output: ANSI escapes removed and tokens masked
+    pub fn capture(timestamp: String, stream: LogStream, raw: &str) -> Self {
+        let text = strip_ansi(raw.trim_end_matches(['\r', '\n']));
+        Self::new(timestamp, stream, log_sanitizer::sanitize(&text))
+    }
+
+    /// On-disk form: `<timestamp> <stream> <message>`
+    pub fn to_line(&self) -> String {
+        format!("{} {} {}", self.timestamp, self.stream.as_str(), self.message)
+    }
+
+    /// Parse a line of gateway.log. Lines written by `to_line` keep their timestamp and
+    /// stream; anything else (written by an older Manager) is cleaned up like captured
+    /// stdout.
+    pub fn parse(line: &str) -> Self {
+        let mut parts = line.splitn(3, ' ');
+        if let (Some(timestamp), Some(stream), message) = (parts.next(), parts.next(), parts.next()) {
+            let stream = match stream {
+                "stdout" => Some(LogStream::Stdout),
+                "stderr" => Some(LogStream::Stderr),
+                _ => None,
+            };
+            if let Some(stream) = stream {
+                if chrono::DateTime::parse_from_rfc3339(timestamp).is_ok() {
+                    return Self::new(timestamp.to_string(), stream, message.unwrap_or_default().to_string());
+                }
+            }
+        }
+        Self::capture(String::new(), LogStream::Stdout, line)
+    }
+}
+
+/// Remove ANSI color/cursor escape sequences from terminal output
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        if chars.peek() == Some(&'[') {
+            chars.next();
+            // CSI: parameters, then one final byte in '@'..='~'
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Guess the level from common markers (`ERROR`, `[warn]`, `level=debug`, ...).
+/// Unmarked stderr output is a warning: node prints deprecation notices and
+/// progress there, not just failures.
+pub fn detect_level(message: &str, stream: LogStream) -> LogLevel {
+    let lower = message.to_lowercase();
+    let has = |words: &[&str]| {
+        lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|token| words.contains(&token))
+    };
+    if has(&["error", "err", "fatal", "panic"]) {
+        LogLevel::Error
+    } else if has(&["warn", "warning"]) {
+        LogLevel::Warn
+    } else if has(&["debug", "trace"]) {
+        LogLevel::Debug
+    } else if has(&["info"]) || stream == LogStream::Stdout {
+        LogLevel::Info
+    } else {
+        LogLevel::Warn
+    }
+}
+
+/// Open gateway.log for appending, readable by the current user only
+pub fn open_output(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// Copy gateway output from `reader` to `output` line by line, sanitized and in the
+/// `to_line` format. Returns when the stream closes.
+pub fn capture_lines(reader: impl BufRead, stream: LogStream, output: &mut impl Write) -> io::Result<()> {
+    for line in reader.split(b'\n') {
+        let line = line?;
+        let entry = GatewayLogEntry::capture(chrono::Local::now().to_rfc3339(), stream, &String::from_utf8_lossy(&line));
+        let _lock = LOG_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        writeln!(output, "{}", entry.to_line())?;
+    }
+    Ok(())
+}
+
+/// Capture one of the gateway's output streams into `path` on a background thread
+pub fn capture(reader: impl Read + Send + 'static, stream: LogStream, path: &Path) -> io::Result<()> {
+    let mut output = open_output(path)?;
+    thread::Builder::new()
+        .name(format!("gateway-{}", stream.as_str()))
+        .spawn(move || {
+            if let Err(e) = capture_lines(BufReader::new(reader), stream, &mut output) {
+                warn!("[Gateway Logs] Stopped capturing {}: {}", stream.as_str(), e);
+            }
+        })?;
+    Ok(())
+}
+
+/// Rotate gateway.log once it is larger than `max_bytes`:
+/// gateway.log.N is dropped, the others shift up and gateway.log is copied to
+/// gateway.log.1 and truncated. The capture threads hold gateway.log open in append
+/// mode, so it has to be truncated in place rather than renamed. Returns whether it rotated.
+pub fn rotate_if_needed(path: &Path, max_bytes: u64, max_files: usize) -> io::Result<bool> {
+    match fs::metadata(path) {
+        Ok(meta) if meta.len() > max_bytes => {}
+        Ok(_) => return Ok(false),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    }
+    if max_files > 0 {
+        let _ = fs::remove_file(rotated_path(path, max_files));
+        for i in (1..max_files).rev() {
+            let from = rotated_path(path, i);
+            if from.exists() {
+                fs::rename(&from, rotated_path(path, i + 1))?;
+            }
+        }
+        fs::copy(path, rotated_path(path, 1))?;
+    }
+    OpenOptions::new().write(true).open(path)?.set_len(0)?;
+    Ok(true)
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+/// Existing log files, oldest first
+pub fn log_files(path: &Path, max_files: usize) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = (1..=max_files).rev().map(|i| rotated_path(path, i)).collect();
+    files.push(path.to_path_buf());
+    files.into_iter().filter(|p| p.exists()).collect()
+}
+
+/// Follows gateway.log for the live stream
+pub struct LogTail {
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+}
+
+impl LogTail {
+    /// Start at the current end of `path`; earlier lines are history
+    pub fn at_end(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let offset = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        Self { path, offset, partial: Vec::new() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Complete lines appended since the last call. A file that shrank was
+    /// truncated by a rotation and is read again from the start.
+    pub fn read_lines(&mut self) -> io::Result<Vec<String>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let len = file.metadata()?.len();
+        if len < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+        if len == self.offset {
+            return Ok(Vec::new());
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let read = file.take(len - self.offset).read_to_end(&mut self.partial)?;
+        self.offset += read as u64;
+
+        let Some(end) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let rest = self.partial.split_off(end + 1);
+        let complete = std::mem::replace(&mut self.partial, rest);
+        Ok(String::from_utf8_lossy(&complete)
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+/// Lines of one file, newest first, read in chunks from the end
+struct ReverseLines {
+    file: File,
+    pos: u64,
+    buf: Vec<u8>,
+}
+
+impl ReverseLines {
+    fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let pos = file.metadata()?.len();
+        Ok(Self { file, pos, buf: Vec::new() })
+    }
+
+    fn next_line(&mut self) -> io::Result<Option<String>> {
+        loop {
+            if let Some(i) = self.buf.iter().rposition(|&b| b == b'\n') {
+                let line = self.buf.split_off(i + 1);
+                self.buf.truncate(i);
+                if let Some(line) = non_empty_line(&line) {
+                    return Ok(Some(line));
+                }
+                continue;
+            }
+            if self.pos == 0 {
+                let line = std::mem::take(&mut self.buf);
+                return Ok(non_empty_line(&line));
+            }
+            let step = READ_CHUNK.min(self.pos);
+            self.pos -= step;
+            let mut chunk = vec![0; step as usize];
+            self.file.seek(SeekFrom::Start(self.pos))?;
+            self.file.read_exact(&mut chunk)?;
+            chunk.append(&mut self.buf);
+            self.buf = chunk;
+        }
+    }
+}
+
+fn non_empty_line(bytes: &[u8]) -> Option<String> {
+    let line = String::from_utf8_lossy(bytes);
+    let line = line.trim_end_matches('\r');
+    (!line.trim().is_empty()).then(|| line.to_string())
+}
+
+/// One page of gateway log entries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GatewayLogPage {
+    /// Oldest first
+    pub entries: Vec<GatewayLogEntry>,
+    /// Matching entries from the newest up to and including this page;
+    /// pass it as `skip` to get the page before it
+    pub next_skip: usize,
+    /// Older matching entries exist
+    pub has_more: bool,
+}
+
+/// Read `limit` entries at or above `min_level`, skipping the `skip` newest ones.
+/// Files are read backwards from gateway.log and only until the page is filled.
+pub fn read_page(
+    path: &Path,
+    max_files: usize,
+    skip: usize,
+    limit: usize,
+    min_level: Option<LogLevel>,
+) -> io::Result<GatewayLogPage> {
+    let mut entries = Vec::new();
+    let mut matched = 0;
+    let mut has_more = false;
+    'files: for file in log_files(path, max_files).iter().rev() {
+        let mut lines = ReverseLines::open(file)?;
+        while let Some(line) = lines.next_line()? {
+            let entry = GatewayLogEntry::parse(&line);
+            if min_level.is_some_and(|min| entry.level < min) {
+                continue;
+            }
+            if entries.len() == limit {
+                has_more = true;
+                break 'files;
+            }
+            matched += 1;
+            if matched > skip {
+                entries.push(entry);
+            }
+        }
+    }
+    entries.reverse();
+    Ok(GatewayLogPage { entries, next_skip: matched, has_more })
+}
@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod tests {
+    use super::super::gateway_logs::{
+        capture_lines, detect_level, log_files, open_output, read_page, rotate_if_needed, strip_ansi, GatewayLogEntry,
+        LogLevel, LogStream, LogTail,
+    };
+    use super::super::test_dir;
+    use std::fs;
+    use std::io::Write;
+
+    const TS: &str = "2026-01-29T10:00:00.000+00:00";
+
+    fn entry(message: &str) -> GatewayLogEntry {
+        GatewayLogEntry::new(TS.to_string(), LogStream::Stdout, message.to_string())
+    }
+
+    #[test]
+    fn test_detect_level() {
+        assert_eq!(detect_level("[gateway] ERROR bind failed", LogStream::Stdout), LogLevel::Error);
+        assert_eq!(detect_level("level=warn msg=slow", LogStream::Stdout), LogLevel::Warn);
+        assert_eq!(detect_level("[debug] tick", LogStream::Stdout), LogLevel::Debug);
+        assert_eq!(detect_level("listening on 18789", LogStream::Stdout), LogLevel::Info);
+        assert_eq!(detect_level("info: ready", LogStream::Stderr), LogLevel::Info);
+        // Unmarked stderr output is not necessarily a failure
+        assert_eq!(detect_level("(node:42) ExperimentalWarning", LogStream::Stderr), LogLevel::Warn);
+        assert_eq!(detect_level("Cannot find module 'x'", LogStream::Stderr), LogLevel::Warn);
+        // Substrings do not count
+        assert_eq!(detect_level("errorless terror", LogStream::Stdout), LogLevel::Info);
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\u{1b}[32minfo\u{1b}[0m ready"), "info ready");
+        assert_eq!(strip_ansi("\u{1b}[1;31mERROR\u{1b}[39;22m x"), "ERROR x");
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn test_line_roundtrip() {
+        let e = GatewayLogEntry::new(TS.to_string(), LogStream::Stderr, "warn: a b  c".to_string());
+        assert_eq!(GatewayLogEntry::parse(&e.to_line()), e);
+
+        let foreign = GatewayLogEntry::parse("plain text line");
+        assert_eq!(foreign.message, "plain text line");
+        assert_eq!(foreign.stream, LogStream::Stdout);
+        assert_eq!(foreign.timestamp, "");
+
+        // Raw gateway output is cleaned up on read
+        let raw = GatewayLogEntry::parse("\u{1b}[31mERROR\u{1b}[0m listen failed\r");
+        assert_eq!(raw.message, "ERROR listen failed");
+        assert_eq!(raw.level, LogLevel::Error);
+    }
+
+    #[test]
+    fn test_captured_output_is_sanitized_before_it_is_written() {
+        let raw = "\u{1b}[31mERROR\u{1b}[0m auth failed: Bearer eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxIn0\r\nready\n";
+        let mut output = Vec::new();
+        capture_lines(raw.as_bytes(), LogStream::Stderr, &mut output).unwrap();
+
+        let written = String::from_utf8(output).unwrap();
+        assert!(!written.contains("eyJhbGciOiJIUzI1NiIs"), "tokens never reach the file");
+        let entries: Vec<GatewayLogEntry> = written.lines().map(GatewayLogEntry::parse).collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].stream, LogStream::Stderr);
+        assert_eq!(entries[0].level, LogLevel::Error);
+        assert!(entries[0].message.starts_with("ERROR auth failed: Bearer ***[REDACTED]***"));
+        assert_eq!(entries[1].message, "ready");
+        assert!(!entries[1].timestamp.is_empty());
+    }
+
+    #[test]
+    fn test_rotation_keeps_max_files_and_the_writer() {
+        let dir = test_dir("rotate");
+        let path = dir.join("logs").join("gateway.log");
+
+        // Stands in for a capture thread, which keeps its handle across rotations
+        let mut output = open_output(&path).unwrap();
+        for i in 0..20 {
+            writeln!(output, "{}", entry(&format!("line {:02}", i)).to_line()).unwrap();
+            rotate_if_needed(&path, 64, 2).unwrap();
+        }
+        writeln!(output, "after").unwrap();
+        drop(output);
+
+        let files = log_files(&path, 2);
+        assert_eq!(files.len(), 3);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "after\n", "writes continue at the start of the live file");
+
+        let page = read_page(&path, 2, 0, 100, None).unwrap();
+        let messages: Vec<&str> = page.entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages.last(), Some(&"after"));
+        assert_eq!(messages[messages.len() - 2], "line 19");
+        assert!(!messages.contains(&"line 00"), "oldest rotated file is dropped");
+        assert!(!page.has_more);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_output_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = test_dir("private");
+        let path = dir.join("gateway.log");
+        drop(open_output(&path).unwrap());
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_tail_follows_appends_and_truncation() {
+        let dir = test_dir("tail");
+        let path = dir.join("gateway.log");
+        let mut output = open_output(&path).unwrap();
+        writeln!(output, "history").unwrap();
+
+        let mut tail = LogTail::at_end(&path);
+        assert!(tail.read_lines().unwrap().is_empty());
+
+        write!(output, "one\ntw").unwrap();
+        assert_eq!(tail.read_lines().unwrap(), ["one"]);
+        writeln!(output, "o").unwrap();
+        assert_eq!(tail.read_lines().unwrap(), ["two"]);
+
+        rotate_if_needed(&path, 0, 1).unwrap();
+        writeln!(output, "three").unwrap();
+        assert_eq!(tail.read_lines().unwrap(), ["three"]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_paging_backwards_with_level_filter() {
+        let dir = test_dir("page");
+        let path = dir.join("gateway.log");
+        let mut output = open_output(&path).unwrap();
+        for i in 0..10 {
+            let message = format!("{} {}", if i % 3 == 0 { "ERROR" } else { "info" }, i);
+            writeln!(output, "{}", entry(&message).to_line()).unwrap();
+            // Spread the entries over the live file and rotated ones
+            if i == 4 {
+                rotate_if_needed(&path, 0, 3).unwrap();
+            }
+        }
+        drop(output);
+
+        let last = read_page(&path, 3, 0, 4, None).unwrap();
+        let messages: Vec<&str> = last.entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["ERROR 6", "info 7", "info 8", "ERROR 9"]);
+        assert_eq!((last.next_skip, last.has_more), (4, true));
+
+        let older = read_page(&path, 3, last.next_skip, 4, None).unwrap();
+        let messages: Vec<&str> = older.entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["info 2", "ERROR 3", "info 4", "info 5"]);
+
+        let errors = read_page(&path, 3, 1, 10, Some(LogLevel::Warn)).unwrap();
+        let messages: Vec<&str> = errors.entries.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["ERROR 0", "ERROR 3", "ERROR 6"]);
+        assert_eq!((errors.next_skip, errors.has_more), (4, false));
+
+        let past_end = read_page(&path, 3, 50, 4, None).unwrap();
+        assert!(past_end.entries.is_empty() && !past_end.has_more);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,254 @@
+use crate::models::GatewayState;
+use crate::utils::file;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+/// Windows CREATE_NO_WINDOW flag, used to hide console window
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Contents of gateway.pid: the PID on the first line, the port on the second and
+/// the process start time (see `process_start_time`) on the third
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PidRecord {
+    pub pid: u32,
+    pub port: u16,
+    /// Missing in files written by older Managers
+    pub started: Option<String>,
+}
+
+impl PidRecord {
+    pub fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.lines().map(str::trim).filter(|l| !l.is_empty());
+        let pid = lines.next()?.parse().ok().filter(|pid| *pid > 0)?;
+        let port = lines.next()?.parse().ok().filter(|port| *port > 0)?;
+        let started = lines.next().map(str::to_string);
+        Some(Self { pid, port, started })
+    }
+
+    pub fn render(&self) -> String {
+        match &self.started {
+            Some(started) => format!("{}\n{}\n{}\n", self.pid, self.port, started),
+            None => format!("{}\n{}\n", self.pid, self.port),
+        }
+    }
+
+    /// Whether the process now running as `pid` is still the gateway this record was
+    /// written for: same start time (PIDs get reused) and a node/openclaw command line.
+    /// Records without a start time never match where the start time can be read.
+    pub fn matches(&self, started: Option<&str>, command: Option<&str>) -> bool {
+        self.started.as_deref() == started && command.is_some_and(is_gateway_command)
+    }
+}
+
+/// Read the PID file; missing or malformed files yield None
+pub fn read_pid_file(path: &str) -> Option<PidRecord> {
+    if !file::file_exists(path) {
+        return None;
+    }
+    PidRecord::parse(&file::read_file(path).ok()?)
+}
+
+pub fn write_pid_file(path: &str, record: &PidRecord) -> io::Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    file::write_file(path, &record.render())
+}
+
+pub fn remove_pid_file(path: &str) {
+    if file::file_exists(path) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Classify the gateway port.
+///
+/// `owned` is the live PID of the gateway the Manager started, `listener` the PID
+/// listening on the port and `listener_parent` its parent (the Windows `.cmd`
+/// shim starts node as a child, so the listener is not always the spawned PID).
+pub fn classify(owned: Option<u32>, listener: Option<u32>, listener_parent: Option<u32>) -> GatewayState {
+    match (owned, listener) {
+        (Some(_), None) => GatewayState::Ours,
+        (Some(owned), Some(listener)) if listener == owned || listener_parent == Some(owned) => GatewayState::Ours,
+        (_, Some(_)) => GatewayState::Foreign,
+        (None, None) => GatewayState::Stopped,
+    }
+}
+
+/// Whether a process with `pid` exists
+pub fn is_process_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("tasklist");
+        cmd.args(["/FI", &format!("PID eq {}", pid), "/NH", "/FO", "CSV"]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        cmd.output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains(&format!("\"{}\"", pid)))
+            .unwrap_or(false)
+    }
+}
+
+/// Start time field (clock ticks since boot) of a /proc/<pid>/stat line
+pub fn parse_stat_start_time(stat: &str) -> Option<u64> {
+    // The command name in parentheses may contain spaces; fields resume after the last ')'
+    let rest = &stat[stat.rfind(')')? + 1..];
+    // starttime is field 22; the remainder starts at field 3 (state)
+    rest.split_whitespace().nth(22 - 3)?.parse().ok()
+}
+
+/// Opaque start time of `pid`, stable for the life of the process; together with the
+/// PID it identifies a process even after the PID is reused
+pub fn process_start_time(pid: u32) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+        parse_stat_start_time(&stat).map(|ticks| ticks.to_string())
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        let output = Command::new("ps").args(["-o", "lstart=", "-p", &pid.to_string()]).output().ok()?;
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|s| !s.is_empty())
+    }
+
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("powershell");
+        cmd.args([
+            "-NoProfile",
+            "-Command",
+            &format!("(Get-Process -Id {}).StartTime.ToFileTimeUtc()", pid),
+        ]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        let output = cmd.output().ok()?;
+        let started = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Some(started).filter(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()))
+    }
+}
+
+/// Parent PID of `pid`
+pub fn parent_pid(pid: u32) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        let output = Command::new("ps")
+            .args(["-o", "ppid=", "-p", &pid.to_string()])
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    }
+
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("powershell");
+        cmd.args([
+            "-NoProfile",
+            "-Command",
+            &format!("(Get-CimInstance Win32_Process -Filter \"ProcessId={}\").ParentProcessId", pid),
+        ]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        let output = cmd.output().ok()?;
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    }
+}
+
+/// Signal sent by the stop sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopSignal {
+    /// SIGTERM (Windows: taskkill without /F)
+    Terminate,
+    /// SIGKILL (Windows: taskkill /F)
+    Kill,
+}
+
+/// Send `signal` to `pid`. With `group`, the whole process group led by `pid`
+/// (Windows: the process tree); falls back to the single process when `pid`
+/// does not lead a group (a gateway adopted from an older Manager).
+pub fn send_signal(pid: u32, signal: StopSignal, group: bool) -> bool {
+    #[cfg(unix)]
+    {
+        let flag = match signal {
+            StopSignal::Terminate => "-TERM",
+            StopSignal::Kill => "-KILL",
+        };
+        let kill = |target: &str| {
+            Command::new("kill")
+                .args([flag, "--", target])
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        (group && kill(&format!("-{}", pid))) || kill(&pid.to_string())
+    }
+
+    #[cfg(windows)]
+    {
+        let pid = pid.to_string();
+        let mut args = vec!["/PID", pid.as_str()];
+        if group {
+            args.push("/T");
+        }
+        if signal == StopSignal::Kill {
+            args.push("/F");
+        }
+        let mut cmd = Command::new("taskkill");
+        cmd.args(&args);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        cmd.output().map(|o| o.status.success()).unwrap_or(false)
+    }
+}
+
+/// Command line of `pid` (executable name on Windows)
+pub fn process_command(pid: u32) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(raw) = std::fs::read(format!("/proc/{}/cmdline", pid)) {
+            let cmdline = String::from_utf8_lossy(&raw).replace('\0', " ").trim().to_string();
+            if !cmdline.is_empty() {
+                return Some(cmdline);
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        let output = Command::new("ps").args(["-o", "args=", "-p", &pid.to_string()]).output().ok()?;
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|s| !s.is_empty())
+    }
+
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("tasklist");
+        cmd.args(["/FI", &format!("PID eq {}", pid), "/NH", "/FO", "CSV"]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        let output = cmd.output().ok()?;
+        // "node.exe","1234","Console","1","45,000 K"
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let image = stdout.lines().next()?.split("\",\"").next()?.trim_start_matches('"');
+        Some(image.to_string()).filter(|s| !s.is_empty() && !s.starts_with("INFO:"))
+    }
+}
+
+/// Whether a command line belongs to node or openclaw (safe for Kill All without asking)
+pub fn is_gateway_command(command: &str) -> bool {
+    let lower = command.to_lowercase();
+    if lower.contains("openclaw") {
+        return true;
+    }
+    let program = lower.split_whitespace().next().unwrap_or("");
+    let name = program.rsplit(['/', '\\']).next().unwrap_or(program);
+    matches!(name, "node" | "node.exe" | "nodejs")
+}
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use super::super::gateway_process::{
+        classify, is_gateway_command, parse_stat_start_time, read_pid_file, remove_pid_file, write_pid_file,
+        PidRecord,
+    };
+    use crate::models::GatewayState;
+    use std::fs;
+
+    #[test]
+    fn test_classify_ownership() {
+        assert_eq!(classify(None, None, None), GatewayState::Stopped);
+        assert_eq!(classify(None, Some(42), None), GatewayState::Foreign);
+        // Our gateway is still starting and not listening yet
+        assert_eq!(classify(Some(42), None, None), GatewayState::Ours);
+        assert_eq!(classify(Some(42), Some(42), None), GatewayState::Ours);
+        // node started by our cmd.exe shim
+        assert_eq!(classify(Some(42), Some(43), Some(42)), GatewayState::Ours);
+        assert_eq!(classify(Some(42), Some(77), Some(1)), GatewayState::Foreign);
+    }
+
+    #[test]
+    fn test_pid_record_parse() {
+        let legacy = PidRecord { pid: 1234, port: 18789, started: None };
+        assert_eq!(PidRecord::parse("1234\n18789\n"), Some(legacy.clone()));
+        assert_eq!(PidRecord::parse("\u{20}1234\r\n18789"), Some(legacy));
+        assert_eq!(
+            PidRecord::parse("1234\n18789\n987654\n"),
+            Some(PidRecord { pid: 1234, port: 18789, started: Some("987654".into()) })
+        );
+        assert_eq!(PidRecord::parse("1234\n"), None);
+        assert_eq!(PidRecord::parse("0\n18789\n"), None);
+        assert_eq!(PidRecord::parse("abc\n18789\n"), None);
+        assert_eq!(PidRecord::parse(""), None);
+    }
+
+    #[test]
+    fn test_pid_file_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("openclaw-pidfile-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("gateway.pid");
+        let path = path.to_str().unwrap();
+
+        assert_eq!(read_pid_file(path), None);
+        let record = PidRecord { pid: 4321, port: 19001, started: Some("123456".into()) };
+        write_pid_file(path, &record).unwrap();
+        assert_eq!(read_pid_file(path), Some(record));
+        remove_pid_file(path);
+        assert_eq!(read_pid_file(path), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_record_matches_only_the_same_gateway_process() {
+        let record = PidRecord { pid: 4321, port: 18789, started: Some("5000".into()) };
+        let node = Some("node /usr/lib/node_modules/openclaw/dist/index.js gateway");
+        assert!(record.matches(Some("5000"), node));
+        // PID reused by a later process
+        assert!(!record.matches(Some("9000"), node));
+        assert!(!record.matches(Some("5000"), Some("/usr/sbin/sshd -D")));
+        assert!(!record.matches(Some("5000"), None));
+        // Older PID files carry no start time and can't be trusted
+        let legacy = PidRecord { started: None, ..record };
+        assert!(!legacy.matches(Some("5000"), node));
+    }
+
+    #[test]
+    fn test_parse_stat_start_time() {
+        let stat = "4321 (node server) S 1 4321 4321 0 -1 4194560 5000 0 0 0 120 30 0 0 20 0 11 0 98765 1234567 890";
+        assert_eq!(parse_stat_start_time(stat), Some(98765));
+        assert_eq!(parse_stat_start_time("4321 (node)"), None);
+        assert_eq!(parse_stat_start_time("garbage"), None);
+    }
+
+    #[test]
+    fn test_is_gateway_command() {
+        assert!(is_gateway_command("node /usr/lib/node_modules/openclaw/dist/index.js gateway --port 18789"));
+        assert!(is_gateway_command("/opt/homebrew/bin/node server.js"));
+        assert!(is_gateway_command("node.exe"));
+        assert!(is_gateway_command("C:\\Users\\me\\AppData\\Roaming\\npm\\openclaw.cmd gateway"));
+        assert!(!is_gateway_command("nginx: master process /usr/sbin/nginx"));
+        assert!(!is_gateway_command("python3 -m http.server 18789"));
+        assert!(!is_gateway_command("/usr/bin/nodemon app.js"));
+    }
+}
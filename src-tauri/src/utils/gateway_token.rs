@@ -0,0 +1,69 @@
+use crate::utils::secret_refs::{self, RefSource};
+use crate::utils::secrets_vault;
+use serde::{Deserialize, Serialize};
+
+/// Random bytes in a gateway token
+pub const TOKEN_BYTES: usize = 32;
+/// Length of a token: lower-case hex of `TOKEN_BYTES`
+pub const TOKEN_LEN: usize = TOKEN_BYTES * 2;
+/// manager.json key for token bookkeeping
+pub const MANAGER_KEY: &str = "gatewayToken";
+/// Vault / env name used when the token is moved out of openclaw.json
+pub const SECRET_NAME: &str = "OPENCLAW_GATEWAY_TOKEN";
+
+/// New gateway token from the OS CSPRNG, hex encoded
+pub fn generate() -> String {
+    secrets_vault::random_bytes::<TOKEN_BYTES>()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Shorter than a generated token: set by hand, or made by the old timestamp-based generator
+pub fn is_weak(token: &str) -> bool {
+    token.len() < TOKEN_LEN
+}
+
+/// Token bookkeeping kept in manager.json under "gatewayToken"
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenState {
+    /// When the token was last rotated (RFC 3339)
+    #[serde(default)]
+    pub rotated_at: Option<String>,
+}
+
+impl TokenState {
+    pub fn from_manager_config(manager: &serde_json::Value) -> Self {
+        manager
+            .get(MANAGER_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Where the value of `gateway.auth.token` is kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenStorage {
+    /// The token is written in openclaw.json
+    Config,
+    /// `${secret:NAME}`, or `${NAME}` naming a vault entry
+    Vault(String),
+    /// `${env:NAME}`, or `${NAME}` not in the vault
+    EnvFile(String),
+}
+
+/// Where a rotated token has to be written, given the configured value. Only a value that
+/// is exactly one reference is followed; anything else is replaced in the config.
+pub fn storage(configured: &str, in_vault: impl Fn(&str) -> bool) -> TokenStorage {
+    let references = secret_refs::references(configured);
+    match references.as_slice() {
+        [reference] if reference.text() == configured => match reference.source {
+            RefSource::Secret => TokenStorage::Vault(reference.name.clone()),
+            RefSource::Env => TokenStorage::EnvFile(reference.name.clone()),
+            RefSource::Native if in_vault(&reference.name) => TokenStorage::Vault(reference.name.clone()),
+            RefSource::Native => TokenStorage::EnvFile(reference.name.clone()),
+        },
+        _ => TokenStorage::Config,
+    }
+}
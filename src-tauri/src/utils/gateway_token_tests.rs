@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use super::super::gateway_token::{generate, is_weak, storage, TokenStorage, TOKEN_LEN};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_token_length_and_alphabet() {
+        let tokens: HashSet<String> = (0..64).map(|_| generate()).collect();
+        assert_eq!(tokens.len(), 64);
+        for token in &tokens {
+            assert_eq!(token.len(), TOKEN_LEN);
+            assert!(token.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')), "{}", token);
+            assert!(!is_weak(token));
+        }
+        // Tokens from the old timestamp-based generator were 48 hex characters
+        assert!(is_weak("5deece66d0000000a1b2c3d4e5f60718293a4b5c6d7e8f90"));
+    }
+
+    #[test]
+    fn test_storage_follows_single_references() {
+        let in_vault = |name: &str| name == "OPENCLAW_GATEWAY_TOKEN";
+        assert_eq!(storage("abc123", in_vault), TokenStorage::Config);
+        assert_eq!(storage("", in_vault), TokenStorage::Config);
+        assert_eq!(
+            storage("${OPENCLAW_GATEWAY_TOKEN}", in_vault),
+            TokenStorage::Vault("OPENCLAW_GATEWAY_TOKEN".to_string())
+        );
+        assert_eq!(storage("${GW_TOKEN}", in_vault), TokenStorage::EnvFile("GW_TOKEN".to_string()));
+        assert_eq!(storage("${secret:GW}", in_vault), TokenStorage::Vault("GW".to_string()));
+        assert_eq!(storage("${env:GW}", in_vault), TokenStorage::EnvFile("GW".to_string()));
+        // A token built from several parts can't be rotated in place
+        assert_eq!(storage("${env:A}${env:B}", in_vault), TokenStorage::Config);
+    }
+}
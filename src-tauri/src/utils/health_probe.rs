@@ -0,0 +1,192 @@
+use crate::models::{HealthReport, HealthState};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::{Duration, Instant};
+
+/// Gateway health endpoint
+pub const HEALTH_PATH: &str = "/health";
+
+/// A healthy answer slower than this reports as degraded
+pub const SLOW_RESPONSE_MS: u64 = 2000;
+
+/// Largest response read from the health endpoint
+const MAX_RESPONSE_BYTES: u64 = 64 * 1024;
+
+/// Parsed HTTP response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    /// Header names lowercased
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// GET request for the health endpoint
+pub fn build_request(port: u16, token: Option<&str>) -> String {
+    let mut request = format!(
+        "GET {} HTTP/1.1\r\nHost: 127.0.0.1:{}\r\nAccept: application/json\r\nConnection: close\r\n",
+        HEALTH_PATH, port
+    );
+    if let Some(token) = token.filter(|t| !t.is_empty()) {
+        request.push_str(&format!("Authorization: Bearer {}\r\n", token));
+    }
+    request.push_str("\r\n");
+    request
+}
+
+/// Parse a complete (connection-closed) HTTP/1.x response, including chunked bodies
+pub fn parse_response(raw: &[u8]) -> Option<HttpResponse> {
+    let (head, body) = match find(raw, b"\r\n\r\n") {
+        Some(end) => (&raw[..end], &raw[end + 4..]),
+        None => (raw, &[][..]),
+    };
+    let head = String::from_utf8_lossy(head);
+    let mut lines = head.split("\r\n");
+    let status_line = lines.next()?;
+    if !status_line.starts_with("HTTP/1.") {
+        return None;
+    }
+    let status = status_line.split_whitespace().nth(1)?.parse().ok()?;
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(n, v)| (n.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let mut response = HttpResponse {
+        status,
+        headers,
+        body: String::new(),
+    };
+    // Chunks are sized in bytes and may split a UTF-8 character, so decode before converting
+    response.body = if response
+        .header("transfer-encoding")
+        .is_some_and(|v| v.eq_ignore_ascii_case("chunked"))
+    {
+        String::from_utf8_lossy(&decode_chunked(body)).into_owned()
+    } else {
+        String::from_utf8_lossy(body).into_owned()
+    };
+    Some(response)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn decode_chunked(body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut rest = body;
+    while let Some(line_end) = find(rest, b"\r\n") {
+        let size_line = String::from_utf8_lossy(&rest[..line_end]);
+        let size = usize::from_str_radix(size_line.split(';').next().unwrap_or("").trim(), 16).unwrap_or(0);
+        let after = &rest[line_end + 2..];
+        if size == 0 || after.len() < size {
+            break;
+        }
+        out.extend_from_slice(&after[..size]);
+        rest = after[size..].strip_prefix(b"\r\n").unwrap_or(&after[size..]);
+    }
+    out
+}
+
+/// Turn a health endpoint answer into a report
+pub fn evaluate(response: &HttpResponse, latency_ms: u64) -> HealthReport {
+    let json: Option<serde_json::Value> = serde_json::from_str(&response.body).ok();
+    let field = |name: &str| {
+        json.as_ref()
+            .and_then(|j| j.get(name))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    };
+    let version = field("version").or_else(|| response.header("x-openclaw-version").map(|v| v.to_string()));
+
+    let (state, ready, message) = match response.status {
+        200..=299 => {
+            let reported_ok = json.as_ref().and_then(|j| j.get("ok")).and_then(|v| v.as_bool());
+            let status = field("status").map(|s| s.to_lowercase());
+            match (reported_ok, status.as_deref()) {
+                (_, Some("starting")) => (HealthState::Degraded, false, Some("Gateway is starting".to_string())),
+                (Some(false), _) | (_, Some("degraded" | "error" | "unhealthy")) => (
+                    HealthState::Degraded,
+                    true,
+                    field("error").or(field("message")).or(Some("Gateway reports a problem".to_string())),
+                ),
+                _ if latency_ms > SLOW_RESPONSE_MS => (
+                    HealthState::Degraded,
+                    true,
+                    Some(format!("Health check took {} ms", latency_ms)),
+                ),
+                _ => (HealthState::Healthy, true, None),
+            }
+        }
+        401 | 403 => (
+            HealthState::Degraded,
+            true,
+            Some("Gateway rejected the configured token".to_string()),
+        ),
+        404 => (
+            HealthState::Degraded,
+            true,
+            Some("Gateway answers but has no health endpoint".to_string()),
+        ),
+        503 => (HealthState::Degraded, false, Some("Gateway is not ready yet".to_string())),
+        status => (
+            HealthState::Degraded,
+            false,
+            Some(format!("Health endpoint returned HTTP {}", status)),
+        ),
+    };
+
+    HealthReport {
+        state,
+        ready,
+        version,
+        latency_ms: Some(latency_ms),
+        http_status: Some(response.status),
+        message,
+    }
+}
+
+/// Probe the gateway on localhost
+pub fn probe(port: u16, token: Option<&str>, timeout: Duration) -> HealthReport {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let started = Instant::now();
+
+    let mut stream = match TcpStream::connect_timeout(&addr, timeout) {
+        Ok(stream) => stream,
+        Err(e) => return HealthReport::down(format!("Cannot connect to port {}: {}", port, e)),
+    };
+    let _ = stream.set_read_timeout(Some(timeout));
+    let _ = stream.set_write_timeout(Some(timeout));
+
+    if let Err(e) = stream.write_all(build_request(port, token).as_bytes()) {
+        return HealthReport::down(format!("Failed to send health request: {}", e));
+    }
+    let mut raw = Vec::new();
+    if let Err(e) = stream.take(MAX_RESPONSE_BYTES).read_to_end(&mut raw) {
+        if raw.is_empty() {
+            return HealthReport::down(format!("No answer from gateway: {}", e));
+        }
+    }
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    match parse_response(&raw) {
+        Some(response) => {
+            let mut report = evaluate(&response, latency_ms);
+            if token.is_none() && matches!(response.status, 401 | 403) {
+                report.message = Some("Gateway requires a token; none was sent".to_string());
+            }
+            report
+        }
+        None => HealthReport::down(format!("Port {} does not answer HTTP", port)),
+    }
+}
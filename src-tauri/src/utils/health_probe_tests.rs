@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use super::super::health_probe::{build_request, evaluate, parse_response, SLOW_RESPONSE_MS};
+    use crate::models::HealthState;
+
+    #[test]
+    fn test_build_request_with_token() {
+        let request = build_request(18789, Some("abc123"));
+        assert!(request.starts_with("GET /health HTTP/1.1\r\n"));
+        assert!(request.contains("Host: 127.0.0.1:18789\r\n"));
+        assert!(request.contains("Authorization: Bearer abc123\r\n"));
+        assert!(request.ends_with("\r\n\r\n"));
+
+        assert!(!build_request(18789, Some("")).contains("Authorization"));
+        assert!(!build_request(18789, None).contains("Authorization"));
+    }
+
+    #[test]
+    fn test_parse_response_plain_and_chunked() {
+        let raw = b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nX-OpenClaw-Version: 1.2.3\r\n\r\n{\"ok\":true}";
+        let response = parse_response(raw).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.header("x-openclaw-version"), Some("1.2.3"));
+        assert_eq!(response.body, "{\"ok\":true}");
+
+        let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\n{\"ok\"\r\n6\r\n:true}\r\n0\r\n\r\n";
+        assert_eq!(parse_response(chunked).unwrap().body, "{\"ok\":true}");
+
+        // A chunk boundary inside a multi-byte character
+        let split = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n1\r\n\xc3\r\n2\r\n\xa9!\r\n0\r\n\r\n";
+        assert_eq!(parse_response(split).unwrap().body, "\u{e9}!");
+
+        assert!(parse_response(b"SSH-2.0-OpenSSH_9.6\r\n").is_none());
+        assert!(parse_response(b"").is_none());
+    }
+
+    #[test]
+    fn test_evaluate_tri_state() {
+        let healthy = parse_response(b"HTTP/1.1 200 OK\r\n\r\n{\"ok\":true,\"version\":\"2026.1.5\"}").unwrap();
+        let report = evaluate(&healthy, 12);
+        assert_eq!(report.state, HealthState::Healthy);
+        assert!(report.ready);
+        assert_eq!(report.version.as_deref(), Some("2026.1.5"));
+        assert_eq!(report.latency_ms, Some(12));
+
+        let slow = evaluate(&healthy, SLOW_RESPONSE_MS + 1);
+        assert_eq!(slow.state, HealthState::Degraded);
+        assert!(slow.ready);
+
+        let starting = parse_response(b"HTTP/1.1 200 OK\r\n\r\n{\"status\":\"starting\"}").unwrap();
+        assert!(!evaluate(&starting, 5).ready);
+
+        let failing = parse_response(b"HTTP/1.1 200 OK\r\n\r\n{\"ok\":false,\"error\":\"no model\"}").unwrap();
+        let report = evaluate(&failing, 5);
+        assert_eq!(report.state, HealthState::Degraded);
+        assert_eq!(report.message.as_deref(), Some("no model"));
+
+        let unauthorized = parse_response(b"HTTP/1.1 401 Unauthorized\r\n\r\n").unwrap();
+        let report = evaluate(&unauthorized, 5);
+        assert_eq!(report.state, HealthState::Degraded);
+        assert!(report.ready);
+
+        let unavailable = parse_response(b"HTTP/1.1 503 Service Unavailable\r\n\r\n").unwrap();
+        let report = evaluate(&unavailable, 5);
+        assert_eq!(report.state, HealthState::Degraded);
+        assert!(!report.ready);
+        assert_eq!(report.http_status, Some(503));
+    }
+}
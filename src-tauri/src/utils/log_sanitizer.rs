@@ -1,47 +1,100 @@
 use regex::Regex;
 use std::sync::OnceLock;
 
-/// Sanitizes sensitive information from log messages.
-/// 
-/// Redacts:
-/// - API Keys (OpenAI, Anthropic, Google, generic patterns)
-/// - Bearer tokens
-/// - Private keys
-/// - Generic secrets/tokens
-/// - Sensitive URL parameters
-pub fn sanitize(message: &str) -> String {
-    let mut sanitized = message.to_string();
+/// Known secret format, shared by the log sanitizer and the leak scanner
+pub struct TokenPattern {
+    /// Short description shown in scan results
+    pub name: &'static str,
+    pub regex: Regex,
+    /// Replacement used when sanitizing logs
+    pub replacement: &'static str,
+    /// Capture group holding the secret itself (0 = the whole match)
+    pub secret_group: usize,
+    /// Matches any `key=value` shape rather than a vendor format, so it needs
+    /// an entropy check before being reported as a leak
+    pub generic: bool,
+    /// Env var name suggested when the value is moved out of a file
+    pub suggested_name: &'static str,
+}
+
+fn pattern(name: &'static str, regex: &str, replacement: &'static str, suggested_name: &'static str) -> TokenPattern {
+    TokenPattern { name, regex: Regex::new(regex).unwrap(), replacement, secret_group: 0, generic: false, suggested_name }
+}
 
-    // Compile regex patterns once
-    static PATTERNS: OnceLock<Vec<(Regex, &'static str)>> = OnceLock::new();
-    let patterns = PATTERNS.get_or_init(|| {
+/// Secret patterns, compiled once
+pub fn token_patterns() -> &'static [TokenPattern] {
+    static PATTERNS: OnceLock<Vec<TokenPattern>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
         vec![
             // OpenAI API Keys (sk-...)
-            (Regex::new(r"sk-[a-zA-Z0-9]{32,}").unwrap(), "sk-***[REDACTED]***"),
+            pattern("OpenAI API key", r"sk-[a-zA-Z0-9]{32,}", "sk-***[REDACTED]***", "OPENAI_API_KEY"),
             // Anthropic API Keys (sk-ant-...)
-            (Regex::new(r"sk-ant-[a-zA-Z0-9\-_]{20,}").unwrap(), "sk-ant-***[REDACTED]***"),
+            pattern("Anthropic API key", r"sk-ant-[a-zA-Z0-9\-_]{20,}", "sk-ant-***[REDACTED]***", "ANTHROPIC_API_KEY"),
             // Google API Keys (AIza...)
-            (Regex::new(r"AIza[0-9A-Za-z\-_]{35}").unwrap(), "AIza***[REDACTED]***"),
+            pattern("Google API key", r"AIza[0-9A-Za-z\-_]{35}", "AIza***[REDACTED]***", "GOOGLE_API_KEY"),
             // Hugging Face Tokens (hf_...)
-            (Regex::new(r"hf_[a-zA-Z0-9]{30,}").unwrap(), "hf_***[REDACTED]***"),
+            pattern("Hugging Face token", r"hf_[a-zA-Z0-9]{30,}", "hf_***[REDACTED]***", "HF_TOKEN"),
             // Bearer Tokens
-            (Regex::new(r"(Bearer\s+)[a-zA-Z0-9\-_=\.]{20,}").unwrap(), "$1***[REDACTED]***"),
+            TokenPattern {
+                secret_group: 2,
+                ..pattern("Bearer token", r"(Bearer\s+)([a-zA-Z0-9\-_=\.]{20,})", "$1***[REDACTED]***", "BEARER_TOKEN")
+            },
             // Telegram Bot Tokens (123456:ABC-DEF1234ghIkl-zyx57W2v1u123ew11)
-            (Regex::new(r"[0-9]{8,10}:[a-zA-Z0-9_-]{35}").unwrap(), "***[TELEGRAM TOKEN REDACTED]***"),
+            pattern(
+                "Telegram bot token",
+                r"[0-9]{8,10}:[a-zA-Z0-9_-]{35}",
+                "***[TELEGRAM TOKEN REDACTED]***",
+                "TELEGRAM_BOT_TOKEN",
+            ),
             // Slack Tokens (xoxb-...)
-            (Regex::new(r"xox[baprs]-[0-9a-zA-Z]{10,48}").unwrap(), "xox-***[REDACTED]***"),
+            pattern("Slack token", r"xox[baprs]-[0-9a-zA-Z]{10,48}", "xox-***[REDACTED]***", "SLACK_TOKEN"),
             // Private Keys (BEGIN PRIVATE KEY...)
-            (Regex::new(r"-----BEGIN [A-Z ]+ PRIVATE KEY-----").unwrap(), "[PRIVATE KEY BLOCK REDACTED]"),
+            pattern(
+                "Private key",
+                r"-----BEGIN [A-Z ]+ PRIVATE KEY-----",
+                "[PRIVATE KEY BLOCK REDACTED]",
+                "PRIVATE_KEY",
+            ),
             // Generic "key", "token", "secret" assignments in JSON or query params
             // Matches: "api_key": "..." or apiKey=...
-            (Regex::new(r#"(?i)(api_?key|access_?token|secret|password|private_?key)["']?\s*[:=]\s*["']?([a-zA-Z0-9\-_=]+)["']?"#).unwrap(), "$1=***[REDACTED]***"),
-             // Sensitive URL parameters
-            (Regex::new(r"(?i)(key|token|sig|signature)=([a-zA-Z0-9\-_%]+)").unwrap(), "$1=***[REDACTED]***"),
+            TokenPattern {
+                secret_group: 2,
+                generic: true,
+                ..pattern(
+                    "Secret assignment",
+                    r#"(?i)(api_?key|access_?token|secret|password|private_?key)["']?\s*[:=]\s*["']?([a-zA-Z0-9\-_=]+)["']?"#,
+                    "$1=***[REDACTED]***",
+                    "SECRET",
+                )
+            },
+            // Sensitive URL parameters
+            TokenPattern {
+                secret_group: 2,
+                generic: true,
+                ..pattern(
+                    "URL credential",
+                    r"(?i)(key|token|sig|signature)=([a-zA-Z0-9\-_%]+)",
+                    "$1=***[REDACTED]***",
+                    "URL_TOKEN",
+                )
+            },
         ]
-    });
+    })
+}
+
+/// Sanitizes sensitive information from log messages.
+/// 
+/// Redacts:
+/// - API Keys (OpenAI, Anthropic, Google, generic patterns)
+/// - Bearer tokens
+/// - Private keys
+/// - Generic secrets/tokens
+/// - Sensitive URL parameters
+pub fn sanitize(message: &str) -> String {
+    let mut sanitized = message.to_string();
 
-    for (regex, replacement) in patterns {
-        sanitized = regex.replace_all(&sanitized, *replacement).to_string();
+    for pattern in token_patterns() {
+        sanitized = pattern.regex.replace_all(&sanitized, pattern.replacement).to_string();
     }
 
     sanitized
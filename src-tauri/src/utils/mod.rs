@@ -1,7 +1,84 @@
+pub mod config_history;
+pub mod config_lock;
+pub mod config_migrations;
+pub mod config_validation;
+pub mod config_watcher;
+pub mod dashboard_access;
+pub mod dashboard_proxy;
 pub mod file;
+pub mod gateway_logs;
+pub mod gateway_token;
+pub mod gateway_process;
+pub mod health_probe;
 pub mod log_sanitizer;
 pub mod platform;
+pub mod port_scan;
+pub mod proc_stats;
+pub mod profiles;
+pub mod scheduler;
+pub mod secret_refs;
+pub mod secret_scan;
+pub mod secrets_vault;
+pub mod service_state;
 pub mod shell;
+pub mod stop_policy;
+pub mod supervisor;
+pub mod systemd_unit;
 
+/// Empty scratch directory for a test; `name` must be unique across the test suite
+#[cfg(test)]
+fn test_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("openclaw-test-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(test)]
+mod config_history_tests;
+#[cfg(test)]
+mod config_lock_tests;
+#[cfg(test)]
+mod config_migrations_tests;
+#[cfg(test)]
+mod config_validation_tests;
+#[cfg(test)]
+mod config_watcher_tests;
+#[cfg(test)]
+mod dashboard_access_tests;
+#[cfg(test)]
+mod dashboard_proxy_tests;
+#[cfg(test)]
+mod file_tests;
+#[cfg(test)]
+mod gateway_logs_tests;
+#[cfg(test)]
+mod gateway_token_tests;
+#[cfg(test)]
+mod gateway_process_tests;
+#[cfg(test)]
+mod health_probe_tests;
 #[cfg(test)]
 mod log_sanitizer_tests;
+#[cfg(test)]
+mod port_scan_tests;
+#[cfg(test)]
+mod proc_stats_tests;
+#[cfg(test)]
+mod profiles_tests;
+#[cfg(test)]
+mod scheduler_tests;
+#[cfg(test)]
+mod secret_refs_tests;
+#[cfg(test)]
+mod secret_scan_tests;
+#[cfg(test)]
+mod secrets_vault_tests;
+#[cfg(test)]
+mod service_state_tests;
+#[cfg(test)]
+mod stop_policy_tests;
+#[cfg(test)]
+mod supervisor_tests;
+#[cfg(test)]
+mod systemd_unit_tests;
@@ -1,4 +1,9 @@
+use crate::utils::profiles::{self, Profile, ProfileSettings};
+use log::warn;
+use once_cell::sync::Lazy;
 use std::env;
+use std::path::PathBuf;
+use std::sync::RwLock;
 
 /// 获取操作系统类型
 pub fn get_os() -> String {
@@ -10,21 +15,63 @@ pub fn get_arch() -> String {
     env::consts::ARCH.to_string()
 }
 
+/// Default port of the OpenClaw gateway
+pub const DEFAULT_GATEWAY_PORT: u16 = 18789;
+
+/// Profiles loaded from profiles.json, cached after the first read
+static PROFILE_SETTINGS: Lazy<RwLock<ProfileSettings>> = Lazy::new(|| {
+    let path = get_profiles_file_path();
+    let settings = profiles::load(&path).unwrap_or_else(|e| {
+        warn!("[Platform] Failed to read {}: {}, using the default profile", path, e);
+        ProfileSettings::default()
+    });
+    RwLock::new(settings)
+});
+
+/// Get Manager-level profiles file path (independent of any OpenClaw config dir)
+pub fn get_profiles_file_path() -> String {
+    let base = dirs::config_dir().or_else(dirs::home_dir).unwrap_or_else(|| PathBuf::from("."));
+    base.join("openclaw-manager").join("profiles.json").to_string_lossy().to_string()
+}
+
+/// Current profile settings
+pub fn get_profile_settings() -> ProfileSettings {
+    PROFILE_SETTINGS.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Persist new profile settings and make them current for all path helpers
+pub fn set_profile_settings(settings: ProfileSettings) -> std::io::Result<()> {
+    profiles::save(&get_profiles_file_path(), &settings)?;
+    *PROFILE_SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = settings;
+    Ok(())
+}
+
+/// Active profile, or None for the default profile
+pub fn get_active_profile() -> Option<Profile> {
+    get_profile_settings().active_profile().cloned()
+}
+
 /// 获取配置目录路径
+/// Honors the active profile and the OPENCLAW_HOME override (see `profiles::resolve_config_dir`)
 pub fn get_config_dir() -> String {
-    if let Some(home) = dirs::home_dir() {
-        if is_windows() {
-            format!("{}\\.openclaw", home.display())
-        } else {
-            format!("{}/.openclaw", home.display())
-        }
-    } else {
-        String::from("~/.openclaw")
-    }
+    let openclaw_home = env::var(profiles::OPENCLAW_HOME_ENV).ok();
+    profiles::resolve_config_dir(
+        get_active_profile().as_ref(),
+        openclaw_home.as_deref(),
+        dirs::home_dir().as_deref(),
+    )
+}
+
+/// Gateway port of the active profile, if it sets one
+pub fn get_profile_gateway_port() -> Option<u16> {
+    get_active_profile().and_then(|p| p.gateway_port)
 }
 
 /// 获取环境变量文件路径
 pub fn get_env_file_path() -> String {
+    if let Some(env_file) = get_active_profile().and_then(|p| p.env_file).filter(|f| !f.is_empty()) {
+        return profiles::expand_home(&env_file, dirs::home_dir().as_deref());
+    }
     if is_windows() {
         format!("{}\\env", get_config_dir())
     } else {
@@ -55,7 +102,92 @@ pub fn get_log_file_path() -> String {
     if is_windows() {
         format!("{}\\openclaw-gateway.log", get_config_dir())
     } else {
-        String::from("/tmp/openclaw-gateway.log")
+        match get_active_profile() {
+            Some(profile) => format!("/tmp/openclaw-gateway-{}.log", profile.name),
+            None => String::from("/tmp/openclaw-gateway.log"),
+        }
+    }
+}
+
+/// Get path of the captured gateway stdout/stderr log (rotated as gateway.log.N)
+pub fn get_gateway_log_file_path() -> String {
+    if is_windows() {
+        format!("{}\\logs\\gateway.log", get_config_dir())
+    } else {
+        format!("{}/logs/gateway.log", get_config_dir())
+    }
+}
+
+/// Get PID file path of the gateway started by the Manager
+pub fn get_gateway_pid_file_path() -> String {
+    if is_windows() {
+        format!("{}\\gateway.pid", get_config_dir())
+    } else {
+        format!("{}/gateway.pid", get_config_dir())
+    }
+}
+
+/// Get path of the env file rendered for the systemd user unit (`EnvironmentFile=`)
+pub fn get_gateway_env_file_path() -> String {
+    if is_windows() {
+        format!("{}\\gateway.env", get_config_dir())
+    } else {
+        format!("{}/gateway.env", get_config_dir())
+    }
+}
+
+/// Get path of the openclaw.json copy the gateway runs with when the config uses
+/// `${env:NAME}` / `${secret:NAME}` references (rewritten to openclaw's `${NAME}`)
+pub fn get_runtime_config_file_path() -> String {
+    if is_windows() {
+        format!("{}\\openclaw.runtime.json", get_config_dir())
+    } else {
+        format!("{}/openclaw.runtime.json", get_config_dir())
+    }
+}
+
+/// Get gateway supervisor restart history file path
+pub fn get_restart_history_file_path() -> String {
+    if is_windows() {
+        format!("{}\\restart-history.json", get_config_dir())
+    } else {
+        format!("{}/restart-history.json", get_config_dir())
+    }
+}
+
+/// Get encrypted secrets vault file path
+pub fn get_secrets_vault_file_path() -> String {
+    if is_windows() {
+        format!("{}\\secrets.vault", get_config_dir())
+    } else {
+        format!("{}/secrets.vault", get_config_dir())
+    }
+}
+
+/// Get secrets vault key file path (key-file vaults only)
+pub fn get_secrets_key_file_path() -> String {
+    if is_windows() {
+        format!("{}\\secrets.key", get_config_dir())
+    } else {
+        format!("{}/secrets.key", get_config_dir())
+    }
+}
+
+/// Get maintenance scheduler run history file path
+pub fn get_maintenance_history_file_path() -> String {
+    if is_windows() {
+        format!("{}\\maintenance-history.json", get_config_dir())
+    } else {
+        format!("{}/maintenance-history.json", get_config_dir())
+    }
+}
+
+/// Get openclaw.json snapshot history directory
+pub fn get_config_history_dir() -> String {
+    if is_windows() {
+        format!("{}\\history", get_config_dir())
+    } else {
+        format!("{}/history", get_config_dir())
     }
 }
 
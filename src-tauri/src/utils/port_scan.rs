@@ -0,0 +1,193 @@
+use std::process::Command;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+/// Windows CREATE_NO_WINDOW flag, used to hide console window
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// TCP_LISTEN in /proc/net/tcp
+const TCP_LISTEN: u8 = 0x0A;
+
+/// One row of /proc/net/tcp or /proc/net/tcp6
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SocketEntry {
+    pub local_port: u16,
+    pub state: u8,
+    pub inode: u64,
+}
+
+impl SocketEntry {
+    pub fn is_listening(&self) -> bool {
+        self.state == TCP_LISTEN
+    }
+}
+
+/// Parse /proc/net/tcp or /proc/net/tcp6 (addresses are `HEXADDR:HEXPORT`)
+pub fn parse_proc_net_tcp(content: &str) -> Vec<SocketEntry> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let port = |addr: &str| u16::from_str_radix(addr.rsplit(':').next()?, 16).ok();
+            Some(SocketEntry {
+                local_port: port(fields.get(1)?)?,
+                state: u8::from_str_radix(fields.get(3)?, 16).ok()?,
+                inode: fields.get(9)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Socket inodes bound locally to `port`; with `listening_only`, only listening sockets
+pub fn port_inodes(entries: &[SocketEntry], port: u16, listening_only: bool) -> Vec<u64> {
+    let mut inodes: Vec<u64> = entries
+        .iter()
+        .filter(|e| e.local_port == port && e.inode != 0 && (!listening_only || e.is_listening()))
+        .map(|e| e.inode)
+        .collect();
+    inodes.sort_unstable();
+    inodes.dedup();
+    inodes
+}
+
+/// Inode from a `/proc/<pid>/fd/<n>` link target such as `socket:[123456]`
+pub fn socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+}
+
+/// PIDs from `netstat -ano` whose *local* address uses `port`.
+/// Listening rows are recognized by a `:0` / `*:*` foreign address, so localized
+/// state names (LISTENING, ABHÖREN, ...) do not matter.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn parse_netstat(output: &str, port: u16, listening_only: bool) -> Vec<u32> {
+    let suffix = format!(":{}", port);
+    let mut pids = Vec::new();
+    for line in output.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 || !fields[0].eq_ignore_ascii_case("TCP") {
+            continue;
+        }
+        if !fields[1].ends_with(&suffix) {
+            continue;
+        }
+        let listening = fields[2].ends_with(":0") || fields[2] == "*:*";
+        if listening_only && !listening {
+            continue;
+        }
+        if let Some(pid) = fields.last().and_then(|p| p.parse::<u32>().ok()) {
+            if pid > 0 && !pids.contains(&pid) {
+                pids.push(pid);
+            }
+        }
+    }
+    pids
+}
+
+/// PIDs listening on `port`
+pub fn listening_pids(port: u16) -> Vec<u32> {
+    port_pids(port, true)
+}
+
+/// PIDs with a socket bound locally to `port` (listener and accepted connections)
+pub fn bound_pids(port: u16) -> Vec<u32> {
+    port_pids(port, false)
+}
+
+fn port_pids(port: u16, listening_only: bool) -> Vec<u32> {
+    #[cfg(target_os = "linux")]
+    {
+        if let Some(pids) = proc_port_pids(port, listening_only) {
+            return pids;
+        }
+        log::debug!("[Port Scan] /proc lookup inconclusive for port {}, falling back to lsof", port);
+        lsof_port_pids(port, listening_only)
+    }
+
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        lsof_port_pids(port, listening_only)
+    }
+
+    #[cfg(windows)]
+    {
+        let mut cmd = Command::new("netstat");
+        cmd.args(["-ano"]);
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        match cmd.output() {
+            Ok(output) => parse_netstat(&String::from_utf8_lossy(&output.stdout), port, listening_only),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+/// Resolve through /proc/net/tcp{,6} and /proc/*/fd.
+/// None when /proc is unreadable or a socket's owner is hidden (another user's process).
+#[cfg(target_os = "linux")]
+fn proc_port_pids(port: u16, listening_only: bool) -> Option<Vec<u32>> {
+    let mut entries = Vec::new();
+    let mut readable = false;
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(content) = std::fs::read_to_string(table) {
+            readable = true;
+            entries.extend(parse_proc_net_tcp(&content));
+        }
+    }
+    if !readable {
+        return None;
+    }
+
+    let inodes = port_inodes(&entries, port, listening_only);
+    if inodes.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut pids = Vec::new();
+    for proc_entry in std::fs::read_dir("/proc").ok()?.flatten() {
+        let Some(pid) = proc_entry.file_name().to_str().and_then(|n| n.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(proc_entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            if let Some(inode) = target.to_str().and_then(socket_inode) {
+                if inodes.contains(&inode) && !pids.contains(&pid) {
+                    pids.push(pid);
+                }
+            }
+        }
+    }
+
+    // The port is in use but its owner is hidden from us: let lsof try
+    if pids.is_empty() {
+        return None;
+    }
+    Some(pids)
+}
+
+#[cfg(unix)]
+fn lsof_port_pids(port: u16, listening_only: bool) -> Vec<u32> {
+    let spec = format!("-iTCP:{}", port);
+    let mut args = vec!["-nP", "-t", spec.as_str()];
+    if listening_only {
+        args.push("-sTCP:LISTEN");
+    }
+    let Ok(output) = Command::new("lsof").args(&args).output() else {
+        return Vec::new();
+    };
+    let mut pids = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Ok(pid) = line.trim().parse::<u32>() {
+            if pid > 0 && !pids.contains(&pid) {
+                pids.push(pid);
+            }
+        }
+    }
+    pids
+}
@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use super::super::port_scan::{parse_netstat, parse_proc_net_tcp, port_inodes, socket_inode, SocketEntry};
+
+    const PROC_NET_TCP: &str = include_str!("fixtures/proc_net_tcp.txt");
+    const PROC_NET_TCP6: &str = include_str!("fixtures/proc_net_tcp6.txt");
+    const NETSTAT_ANO: &str = include_str!("fixtures/netstat_ano.txt");
+    const NETSTAT_ANO_DE: &str = include_str!("fixtures/netstat_ano_de.txt");
+
+    #[test]
+    fn test_parse_proc_net_tcp() {
+        let entries = parse_proc_net_tcp(PROC_NET_TCP);
+        assert_eq!(entries.len(), 6);
+        assert_eq!(entries[0], SocketEntry { local_port: 18789, state: 0x0A, inode: 812345 });
+        assert!(entries[0].is_listening());
+        assert_eq!(entries[1].local_port, 53);
+        assert!(!entries[2].is_listening());
+
+        let entries6 = parse_proc_net_tcp(PROC_NET_TCP6);
+        assert_eq!(entries6.len(), 2);
+        assert_eq!(entries6[0], SocketEntry { local_port: 18789, state: 0x0A, inode: 812346 });
+    }
+
+    #[test]
+    fn test_port_inodes_match_local_port_only() {
+        let mut entries = parse_proc_net_tcp(PROC_NET_TCP);
+        entries.extend(parse_proc_net_tcp(PROC_NET_TCP6));
+
+        assert_eq!(port_inodes(&entries, 18789, true), vec![812345, 812346]);
+        // Accepted connection counts; clients connecting to 18789 and inode-less TIME_WAIT rows do not
+        assert_eq!(port_inodes(&entries, 18789, false), vec![812345, 812346, 812400]);
+        assert!(port_inodes(&entries, 18790, false).is_empty());
+        assert!(port_inodes(&entries, 18790, true).is_empty());
+    }
+
+    #[test]
+    fn test_socket_inode() {
+        assert_eq!(socket_inode("socket:[812345]"), Some(812345));
+        assert_eq!(socket_inode("pipe:[812345]"), None);
+        assert_eq!(socket_inode("/dev/null"), None);
+        assert_eq!(socket_inode("socket:[abc]"), None);
+    }
+
+    #[test]
+    fn test_parse_netstat_matches_local_address_only() {
+        assert_eq!(parse_netstat(NETSTAT_ANO, 18789, true), vec![9120]);
+        // Remote port 18789 (PIDs 7788, 6000) and the PID-0 TIME_WAIT row are not ours
+        assert_eq!(parse_netstat(NETSTAT_ANO, 18789, false), vec![9120]);
+        assert_eq!(parse_netstat(NETSTAT_ANO, 1878, false), Vec::<u32>::new());
+        assert_eq!(parse_netstat(NETSTAT_ANO, 18790, true), vec![4321]);
+        assert_eq!(parse_netstat(NETSTAT_ANO, 5353, false), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_parse_netstat_localized() {
+        assert_eq!(parse_netstat(NETSTAT_ANO_DE, 18789, true), vec![5150]);
+    }
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Samples kept in the in-memory resource series (~30 minutes at the 5s minimum interval)
+pub const MAX_SAMPLES: usize = 360;
+
+/// Fields of `/proc/<pid>/stat` needed for CPU and uptime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcStat {
+    /// User + system time in clock ticks
+    pub cpu_ticks: u64,
+    /// Process start time in clock ticks after boot
+    pub start_ticks: u64,
+}
+
+/// Parse `/proc/<pid>/stat`. The command name may contain spaces and parentheses,
+/// so fields are counted from the last ')'.
+pub fn parse_stat(content: &str) -> Option<ProcStat> {
+    let rest = &content[content.rfind(')')? + 1..];
+    // Field 3 (state) is the first after the name; utime/stime are 14/15, starttime is 22
+    let fields: Vec<&str> = rest.split_whitespace().collect();
+    let field = |n: usize| fields.get(n - 3)?.parse::<u64>().ok();
+    Some(ProcStat {
+        cpu_ticks: field(14)? + field(15)?,
+        start_ticks: field(22)?,
+    })
+}
+
+/// Resident set size in kB from `/proc/<pid>/status`
+pub fn parse_rss_kb(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|kb| kb.parse().ok())
+}
+
+/// Seconds since boot from `/proc/uptime`
+pub fn parse_system_uptime(content: &str) -> Option<f64> {
+    content.split_whitespace().next()?.parse().ok()
+}
+
+/// Process uptime in whole seconds
+pub fn process_uptime(system_uptime: f64, start_ticks: u64, ticks_per_second: u64) -> u64 {
+    let started = start_ticks as f64 / ticks_per_second.max(1) as f64;
+    (system_uptime - started).max(0.0) as u64
+}
+
+/// CPU usage between two samples, 100% = one core fully busy
+pub fn cpu_percent(prev_ticks: u64, ticks: u64, elapsed_seconds: f64, ticks_per_second: u64) -> f64 {
+    if elapsed_seconds <= 0.0 {
+        return 0.0;
+    }
+    let busy = ticks.saturating_sub(prev_ticks) as f64 / ticks_per_second.max(1) as f64;
+    busy / elapsed_seconds * 100.0
+}
+
+/// One point of the resource series
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceSample {
+    /// Unix time in milliseconds
+    pub timestamp_ms: i64,
+    pub pid: u32,
+    pub cpu_percent: Option<f64>,
+    pub memory_mb: Option<f64>,
+    pub uptime_seconds: Option<u64>,
+}
+
+/// Bounded series of samples for one gateway process
+#[derive(Debug, Clone, Default)]
+pub struct ResourceSeries {
+    samples: VecDeque<ResourceSample>,
+}
+
+impl ResourceSeries {
+    /// Append a sample; a new PID (gateway restarted) starts a new series
+    pub fn push(&mut self, sample: ResourceSample) {
+        if self.samples.back().is_some_and(|last| last.pid != sample.pid) {
+            self.samples.clear();
+        }
+        self.samples.push_back(sample);
+        while self.samples.len() > MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+    }
+
+    pub fn last(&self) -> Option<&ResourceSample> {
+        self.samples.back()
+    }
+
+    pub fn to_vec(&self) -> Vec<ResourceSample> {
+        self.samples.iter().cloned().collect()
+    }
+}
+
+/// Current CPU ticks, RSS and uptime of `pid`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessSnapshot {
+    pub cpu_ticks: u64,
+    pub memory_mb: Option<f64>,
+    pub uptime_seconds: Option<u64>,
+}
+
+/// Read `/proc` for `pid`; None when the process is gone or `/proc` is unavailable
+#[cfg(target_os = "linux")]
+pub fn read_process(pid: u32, ticks_per_second: u64) -> Option<ProcessSnapshot> {
+    let read = |path: String| std::fs::read_to_string(path).ok();
+    let stat = parse_stat(&read(format!("/proc/{}/stat", pid))?)?;
+    let memory_mb = read(format!("/proc/{}/status", pid))
+        .as_deref()
+        .and_then(parse_rss_kb)
+        .map(|kb| kb as f64 / 1024.0);
+    let uptime_seconds = read("/proc/uptime".to_string())
+        .as_deref()
+        .and_then(parse_system_uptime)
+        .map(|uptime| process_uptime(uptime, stat.start_ticks, ticks_per_second));
+    Some(ProcessSnapshot {
+        cpu_ticks: stat.cpu_ticks,
+        memory_mb,
+        uptime_seconds,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_process(_pid: u32, _ticks_per_second: u64) -> Option<ProcessSnapshot> {
+    None
+}
+
+/// Kernel clock ticks per second (`getconf CLK_TCK`, 100 on virtually every Linux)
+pub fn ticks_per_second() -> u64 {
+    std::process::Command::new("getconf")
+        .arg("CLK_TCK")
+        .output()
+        .ok()
+        .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse().ok())
+        .filter(|ticks| *ticks > 0)
+        .unwrap_or(100)
+}
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use super::super::proc_stats::{
+        cpu_percent, parse_rss_kb, parse_stat, parse_system_uptime, process_uptime, ProcStat, ResourceSample,
+        ResourceSeries, MAX_SAMPLES,
+    };
+
+    // Node process whose name contains spaces and parentheses
+    const STAT: &str = "4242 (node (gateway) x) S 1 4242 4242 0 -1 4194560 25003 0 12 0 \
+                        1500 250 0 0 20 0 11 0 987654 1234567890 45000 18446744073709551615 \
+                        1 1 0 0 0 0 0 16781312 17922 0 0 0 17 3 0 0 0 0 0\n";
+
+    const STATUS: &str = "Name:\tnode\nUmask:\t0022\nState:\tS (sleeping)\nVmPeak:\t 1300000 kB\n\
+                          VmSize:\t 1205632 kB\nVmRSS:\t  184320 kB\nRssAnon:\t  150000 kB\nThreads:\t11\n";
+
+    fn sample(pid: u32, timestamp_ms: i64) -> ResourceSample {
+        ResourceSample {
+            timestamp_ms,
+            pid,
+            cpu_percent: Some(1.0),
+            memory_mb: Some(100.0),
+            uptime_seconds: Some(10),
+        }
+    }
+
+    #[test]
+    fn test_parse_proc_files() {
+        assert_eq!(
+            parse_stat(STAT),
+            Some(ProcStat { cpu_ticks: 1750, start_ticks: 987654 })
+        );
+        assert_eq!(parse_stat("4242 (node) S 1 2"), None);
+        assert_eq!(parse_stat("garbage"), None);
+
+        assert_eq!(parse_rss_kb(STATUS), Some(184320));
+        assert_eq!(parse_rss_kb("Name:\tkthreadd\n"), None);
+
+        assert_eq!(parse_system_uptime("12345.67 45678.90\n"), Some(12345.67));
+        assert_eq!(parse_system_uptime(""), None);
+    }
+
+    #[test]
+    fn test_uptime_and_cpu() {
+        // Started 9876.54s after boot, system up 12345.67s
+        assert_eq!(process_uptime(12345.67, 987654, 100), 2469);
+        assert_eq!(process_uptime(10.0, 987654, 100), 0);
+
+        // 150 ticks at 100 Hz over 3s = 50% of one core
+        assert!((cpu_percent(1000, 1150, 3.0, 100) - 50.0).abs() < 1e-9);
+        assert_eq!(cpu_percent(1000, 1150, 0.0, 100), 0.0);
+        assert_eq!(cpu_percent(2000, 1000, 1.0, 100), 0.0);
+    }
+
+    #[test]
+    fn test_series_is_bounded_and_resets_on_new_pid() {
+        let mut series = ResourceSeries::default();
+        for i in 0..(MAX_SAMPLES as i64 + 10) {
+            series.push(sample(1, i));
+        }
+        let samples = series.to_vec();
+        assert_eq!(samples.len(), MAX_SAMPLES);
+        assert_eq!(samples[0].timestamp_ms, 10);
+
+        series.push(sample(2, 1000));
+        assert_eq!(series.to_vec().len(), 1);
+        assert_eq!(series.last().unwrap().pid, 2);
+    }
+}
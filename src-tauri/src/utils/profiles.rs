@@ -0,0 +1,130 @@
+use crate::utils::file;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// Name of the implicit profile that uses `OPENCLAW_HOME` or `~/.openclaw`
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Environment variable that replaces `~/.openclaw` as the default config directory
+pub const OPENCLAW_HOME_ENV: &str = "OPENCLAW_HOME";
+
+/// A named OpenClaw installation managed from this machine (e.g. staging, production)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// Config directory; None means the default (`OPENCLAW_HOME` or `~/.openclaw`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_dir: Option<String>,
+    /// Gateway port; None means the port from openclaw.json or the default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gateway_port: Option<u16>,
+    /// Env file sourced before starting the gateway; None means `<config_dir>/env`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_file: Option<String>,
+}
+
+/// Contents of the Manager-level profiles.json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileSettings {
+    /// Active profile name; None means the default profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+}
+
+impl ProfileSettings {
+    /// The active profile, if one other than the implicit default is selected
+    pub fn active_profile(&self) -> Option<&Profile> {
+        let active = self.active.as_deref()?;
+        self.profiles.iter().find(|p| p.name == active)
+    }
+
+    /// Add a profile or replace the one with the same name
+    pub fn upsert(&mut self, profile: Profile) -> Result<(), String> {
+        validate_name(&profile.name)?;
+        if profile.gateway_port == Some(0) {
+            return Err("Gateway port must be between 1 and 65535".to_string());
+        }
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+        Ok(())
+    }
+
+    /// Remove a profile; the active profile cannot be removed
+    pub fn remove(&mut self, name: &str) -> Result<(), String> {
+        if self.active.as_deref() == Some(name) {
+            return Err(format!("Profile '{}' is active; switch to another profile first", name));
+        }
+        let before = self.profiles.len();
+        self.profiles.retain(|p| p.name != name);
+        if self.profiles.len() == before {
+            return Err(format!("Profile '{}' not found", name));
+        }
+        Ok(())
+    }
+
+    /// Switch profiles; `DEFAULT_PROFILE` selects the implicit default
+    pub fn set_active(&mut self, name: &str) -> Result<(), String> {
+        if name == DEFAULT_PROFILE {
+            self.active = None;
+            return Ok(());
+        }
+        if !self.profiles.iter().any(|p| p.name == name) {
+            return Err(format!("Profile '{}' not found", name));
+        }
+        self.active = Some(name.to_string());
+        Ok(())
+    }
+}
+
+/// Profile names end up in log file names, so keep them simple
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name == DEFAULT_PROFILE {
+        return Err(format!("Invalid profile name '{}'", name));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!(
+            "Invalid profile name '{}': only letters, digits, '-' and '_' are allowed",
+            name
+        ));
+    }
+    Ok(())
+}
+
+/// Config directory for `profile`, falling back to `openclaw_home`, then `<home>/.openclaw`
+pub fn resolve_config_dir(profile: Option<&Profile>, openclaw_home: Option<&str>, home: Option<&Path>) -> String {
+    if let Some(dir) = profile.and_then(|p| p.config_dir.as_deref()).filter(|d| !d.is_empty()) {
+        return expand_home(dir, home);
+    }
+    if let Some(dir) = openclaw_home.filter(|d| !d.is_empty()) {
+        return expand_home(dir, home);
+    }
+    match home {
+        Some(home) => home.join(".openclaw").to_string_lossy().to_string(),
+        None => String::from("~/.openclaw"),
+    }
+}
+
+/// Expand a leading `~` to the home directory
+pub fn expand_home(path: &str, home: Option<&Path>) -> String {
+    match (path.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') || rest.starts_with('\\') => {
+            format!("{}{}", home.display(), rest)
+        }
+        _ => path.to_string(),
+    }
+}
+
+/// Read profiles.json; a missing file means no profiles
+pub fn load(path: &str) -> io::Result<ProfileSettings> {
+    file::load_json(path)
+}
+
+/// Write profiles.json
+pub fn save(path: &str, settings: &ProfileSettings) -> io::Result<()> {
+    file::save_json(path, settings)
+}
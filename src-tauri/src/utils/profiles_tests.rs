@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use super::super::profiles::{expand_home, load, resolve_config_dir, save, Profile, ProfileSettings};
+    use std::fs;
+    use std::path::Path;
+
+    fn profile(name: &str, config_dir: Option<&str>) -> Profile {
+        Profile {
+            name: name.to_string(),
+            config_dir: config_dir.map(|d| d.to_string()),
+            gateway_port: None,
+            env_file: None,
+        }
+    }
+
+    #[test]
+    fn test_config_dir_resolution_order() {
+        let home = Path::new("/home/me");
+        let staging = profile("staging", Some("~/openclaw-staging"));
+
+        assert_eq!(resolve_config_dir(None, None, Some(home)), "/home/me/.openclaw");
+        assert_eq!(resolve_config_dir(None, Some("/srv/openclaw"), Some(home)), "/srv/openclaw");
+        assert_eq!(resolve_config_dir(Some(&staging), Some("/srv/openclaw"), Some(home)), "/home/me/openclaw-staging");
+        // A profile without its own directory uses the default one
+        assert_eq!(resolve_config_dir(Some(&profile("ports", None)), Some(""), Some(home)), "/home/me/.openclaw");
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = Some(Path::new("/home/me"));
+        assert_eq!(expand_home("~", home), "/home/me");
+        assert_eq!(expand_home("~/env", home), "/home/me/env");
+        assert_eq!(expand_home("~other/env", home), "~other/env");
+        assert_eq!(expand_home("/etc/env", home), "/etc/env");
+    }
+
+    #[test]
+    fn test_profile_management() {
+        let mut settings = ProfileSettings::default();
+        assert!(settings.active_profile().is_none());
+
+        settings.upsert(profile("staging", Some("/srv/staging"))).unwrap();
+        settings.upsert(profile("prod", Some("/srv/prod"))).unwrap();
+        settings.upsert(profile("staging", Some("/srv/staging2"))).unwrap();
+        assert_eq!(settings.profiles.len(), 2);
+
+        settings.set_active("staging").unwrap();
+        assert_eq!(settings.active_profile().unwrap().config_dir.as_deref(), Some("/srv/staging2"));
+        assert!(settings.set_active("missing").is_err());
+        assert!(settings.remove("staging").is_err(), "active profile must not be removable");
+
+        settings.set_active("default").unwrap();
+        assert!(settings.active_profile().is_none());
+        settings.remove("staging").unwrap();
+        assert!(settings.remove("staging").is_err());
+
+        assert!(settings.upsert(profile("default", None)).is_err());
+        assert!(settings.upsert(profile("../etc", None)).is_err());
+        let mut zero_port = profile("zero", None);
+        zero_port.gateway_port = Some(0);
+        assert!(settings.upsert(zero_port).is_err());
+    }
+
+    #[test]
+    fn test_settings_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("openclaw-profiles-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join("profiles.json");
+        let path = path.to_str().unwrap();
+
+        assert_eq!(load(path).unwrap(), ProfileSettings::default());
+
+        let mut settings = ProfileSettings::default();
+        let mut prod = profile("prod", Some("/srv/prod"));
+        prod.gateway_port = Some(19001);
+        prod.env_file = Some("/srv/prod/env".to_string());
+        settings.upsert(prod).unwrap();
+        settings.set_active("prod").unwrap();
+        save(path, &settings).unwrap();
+
+        assert_eq!(load(path).unwrap(), settings);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
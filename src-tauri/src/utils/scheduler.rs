@@ -0,0 +1,342 @@
+use crate::utils::file;
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// manager.json key holding the maintenance schedule
+pub const MANAGER_KEY: &str = "maintenance";
+
+/// Number of job runs kept in the history file
+pub const MAX_RUNS: usize = 200;
+
+/// Minutes the scheduler catches up on after a stall (sleep, suspended laptop);
+/// older occurrences are dropped instead of replayed
+pub const MAX_CATCH_UP_MINUTES: i64 = 5;
+
+/// Parsed five-field cron expression: minute hour day-of-month month day-of-week.
+/// Fields take `*`, numbers, ranges `a-b`, lists `a,b` and steps `*/n` / `a-b/n`;
+/// `@hourly`, `@daily` (`@midnight`) and `@weekly` are accepted as shorthands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    minutes: u64,
+    hours: u32,
+    days: u32,
+    months: u16,
+    weekdays: u8,
+    /// Day-of-month / day-of-week given as `*`; when both are restricted either may match
+    any_day: bool,
+    any_weekday: bool,
+}
+
+fn parse_field(field: &str, min: u32, max: u32) -> Result<(u64, bool), String> {
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("invalid step '{}'", step))?;
+                if step == 0 {
+                    return Err("step must be at least 1".to_string());
+                }
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            let a: u32 = a.parse().map_err(|_| format!("invalid value '{}'", a))?;
+            let b: u32 = b.parse().map_err(|_| format!("invalid value '{}'", b))?;
+            (a, b)
+        } else {
+            let value: u32 = range.parse().map_err(|_| format!("invalid value '{}'", range))?;
+            // `5/15` means "from 5, every 15"
+            (value, if step > 1 { max } else { value })
+        };
+        if start < min || end > max || start > end {
+            return Err(format!("'{}' is outside {}-{}", part, min, max));
+        }
+        let mut value = start;
+        while value <= end {
+            bits |= 1 << value;
+            value += step;
+        }
+    }
+    Ok((bits, field == "*"))
+}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            other => other,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(format!("expected 5 fields (minute hour day month weekday), got {}", fields.len()));
+        }
+        let field = |i: usize, name: &str, min, max| {
+            parse_field(fields[i], min, max).map_err(|e| format!("{}: {}", name, e))
+        };
+
+        let (minutes, _) = field(0, "minute", 0, 59)?;
+        let (hours, _) = field(1, "hour", 0, 23)?;
+        let (days, any_day) = field(2, "day of month", 1, 31)?;
+        let (months, _) = field(3, "month", 1, 12)?;
+        let (weekdays, any_weekday) = field(4, "day of week", 0, 7)?;
+        // 7 is Sunday as well
+        let weekdays = (weekdays | (weekdays >> 7)) & 0x7f;
+
+        Ok(Self {
+            minutes,
+            hours: hours as u32,
+            days: days as u32,
+            months: months as u16,
+            weekdays: weekdays as u8,
+            any_day,
+            any_weekday,
+        })
+    }
+
+    fn matches_day(&self, t: &NaiveDateTime) -> bool {
+        if self.months & (1 << t.month()) == 0 {
+            return false;
+        }
+        let day = self.days & (1 << t.day()) != 0;
+        let weekday = self.weekdays & (1 << t.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (false, true) => day,
+            (true, false) => weekday,
+            (false, false) => day || weekday,
+        }
+    }
+
+    /// Whether the schedule fires in the minute `t` falls in
+    pub fn matches(&self, t: &NaiveDateTime) -> bool {
+        self.minutes & (1 << t.minute()) != 0 && self.hours & (1 << t.hour()) != 0 && self.matches_day(t)
+    }
+
+    /// First minute strictly after `t` the schedule fires in; None if it never does within a year
+    pub fn next_after(&self, t: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut candidate = t.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = t + Duration::days(366);
+        while candidate <= limit {
+            if !self.matches_day(&candidate) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+            } else if self.hours & (1 << candidate.hour()) == 0 {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+            } else if self.matches(&candidate) {
+                return Some(candidate);
+            } else {
+                candidate += Duration::minutes(1);
+            }
+        }
+        None
+    }
+}
+
+/// Daily time range `start`..`end` ("HH:MM", local time); wraps past midnight when end < start
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    pub start: String,
+    pub end: String,
+}
+
+impl TimeWindow {
+    fn bounds(&self) -> Result<(NaiveTime, NaiveTime), String> {
+        let parse = |s: &str| {
+            NaiveTime::parse_from_str(s.trim(), "%H:%M").map_err(|_| format!("'{}' is not a HH:MM time", s))
+        };
+        Ok((parse(&self.start)?, parse(&self.end)?))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.bounds().map(|_| ())
+    }
+
+    /// Whether `t` falls in the window; a malformed window contains nothing
+    pub fn contains(&self, t: NaiveTime) -> bool {
+        match self.bounds() {
+            Ok((start, end)) if start <= end => start <= t && t < end,
+            Ok((start, end)) => t >= start || t < end,
+            Err(_) => false,
+        }
+    }
+}
+
+/// What a maintenance job does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MaintenanceAction {
+    /// Restart the gateway
+    Restart,
+    /// Update openclaw; only runs inside the maintenance window when scheduled
+    Update,
+    /// Repair the device token, then restart the gateway
+    RepairDeviceToken,
+}
+
+/// One scheduled job
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceJob {
+    pub id: String,
+    pub name: String,
+    pub action: MaintenanceAction,
+    /// Cron expression, local time
+    pub schedule: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Maintenance schedule stored in manager.json
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MaintenanceSettings {
+    /// Master switch for scheduled runs; manual runs always work
+    pub enabled: bool,
+    /// No scheduled job runs inside quiet hours
+    pub quiet_hours: Option<TimeWindow>,
+    /// Scheduled updates only run inside this window
+    pub maintenance_window: Option<TimeWindow>,
+    pub jobs: Vec<MaintenanceJob>,
+}
+
+impl MaintenanceSettings {
+    /// Settings from manager.json; missing or malformed settings mean no schedule
+    pub fn from_manager_config(manager: &serde_json::Value) -> Self {
+        manager
+            .get(MANAGER_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Check cron expressions, windows and job ids before saving
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(window) = &self.quiet_hours {
+            window.validate().map_err(|e| format!("Quiet hours: {}", e))?;
+        }
+        if let Some(window) = &self.maintenance_window {
+            window.validate().map_err(|e| format!("Maintenance window: {}", e))?;
+        }
+        let mut ids = std::collections::HashSet::new();
+        for job in &self.jobs {
+            if job.id.trim().is_empty() {
+                return Err(format!("Job '{}' has no id", job.name));
+            }
+            if !ids.insert(job.id.as_str()) {
+                return Err(format!("Duplicate job id '{}'", job.id));
+            }
+            CronSchedule::parse(&job.schedule).map_err(|e| format!("Job '{}': {}", job.name, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn job(&self, id: &str) -> Option<&MaintenanceJob> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    /// Enabled jobs that fire in a minute within (`after`, `until`], looking back at most
+    /// `MAX_CATCH_UP_MINUTES`; each job is returned once even if it fired several times
+    pub fn due_jobs(&self, after: NaiveDateTime, until: NaiveDateTime) -> Vec<&MaintenanceJob> {
+        if !self.enabled || until <= after {
+            return Vec::new();
+        }
+        let after = after.max(until - Duration::minutes(MAX_CATCH_UP_MINUTES));
+        self.jobs
+            .iter()
+            .filter(|job| job.enabled)
+            .filter(|job| {
+                CronSchedule::parse(&job.schedule)
+                    .ok()
+                    .and_then(|schedule| schedule.next_after(after))
+                    .is_some_and(|next| next <= until)
+            })
+            .collect()
+    }
+
+    /// Why a scheduled run of `job` at `now` must be skipped, if it must
+    pub fn skip_reason(&self, job: &MaintenanceJob, now: NaiveTime) -> Option<String> {
+        if self.quiet_hours.as_ref().is_some_and(|w| w.contains(now)) {
+            return Some("Inside quiet hours".to_string());
+        }
+        if job.action == MaintenanceAction::Update {
+            match &self.maintenance_window {
+                None => return Some("Updates need a maintenance window".to_string()),
+                Some(window) if !window.contains(now) => {
+                    return Some(format!("Outside the maintenance window ({}-{})", window.start, window.end))
+                }
+                Some(_) => {}
+            }
+        }
+        None
+    }
+}
+
+/// How a run was started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunTrigger {
+    Scheduled,
+    Manual,
+}
+
+/// Result of a job run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Succeeded,
+    Failed,
+    /// Not run: quiet hours, outside the maintenance window or another job was running
+    Skipped,
+}
+
+/// One entry of the persisted run history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobRun {
+    pub job_id: String,
+    pub job_name: String,
+    pub action: MaintenanceAction,
+    pub trigger: RunTrigger,
+    /// RFC 3339 time the run started
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub outcome: RunOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Contents of maintenance-history.json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunHistory {
+    /// Oldest first
+    #[serde(default)]
+    pub runs: Vec<JobRun>,
+}
+
+impl RunHistory {
+    /// Append a run, dropping the oldest beyond `MAX_RUNS`
+    pub fn push(&mut self, run: JobRun) {
+        self.runs.push(run);
+        if self.runs.len() > MAX_RUNS {
+            let excess = self.runs.len() - MAX_RUNS;
+            self.runs.drain(..excess);
+        }
+    }
+}
+
+/// Read maintenance-history.json; a missing file means no runs yet
+pub fn load(path: &str) -> io::Result<RunHistory> {
+    file::load_json(path)
+}
+
+/// Write maintenance-history.json
+pub fn save(path: &str, history: &RunHistory) -> io::Result<()> {
+    file::save_json(path, history)
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use super::super::scheduler::{
+        CronSchedule, MaintenanceAction, MaintenanceJob, MaintenanceSettings, TimeWindow,
+    };
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, mo, d).unwrap().and_hms_opt(h, mi, 0).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn job(id: &str, action: MaintenanceAction, schedule: &str) -> MaintenanceJob {
+        MaintenanceJob {
+            id: id.to_string(),
+            name: id.to_string(),
+            action,
+            schedule: schedule.to_string(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_cron_parse_and_next_run() {
+        // 2026-10-17 is a Saturday
+        let nightly = CronSchedule::parse("30 3 * * *").unwrap();
+        assert_eq!(nightly.next_after(at(2026, 10, 17, 3, 30)), Some(at(2026, 10, 18, 3, 30)));
+        assert!(nightly.matches(&at(2026, 10, 17, 3, 30)));
+
+        let weekdays = CronSchedule::parse("*/15 9-17 * * 1-5").unwrap();
+        assert_eq!(weekdays.next_after(at(2026, 10, 17, 10, 0)), Some(at(2026, 10, 19, 9, 0)));
+        assert_eq!(weekdays.next_after(at(2026, 10, 19, 9, 7)), Some(at(2026, 10, 19, 9, 15)));
+
+        // 7 is Sunday too
+        let sunday = CronSchedule::parse("0 4 * * 7").unwrap();
+        assert_eq!(sunday.next_after(at(2026, 10, 17, 12, 0)), Some(at(2026, 10, 18, 4, 0)));
+        assert_eq!(CronSchedule::parse("@weekly").unwrap().next_after(at(2026, 10, 17, 0, 0)), Some(at(2026, 10, 18, 0, 0)));
+
+        assert!(CronSchedule::parse("0 3 * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("0 0 31 2 *").unwrap().next_after(at(2026, 1, 1, 0, 0)).is_none());
+    }
+
+    #[test]
+    fn test_time_window_wraps_midnight() {
+        let quiet = TimeWindow { start: "22:00".to_string(), end: "06:30".to_string() };
+        assert!(quiet.contains(time(23, 15)));
+        assert!(quiet.contains(time(2, 0)));
+        assert!(!quiet.contains(time(6, 30)));
+        assert!(!quiet.contains(time(12, 0)));
+
+        let window = TimeWindow { start: "02:00".to_string(), end: "04:00".to_string() };
+        assert!(window.contains(time(3, 59)));
+        assert!(!window.contains(time(4, 0)));
+        assert!(TimeWindow { start: "25:00".to_string(), end: "04:00".to_string() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_due_jobs_and_skip_reasons() {
+        let settings = MaintenanceSettings {
+            enabled: true,
+            quiet_hours: Some(TimeWindow { start: "08:00".to_string(), end: "20:00".to_string() }),
+            maintenance_window: Some(TimeWindow { start: "03:00".to_string(), end: "05:00".to_string() }),
+            jobs: vec![
+                job("restart", MaintenanceAction::Restart, "0 3 * * *"),
+                job("update", MaintenanceAction::Update, "0 * * * *"),
+            ],
+        };
+        assert!(settings.validate().is_ok());
+
+        let due: Vec<&str> = settings
+            .due_jobs(at(2026, 10, 17, 2, 59), at(2026, 10, 17, 3, 0))
+            .iter()
+            .map(|j| j.id.as_str())
+            .collect();
+        assert_eq!(due, vec!["restart", "update"]);
+        // A long stall only catches up on the last few minutes
+        assert!(settings.due_jobs(at(2026, 10, 16, 2, 0), at(2026, 10, 17, 2, 30)).is_empty());
+
+        let update = settings.job("update").unwrap();
+        assert_eq!(settings.skip_reason(update, time(3, 0)), None);
+        assert!(settings.skip_reason(update, time(6, 0)).unwrap().contains("maintenance window"));
+        let restart = settings.job("restart").unwrap();
+        assert_eq!(settings.skip_reason(restart, time(12, 0)).as_deref(), Some("Inside quiet hours"));
+
+        let mut invalid = settings.clone();
+        invalid.jobs.push(job("restart", MaintenanceAction::Restart, "@daily"));
+        assert!(invalid.validate().unwrap_err().contains("Duplicate"));
+        assert!(MaintenanceSettings { enabled: false, ..settings }
+            .due_jobs(at(2026, 10, 17, 2, 59), at(2026, 10, 17, 3, 0))
+            .is_empty());
+    }
+}
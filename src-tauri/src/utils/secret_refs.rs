@@ -0,0 +1,303 @@
+use crate::utils::config_lock::escape_pointer_token;
+use crate::utils::secrets_vault::Secrets;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Where a reference takes its value from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefSource {
+    /// `${env:NAME}`: the env file, then the Manager's environment
+    Env,
+    /// `${secret:NAME}`: the secrets vault
+    Secret,
+    /// `${NAME}`: openclaw's own substitution; the Manager supplies it from the vault or env
+    Native,
+}
+
+/// One `${...}` reference inside a string
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretRef {
+    pub source: RefSource,
+    pub name: String,
+}
+
+impl SecretRef {
+    /// The reference as written in the config
+    pub fn text(&self) -> String {
+        match self.source {
+            RefSource::Env => format!("${{env:{}}}", self.name),
+            RefSource::Secret => format!("${{secret:{}}}", self.name),
+            RefSource::Native => format!("${{{}}}", self.name),
+        }
+    }
+}
+
+/// Upper-case env var name: prefixed references are rewritten to `${NAME}` for
+/// openclaw, which only substitutes upper-case names
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_uppercase() || c == '_')
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// A `${...}` span: byte range in the string and what it refers to.
+/// `Err` carries the text of a prefixed reference with an invalid name.
+type Span = (std::ops::Range<usize>, Result<SecretRef, String>);
+
+fn spans(value: &str) -> Vec<Span> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = value[offset..].find("${").map(|i| offset + i) {
+        let Some(end) = value[start..].find('}').map(|i| start + i) else {
+            break;
+        };
+        let inner = &value[start + 2..end];
+        let parsed = if let Some(name) = inner.strip_prefix("env:") {
+            Some(if is_env_name(name) { Ok(RefSource::Env) } else { Err(()) }.map(|s| (s, name)))
+        } else if let Some(name) = inner.strip_prefix("secret:") {
+            Some(if is_env_name(name) { Ok(RefSource::Secret) } else { Err(()) }.map(|s| (s, name)))
+        } else if is_env_name(inner) {
+            Some(Ok((RefSource::Native, inner)))
+        } else {
+            None
+        };
+        match parsed {
+            Some(Ok((source, name))) => found.push((start..end + 1, Ok(SecretRef { source, name: name.to_string() }))),
+            Some(Err(())) => found.push((start..end + 1, Err(value[start..end + 1].to_string()))),
+            None => {}
+        }
+        offset = end + 1;
+    }
+    found
+}
+
+/// References in a string, in order
+pub fn references(value: &str) -> Vec<SecretRef> {
+    spans(value).into_iter().filter_map(|(_, r)| r.ok()).collect()
+}
+
+pub fn contains_reference(value: &str) -> bool {
+    !references(value).is_empty()
+}
+
+/// Whether any string uses `${env:NAME}` / `${secret:NAME}`, which openclaw itself doesn't understand
+pub fn uses_prefixed_references(config: &Value) -> bool {
+    let mut found = false;
+    visit_strings(config, "", &mut |_, s| {
+        found |= references(s).iter().any(|r| r.source != RefSource::Native);
+    });
+    found
+}
+
+/// Check a value entered in the UI: a literal, or text with well-formed references
+pub fn validate(value: &str) -> Result<(), String> {
+    match spans(value).into_iter().find_map(|(_, r)| r.err()) {
+        Some(bad) => Err(format!(
+            "'{}' is not a valid reference; use ${{env:NAME}} or ${{secret:NAME}} with upper-case letters, digits and _",
+            bad
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Check every string in a JSON value (MCP server config)
+pub fn validate_value(value: &Value) -> Result<(), String> {
+    let mut result = Ok(());
+    visit_strings(value, "", &mut |_, s| {
+        if result.is_ok() {
+            result = validate(s);
+        }
+    });
+    result
+}
+
+fn visit_strings(value: &Value, pointer: &str, f: &mut impl FnMut(&str, &str)) {
+    match value {
+        Value::String(s) => f(pointer, s),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                visit_strings(item, &format!("{}/{}", pointer, i), f);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                visit_strings(item, &format!("{}/{}", pointer, escape_pointer_token(key)), f);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn visit_strings_mut(value: &mut Value, pointer: &str, f: &mut impl FnMut(&str, &mut String)) {
+    match value {
+        Value::String(s) => f(pointer, s),
+        Value::Array(items) => {
+            for (i, item) in items.iter_mut().enumerate() {
+                visit_strings_mut(item, &format!("{}/{}", pointer, i), f);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map.iter_mut() {
+                visit_strings_mut(item, &format!("{}/{}", pointer, escape_pointer_token(key)), f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A reference that has no value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unresolved {
+    /// JSON pointer of the string holding the reference
+    pub pointer: String,
+    pub reference: String,
+    pub reason: String,
+}
+
+/// Format unresolved references for an error detail, one per line
+pub fn format_unresolved(unresolved: &[Unresolved]) -> String {
+    unresolved
+        .iter()
+        .map(|u| format!("{} at {}: {}", u.reference, if u.pointer.is_empty() { "/" } else { &u.pointer }, u.reason))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Value sources for references
+#[derive(Debug, Clone, Default)]
+pub struct Resolver {
+    /// Variables from the OpenClaw env file
+    pub env_file: HashMap<String, String>,
+    /// Unlocked vault secrets; None when there is no vault or it is locked
+    pub secrets: Option<Secrets>,
+    /// The vault exists but is locked
+    pub vault_locked: bool,
+}
+
+impl Resolver {
+    pub fn lookup(&self, reference: &SecretRef) -> Result<String, String> {
+        let from_env = || {
+            self.env_file
+                .get(&reference.name)
+                .cloned()
+                .or_else(|| std::env::var(&reference.name).ok())
+        };
+        let from_vault = || self.secrets.as_ref().and_then(|s| s.get(&reference.name).cloned());
+        let missing_secret = || {
+            if self.vault_locked {
+                "the secrets vault is locked".to_string()
+            } else if self.secrets.is_none() {
+                "no secrets vault exists".to_string()
+            } else {
+                "not in the secrets vault".to_string()
+            }
+        };
+        match reference.source {
+            RefSource::Env => from_env().ok_or_else(|| "not set in the env file or environment".to_string()),
+            RefSource::Secret => from_vault().ok_or_else(missing_secret),
+            RefSource::Native => from_vault()
+                .or_else(from_env)
+                .ok_or_else(|| format!("not in the vault or environment ({})", missing_secret())),
+        }
+    }
+
+    /// Replace every reference in `value` with its value
+    pub fn resolve_str(&self, value: &str) -> Result<String, Vec<Unresolved>> {
+        let mut out = String::with_capacity(value.len());
+        let mut unresolved = Vec::new();
+        let mut last = 0;
+        for (range, reference) in spans(value) {
+            let Ok(reference) = reference else {
+                continue;
+            };
+            out.push_str(&value[last..range.start]);
+            match self.lookup(&reference) {
+                Ok(resolved) => out.push_str(&resolved),
+                Err(reason) => unresolved.push(Unresolved { pointer: String::new(), reference: reference.text(), reason }),
+            }
+            last = range.end;
+        }
+        out.push_str(&value[last..]);
+        if unresolved.is_empty() {
+            Ok(out)
+        } else {
+            Err(unresolved)
+        }
+    }
+
+    /// Replace references in every string of `value` with their values (mcporter.json)
+    pub fn resolve_value(&self, value: &mut Value) -> Result<(), Vec<Unresolved>> {
+        let mut unresolved = Vec::new();
+        visit_strings_mut(value, "", &mut |pointer, s| match self.resolve_str(s) {
+            Ok(resolved) => *s = resolved,
+            Err(missing) => unresolved.extend(missing.into_iter().map(|u| Unresolved { pointer: pointer.to_string(), ..u })),
+        });
+        if unresolved.is_empty() {
+            Ok(())
+        } else {
+            Err(unresolved)
+        }
+    }
+}
+
+/// What the gateway needs to start with the references in its config
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaunchPlan {
+    /// Env vars carrying the referenced values
+    pub env: BTreeMap<String, String>,
+    /// Config with `${env:NAME}` / `${secret:NAME}` rewritten to openclaw's `${NAME}`;
+    /// None when the config only uses native references
+    pub native_config: Option<Value>,
+}
+
+/// Resolve every reference of openclaw.json for a gateway launch. openclaw only understands
+/// `${NAME}`, so prefixed references are rewritten to that form and their values passed as env.
+pub fn plan_launch(config: &Value, resolver: &Resolver) -> Result<LaunchPlan, Vec<Unresolved>> {
+    let mut plan = LaunchPlan::default();
+    let mut unresolved = Vec::new();
+    visit_strings(config, "", &mut |pointer, s| {
+        for reference in references(s) {
+            let value = match resolver.lookup(&reference) {
+                Ok(value) => value,
+                Err(reason) => {
+                    unresolved.push(Unresolved { pointer: pointer.to_string(), reference: reference.text(), reason });
+                    continue;
+                }
+            };
+            match plan.env.get(&reference.name) {
+                Some(existing) if *existing != value => unresolved.push(Unresolved {
+                    pointer: pointer.to_string(),
+                    reference: reference.text(),
+                    reason: format!("resolves to a different value than another reference named {}", reference.name),
+                }),
+                _ => {
+                    plan.env.insert(reference.name.clone(), value);
+                }
+            }
+        }
+    });
+
+    if !unresolved.is_empty() {
+        return Err(unresolved);
+    }
+    if uses_prefixed_references(config) {
+        let mut native = config.clone();
+        visit_strings_mut(&mut native, "", &mut |_, s| {
+            let mut out = String::with_capacity(s.len());
+            let mut last = 0;
+            for (range, reference) in spans(s) {
+                if let Ok(reference) = reference {
+                    out.push_str(&s[last..range.start]);
+                    out.push_str(&format!("${{{}}}", reference.name));
+                    last = range.end;
+                }
+            }
+            out.push_str(&s[last..]);
+            *s = out;
+        });
+        plan.native_config = Some(native);
+    }
+    Ok(plan)
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use super::super::secret_refs::{
+        plan_launch, references, uses_prefixed_references, validate, RefSource, Resolver, SecretRef,
+    };
+    use super::super::secrets_vault::Secrets;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn resolver() -> Resolver {
+        Resolver {
+            env_file: HashMap::from([("PROXY_HOST".to_string(), "proxy.local".to_string())]),
+            secrets: Some(Secrets::from([
+                ("OPENAI_API_KEY".to_string(), "sk-vault".to_string()),
+                ("PROXY_HOST".to_string(), "vault.local".to_string()),
+            ])),
+            vault_locked: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_and_validate_references() {
+        assert_eq!(
+            references("https://${env:PROXY_HOST}:8080/${secret:PATH_TOKEN}?k=${OPENAI_API_KEY}"),
+            vec![
+                SecretRef { source: RefSource::Env, name: "PROXY_HOST".to_string() },
+                SecretRef { source: RefSource::Secret, name: "PATH_TOKEN".to_string() },
+                SecretRef { source: RefSource::Native, name: "OPENAI_API_KEY".to_string() },
+            ]
+        );
+        // Lower-case ${name} is left to openclaw, which doesn't substitute it either
+        assert!(references("${lower} $HOME {x} ${unterminated").is_empty());
+
+        assert!(validate("sk-literal").is_ok());
+        assert!(validate("${secret:OPENAI_API_KEY}").is_ok());
+        assert!(validate("${secret:openai key}").unwrap_err().contains("${secret:openai key}"));
+        assert!(validate("${env:}").is_err());
+        // Rewritten to ${name}, which openclaw would leave unsubstituted
+        assert!(validate("${env:proxy_host}").is_err());
+        assert!(validate("${secret:OpenAI_Key}").is_err());
+    }
+
+    #[test]
+    fn test_plan_launch_rewrites_prefixed_references() {
+        let config = json!({
+            "models": {"providers": {
+                "openai": {"apiKey": "${secret:OPENAI_API_KEY}", "baseUrl": "https://${env:PROXY_HOST}/v1"},
+                "other": {"apiKey": "${OPENAI_API_KEY}"}
+            }},
+            "gateway": {"port": 18789}
+        });
+        let plan = plan_launch(&config, &resolver()).unwrap();
+        assert_eq!(plan.env["OPENAI_API_KEY"], "sk-vault");
+        // env: reads the env file, not the vault entry of the same name
+        assert_eq!(plan.env["PROXY_HOST"], "proxy.local");
+        let native = plan.native_config.unwrap();
+        assert_eq!(native["models"]["providers"]["openai"]["apiKey"], "${OPENAI_API_KEY}");
+        assert_eq!(native["models"]["providers"]["openai"]["baseUrl"], "https://${PROXY_HOST}/v1");
+        assert_eq!(native["gateway"]["port"], 18789);
+
+        // Only native references: openclaw can read the config as-is
+        let native_only = json!({"models": {"providers": {"other": {"apiKey": "${OPENAI_API_KEY}"}}}});
+        assert!(!uses_prefixed_references(&native_only));
+        assert!(plan_launch(&native_only, &resolver()).unwrap().native_config.is_none());
+    }
+
+    #[test]
+    fn test_plan_launch_reports_unresolved_and_conflicts() {
+        let config = json!({
+            "a": "${secret:MISSING_KEY}",
+            "b": ["${env:PROXY_HOST}", "${secret:PROXY_HOST}"]
+        });
+        let unresolved = plan_launch(&config, &resolver()).unwrap_err();
+        assert_eq!(unresolved.len(), 2);
+        assert_eq!(unresolved[0].pointer, "/a");
+        assert_eq!(unresolved[0].reason, "not in the secrets vault");
+        assert_eq!(unresolved[1].pointer, "/b/1");
+        assert_eq!(unresolved[1].reference, "${secret:PROXY_HOST}");
+
+        let locked = Resolver { vault_locked: true, ..Resolver::default() };
+        let unresolved = plan_launch(&json!({"a": "${secret:OPENAI_API_KEY}"}), &locked).unwrap_err();
+        assert_eq!(unresolved[0].reason, "the secrets vault is locked");
+
+        let mut mcp = json!({"env": {"TOKEN": "Bearer ${secret:OPENAI_API_KEY}"}});
+        resolver().resolve_value(&mut mcp).unwrap();
+        assert_eq!(mcp["env"]["TOKEN"], "Bearer sk-vault");
+    }
+}
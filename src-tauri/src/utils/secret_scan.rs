@@ -0,0 +1,208 @@
+use crate::utils::log_sanitizer;
+use crate::utils::secrets_vault;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::path::Path;
+
+/// manager.json key listing paths written by Export Config, so the scan can check them
+pub const EXPORTED_CONFIGS_KEY: &str = "exportedConfigs";
+/// Exported config paths kept in manager.json
+pub const MAX_EXPORTED_CONFIGS: usize = 20;
+/// Larger files (session archives, media) are skipped
+pub const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// A scan stops reporting after this many findings
+pub const MAX_FINDINGS: usize = 500;
+
+/// Shortest unlabelled string considered for the entropy check
+const MIN_RANDOM_LEN: usize = 24;
+/// Longer strings are encoded blobs (images, archives), not credentials
+const MAX_RANDOM_LEN: usize = 512;
+/// Bits per character; hex digests and UUIDs stay below this
+const MIN_RANDOM_ENTROPY: f64 = 4.0;
+/// `password: ...`-style values must be at least this long and this random
+const MIN_ASSIGNED_LEN: usize = 12;
+const MIN_ASSIGNED_ENTROPY: f64 = 3.0;
+
+/// Random-looking ids that appear all over session transcripts
+const ID_PREFIXES: &[&str] = &["msg_", "toolu_", "req_", "call_", "chatcmpl-", "resp_", "run_", "file_"];
+
+/// File types that can hold pasted credentials
+const TEXT_EXTENSIONS: &[&str] = &[
+    "md", "markdown", "txt", "json", "jsonl", "json5", "yaml", "yml", "toml", "ini", "sh", "bash", "zsh", "ps1", "cmd",
+    "bat", "js", "mjs", "ts", "py",
+];
+
+static RANDOM_TOKEN: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z0-9_\-+=]{24,}").unwrap());
+
+/// A secret-looking value in a file. The value itself never leaves the backend;
+/// `fingerprint` identifies it when the user asks to move it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeakFinding {
+    pub file: String,
+    /// 1-based line number
+    pub line: usize,
+    /// Pattern name, or "High-entropy string"
+    pub kind: String,
+    pub masked: String,
+    pub fingerprint: String,
+    pub suggested_name: String,
+    /// Whether the value can be replaced in place (a private key block spans several lines)
+    pub movable: bool,
+}
+
+/// A match within one file's text
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextMatch {
+    pub line: usize,
+    pub kind: &'static str,
+    pub value: String,
+    pub suggested_name: &'static str,
+    pub movable: bool,
+}
+
+impl TextMatch {
+    pub fn to_finding(&self, file: &str) -> LeakFinding {
+        LeakFinding {
+            file: file.to_string(),
+            line: self.line,
+            kind: self.kind.to_string(),
+            masked: mask(&self.value),
+            fingerprint: fingerprint(&self.value),
+            suggested_name: self.suggested_name.to_string(),
+            movable: self.movable,
+        }
+    }
+}
+
+/// Shannon entropy in bits per character
+pub fn shannon_entropy(value: &str) -> f64 {
+    let mut counts = [0usize; 256];
+    for b in value.bytes() {
+        counts[b as usize] += 1;
+    }
+    let len = value.len() as f64;
+    counts
+        .iter()
+        .filter(|c| **c > 0)
+        .map(|c| {
+            let p = *c as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Keep just enough of a value to recognise it
+pub fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() < 12 {
+        return "****".to_string();
+    }
+    format!(
+        "{}****{}",
+        chars[..4].iter().collect::<String>(),
+        chars[chars.len() - 2..].iter().collect::<String>()
+    )
+}
+
+pub fn fingerprint(value: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+fn has_letters_and_digits(value: &str) -> bool {
+    value.chars().any(|c| c.is_ascii_alphabetic()) && value.chars().any(|c| c.is_ascii_digit())
+}
+
+fn looks_random(value: &str) -> bool {
+    (MIN_RANDOM_LEN..=MAX_RANDOM_LEN).contains(&value.len())
+        && has_letters_and_digits(value)
+        && !ID_PREFIXES.iter().any(|p| value.starts_with(p))
+        && shannon_entropy(value) >= MIN_RANDOM_ENTROPY
+}
+
+fn looks_assigned_secret(value: &str) -> bool {
+    value.len() >= MIN_ASSIGNED_LEN && has_letters_and_digits(value) && shannon_entropy(value) >= MIN_ASSIGNED_ENTROPY
+}
+
+fn overlaps(taken: &[Range<usize>], range: &Range<usize>) -> bool {
+    taken.iter().any(|t| t.start < range.end && range.start < t.end)
+}
+
+/// Known token formats (the log sanitizer's patterns) and high-entropy strings, line by line
+pub fn scan_text(content: &str) -> Vec<TextMatch> {
+    let mut found = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let mut taken: Vec<Range<usize>> = Vec::new();
+        let push = |found: &mut Vec<TextMatch>, value: &str, kind, suggested_name, movable| {
+            if !found.iter().any(|m: &TextMatch| m.line == index + 1 && m.value == value) {
+                found.push(TextMatch { line: index + 1, kind, value: value.to_string(), suggested_name, movable });
+            }
+        };
+
+        for pattern in log_sanitizer::token_patterns() {
+            for captures in pattern.regex.captures_iter(line) {
+                let Some(secret) = captures.get(pattern.secret_group) else {
+                    continue;
+                };
+                let range = secret.range();
+                if overlaps(&taken, &range) || (pattern.generic && !looks_assigned_secret(secret.as_str())) {
+                    continue;
+                }
+                taken.push(range);
+                // Only the header line of a private key matches; the block can't be moved line by line
+                let movable = !pattern.suggested_name.ends_with("PRIVATE_KEY");
+                push(&mut found, secret.as_str(), pattern.name, pattern.suggested_name, movable);
+            }
+        }
+
+        for token in RANDOM_TOKEN.find_iter(line) {
+            let value = token.as_str().trim_end_matches('=');
+            if overlaps(&taken, &token.range()) || !looks_random(value) {
+                continue;
+            }
+            taken.push(token.range());
+            push(&mut found, value, "High-entropy string", "LEAKED_SECRET", true);
+        }
+    }
+    found
+}
+
+/// Replace `value` on 1-based `line` of `content`, keeping line endings; None if it isn't there
+pub fn replace_on_line(content: &str, line: usize, value: &str, replacement: &str) -> Option<String> {
+    let mut out = String::with_capacity(content.len());
+    let mut replaced = false;
+    for (index, text) in content.split_inclusive('\n').enumerate() {
+        if index + 1 == line && text.contains(value) {
+            out.push_str(&text.replace(value, replacement));
+            replaced = true;
+        } else {
+            out.push_str(text);
+        }
+    }
+    replaced.then_some(out)
+}
+
+/// Whether a file is worth reading: a text format, and not a place secrets are meant to be
+pub fn is_scannable(path: &Path, excluded: &[String]) -> bool {
+    let text = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| TEXT_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+    text && !excluded.iter().any(|e| Path::new(e) == path)
+}
+
+/// Name to suggest for a finding: the pattern's name, made unique against the vault
+pub fn unique_name(suggested: &str, taken: &secrets_vault::Secrets) -> String {
+    let mut name = suggested.to_string();
+    let mut n = 2;
+    while taken.contains_key(&name) {
+        name = format!("{}_{}", suggested, n);
+        n += 1;
+    }
+    name
+}
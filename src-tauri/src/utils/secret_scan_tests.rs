@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use super::super::secret_scan::{mask, replace_on_line, scan_text, shannon_entropy};
+
+    #[test]
+    fn test_scan_finds_known_formats_and_random_strings() {
+        let content = "# Notes\n\
+            Use key sk-ant-REDACTED for Claude\n\
+            bot: 1234567890:AAHfiqksKZ8WmR2zSjiQ7_v4TMAKdiHm9T0\n\
+            token Zk9Qx2LmV7rT4wYb8NcH3sJdE6uAq1Gp here\n\
+            commit 3f786850e387550fdab836ed7e6dc881de23001b, id 123e4567-e89b-12d3-a456-426614174000\n\
+            {\"id\": \"msg_01XFDUDYJgAACzvnptvVoYEL\", \"password\": \"hunter2\"}\n\
+            apiKey: ${secret:OPENAI_API_KEY}\n";
+        let found = scan_text(content);
+        let summary: Vec<(usize, &str)> = found.iter().map(|m| (m.line, m.kind)).collect();
+        assert_eq!(
+            summary,
+            vec![(2, "Anthropic API key"), (3, "Telegram bot token"), (4, "High-entropy string")]
+        );
+        assert_eq!(found[0].value, "sk-ant-REDACTED");
+        assert_eq!(found[0].suggested_name, "ANTHROPIC_API_KEY");
+        assert_eq!(found[2].value, "Zk9Qx2LmV7rT4wYb8NcH3sJdE6uAq1Gp");
+    }
+
+    #[test]
+    fn test_entropy_and_mask() {
+        assert_eq!(shannon_entropy("aaaa"), 0.0);
+        assert_eq!(shannon_entropy("abcd"), 2.0);
+        assert_eq!(mask("sk-ant-REDACTED"), "sk-a****wx");
+        assert_eq!(mask("short"), "****");
+    }
+
+    #[test]
+    fn test_replace_on_line_keeps_other_lines() {
+        let content = "a=SECRET\r\nb=SECRET\nc\n";
+        assert_eq!(
+            replace_on_line(content, 2, "SECRET", "${B_KEY}").unwrap(),
+            "a=SECRET\r\nb=${B_KEY}\nc\n"
+        );
+        assert!(replace_on_line(content, 3, "SECRET", "${B_KEY}").is_none());
+    }
+}
@@ -0,0 +1,390 @@
+use crate::utils::config_lock::escape_pointer_token;
+use crate::utils::secret_refs;
+use crate::utils::{file, platform};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::Mutex;
+
+/// Format version of secrets.vault
+pub const VAULT_VERSION: u32 = 1;
+
+/// Length of the symmetric key and of the key file contents
+pub const KEY_LEN: usize = 32;
+
+const SALT_LEN: usize = 16;
+
+/// Bound into every ciphertext so a vault cannot be swapped for another format
+const AAD: &[u8] = b"openclaw-manager-vault-v1";
+
+/// Secret name -> value; names double as the env vars the gateway receives
+pub type Secrets = BTreeMap<String, String>;
+
+/// Where the vault key comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// Derived from a passphrase with Argon2id; the vault must be unlocked each session
+    Passphrase,
+    /// Random key in secrets.key next to the vault (owner-only permissions); unlocks automatically
+    KeyFile,
+}
+
+/// Argon2id cost parameters, stored with the vault so they can be raised later
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            memory_kib: 19_456,
+            iterations: 2,
+            parallelism: 1,
+        }
+    }
+}
+
+/// On-disk contents of secrets.vault: XChaCha20-Poly1305 over the JSON-encoded secrets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultFile {
+    pub version: u32,
+    pub key_source: KeySource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<KdfParams>,
+    /// Base64; empty for key-file vaults
+    #[serde(default)]
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+/// Fill `N` bytes from the OS CSPRNG
+pub fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0u8; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Argon2id key from `passphrase`
+pub fn derive_key(passphrase: &str, salt: &[u8], params: KdfParams) -> Result<[u8; KEY_LEN], String> {
+    let params = Params::new(params.memory_kib, params.iterations, params.parallelism, Some(KEY_LEN))
+        .map_err(|e| format!("Invalid key derivation parameters: {}", e))?;
+    let mut key = [0u8; KEY_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+/// Encrypt `secrets` under `key` with a fresh nonce; `template` carries the key source, KDF and salt
+pub fn seal(secrets: &Secrets, key: &[u8; KEY_LEN], template: &VaultFile) -> Result<VaultFile, String> {
+    let plaintext = serde_json::to_vec(secrets).map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(&nonce, Payload { msg: &plaintext, aad: AAD })
+        .map_err(|_| "Encryption failed".to_string())?;
+    Ok(VaultFile {
+        version: VAULT_VERSION,
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+        ..template.clone()
+    })
+}
+
+/// Decrypt a vault; a wrong key and a tampered file look the same
+pub fn open(vault: &VaultFile, key: &[u8; KEY_LEN]) -> Result<Secrets, String> {
+    if vault.version != VAULT_VERSION {
+        return Err(format!("Unsupported vault version {}", vault.version));
+    }
+    let nonce = STANDARD.decode(&vault.nonce).map_err(|_| "Vault nonce is not valid base64".to_string())?;
+    if nonce.len() != 24 {
+        return Err("Vault nonce has the wrong length".to_string());
+    }
+    let ciphertext = STANDARD.decode(&vault.ciphertext).map_err(|_| "Vault data is not valid base64".to_string())?;
+    let plaintext = XChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(XNonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: AAD })
+        .map_err(|_| "Wrong passphrase or corrupted vault".to_string())?;
+    serde_json::from_slice(&plaintext).map_err(|e| format!("Vault contents are malformed: {}", e))
+}
+
+/// Key of a vault: derived from the passphrase, or read from the key file
+pub fn vault_key(vault: &VaultFile, passphrase: Option<&str>, key_file: &str) -> Result<[u8; KEY_LEN], String> {
+    match vault.key_source {
+        KeySource::Passphrase => {
+            let passphrase = passphrase.ok_or("This vault needs its passphrase")?;
+            let salt = STANDARD.decode(&vault.salt).map_err(|_| "Vault salt is not valid base64".to_string())?;
+            derive_key(passphrase, &salt, vault.kdf.unwrap_or_default())
+        }
+        KeySource::KeyFile => {
+            let bytes = std::fs::read(key_file).map_err(|e| format!("Failed to read key file {}: {}", key_file, e))?;
+            bytes.try_into().map_err(|_| format!("Key file {} is corrupted", key_file))
+        }
+    }
+}
+
+/// Secret names are env var names openclaw can substitute: `[A-Z_][A-Z0-9_]*`
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_uppercase() || c == '_')
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && name.len() <= 128
+}
+
+/// Suggested secret name for free text: `my-bot` -> `MY_BOT`
+pub fn to_secret_name(text: &str) -> String {
+    let mut name: String = text
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Config value referencing a secret; openclaw substitutes `${NAME}` from its environment
+pub fn reference(name: &str) -> String {
+    format!("${{{}}}", name)
+}
+
+/// Plaintext secret found in a config file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaintextSecret {
+    /// JSON pointer of the value
+    pub pointer: String,
+    /// Suggested secret name
+    pub name: String,
+}
+
+fn push_if_plaintext(found: &mut Vec<PlaintextSecret>, config: &Value, pointer: String, name: String) {
+    let plaintext = config
+        .pointer(&pointer)
+        .and_then(|v| v.as_str())
+        .is_some_and(|v| !v.is_empty() && !secret_refs::contains_reference(v));
+    if plaintext {
+        found.push(PlaintextSecret { pointer, name });
+    }
+}
+
+/// Provider keys, Telegram bot tokens, the Brave key and the gateway token stored in plaintext.
+/// Handles openclaw.json and per-agent models.json (providers at the top level).
+pub fn find_plaintext_secrets(config: &Value) -> Vec<PlaintextSecret> {
+    let mut found = Vec::new();
+
+    for prefix in ["/models/providers", "/providers"] {
+        if let Some(providers) = config.pointer(prefix).and_then(|v| v.as_object()) {
+            for provider in providers.keys() {
+                push_if_plaintext(
+                    &mut found,
+                    config,
+                    format!("{}/{}/apiKey", prefix, escape_pointer_token(provider)),
+                    format!("{}_API_KEY", to_secret_name(provider)),
+                );
+            }
+        }
+    }
+
+    push_if_plaintext(&mut found, config, "/channels/telegram/botToken".to_string(), "TELEGRAM_BOT_TOKEN".to_string());
+    if let Some(accounts) = config.pointer("/channels/telegram/accounts").and_then(|v| v.as_object()) {
+        for account in accounts.keys() {
+            push_if_plaintext(
+                &mut found,
+                config,
+                format!("/channels/telegram/accounts/{}/botToken", escape_pointer_token(account)),
+                format!("TELEGRAM_{}_BOT_TOKEN", to_secret_name(account)),
+            );
+        }
+    }
+
+    push_if_plaintext(&mut found, config, "/web/braveApiKey".to_string(), "BRAVE_API_KEY".to_string());
+    push_if_plaintext(&mut found, config, "/gateway/auth/token".to_string(), "OPENCLAW_GATEWAY_TOKEN".to_string());
+    found
+}
+
+/// Move every plaintext secret of `config` into `secrets`, leaving `${NAME}` references behind.
+/// A name already holding a different value gets a numeric suffix. Returns the names used.
+pub fn move_plaintext_secrets(config: &mut Value, secrets: &mut Secrets) -> Vec<String> {
+    let mut moved = Vec::new();
+    for found in find_plaintext_secrets(config) {
+        let Some(slot) = config.pointer_mut(&found.pointer) else {
+            continue;
+        };
+        let value = slot.as_str().unwrap_or_default().to_string();
+
+        let mut name = found.name.clone();
+        let mut suffix = 2;
+        while secrets.get(&name).is_some_and(|existing| *existing != value) {
+            name = format!("{}_{}", found.name, suffix);
+            suffix += 1;
+        }
+
+        *slot = Value::String(reference(&name));
+        secrets.insert(name.clone(), value);
+        moved.push(name);
+    }
+    moved
+}
+
+/// Read secrets.vault; None when no vault was created
+pub fn load_vault(path: &str) -> io::Result<Option<VaultFile>> {
+    if !file::file_exists(path) {
+        return Ok(None);
+    }
+    let content = file::read_file(path)?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Write secrets.vault
+pub fn save_vault(path: &str, vault: &VaultFile) -> io::Result<()> {
+    let content = serde_json::to_string_pretty(vault).map_err(io::Error::other)?;
+    file::write_private_file(path, content.as_bytes())
+}
+
+/// Unlocked vault of the active profile
+struct Session {
+    path: String,
+    key: [u8; KEY_LEN],
+    template: VaultFile,
+    secrets: Secrets,
+}
+
+// Held while the vault is unlocked; dropped on lock or when the profile's vault changes
+static SESSION: Lazy<Mutex<Option<Session>>> = Lazy::new(|| Mutex::new(None));
+
+/// Lifecycle state of the active profile's vault
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultStatus {
+    pub exists: bool,
+    pub key_source: Option<KeySource>,
+    pub unlocked: bool,
+    /// Secret names; values never leave the backend
+    pub names: Vec<String>,
+}
+
+/// Status of the active profile's vault; key-file vaults are unlocked on the way
+pub fn status() -> Result<VaultStatus, String> {
+    let path = platform::get_secrets_vault_file_path();
+    let Some(vault) = load_vault(&path).map_err(|e| format!("Failed to read vault: {}", e))? else {
+        return Ok(VaultStatus { exists: false, key_source: None, unlocked: false, names: Vec::new() });
+    };
+    let secrets = session_secrets()?;
+    Ok(VaultStatus {
+        exists: true,
+        key_source: Some(vault.key_source),
+        unlocked: secrets.is_some(),
+        names: secrets.map(|s| s.into_keys().collect()).unwrap_or_default(),
+    })
+}
+
+/// Create a vault for the active profile: passphrase-derived key, or a new key file when None
+pub fn create(passphrase: Option<&str>, secrets: Secrets) -> Result<(), String> {
+    let path = platform::get_secrets_vault_file_path();
+    if file::file_exists(&path) {
+        return Err("A secrets vault already exists for this profile".to_string());
+    }
+    let (key, template) = match passphrase {
+        Some(passphrase) => {
+            let salt = random_bytes::<SALT_LEN>();
+            let kdf = KdfParams::default();
+            let template = VaultFile {
+                version: VAULT_VERSION,
+                key_source: KeySource::Passphrase,
+                kdf: Some(kdf),
+                salt: STANDARD.encode(salt),
+                nonce: String::new(),
+                ciphertext: String::new(),
+            };
+            (derive_key(passphrase, &salt, kdf)?, template)
+        }
+        None => {
+            let key = random_bytes::<KEY_LEN>();
+            file::write_private_file(&platform::get_secrets_key_file_path(), &key)
+                .map_err(|e| format!("Failed to write key file: {}", e))?;
+            let template = VaultFile {
+                version: VAULT_VERSION,
+                key_source: KeySource::KeyFile,
+                kdf: None,
+                salt: String::new(),
+                nonce: String::new(),
+                ciphertext: String::new(),
+            };
+            (key, template)
+        }
+    };
+    let vault = seal(&secrets, &key, &template)?;
+    save_vault(&path, &vault).map_err(|e| format!("Failed to write vault: {}", e))?;
+    *SESSION.lock().unwrap_or_else(|e| e.into_inner()) = Some(Session { path, key, template, secrets });
+    Ok(())
+}
+
+/// Unlock the active profile's vault
+pub fn unlock(passphrase: Option<&str>) -> Result<Secrets, String> {
+    let path = platform::get_secrets_vault_file_path();
+    let vault = load_vault(&path)
+        .map_err(|e| format!("Failed to read vault: {}", e))?
+        .ok_or("No secrets vault exists for this profile")?;
+    let key = vault_key(&vault, passphrase, &platform::get_secrets_key_file_path())?;
+    let secrets = open(&vault, &key)?;
+    *SESSION.lock().unwrap_or_else(|e| e.into_inner()) = Some(Session {
+        path,
+        key,
+        template: vault,
+        secrets: secrets.clone(),
+    });
+    Ok(secrets)
+}
+
+/// Forget the key; passphrase vaults need unlocking again
+pub fn lock() {
+    *SESSION.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Secrets of the active profile's vault. None when there is no vault or it is
+/// locked behind a passphrase; key-file vaults are unlocked automatically.
+pub fn session_secrets() -> Result<Option<Secrets>, String> {
+    let path = platform::get_secrets_vault_file_path();
+    {
+        let session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(session) = session.as_ref().filter(|s| s.path == path) {
+            return Ok(Some(session.secrets.clone()));
+        }
+    }
+    match load_vault(&path).map_err(|e| format!("Failed to read vault: {}", e))? {
+        Some(vault) if vault.key_source == KeySource::KeyFile => unlock(None).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Change the unlocked vault's secrets and write it back
+pub fn update(change: impl FnOnce(&mut Secrets)) -> Result<Secrets, String> {
+    let path = platform::get_secrets_vault_file_path();
+    if !file::file_exists(&path) {
+        return Err("No secrets vault exists for this profile".to_string());
+    }
+    // Key-file vaults may not have a session yet
+    session_secrets()?;
+    let mut session = SESSION.lock().unwrap_or_else(|e| e.into_inner());
+    let session = session
+        .as_mut()
+        .filter(|s| s.path == path)
+        .ok_or("The secrets vault is locked")?;
+    let mut secrets = session.secrets.clone();
+    change(&mut secrets);
+    let vault = seal(&secrets, &session.key, &session.template)?;
+    save_vault(&path, &vault).map_err(|e| format!("Failed to write vault: {}", e))?;
+    session.secrets = secrets.clone();
+    Ok(secrets)
+}
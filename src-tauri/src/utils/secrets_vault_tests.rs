@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use super::super::secrets_vault::{
+        derive_key, is_valid_name, move_plaintext_secrets, open, seal, KdfParams, KeySource, Secrets,
+        VaultFile, VAULT_VERSION,
+    };
+    use super::super::secret_refs::contains_reference;
+    use serde_json::json;
+
+    // Argon2's minimum cost, so the test doesn't spend a second per derivation
+    const FAST_KDF: KdfParams = KdfParams { memory_kib: 8, iterations: 1, parallelism: 1 };
+
+    fn template() -> VaultFile {
+        VaultFile {
+            version: VAULT_VERSION,
+            key_source: KeySource::Passphrase,
+            kdf: Some(FAST_KDF),
+            salt: "c2FsdHNhbHRzYWx0c2FsdA==".to_string(),
+            nonce: String::new(),
+            ciphertext: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_seal_open_roundtrip_and_wrong_key() {
+        let key = derive_key("correct horse", b"saltsaltsaltsalt", FAST_KDF).unwrap();
+        assert_eq!(key, derive_key("correct horse", b"saltsaltsaltsalt", FAST_KDF).unwrap());
+        let wrong = derive_key("battery staple", b"saltsaltsaltsalt", FAST_KDF).unwrap();
+        assert_ne!(key, wrong);
+
+        let secrets = Secrets::from([("OPENAI_API_KEY".to_string(), "sk-test".to_string())]);
+        let vault = seal(&secrets, &key, &template()).unwrap();
+        assert!(!vault.ciphertext.contains("sk-test"));
+        assert_eq!(open(&vault, &key).unwrap(), secrets);
+        assert_eq!(open(&vault, &wrong).unwrap_err(), "Wrong passphrase or corrupted vault");
+
+        // Fresh nonce on every seal
+        assert_ne!(seal(&secrets, &key, &template()).unwrap().nonce, vault.nonce);
+
+        let mut tampered = vault.clone();
+        tampered.ciphertext = seal(&Secrets::new(), &wrong, &template()).unwrap().ciphertext;
+        assert!(open(&tampered, &key).is_err());
+    }
+
+    #[test]
+    fn test_move_plaintext_secrets_leaves_references() {
+        let mut config = json!({
+            "models": {"providers": {
+                "anthropic": {"apiKey": "sk-ant"},
+                "my-proxy": {"apiKey": "${MY_PROXY_API_KEY}"},
+                "local": {"apiKey": ""}
+            }},
+            "channels": {"telegram": {"accounts": {"bot2": {"botToken": "123:abc"}}}},
+            "web": {"braveApiKey": "brave"},
+            "gateway": {"auth": {"token": "tok"}}
+        });
+        let mut secrets = Secrets::from([("ANTHROPIC_API_KEY".to_string(), "sk-other".to_string())]);
+
+        let moved = move_plaintext_secrets(&mut config, &mut secrets);
+        assert_eq!(
+            moved,
+            vec!["ANTHROPIC_API_KEY_2", "TELEGRAM_BOT2_BOT_TOKEN", "BRAVE_API_KEY", "OPENCLAW_GATEWAY_TOKEN"]
+        );
+        assert_eq!(config["models"]["providers"]["anthropic"]["apiKey"], "${ANTHROPIC_API_KEY_2}");
+        assert_eq!(config["models"]["providers"]["my-proxy"]["apiKey"], "${MY_PROXY_API_KEY}");
+        assert_eq!(config["models"]["providers"]["local"]["apiKey"], "");
+        assert_eq!(config["channels"]["telegram"]["accounts"]["bot2"]["botToken"], "${TELEGRAM_BOT2_BOT_TOKEN}");
+        assert_eq!(secrets["ANTHROPIC_API_KEY"], "sk-other");
+        assert_eq!(secrets["ANTHROPIC_API_KEY_2"], "sk-ant");
+        assert_eq!(secrets["OPENCLAW_GATEWAY_TOKEN"], "tok");
+
+        // Per-agent models.json keeps providers at the top level
+        let mut models = json!({"providers": {"glm": {"apiKey": "glm-key"}}});
+        assert_eq!(move_plaintext_secrets(&mut models, &mut secrets), vec!["GLM_API_KEY"]);
+        assert!(move_plaintext_secrets(&mut config, &mut secrets).is_empty());
+    }
+
+    #[test]
+    fn test_secret_names() {
+        assert!(is_valid_name("OPENAI_API_KEY"));
+        assert!(is_valid_name("_X1"));
+        assert!(!is_valid_name("openai"));
+        assert!(!is_valid_name("1KEY"));
+        assert!(!is_valid_name(""));
+        assert!(contains_reference("${BRAVE_API_KEY}"));
+        assert!(contains_reference("${secret:BRAVE_API_KEY}"));
+        assert!(!contains_reference("${lower}"));
+    }
+}
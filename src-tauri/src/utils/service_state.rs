@@ -0,0 +1,145 @@
+use serde::{Deserialize, Serialize};
+
+/// User-requested service operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceOperation {
+    Start,
+    Stop,
+    Restart,
+}
+
+/// Step of a service operation, reported through progress events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServicePhase {
+    /// Accepted, nothing done yet
+    Queued,
+    /// Stopping the running gateway (stop and restart)
+    Stopping,
+    /// Launching the gateway process
+    Spawning,
+    /// Gateway spawned, waiting for the port to bind
+    WaitingForPort,
+    /// Port bound, waiting for the health endpoint to report ready
+    WaitingForHealth,
+    /// Gateway is up and ready (start and restart finished)
+    Healthy,
+    /// Gateway is down (stop finished)
+    Stopped,
+    Failed,
+    Cancelled,
+}
+
+impl ServicePhase {
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            ServicePhase::Healthy | ServicePhase::Stopped | ServicePhase::Failed | ServicePhase::Cancelled
+        )
+    }
+
+    /// Order of the non-terminal steps; phases never move backwards
+    fn rank(self) -> u8 {
+        match self {
+            ServicePhase::Queued => 0,
+            ServicePhase::Stopping => 1,
+            ServicePhase::Spawning => 2,
+            ServicePhase::WaitingForPort => 3,
+            ServicePhase::WaitingForHealth => 4,
+            _ => 5,
+        }
+    }
+}
+
+/// Payload of the service progress event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceProgress {
+    pub operation_id: u64,
+    pub operation: ServiceOperation,
+    pub phase: ServicePhase,
+    pub message: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+/// The operation currently owning the service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActiveOperation {
+    pub id: u64,
+    pub operation: ServiceOperation,
+    pub phase: ServicePhase,
+    pub cancel_requested: bool,
+}
+
+/// Why a transition was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionError {
+    /// The operation is not the active one (already finished or superseded)
+    NotActive,
+    /// Cancellation was requested; the operation must wind down
+    Cancelled,
+    /// Phases only move forward
+    Backwards,
+}
+
+/// Single state machine all start/stop/restart requests go through.
+/// Callers serialize operations (one `begin` .. `finish` at a time); the machine
+/// tracks the active one, validates its phase changes and carries cancel requests.
+#[derive(Debug, Clone, Default)]
+pub struct ServiceMachine {
+    next_id: u64,
+    active: Option<ActiveOperation>,
+}
+
+impl ServiceMachine {
+    /// Make `operation` the active one, superseding whatever was left behind
+    pub fn begin(&mut self, operation: ServiceOperation) -> u64 {
+        self.next_id += 1;
+        self.active = Some(ActiveOperation {
+            id: self.next_id,
+            operation,
+            phase: ServicePhase::Queued,
+            cancel_requested: false,
+        });
+        self.next_id
+    }
+
+    pub fn active(&self) -> Option<ActiveOperation> {
+        self.active
+    }
+
+    /// Move the active operation `id` to `phase`; terminal phases go through `finish`
+    pub fn advance(&mut self, id: u64, phase: ServicePhase) -> Result<(), TransitionError> {
+        let active = self.active.as_mut().filter(|a| a.id == id).ok_or(TransitionError::NotActive)?;
+        if active.cancel_requested {
+            return Err(TransitionError::Cancelled);
+        }
+        if phase.is_terminal() || phase.rank() < active.phase.rank() {
+            return Err(TransitionError::Backwards);
+        }
+        active.phase = phase;
+        Ok(())
+    }
+
+    /// Whether operation `id` should stop what it is doing
+    pub fn is_cancelled(&self, id: u64) -> bool {
+        !self.active.is_some_and(|a| a.id == id && !a.cancel_requested)
+    }
+
+    /// Ask the active operation to cancel; `only` limits which kinds may be cancelled
+    pub fn request_cancel(&mut self, only: &[ServiceOperation]) -> Option<ActiveOperation> {
+        let active = self.active.as_mut().filter(|a| only.contains(&a.operation))?;
+        active.cancel_requested = true;
+        Some(*active)
+    }
+
+    /// End operation `id`; returns false when it was no longer the active one
+    pub fn finish(&mut self, id: u64) -> bool {
+        if self.active.is_some_and(|a| a.id == id) {
+            self.active = None;
+            true
+        } else {
+            false
+        }
+    }
+}
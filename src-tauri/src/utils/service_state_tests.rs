@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use super::super::service_state::{ServiceMachine, ServiceOperation, ServicePhase, TransitionError};
+
+    #[test]
+    fn test_phases_only_move_forward() {
+        let mut machine = ServiceMachine::default();
+        let id = machine.begin(ServiceOperation::Restart);
+        assert_eq!(machine.active().unwrap().phase, ServicePhase::Queued);
+
+        assert_eq!(machine.advance(id, ServicePhase::Stopping), Ok(()));
+        assert_eq!(machine.advance(id, ServicePhase::Spawning), Ok(()));
+        assert_eq!(machine.advance(id, ServicePhase::WaitingForPort), Ok(()));
+        assert_eq!(machine.advance(id, ServicePhase::Stopping), Err(TransitionError::Backwards));
+        assert_eq!(machine.advance(id, ServicePhase::Healthy), Err(TransitionError::Backwards));
+        assert_eq!(machine.advance(id, ServicePhase::WaitingForHealth), Ok(()));
+
+        assert!(machine.finish(id));
+        assert!(machine.active().is_none());
+        assert_eq!(machine.advance(id, ServicePhase::WaitingForHealth), Err(TransitionError::NotActive));
+    }
+
+    #[test]
+    fn test_cancel_only_matching_operations() {
+        let mut machine = ServiceMachine::default();
+        let stop = machine.begin(ServiceOperation::Stop);
+        assert!(machine.request_cancel(&[ServiceOperation::Start, ServiceOperation::Restart]).is_none());
+        assert!(!machine.is_cancelled(stop));
+        assert!(machine.finish(stop));
+
+        let start = machine.begin(ServiceOperation::Start);
+        machine.advance(start, ServicePhase::Spawning).unwrap();
+        let cancelled = machine.request_cancel(&[ServiceOperation::Start, ServiceOperation::Restart]).unwrap();
+        assert_eq!(cancelled.id, start);
+        assert!(machine.is_cancelled(start));
+        assert_eq!(machine.advance(start, ServicePhase::WaitingForPort), Err(TransitionError::Cancelled));
+    }
+
+    #[test]
+    fn test_superseded_operation_is_cancelled() {
+        let mut machine = ServiceMachine::default();
+        let first = machine.begin(ServiceOperation::Start);
+        let second = machine.begin(ServiceOperation::Stop);
+        assert_ne!(first, second);
+        assert!(machine.is_cancelled(first));
+        assert!(!machine.finish(first));
+        assert_eq!(machine.active().unwrap().id, second);
+    }
+}
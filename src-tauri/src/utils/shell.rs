@@ -1,13 +1,23 @@
-use std::process::{Command, Output, Stdio};
+use std::process::{Child, Command, Output, Stdio};
 use std::io;
 use std::collections::HashMap;
+use crate::models::AppError;
 use crate::utils::platform;
 use crate::utils::file;
+use crate::utils::config_lock::ConfigLock;
+use crate::utils::config_watcher;
+use crate::utils::gateway_logs;
+use crate::utils::gateway_token;
+use crate::utils::secret_refs::{self, LaunchPlan, Resolver, Unresolved};
+use crate::utils::secrets_vault;
 use log::{info, debug, warn};
 
 #[cfg(windows)]
 use std::os::windows::process::CommandExt;
 
+#[cfg(unix)]
+use std::os::unix::process::CommandExt as UnixCommandExt;
+
 /// Windows CREATE_NO_WINDOW flag, used to hide console window
 #[cfg(windows)]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
@@ -65,10 +75,53 @@ pub fn get_extended_path() -> String {
     paths.join(":")
 }
 
+/// Point a spawned process at the active profile: openclaw reads its config
+/// dir, config file and gateway port from these variables
+fn apply_profile_env(cmd: &mut Command) {
+    cmd.env("OPENCLAW_STATE_DIR", platform::get_config_dir());
+    cmd.env("OPENCLAW_CONFIG_PATH", platform::get_config_file_path());
+    cmd.env("OPENCLAW_GATEWAY_PORT", get_gateway_port().to_string());
+}
+
+/// Vault secrets for openclaw CLI commands, which load the same `${NAME}` references
+/// as the gateway; a locked vault is left for the command to report
+fn apply_vault_env(cmd: &mut Command) {
+    if let Ok(Some(secrets)) = secrets_vault::session_secrets() {
+        cmd.envs(&secrets);
+    }
+}
+
+/// Port the gateway listens on: `gateway.port` in openclaw.json, then the
+/// active profile's port, then 18789. Every start/stop/status check uses this.
+pub fn get_gateway_port() -> u16 {
+    let configured = file::read_file(&platform::get_config_file_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{FEFF}')).ok())
+        .and_then(|config| config.pointer("/gateway/port").and_then(|v| v.as_u64()))
+        .and_then(|port| u16::try_from(port).ok())
+        .filter(|port| *port != 0);
+
+    configured
+        .or_else(platform::get_profile_gateway_port)
+        .unwrap_or(platform::DEFAULT_GATEWAY_PORT)
+}
+
+/// `gateway.auth.token` from openclaw.json without creating one (used by the health probe).
+/// References are resolved through the env file and the secrets vault.
+pub fn read_gateway_token() -> Option<String> {
+    file::read_file(&platform::get_config_file_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{FEFF}')).ok())
+        .and_then(|config| config.pointer("/gateway/auth/token").and_then(|v| v.as_str()).map(|t| t.to_string()))
+        .and_then(|token| resolve_config_string(&token))
+        .filter(|token| !token.is_empty())
+}
+
 /// Execute shell command (with extended PATH)
 pub fn run_command(cmd: &str, args: &[&str]) -> io::Result<Output> {
     let mut command = Command::new(cmd);
     command.args(args);
+    apply_profile_env(&mut command);
     
     // Use extended PATH on non-Windows systems
     #[cfg(not(windows))]
@@ -84,16 +137,16 @@ pub fn run_command(cmd: &str, args: &[&str]) -> io::Result<Output> {
 }
 
 /// Execute shell command and get output string
-pub fn run_command_output(cmd: &str, args: &[&str]) -> Result<String, String> {
+pub fn run_command_output(cmd: &str, args: &[&str]) -> Result<String, AppError> {
     match run_command(cmd, args) {
         Ok(output) => {
             if output.status.success() {
                 Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
             } else {
-                Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+                Err(AppError::command_failed(String::from_utf8_lossy(&output.stderr).trim()))
             }
         }
-        Err(e) => Err(e.to_string()),
+        Err(e) => Err(AppError::from(e)),
     }
 }
 
@@ -101,6 +154,7 @@ pub fn run_command_output(cmd: &str, args: &[&str]) -> Result<String, String> {
 pub fn run_bash(script: &str) -> io::Result<Output> {
     let mut command = Command::new("bash");
     command.arg("-c").arg(script);
+    apply_profile_env(&mut command);
     
     // Use extended PATH on non-Windows systems
     #[cfg(not(windows))]
@@ -116,7 +170,7 @@ pub fn run_bash(script: &str) -> io::Result<Output> {
 }
 
 /// Execute bash command and get output
-pub fn run_bash_output(script: &str) -> Result<String, String> {
+pub fn run_bash_output(script: &str) -> Result<String, AppError> {
     match run_bash(script) {
         Ok(output) => {
             if output.status.success() {
@@ -124,13 +178,13 @@ pub fn run_bash_output(script: &str) -> Result<String, String> {
             } else {
                 let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
                 if stderr.is_empty() {
-                    Err(format!("Command failed with exit code: {:?}", output.status.code()))
+                    Err(AppError::command_failed(format!("Command failed with exit code: {:?}", output.status.code())))
                 } else {
-                    Err(stderr)
+                    Err(AppError::command_failed(stderr))
                 }
             }
         }
-        Err(e) => Err(e.to_string()),
+        Err(e) => Err(AppError::from(e)),
     }
 }
 
@@ -138,6 +192,7 @@ pub fn run_bash_output(script: &str) -> Result<String, String> {
 pub fn run_cmd(script: &str) -> io::Result<Output> {
     let mut cmd = Command::new("cmd");
     cmd.args(["/c", script]);
+    apply_profile_env(&mut cmd);
     
     #[cfg(windows)]
     cmd.creation_flags(CREATE_NO_WINDOW);
@@ -146,7 +201,7 @@ pub fn run_cmd(script: &str) -> io::Result<Output> {
 }
 
 /// Execute cmd.exe command and get output (Windows)
-pub fn run_cmd_output(script: &str) -> Result<String, String> {
+pub fn run_cmd_output(script: &str) -> Result<String, AppError> {
     match run_cmd(script) {
         Ok(output) => {
             if output.status.success() {
@@ -156,16 +211,16 @@ pub fn run_cmd_output(script: &str) -> Result<String, String> {
                 if stderr.is_empty() {
                     let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
                     if stdout.is_empty() {
-                        Err(format!("Command failed with exit code: {:?}", output.status.code()))
+                        Err(AppError::command_failed(format!("Command failed with exit code: {:?}", output.status.code())))
                     } else {
-                        Err(stdout)
+                        Err(AppError::command_failed(stdout))
                     }
                 } else {
-                    Err(stderr)
+                    Err(AppError::command_failed(stderr))
                 }
             }
         }
-        Err(e) => Err(e.to_string()),
+        Err(e) => Err(AppError::from(e)),
     }
 }
 
@@ -175,6 +230,7 @@ pub fn run_powershell(script: &str) -> io::Result<Output> {
     let mut cmd = Command::new("powershell");
     // Use -ExecutionPolicy Bypass to bypass execution policy restrictions
     cmd.args(["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-Command", script]);
+    apply_profile_env(&mut cmd);
     
     #[cfg(windows)]
     cmd.creation_flags(CREATE_NO_WINDOW);
@@ -183,7 +239,7 @@ pub fn run_powershell(script: &str) -> io::Result<Output> {
 }
 
 /// Execute PowerShell command and get output (Windows)
-pub fn run_powershell_output(script: &str) -> Result<String, String> {
+pub fn run_powershell_output(script: &str) -> Result<String, AppError> {
     match run_powershell(script) {
         Ok(output) => {
             if output.status.success() {
@@ -193,22 +249,22 @@ pub fn run_powershell_output(script: &str) -> Result<String, String> {
                 if stderr.is_empty() {
                     let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
                     if stdout.is_empty() {
-                        Err(format!("Command failed with exit code: {:?}", output.status.code()))
+                        Err(AppError::command_failed(format!("Command failed with exit code: {:?}", output.status.code())))
                     } else {
-                        Err(stdout)
+                        Err(AppError::command_failed(stdout))
                     }
                 } else {
-                    Err(stderr)
+                    Err(AppError::command_failed(stderr))
                 }
             }
         }
-        Err(e) => Err(e.to_string()),
+        Err(e) => Err(AppError::from(e)),
     }
 }
 
 /// Cross-platform script command execution
 /// Uses cmd.exe on Windows (avoid PowerShell execution policy issues)
-pub fn run_script_output(script: &str) -> Result<String, String> {
+pub fn run_script_output(script: &str) -> Result<String, AppError> {
     if platform::is_windows() {
         run_cmd_output(script)
     } else {
@@ -221,16 +277,17 @@ pub fn spawn_background(script: &str) -> io::Result<()> {
     if platform::is_windows() {
         let mut cmd = Command::new("cmd");
         cmd.args(["/c", script]);
+        apply_profile_env(&mut cmd);
         
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
         
         cmd.spawn()?;
     } else {
-        Command::new("bash")
-            .arg("-c")
-            .arg(script)
-            .spawn()?;
+        let mut cmd = Command::new("bash");
+        cmd.arg("-c").arg(script);
+        apply_profile_env(&mut cmd);
+        cmd.spawn()?;
     }
     Ok(())
 }
@@ -350,12 +407,12 @@ fn get_windows_openclaw_paths() -> Vec<String> {
 }
 
 /// Execute openclaw command and get output
-pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
+pub fn run_openclaw(args: &[&str]) -> Result<String, AppError> {
     debug!("[Shell] Executing openclaw command: {:?}", args);
     
     let openclaw_path = get_openclaw_path().ok_or_else(|| {
         warn!("[Shell] Cannot find openclaw command");
-        "Cannot find openclaw command, please ensure it is installed via npm install -g openclaw".to_string()
+        AppError::openclaw_not_installed()
     })?;
     
     debug!("[Shell] openclaw path: {}", openclaw_path);
@@ -371,6 +428,8 @@ pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
         cmd.args(args)
             .env("OPENCLAW_GATEWAY_TOKEN", &gw_token)
             .env("PATH", &extended_path);
+        apply_vault_env(&mut cmd);
+        apply_profile_env(&mut cmd);
         
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
@@ -382,6 +441,8 @@ pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
         cmd.args(args)
             .env("OPENCLAW_GATEWAY_TOKEN", &gw_token)
             .env("PATH", &extended_path);
+        apply_vault_env(&mut cmd);
+        apply_profile_env(&mut cmd);
         
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
@@ -399,12 +460,12 @@ pub fn run_openclaw(args: &[&str]) -> Result<String, String> {
                 Ok(stdout)
             } else {
                 debug!("[Shell] Command execution failed, stderr: {}", stderr);
-                Err(format!("{}\n{}", stdout, stderr).trim().to_string())
+                Err(AppError::command_failed(format!("{}\n{}", stdout, stderr).trim()))
             }
         }
         Err(e) => {
             warn!("[Shell] Failed to execute openclaw: {}", e);
-            Err(format!("Failed to execute openclaw: {}", e))
+            Err(AppError::command_failed("Failed to execute openclaw").with_detail(e))
         }
     }
 }
@@ -418,6 +479,15 @@ pub const DEFAULT_GATEWAY_TOKEN: &str = "openclaw-manager-local-token";
 fn get_gateway_token_from_config() -> String {
     let config_path = platform::get_config_file_path();
 
+    // Held for the whole read/modify/write so a concurrent Manager save isn't lost
+    let _lock = match ConfigLock::acquire(&config_path) {
+        Ok(lock) => Some(lock),
+        Err(e) => {
+            warn!("[Shell] Failed to lock config file, continuing unlocked: {}", e);
+            None
+        }
+    };
+
     // Try to read existing config
     let mut config = if let Ok(content) = file::read_file(&config_path) {
         let content = content.strip_prefix('\u{FEFF}').unwrap_or(&content);
@@ -434,7 +504,7 @@ fn get_gateway_token_from_config() -> String {
         .pointer("/gateway/auth/token")
         .and_then(|v| v.as_str())
         .filter(|t| !t.is_empty())
-        .map(|t| t.to_string());
+        .map(|t| resolve_config_string(t).unwrap_or_else(|| t.to_string()));
 
     if let Some(token) = existing_token {
         // Ensure controlUi.allowInsecureAuth is set (may be missing on older configs)
@@ -449,6 +519,7 @@ fn get_gateway_token_from_config() -> String {
             }
             config["gateway"]["controlUi"]["allowInsecureAuth"] = serde_json::json!(true);
             if let Ok(content) = serde_json::to_string_pretty(&config) {
+                config_watcher::note_own_write(&config_path, content.as_bytes());
                 let _ = file::write_file(&config_path, &content);
             }
         }
@@ -458,18 +529,7 @@ fn get_gateway_token_from_config() -> String {
 
     // No token found — generate one and save it to config
     info!("[Shell] No gateway token found, generating new token...");
-    use std::time::{SystemTime, UNIX_EPOCH};
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_nanos();
-    let random_part: u64 = (timestamp as u64) ^ 0x5DEECE66Du64;
-    let new_token = format!(
-        "{:016x}{:016x}{:016x}",
-        random_part,
-        random_part.wrapping_mul(0x5DEECE66Du64),
-        timestamp as u64
-    );
+    let new_token = gateway_token::generate();
 
     // Ensure gateway.auth path exists in config
     if config.get("gateway").is_none() {
@@ -491,6 +551,7 @@ fn get_gateway_token_from_config() -> String {
 
     // Save config
     if let Ok(content) = serde_json::to_string_pretty(&config) {
+        config_watcher::note_own_write(&config_path, content.as_bytes());
         if let Err(e) = file::write_file(&config_path, &content) {
             warn!("[Shell] Failed to save generated token to config: {}", e);
             return DEFAULT_GATEWAY_TOKEN.to_string();
@@ -503,7 +564,7 @@ fn get_gateway_token_from_config() -> String {
 
 /// Read all environment variables from ~/.openclaw/env file
 /// Consistent with shell script `source ~/.openclaw/env` behavior
-fn load_openclaw_env_vars() -> HashMap<String, String> {
+pub fn load_openclaw_env_vars() -> HashMap<String, String> {
     let mut env_vars = HashMap::new();
     let env_path = platform::get_env_file_path();
     
@@ -530,9 +591,39 @@ fn load_openclaw_env_vars() -> HashMap<String, String> {
     env_vars
 }
 
+/// Value sources for `${env:NAME}` / `${secret:NAME}` / `${NAME}` references:
+/// the env file, the Manager's environment and this session's vault secrets
+pub fn reference_resolver() -> Resolver {
+    let secrets = secrets_vault::session_secrets().ok().flatten();
+    Resolver {
+        vault_locked: secrets.is_none() && file::file_exists(&platform::get_secrets_vault_file_path()),
+        secrets,
+        env_file: load_openclaw_env_vars(),
+    }
+}
+
+/// Resolve the references in a single config value; None if any is missing
+pub fn resolve_config_string(value: &str) -> Option<String> {
+    reference_resolver().resolve_str(value).ok()
+}
+
+/// openclaw.json as JSON; a missing or unparsable file reads as an empty object
+pub fn read_openclaw_config_value() -> serde_json::Value {
+    file::read_file(&platform::get_config_file_path())
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(content.trim_start_matches('\u{FEFF}')).ok())
+        .unwrap_or_else(|| serde_json::json!({}))
+}
+
+/// Resolve every reference in openclaw.json for a gateway start, so a missing
+/// value is reported up front instead of as a gateway that never comes up
+pub fn plan_gateway_launch() -> Result<LaunchPlan, Vec<Unresolved>> {
+    secret_refs::plan_launch(&read_openclaw_config_value(), &reference_resolver())
+}
+
 /// Start openclaw gateway in background
 /// Consistent with shell script behavior: load env file first, then start gateway
-pub fn spawn_openclaw_gateway() -> io::Result<()> {
+pub fn spawn_openclaw_gateway() -> io::Result<Child> {
     info!("[Shell] Starting openclaw gateway in background...");
     
     let openclaw_path = get_openclaw_path().ok_or_else(|| {
@@ -557,17 +648,19 @@ pub fn spawn_openclaw_gateway() -> io::Result<()> {
     let extended_path = get_extended_path();
     info!("[Shell] Extended PATH: {}", extended_path);
     
+    let port = get_gateway_port().to_string();
+
     // On Windows, .cmd files can be executed directly by Command::new
     // Set environment variable OPENCLAW_GATEWAY_TOKEN so all subcommands can use it automatically
     let mut cmd = if platform::is_windows() && openclaw_path.ends_with(".cmd") {
         info!("[Shell] Windows mode: executing .cmd directly");
         let mut c = Command::new(&openclaw_path);
-        c.args(["gateway", "--port", "18789"]);
+        c.args(["gateway", "--port", port.as_str()]);
         c
     } else {
         info!("[Shell] Unix/Direct mode: executing directly");
         let mut c = Command::new(&openclaw_path);
-        c.args(["gateway", "--port", "18789"]);
+        c.args(["gateway", "--port", port.as_str()]);
         c
     };
     
@@ -576,29 +669,89 @@ pub fn spawn_openclaw_gateway() -> io::Result<()> {
         cmd.env(key, value);
     }
     
+    // Secrets vault entries, referenced from the config as ${NAME}
+    match secrets_vault::session_secrets() {
+        Ok(Some(secrets)) => {
+            info!("[Shell] Injecting {} secret(s) from the vault", secrets.len());
+            for (key, value) in &secrets {
+                cmd.env(key, value);
+            }
+        }
+        Ok(None) if file::file_exists(&platform::get_secrets_vault_file_path()) => {
+            warn!("[Shell] Secrets vault is locked");
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "The secrets vault is locked. Unlock it in Settings before starting the gateway",
+            ));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            warn!("[Shell] Failed to open secrets vault: {}", e);
+            return Err(io::Error::other(format!("Failed to open the secrets vault: {}", e)));
+        }
+    }
+
+    // Values of the config's references; openclaw substitutes them as ${NAME}
+    let plan = plan_gateway_launch().map_err(|unresolved| {
+        warn!("[Shell] {} unresolved reference(s) in openclaw.json", unresolved.len());
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unresolved references in openclaw.json:\n{}", secret_refs::format_unresolved(&unresolved)),
+        )
+    })?;
+    cmd.envs(&plan.env);
+
     // Set PATH and gateway token (read from config to avoid mismatch)
     let gateway_token = get_gateway_token_from_config();
     cmd.env("PATH", &extended_path);
     cmd.env("OPENCLAW_GATEWAY_TOKEN", &gateway_token);
+    apply_profile_env(&mut cmd);
+
+    // openclaw doesn't know the env:/secret: prefixes, so it runs with a copy using plain
+    // ${NAME}. The copy is rewritten on every start; edits to openclaw.json apply on restart.
+    if let Some(native) = plan.native_config {
+        let runtime_path = platform::get_runtime_config_file_path();
+        let content = serde_json::to_string_pretty(&native).map_err(io::Error::other)?;
+        file::write_file(&runtime_path, &content)?;
+        info!("[Shell] Config uses env:/secret: references, running with {}", runtime_path);
+        cmd.env("OPENCLAW_CONFIG_PATH", &runtime_path);
+    }
     info!("[Shell] Gateway token: {}...", &gateway_token[..8.min(gateway_token.len())]);
     
     // Windows: hide console window
     #[cfg(windows)]
     cmd.creation_flags(CREATE_NO_WINDOW);
+
+    // Own process group, so Stop can signal the gateway and its children together
+    #[cfg(unix)]
+    cmd.process_group(0);
     
     info!("[Shell] Starting gateway process...");
     
-    // Explicitly set stdio to null to prevent EBADF errors when running in background/supervisor
-    cmd.stdout(Stdio::null());
-    cmd.stderr(Stdio::null());
+    // Output is sanitized into gateway.log, which the Manager tails; stdin stays null
+    // to prevent EBADF errors
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
     cmd.stdin(Stdio::null());
 
     let child = cmd.spawn();
     
     match child {
-        Ok(c) => {
+        Ok(mut c) => {
             info!("[Shell] ✓ Gateway process started, PID: {}", c.id());
-            Ok(())
+            let log_path = platform::get_gateway_log_file_path();
+            let log_path = std::path::Path::new(&log_path);
+            if let Some(stdout) = c.stdout.take() {
+                if let Err(e) = gateway_logs::capture(stdout, gateway_logs::LogStream::Stdout, log_path) {
+                    warn!("[Shell] Failed to capture gateway stdout: {}", e);
+                }
+            }
+            if let Some(stderr) = c.stderr.take() {
+                if let Err(e) = gateway_logs::capture(stderr, gateway_logs::LogStream::Stderr, log_path) {
+                    warn!("[Shell] Failed to capture gateway stderr: {}", e);
+                }
+            }
+            Ok(c)
         }
         Err(e) => {
             warn!("[Shell] ✗ Gateway startup failed: {}", e);
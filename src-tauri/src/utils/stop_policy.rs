@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// manager.json key holding the stop policy
+pub const MANAGER_KEY: &str = "stopPolicy";
+
+/// Upper bound for both waits, so a typo cannot hang Stop for hours
+pub const MAX_WAIT_MS: u64 = 120_000;
+
+/// How the Manager stops its gateway. Steps run in order and each is skipped
+/// when disabled; the first one that leaves the port free ends the sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StopPolicy {
+    /// Ask openclaw to stop the gateway (`openclaw gateway stop`)
+    pub cli_stop: bool,
+    /// How long the CLI stop gets to take effect
+    pub cli_timeout_ms: u64,
+    /// Send SIGTERM to the gateway's process group (Windows: close the process tree)
+    pub terminate: bool,
+    /// Time the gateway gets after SIGTERM to flush sessions and exit
+    pub grace_period_ms: u64,
+    /// SIGKILL whatever is left after the grace period
+    pub force_kill: bool,
+}
+
+impl Default for StopPolicy {
+    fn default() -> Self {
+        Self {
+            cli_stop: true,
+            cli_timeout_ms: 3_000,
+            terminate: true,
+            grace_period_ms: 10_000,
+            force_kill: true,
+        }
+    }
+}
+
+impl StopPolicy {
+    /// Policy from manager.json; missing or malformed settings fall back to the defaults
+    pub fn from_manager_config(manager: &Value) -> Self {
+        manager
+            .get(MANAGER_KEY)
+            .and_then(|v| serde_json::from_value::<StopPolicy>(v.clone()).ok())
+            .unwrap_or_default()
+            .clamped()
+    }
+
+    pub fn clamped(self) -> Self {
+        Self {
+            cli_timeout_ms: self.cli_timeout_ms.min(MAX_WAIT_MS),
+            grace_period_ms: self.grace_period_ms.min(MAX_WAIT_MS),
+            ..self
+        }
+    }
+
+    /// Enabled steps in the order they run
+    pub fn steps(&self) -> Vec<StopStep> {
+        let mut steps = Vec::new();
+        if self.cli_stop {
+            steps.push(StopStep::CliStop);
+        }
+        if self.terminate {
+            steps.push(StopStep::Terminate);
+        }
+        if self.force_kill {
+            steps.push(StopStep::ForceKill);
+        }
+        steps
+    }
+}
+
+/// One escalation step of the stop sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopStep {
+    CliStop,
+    Terminate,
+    ForceKill,
+}
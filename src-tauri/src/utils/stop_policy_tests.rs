@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use super::super::stop_policy::{StopPolicy, StopStep, MAX_WAIT_MS};
+    use serde_json::json;
+
+    #[test]
+    fn test_defaults_when_missing_or_malformed() {
+        assert_eq!(StopPolicy::from_manager_config(&json!({})), StopPolicy::default());
+        assert_eq!(
+            StopPolicy::from_manager_config(&json!({ "stopPolicy": "fast" })),
+            StopPolicy::default()
+        );
+        assert_eq!(
+            StopPolicy::default().steps(),
+            vec![StopStep::CliStop, StopStep::Terminate, StopStep::ForceKill]
+        );
+    }
+
+    #[test]
+    fn test_partial_policy_is_merged_and_clamped() {
+        let policy = StopPolicy::from_manager_config(&json!({
+            "stopPolicy": { "cliStop": false, "gracePeriodMs": 9_999_999 }
+        }));
+        assert!(!policy.cli_stop);
+        assert!(policy.terminate);
+        assert_eq!(policy.cli_timeout_ms, StopPolicy::default().cli_timeout_ms);
+        assert_eq!(policy.grace_period_ms, MAX_WAIT_MS);
+        assert_eq!(policy.steps(), vec![StopStep::Terminate, StopStep::ForceKill]);
+    }
+}
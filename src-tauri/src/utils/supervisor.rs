@@ -0,0 +1,163 @@
+use crate::utils::file;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Number of restart records kept in the history file
+pub const MAX_HISTORY: usize = 200;
+
+/// Restart timing and crash-loop limits for the gateway supervisor
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackoffPolicy {
+    /// Delay before the first restart
+    pub initial_delay: Duration,
+    /// Upper bound for the doubled delay
+    pub max_delay: Duration,
+    /// Crashes within `crash_window` after which the supervisor gives up
+    pub crash_loop_threshold: usize,
+    pub crash_window: Duration,
+    /// A gateway that stayed up this long counts as healthy; backoff starts over
+    pub stable_after: Duration,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(2),
+            max_delay: Duration::from_secs(60),
+            crash_loop_threshold: 5,
+            crash_window: Duration::from_secs(300),
+            stable_after: Duration::from_secs(120),
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Delay before restart number `attempt` (1-based): initial, 2x, 4x, ... capped at max
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// What the supervisor should do about a crash
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashDecision {
+    /// Restart after `delay`
+    Restart { attempt: u32, delay: Duration },
+    /// Too many crashes in the window; stop restarting
+    GiveUp { crashes: usize },
+}
+
+/// Counts crashes and decides between backing off and giving up
+#[derive(Debug, Clone)]
+pub struct CrashTracker {
+    policy: BackoffPolicy,
+    crashes: VecDeque<Instant>,
+    attempt: u32,
+}
+
+impl CrashTracker {
+    pub fn new(policy: BackoffPolicy) -> Self {
+        Self {
+            policy,
+            crashes: VecDeque::new(),
+            attempt: 0,
+        }
+    }
+
+    /// Record a crash at `now` of a gateway that was started at `started_at`
+    pub fn on_crash(&mut self, now: Instant, started_at: Instant) -> CrashDecision {
+        if now.saturating_duration_since(started_at) >= self.policy.stable_after {
+            self.attempt = 0;
+        }
+
+        self.crashes.push_back(now);
+        while let Some(first) = self.crashes.front() {
+            if now.saturating_duration_since(*first) > self.policy.crash_window {
+                self.crashes.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.crashes.len() >= self.policy.crash_loop_threshold {
+            return CrashDecision::GiveUp { crashes: self.crashes.len() };
+        }
+
+        self.attempt += 1;
+        CrashDecision::Restart {
+            attempt: self.attempt,
+            delay: self.policy.delay_for(self.attempt),
+        }
+    }
+}
+
+/// Result of one supervisor intervention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartOutcome {
+    /// Gateway came back up
+    Restarted,
+    /// Gateway process could not be spawned
+    SpawnFailed,
+    /// Gateway was spawned but never started listening
+    StartTimeout,
+    /// Crash loop detected; supervisor stopped
+    GaveUp,
+}
+
+/// One entry of the persisted restart history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestartRecord {
+    /// RFC 3339 time of the crash
+    pub timestamp: String,
+    /// Restart attempt in the current backoff sequence (0 when giving up)
+    pub attempt: u32,
+    /// Exit status of the gateway process, when the Manager started it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_status: Option<String>,
+    /// Seconds the gateway was up before the crash
+    pub uptime_seconds: u64,
+    /// Backoff delay before the restart
+    pub delay_ms: u64,
+    pub outcome: RestartOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Contents of restart-history.json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RestartHistory {
+    /// Oldest first
+    #[serde(default)]
+    pub records: Vec<RestartRecord>,
+    /// Supervisor gave up after a crash loop; cleared by Stop/Start
+    #[serde(default)]
+    pub crash_loop: bool,
+}
+
+impl RestartHistory {
+    /// Append a record, dropping the oldest beyond `MAX_HISTORY`
+    pub fn push(&mut self, record: RestartRecord) {
+        if record.outcome == RestartOutcome::GaveUp {
+            self.crash_loop = true;
+        }
+        self.records.push(record);
+        if self.records.len() > MAX_HISTORY {
+            let excess = self.records.len() - MAX_HISTORY;
+            self.records.drain(..excess);
+        }
+    }
+}
+
+/// Read restart-history.json; a missing file means no restarts yet
+pub fn load(path: &str) -> io::Result<RestartHistory> {
+    file::load_json(path)
+}
+
+/// Write restart-history.json
+pub fn save(path: &str, history: &RestartHistory) -> io::Result<()> {
+    file::save_json(path, history)
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use super::super::supervisor::{
+        load, save, BackoffPolicy, CrashDecision, CrashTracker, RestartHistory, RestartOutcome, RestartRecord,
+        MAX_HISTORY,
+    };
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    fn record(attempt: u32, outcome: RestartOutcome) -> RestartRecord {
+        RestartRecord {
+            timestamp: "2026-01-29T10:00:00+00:00".to_string(),
+            attempt,
+            exit_status: Some("exit status: 1".to_string()),
+            uptime_seconds: 3,
+            delay_ms: 2000,
+            outcome,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = BackoffPolicy::default();
+        assert_eq!(policy.delay_for(1), Duration::from_secs(2));
+        assert_eq!(policy.delay_for(2), Duration::from_secs(4));
+        assert_eq!(policy.delay_for(4), Duration::from_secs(16));
+        assert_eq!(policy.delay_for(6), Duration::from_secs(60));
+        assert_eq!(policy.delay_for(200), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_crash_loop_gives_up() {
+        let mut tracker = CrashTracker::new(BackoffPolicy::default());
+        let start = Instant::now();
+
+        for attempt in 1..=4u32 {
+            let now = start + Duration::from_secs(attempt as u64 * 10);
+            match tracker.on_crash(now, now - Duration::from_secs(1)) {
+                CrashDecision::Restart { attempt: a, .. } => assert_eq!(a, attempt),
+                other => panic!("unexpected decision {:?}", other),
+            }
+        }
+        let now = start + Duration::from_secs(50);
+        assert_eq!(
+            tracker.on_crash(now, now - Duration::from_secs(1)),
+            CrashDecision::GiveUp { crashes: 5 }
+        );
+    }
+
+    #[test]
+    fn test_stable_run_resets_backoff_and_old_crashes_expire() {
+        let mut tracker = CrashTracker::new(BackoffPolicy::default());
+        let start = Instant::now();
+
+        tracker.on_crash(start, start);
+        tracker.on_crash(start + Duration::from_secs(5), start);
+        // Gateway then ran for 10 minutes: backoff starts over, earlier crashes left the window
+        let later = start + Duration::from_secs(605);
+        assert_eq!(
+            tracker.on_crash(later, start + Duration::from_secs(5)),
+            CrashDecision::Restart { attempt: 1, delay: Duration::from_secs(2) }
+        );
+        for i in 1..=3 {
+            let now = later + Duration::from_secs(i);
+            assert!(matches!(tracker.on_crash(now, now), CrashDecision::Restart { .. }));
+        }
+    }
+
+    #[test]
+    fn test_history_is_capped_and_persisted() {
+        let mut history = RestartHistory::default();
+        for i in 0..(MAX_HISTORY as u32 + 5) {
+            history.push(record(i, RestartOutcome::Restarted));
+        }
+        assert_eq!(history.records.len(), MAX_HISTORY);
+        assert_eq!(history.records[0].attempt, 5);
+        assert!(!history.crash_loop);
+
+        history.push(record(0, RestartOutcome::GaveUp));
+        assert!(history.crash_loop);
+
+        let dir = std::env::temp_dir().join(format!("openclaw-supervisor-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("restart-history.json");
+        let path = path.to_str().unwrap();
+
+        assert_eq!(load(path).unwrap(), RestartHistory::default());
+        save(path, &history).unwrap();
+        assert_eq!(load(path).unwrap(), history);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
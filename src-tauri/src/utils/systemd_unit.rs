@@ -0,0 +1,155 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name of the generated `systemd --user` unit
+pub const UNIT_NAME: &str = "openclaw-gateway.service";
+
+/// Everything the gateway unit is rendered from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnitSpec {
+    /// Absolute path of the openclaw executable
+    pub openclaw_path: String,
+    pub port: u16,
+    /// systemd-compatible copy of the env file (see `render_environment_file`)
+    pub environment_file: String,
+    /// Extra `Environment=` entries (PATH, profile variables)
+    pub environment: Vec<(String, String)>,
+    /// `RestartSec=` in seconds
+    pub restart_sec: u64,
+    /// Crash-loop limit: at most `start_limit_burst` starts per `start_limit_interval_sec`
+    pub start_limit_burst: usize,
+    pub start_limit_interval_sec: u64,
+    /// `TimeoutStopSec=`: time between SIGTERM and SIGKILL
+    pub stop_timeout_sec: u64,
+}
+
+/// Quote a word for the unit file. `%` specifiers are always escaped, `$` only in
+/// `ExecStart=` (the only place systemd expands variables); whitespace, quotes and
+/// backslashes need a double-quoted string.
+pub fn quote(word: &str, in_exec: bool) -> String {
+    let mut escaped = word.replace('%', "%%");
+    if in_exec {
+        escaped = escaped.replace('$', "$$");
+    }
+    if !escaped.is_empty() && !escaped.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '\\') {
+        return escaped;
+    }
+    format!("\"{}\"", escaped.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Render the unit file
+pub fn render_unit(spec: &UnitSpec) -> String {
+    let mut unit = String::new();
+    unit.push_str("# Generated by OpenClaw Manager. Changes are overwritten when the service is enabled again.\n");
+    unit.push_str("[Unit]\n");
+    unit.push_str("Description=OpenClaw Gateway\n");
+    unit.push_str("After=network-online.target\n");
+    unit.push_str("Wants=network-online.target\n");
+    unit.push_str(&format!("StartLimitIntervalSec={}\n", spec.start_limit_interval_sec));
+    unit.push_str(&format!("StartLimitBurst={}\n", spec.start_limit_burst));
+    unit.push('\n');
+
+    unit.push_str("[Service]\n");
+    unit.push_str("Type=simple\n");
+    unit.push_str(&format!(
+        "ExecStart={} gateway --port {}\n",
+        quote(&spec.openclaw_path, true),
+        spec.port
+    ));
+    // Leading '-': a missing env file is not an error. The path is taken verbatim (no quoting)
+    unit.push_str(&format!("EnvironmentFile=-{}\n", spec.environment_file.replace('%', "%%")));
+    for (key, value) in &spec.environment {
+        unit.push_str(&format!("Environment={}\n", quote(&format!("{}={}", key, value), false)));
+    }
+    unit.push_str("Restart=on-failure\n");
+    unit.push_str(&format!("RestartSec={}\n", spec.restart_sec));
+    unit.push_str("KillMode=control-group\n");
+    unit.push_str("KillSignal=SIGTERM\n");
+    unit.push_str(&format!("TimeoutStopSec={}\n", spec.stop_timeout_sec));
+    unit.push('\n');
+
+    unit.push_str("[Install]\n");
+    unit.push_str("WantedBy=default.target\n");
+    unit
+}
+
+/// Turn the Manager's env file (`export KEY="value"` lines) into `KEY=value` lines
+/// systemd's `EnvironmentFile=` understands; comments and malformed lines are dropped
+pub fn render_environment_file(content: &str) -> String {
+    let mut out = String::from("# Generated from the OpenClaw env file by OpenClaw Manager\n");
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            continue;
+        }
+        let value = value.trim().trim_matches('"').trim_matches('\'');
+        out.push_str(&format!("{}=\"{}\"\n", key, value.replace('\\', "\\\\").replace('"', "\\\"")));
+    }
+    out
+}
+
+/// `$XDG_CONFIG_HOME/systemd/user` (default `~/.config/systemd/user`)
+pub fn user_unit_dir(xdg_config_home: Option<&Path>, home: Option<&Path>) -> Option<PathBuf> {
+    let config = match xdg_config_home.filter(|p| p.is_absolute()) {
+        Some(dir) => dir.to_path_buf(),
+        None => home?.join(".config"),
+    };
+    Some(config.join("systemd").join("user"))
+}
+
+/// State of the unit as reported by `systemctl --user show`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnitStatus {
+    /// Unit file written by the Manager exists
+    pub installed: bool,
+    /// systemd and a user session bus are reachable
+    pub systemd_available: bool,
+    pub unit_path: Option<String>,
+    /// loaded / not-found / ...
+    pub load_state: Option<String>,
+    /// active / inactive / failed / activating ...
+    pub active_state: Option<String>,
+    pub sub_state: Option<String>,
+    /// enabled / disabled / ...
+    pub unit_file_state: Option<String>,
+    pub main_pid: Option<u32>,
+    /// User lingering (`loginctl enable-linger`): the unit keeps running without a login session
+    pub linger: Option<bool>,
+    pub message: Option<String>,
+}
+
+impl UnitStatus {
+    pub fn is_enabled(&self) -> bool {
+        self.unit_file_state.as_deref() == Some("enabled")
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_state.as_deref() == Some("active")
+    }
+}
+
+/// Fill `status` from `systemctl --user show -p LoadState,ActiveState,SubState,UnitFileState,MainPID`
+pub fn apply_show_output(status: &mut UnitStatus, output: &str) {
+    let value = |v: &str| Some(v.trim().to_string()).filter(|v| !v.is_empty());
+    for line in output.lines() {
+        let Some((key, v)) = line.split_once('=') else {
+            continue;
+        };
+        match key.trim() {
+            "LoadState" => status.load_state = value(v),
+            "ActiveState" => status.active_state = value(v),
+            "SubState" => status.sub_state = value(v),
+            "UnitFileState" => status.unit_file_state = value(v),
+            "MainPID" => status.main_pid = v.trim().parse().ok().filter(|pid| *pid > 0),
+            _ => {}
+        }
+    }
+}
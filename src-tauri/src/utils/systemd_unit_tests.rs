@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use super::super::systemd_unit::{
+        apply_show_output, quote, render_environment_file, render_unit, user_unit_dir, UnitSpec, UnitStatus,
+    };
+    use std::path::{Path, PathBuf};
+
+    fn spec() -> UnitSpec {
+        UnitSpec {
+            openclaw_path: "/home/ana/.nvm/versions/node/v22.12.0/bin/openclaw".to_string(),
+            port: 18790,
+            environment_file: "/home/ana/.openclaw/gateway.env".to_string(),
+            environment: vec![
+                ("PATH".to_string(), "/usr/local/bin:/usr/bin".to_string()),
+                ("OPENCLAW_STATE_DIR".to_string(), "/home/ana/My Claw".to_string()),
+            ],
+            restart_sec: 2,
+            start_limit_burst: 5,
+            start_limit_interval_sec: 300,
+            stop_timeout_sec: 10,
+        }
+    }
+
+    #[test]
+    fn test_render_unit() {
+        let unit = render_unit(&spec());
+        let lines: Vec<&str> = unit.lines().collect();
+        assert!(lines.contains(&"[Unit]"));
+        assert!(lines.contains(&"StartLimitIntervalSec=300"));
+        assert!(lines.contains(&"StartLimitBurst=5"));
+        assert!(lines.contains(&"ExecStart=/home/ana/.nvm/versions/node/v22.12.0/bin/openclaw gateway --port 18790"));
+        assert!(lines.contains(&"EnvironmentFile=-/home/ana/.openclaw/gateway.env"));
+        assert!(lines.contains(&"Environment=PATH=/usr/local/bin:/usr/bin"));
+        assert!(lines.contains(&"Environment=\"OPENCLAW_STATE_DIR=/home/ana/My Claw\""));
+        assert!(lines.contains(&"Restart=on-failure"));
+        assert!(lines.contains(&"RestartSec=2"));
+        assert!(lines.contains(&"TimeoutStopSec=10"));
+        assert_eq!(lines.last(), Some(&"WantedBy=default.target"));
+
+        // Sections come in order
+        let section = |name: &str| lines.iter().position(|l| *l == name).unwrap();
+        assert!(section("[Unit]") < section("[Service]") && section("[Service]") < section("[Install]"));
+    }
+
+    #[test]
+    fn test_quote_escapes_specifiers_and_variables() {
+        assert_eq!(quote("/usr/bin/openclaw", true), "/usr/bin/openclaw");
+        assert_eq!(quote("/opt/my apps/openclaw", true), "\"/opt/my apps/openclaw\"");
+        assert_eq!(quote("/opt/100%/$HOME", true), "/opt/100%%/$$HOME");
+        assert_eq!(quote("KEY=$HOME", false), "KEY=$HOME");
+        assert_eq!(quote("KEY=say \"hi\"", false), "\"KEY=say \\\"hi\\\"\"");
+        assert_eq!(quote("", true), "\"\"");
+    }
+
+    #[test]
+    fn test_render_environment_file() {
+        let content = "# API keys\nexport ANTHROPIC_API_KEY=\"sk-ant-123\"\nOPENAI_API_KEY='sk-456'\n\nexport BAD KEY=1\nnot an assignment\nexport QUOTE=\"a\\\"b\"\n";
+        let rendered = render_environment_file(content);
+        let lines: Vec<&str> = rendered.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(
+            lines,
+            vec!["ANTHROPIC_API_KEY=\"sk-ant-123\"", "OPENAI_API_KEY=\"sk-456\"", "QUOTE=\"a\\\\\\\"b\""]
+        );
+    }
+
+    #[test]
+    fn test_user_unit_dir_and_show_output() {
+        let home = Path::new("/home/ana");
+        assert_eq!(
+            user_unit_dir(None, Some(home)),
+            Some(PathBuf::from("/home/ana/.config/systemd/user"))
+        );
+        assert_eq!(
+            user_unit_dir(Some(Path::new("/xdg")), Some(home)),
+            Some(PathBuf::from("/xdg/systemd/user"))
+        );
+        // Relative XDG_CONFIG_HOME is invalid per the spec and ignored
+        assert_eq!(
+            user_unit_dir(Some(Path::new("xdg")), Some(home)),
+            Some(PathBuf::from("/home/ana/.config/systemd/user"))
+        );
+        assert_eq!(user_unit_dir(None, None), None);
+
+        let mut status = UnitStatus::default();
+        apply_show_output(
+            &mut status,
+            "LoadState=loaded\nActiveState=active\nSubState=running\nUnitFileState=enabled\nMainPID=4242\n",
+        );
+        assert!(status.is_enabled());
+        assert!(status.is_active());
+        assert_eq!(status.sub_state.as_deref(), Some("running"));
+        assert_eq!(status.main_pid, Some(4242));
+
+        let mut stopped = UnitStatus::default();
+        apply_show_output(&mut stopped, "LoadState=loaded\nActiveState=inactive\nUnitFileState=disabled\nMainPID=0\n");
+        assert!(!stopped.is_enabled());
+        assert_eq!(stopped.main_pid, None);
+    }
+}
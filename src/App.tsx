@@ -12,7 +12,7 @@ import { Settings } from './components/Settings';
 
 import { Logs } from './components/Logs';
 import { appLogger } from './lib/logger';
-import { isTauri } from './lib/tauri';
+import { isTauri, errorMessage } from './lib/tauri';
 import { Download, X, Loader2, CheckCircle, AlertCircle } from 'lucide-react';
 
 import { Agents } from './components/Agents';
@@ -207,7 +207,7 @@ function App() {
       setUpdateResult({
         success: false,
         message: 'Error occurred during update',
-        error: String(e),
+        error: errorMessage(e),
       });
     } finally {
       setUpdating(false);
@@ -250,7 +250,7 @@ function App() {
       }, 2000);
     } catch (e: any) {
       appLogger.error('Manager update download failed', e);
-      setManagerUpdateResult({ success: false, message: 'Update failed', error: e?.message || String(e) });
+      setManagerUpdateResult({ success: false, message: 'Update failed', error: errorMessage(e) });
       setManagerUpdating(false);
     }
   };
@@ -23,6 +23,8 @@ import {
 } from 'lucide-react';
 import clsx from 'clsx';
 import { aiLogger } from '../../lib/logger';
+import { errorMessage } from '../../lib/tauri';
+import { useConfigChanges } from '../../hooks/useConfigChanges';
 
 // ============ Type Definitions ============
 
@@ -246,7 +248,7 @@ function ProviderDialog({ officialProviders, onClose, onSave, editingProvider }:
       onClose();
     } catch (e) {
       aiLogger.error('Failed to save Provider', e);
-      setFormError('Save failed: ' + String(e));
+      setFormError('Save failed: ' + errorMessage(e));
     } finally {
       setSaving(false);
     }
@@ -663,7 +665,7 @@ function ProviderCard({ provider, officialProviders, onSetPrimary, onRefresh, on
       setShowDeleteConfirm(false);
       onRefresh();
     } catch (e) {
-      setDeleteError('Delete failed: ' + String(e));
+      setDeleteError('Delete failed: ' + errorMessage(e));
     } finally {
       setDeleting(false);
     }
@@ -879,7 +881,7 @@ export function AIConfig() {
         provider: 'unknown',
         model: 'unknown',
         response: null,
-        error: String(e),
+        error: errorMessage(e),
         latency_ms: null,
       });
     } finally {
@@ -901,7 +903,7 @@ export function AIConfig() {
       aiLogger.info(`Loading complete: ${officials.length} official providers, ${config.configured_providers.length} configured`);
     } catch (e) {
       aiLogger.error('Failed to load AI configuration', e);
-      setError(String(e));
+      setError(errorMessage(e));
     } finally {
       setLoading(false);
     }
@@ -911,6 +913,8 @@ export function AIConfig() {
     loadData();
   }, [loadData]);
 
+  useConfigChanges(loadData, 'openclaw', ['models', 'agents', 'env']);
+
   const handleSetPrimary = async (modelId: string) => {
     try {
       await invoke('set_primary_model', { modelId });
@@ -918,7 +922,7 @@ export function AIConfig() {
       loadData();
     } catch (e) {
       aiLogger.error('Failed to set primary model', e);
-      alert('Failed to set: ' + e);
+      alert('Failed to set: ' + errorMessage(e));
     }
   };
 
@@ -22,6 +22,8 @@ import {
     Sparkles
 } from 'lucide-react';
 import { appLogger } from '../../lib/logger';
+import { errorMessage } from '../../lib/tauri';
+import { useConfigChanges } from '../../hooks/useConfigChanges';
 
 // Types corresponding to Rust backend
 interface SubagentConfig {
@@ -133,7 +135,7 @@ export function Agents() {
             setAgents(data.agents);
             setBindings(data.bindings);
         } catch (e) {
-            setError(String(e));
+            setError(errorMessage(e));
             appLogger.error('Failed to fetch agents config', e);
         } finally {
             setLoading(false);
@@ -153,6 +155,11 @@ export function Agents() {
         fetchAccounts();
     }, []);
 
+    useConfigChanges(() => {
+        fetchData();
+        fetchAccounts();
+    }, 'openclaw', ['agents', 'bindings', 'channels']);
+
     const handleSaveAgent = async () => {
         if (!agentForm.id) return;
         setSaving(true);
@@ -162,7 +169,7 @@ export function Agents() {
             setShowAgentDialog(false);
             fetchData();
         } catch (e) {
-            setError(String(e));
+            setError(errorMessage(e));
         } finally {
             setSaving(false);
         }
@@ -174,7 +181,7 @@ export function Agents() {
             await invoke('delete_agent', { agentId: id });
             fetchData();
         } catch (e) {
-            setError(String(e));
+            setError(errorMessage(e));
         }
     };
 
@@ -186,7 +193,7 @@ export function Agents() {
             setShowBindingDialog(false);
             fetchData();
         } catch (e) {
-            setError(String(e));
+            setError(errorMessage(e));
         } finally {
             setSaving(false);
         }
@@ -198,7 +205,7 @@ export function Agents() {
             await invoke('delete_agent_binding', { index });
             fetchData();
         } catch (e) {
-            setError(String(e));
+            setError(errorMessage(e));
         }
     };
 
@@ -227,7 +234,7 @@ export function Agents() {
             const result = await invoke<RoutingTestResult>('test_agent_routing', { accountId });
             setTestResult(result);
         } catch (e) {
-            setError(String(e));
+            setError(errorMessage(e));
         } finally {
             setTestingAccount(null);
         }
@@ -258,7 +265,7 @@ export function Agents() {
             setWizardForm({ botAccountId: '', agentId: '', model: '', isDefault: false });
             fetchData();
         } catch (e) {
-            setError(String(e));
+            setError(errorMessage(e));
         } finally {
             setSaving(false);
         }
@@ -29,6 +29,8 @@ import {
   Users,
 } from 'lucide-react';
 import clsx from 'clsx';
+import { errorMessage } from '../../lib/tauri';
+import { useConfigChanges } from '../../hooks/useConfigChanges';
 
 // Reusable component for DM Allowlist management with Fetch capability
 const DmAllowListEditor = ({
@@ -80,7 +82,7 @@ const DmAllowListEditor = ({
         }
       }
     } catch (e) {
-      alert('Failed to fetch users: ' + e);
+      alert('Failed to fetch users: ' + errorMessage(e));
     } finally {
       setFetching(false);
     }
@@ -469,7 +471,7 @@ export function Channels() {
       // Refresh plugin status
       await checkFeishuPlugin();
     } catch (e) {
-      alert('Installation failed: ' + e);
+      alert('Installation failed: ' + errorMessage(e));
     } finally {
       setFeishuPluginInstalling(false);
     }
@@ -505,7 +507,7 @@ export function Channels() {
       setTestResult({
         success: false,
         message: 'Clear failed',
-        error: String(e),
+        error: errorMessage(e),
       });
     } finally {
       setClearing(false);
@@ -536,7 +538,7 @@ export function Channels() {
       setTestResult({
         success: false,
         message: 'Test failed',
-        error: String(e),
+        error: errorMessage(e),
       });
     } finally {
       setTesting(false);
@@ -582,7 +584,7 @@ export function Channels() {
 
       alert('Please scan the QR code in the popup terminal window to complete login\n\nThe interface will update automatically after successful login');
     } catch (e) {
-      alert('Failed to start login: ' + e);
+      alert('Failed to start login: ' + errorMessage(e));
       setLoginLoading(false);
     }
   };
@@ -615,6 +617,13 @@ export function Channels() {
     init();
   }, []);
 
+  useConfigChanges(async () => {
+    const result = await fetchChannels();
+    if (selectedChannel) {
+      handleChannelSelect(selectedChannel, result);
+    }
+  }, 'openclaw', ['channels', 'plugins']);
+
   const handleChannelSelect = (channelId: string, channelList?: ChannelConfig[]) => {
     setSelectedChannel(channelId);
     setTestResult(null); // Clear test result
@@ -774,7 +783,7 @@ export function Channels() {
       alert('Channel configuration saved!');
     } catch (e) {
       console.error('Save failed:', e);
-      alert('Save failed: ' + e);
+      alert('Save failed: ' + errorMessage(e));
     } finally {
       setSaving(false);
     }
@@ -1572,7 +1581,7 @@ export function Channels() {
                               type="password"
                               value={newAccountToken}
                               onChange={e => setNewAccountToken(e.target.value)}
-                              placeholder="Bot Token from @BotFather"
+                              placeholder="Bot Token from @BotFather, or ${secret:NAME}"
                               className="input-base text-sm"
                             />
                             <div className="flex gap-2">
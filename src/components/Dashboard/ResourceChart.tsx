@@ -0,0 +1,59 @@
+import { Cpu, HardDrive } from 'lucide-react';
+import { ResourceSample } from '../../lib/tauri';
+
+interface ResourceChartProps {
+  samples: ResourceSample[];
+}
+
+const WIDTH = 300;
+const HEIGHT = 60;
+
+// SVG polyline points scaled to the chart box
+function toPoints(values: number[]): string {
+  const max = Math.max(...values, 1);
+  const step = values.length > 1 ? WIDTH / (values.length - 1) : WIDTH;
+  return values
+    .map((v, i) => `${(i * step).toFixed(1)},${(HEIGHT - (v / max) * HEIGHT).toFixed(1)}`)
+    .join(' ');
+}
+
+function Sparkline({ values, color }: { values: number[]; color: string }) {
+  return (
+    <svg viewBox={`0 0 ${WIDTH} ${HEIGHT}`} preserveAspectRatio="none" className="w-full h-16">
+      <polyline points={toPoints(values)} fill="none" stroke={color} strokeWidth={2} vectorEffect="non-scaling-stroke" />
+    </svg>
+  );
+}
+
+export function ResourceChart({ samples }: ResourceChartProps) {
+  const cpu = samples.map((s) => s.cpu_percent ?? 0);
+  const memory = samples.map((s) => s.memory_mb ?? 0);
+  const minutes = Math.round((samples[samples.length - 1].timestamp_ms - samples[0].timestamp_ms) / 60000);
+
+  return (
+    <div className="bg-dark-700 rounded-2xl p-6 border border-dark-500">
+      <div className="flex items-center justify-between mb-4">
+        <h3 className="text-lg font-semibold text-white">Resource Usage</h3>
+        <span className="text-xs text-gray-500">Last {Math.max(minutes, 1)} min</span>
+      </div>
+      <div className="grid grid-cols-1 md:grid-cols-2 gap-4">
+        <div className="bg-dark-600 rounded-xl p-4">
+          <div className="flex items-center gap-2 mb-2">
+            <Cpu size={16} className="text-accent-purple" />
+            <span className="text-xs text-gray-400">CPU</span>
+            <span className="ml-auto text-sm text-white">{cpu[cpu.length - 1].toFixed(1)}%</span>
+          </div>
+          <Sparkline values={cpu} color="#a78bfa" />
+        </div>
+        <div className="bg-dark-600 rounded-xl p-4">
+          <div className="flex items-center gap-2 mb-2">
+            <HardDrive size={16} className="text-accent-green" />
+            <span className="text-xs text-gray-400">Memory</span>
+            <span className="ml-auto text-sm text-white">{memory[memory.length - 1].toFixed(1)} MB</span>
+          </div>
+          <Sparkline values={memory} color="#4ade80" />
+        </div>
+      </div>
+    </div>
+  );
+}
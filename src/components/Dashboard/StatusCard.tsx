@@ -3,11 +3,18 @@ import clsx from 'clsx';
 
 interface ServiceStatus {
   running: boolean;
+  state?: 'ours' | 'foreign' | 'stopped';
   pid: number | null;
   port: number;
   uptime_seconds: number | null;
   memory_mb: number | null;
   cpu_percent: number | null;
+  health?: {
+    state: 'healthy' | 'degraded' | 'down';
+    version: string | null;
+    latency_ms: number | null;
+    message: string | null;
+  } | null;
 }
 
 interface StatusCardProps {
@@ -24,6 +31,8 @@ export function StatusCard({ status, loading }: StatusCardProps) {
     return `${minutes}m`;
   };
 
+  const health = status?.running ? status.health : null;
+
   return (
     <div className="bg-dark-700 rounded-2xl p-6 border border-dark-500">
       <div className="flex items-center justify-between mb-6">
@@ -32,20 +41,26 @@ export function StatusCard({ status, loading }: StatusCardProps) {
           <div
             className={clsx(
               'status-dot',
-              loading ? 'warning' : status?.running ? 'running' : 'stopped'
+              loading || status?.state === 'foreign' ? 'warning' : status?.running ? 'running' : 'stopped'
             )}
           />
           <span
             className={clsx(
               'text-sm font-medium',
-              loading
+              loading || status?.state === 'foreign'
                 ? 'text-yellow-400'
                 : status?.running
                 ? 'text-green-400'
                 : 'text-red-400'
             )}
           >
-            {loading ? 'Detecting...' : status?.running ? 'Running' : 'Stopped'}
+            {loading
+              ? 'Detecting...'
+              : status?.state === 'foreign'
+              ? 'Port in use (not started by Manager)'
+              : status?.running
+              ? 'Running'
+              : 'Stopped'}
           </span>
         </div>
       </div>
@@ -91,6 +106,26 @@ export function StatusCard({ status, loading }: StatusCardProps) {
           </p>
         </div>
       </div>
+
+      {health && (
+        <div className="mt-4 flex items-center gap-3 text-sm">
+          <span
+            className={clsx(
+              'font-medium',
+              health.state === 'healthy'
+                ? 'text-green-400'
+                : health.state === 'degraded'
+                ? 'text-yellow-400'
+                : 'text-red-400'
+            )}
+          >
+            {health.state === 'healthy' ? 'Healthy' : health.state === 'degraded' ? 'Degraded' : 'Not responding'}
+          </span>
+          {health.latency_ms !== null && <span className="text-gray-500">{health.latency_ms} ms</span>}
+          {health.version && <span className="text-gray-500">v{health.version}</span>}
+          {health.message && <span className="text-gray-400 truncate">{health.message}</span>}
+        </div>
+      )}
     </div>
   );
 }
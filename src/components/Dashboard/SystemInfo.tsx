@@ -13,7 +13,7 @@ import {
   RefreshCw,
   Server,
 } from 'lucide-react';
-import { isTauri } from '../../lib/tauri';
+import { isTauri, errorMessage } from '../../lib/tauri';
 
 interface EnvironmentStatus {
   node_installed: boolean;
@@ -66,7 +66,7 @@ export function SystemInfo() {
       setEnvStatus(status);
       setError(null);
     } catch (e) {
-      setError(`Failed to check environment: ${e}`);
+      setError(`Failed to check environment: ${errorMessage(e)}`);
     } finally {
       setLoading(false);
       setRefreshing(false);
@@ -93,7 +93,7 @@ export function SystemInfo() {
         setError(result.error || result.message);
       }
     } catch (e) {
-      setError(`Failed to install Node.js: ${e}`);
+      setError(`Failed to install Node.js: ${errorMessage(e)}`);
     } finally {
       setInstalling(null);
     }
@@ -111,7 +111,7 @@ export function SystemInfo() {
         setError(result.error || result.message);
       }
     } catch (e) {
-      setError(`Failed to install OpenClaw: ${e}`);
+      setError(`Failed to install OpenClaw: ${errorMessage(e)}`);
     } finally {
       setInstalling(null);
     }
@@ -122,10 +122,11 @@ export function SystemInfo() {
     setError(null);
     try {
       await invoke<string>('install_gateway_service');
-      // Gateway install opens an elevated terminal — user needs to complete it there
-      // Don't auto-refresh; user clicks Refresh when done
+      // Linux enables a systemd user unit directly; Windows/macOS open an elevated
+      // terminal, so the user clicks Refresh once it is done there
+      await checkEnvironment();
     } catch (e) {
-      setError(`Failed to install Gateway Service: ${e}`);
+      setError(`Failed to install Gateway Service: ${errorMessage(e)}`);
     } finally {
       setInstalling(null);
     }
@@ -1,11 +1,11 @@
 import { useEffect, useState } from 'react';
 import { motion } from 'framer-motion';
-import { invoke } from '@tauri-apps/api/core';
 import { StatusCard } from './StatusCard';
 import { QuickActions } from './QuickActions';
 import { SystemInfo } from './SystemInfo';
+import { ResourceChart } from './ResourceChart';
 import { Setup } from '../Setup';
-import { api, ServiceStatus, isTauri } from '../../lib/tauri';
+import { api, ResourceSample, ServiceStatus, isAppError, isTauri } from '../../lib/tauri';
 import { EnvironmentStatus } from '../../App';
 
 interface DashboardProps {
@@ -15,6 +15,7 @@ interface DashboardProps {
 
 export function Dashboard({ envStatus, onSetupComplete }: DashboardProps) {
   const [status, setStatus] = useState<ServiceStatus | null>(null);
+  const [resources, setResources] = useState<ResourceSample[]>([]);
   const [loading, setLoading] = useState(true);
   const [actionLoading, setActionLoading] = useState(false);
 
@@ -26,6 +27,7 @@ export function Dashboard({ envStatus, onSetupComplete }: DashboardProps) {
     try {
       const result = await api.getServiceStatus();
       setStatus(result);
+      setResources(result.running ? await api.getResourceHistory() : []);
     } catch {
       // Handle silently
     } finally {
@@ -87,7 +89,14 @@ export function Dashboard({ envStatus, onSetupComplete }: DashboardProps) {
     if (!isTauri()) return;
     setActionLoading(true);
     try {
-      await invoke<string>('kill_all_port_processes');
+      try {
+        await api.killAllPortProcesses();
+      } catch (e) {
+        // Processes that are not node/openclaw are only killed after confirmation
+        if (!isAppError(e) || e.code !== 'confirmation_required') throw e;
+        if (!confirm(`${e.message}:\n${e.detail ?? ''}\n\nKill them anyway?`)) return;
+        await api.killAllPortProcesses(true);
+      }
       await fetchStatus();
     } catch (e) {
       console.error('Kill All failed:', e);
@@ -134,6 +143,13 @@ export function Dashboard({ envStatus, onSetupComplete }: DashboardProps) {
           <StatusCard status={status} loading={loading} />
         </motion.div>
 
+        {/* Resource usage */}
+        {resources.length > 1 && (
+          <motion.div variants={itemVariants}>
+            <ResourceChart samples={resources} />
+          </motion.div>
+        )}
+
         {/* Quick actions */}
         <motion.div variants={itemVariants}>
           <QuickActions
@@ -0,0 +1,166 @@
+import { useEffect, useRef, useState } from 'react';
+import { ChevronUp, Filter, RefreshCw, Server } from 'lucide-react';
+import clsx from 'clsx';
+import { api, errorMessage, GatewayLogEntry, GatewayLogLevel, onGatewayLog } from '../../lib/tauri';
+import { appLogger } from '../../lib/logger';
+
+const PAGE_SIZE = 200;
+
+// Keep the live view bounded
+const MAX_ENTRIES = 5000;
+
+const LEVEL_ORDER: GatewayLogLevel[] = ['debug', 'info', 'warn', 'error'];
+
+const LEVEL_COLORS: Record<GatewayLogLevel, string> = {
+  debug: 'text-gray-400',
+  info: 'text-green-400',
+  warn: 'text-yellow-400',
+  error: 'text-red-400',
+};
+
+export function GatewayLogs() {
+  const [entries, setEntries] = useState<GatewayLogEntry[]>([]);
+  const [level, setLevel] = useState<GatewayLogLevel | 'all'>('all');
+  // Matching entries loaded so far, counted from the newest (live entries included)
+  const [loaded, setLoaded] = useState(0);
+  const [hasMore, setHasMore] = useState(false);
+  const [follow, setFollow] = useState(true);
+  const [error, setError] = useState<string | null>(null);
+  const logsEndRef = useRef<HTMLDivElement>(null);
+
+  const minLevel = level === 'all' ? undefined : level;
+
+  // Newest page for the current filter
+  const loadLatest = async () => {
+    try {
+      const page = await api.readGatewayLogs(undefined, PAGE_SIZE, minLevel);
+      setEntries(page.entries);
+      setLoaded(page.next_skip);
+      setHasMore(page.has_more);
+      setError(null);
+    } catch (e) {
+      appLogger.error('Failed to read gateway logs', e);
+      setError(errorMessage(e));
+    }
+  };
+
+  // Prepend the page before the oldest loaded entry
+  const loadOlder = async () => {
+    if (!hasMore) return;
+    try {
+      const page = await api.readGatewayLogs(loaded, PAGE_SIZE, minLevel);
+      setEntries((prev) => [...page.entries, ...prev]);
+      setLoaded(page.next_skip);
+      setHasMore(page.has_more);
+    } catch (e) {
+      setError(errorMessage(e));
+    }
+  };
+
+  useEffect(() => {
+    loadLatest();
+  }, [level]);
+
+  // Live stream
+  useEffect(() => {
+    if (!follow) return;
+    const unlisten = onGatewayLog((entry) => {
+      if (minLevel && LEVEL_ORDER.indexOf(entry.level) < LEVEL_ORDER.indexOf(minLevel)) return;
+      setEntries((prev) => [...prev, entry].slice(-MAX_ENTRIES));
+      setLoaded((prev) => prev + 1);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [follow, level]);
+
+  useEffect(() => {
+    if (follow && logsEndRef.current) {
+      logsEndRef.current.scrollIntoView({ behavior: 'smooth' });
+    }
+  }, [entries, follow]);
+
+  return (
+    <div className="h-full flex flex-col overflow-hidden">
+      {/* Toolbar */}
+      <div className="flex items-center gap-4 mb-4 flex-wrap">
+        <div className="flex items-center gap-2">
+          <Filter size={14} className="text-gray-500" />
+          <select
+            value={level}
+            onChange={(e) => setLevel(e.target.value as GatewayLogLevel | 'all')}
+            className="bg-dark-700 border border-dark-500 rounded-lg px-3 py-1.5 text-sm text-gray-300"
+          >
+            <option value="all">All Levels</option>
+            <option value="debug">Debug and above</option>
+            <option value="info">Info and above</option>
+            <option value="warn">Warn and above</option>
+            <option value="error">Error only</option>
+          </select>
+        </div>
+
+        <div className="flex-1" />
+
+        <span className="text-xs text-gray-500">
+          {entries.length} entries
+        </span>
+
+        <label className="flex items-center gap-1 text-xs text-gray-400">
+          <input
+            type="checkbox"
+            checked={follow}
+            onChange={(e) => setFollow(e.target.checked)}
+            className="w-3 h-3 rounded"
+          />
+          Follow
+        </label>
+        <button onClick={loadLatest} className="icon-button text-gray-400 hover:text-white" title="Refresh">
+          <RefreshCw size={16} />
+        </button>
+      </div>
+
+      <div className="flex-1 bg-dark-800 rounded-xl border border-dark-600 overflow-hidden flex flex-col">
+        <div className="flex items-center gap-2 px-4 py-2 bg-dark-700 border-b border-dark-600">
+          <Server size={14} className="text-gray-500" />
+          <span className="text-xs text-gray-400 font-medium">Gateway Output</span>
+        </div>
+
+        <div className="flex-1 overflow-y-auto p-2 font-mono text-xs">
+          {error && <div className="px-2 py-1 text-red-400">{error}</div>}
+          {hasMore && (
+            <button
+              onClick={loadOlder}
+              className="w-full flex items-center justify-center gap-1 py-1 mb-1 text-gray-500 hover:text-gray-300"
+            >
+              <ChevronUp size={12} />
+              Load older
+            </button>
+          )}
+          {entries.length === 0 && !error ? (
+            <div className="h-full flex items-center justify-center text-gray-500">
+              <div className="text-center">
+                <Server size={32} className="mx-auto mb-2 opacity-50" />
+                <p>No gateway output captured yet</p>
+              </div>
+            </div>
+          ) : (
+            entries.map((entry, index) => (
+              <div key={loaded - entries.length + index} className="flex items-start gap-2 py-0.5 px-2">
+                <span className="text-gray-600 flex-shrink-0">
+                  {entry.timestamp ? new Date(entry.timestamp).toLocaleTimeString('zh-CN', { hour12: false }) : '--'}
+                </span>
+                <span className={clsx('w-10 uppercase text-[10px] flex-shrink-0', LEVEL_COLORS[entry.level])}>
+                  {entry.level}
+                </span>
+                <span className={clsx('break-all', entry.stream === 'stderr' ? 'text-red-300' : 'text-gray-300')}>
+                  {entry.message}
+                </span>
+              </div>
+            ))
+          )}
+          <div ref={logsEndRef} />
+        </div>
+      </div>
+    </div>
+  );
+}
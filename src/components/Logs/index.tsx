@@ -9,6 +9,7 @@ import {
 } from 'lucide-react';
 import clsx from 'clsx';
 import { logStore, LogEntry } from '../../lib/logger';
+import { GatewayLogs } from './GatewayLogs';
 
 type FilterLevel = 'all' | 'debug' | 'info' | 'warn' | 'error';
 
@@ -38,7 +39,38 @@ const MODULE_COLORS: Record<string, string> = {
   API: 'text-amber-400',
 };
 
+type LogSource = 'manager' | 'gateway';
+
 export function Logs() {
+  const [source, setSource] = useState<LogSource>('manager');
+
+  return (
+    <div className="h-full flex flex-col overflow-hidden">
+      {/* Source switch */}
+      <div className="flex items-center gap-2 mb-4">
+        {(['manager', 'gateway'] as LogSource[]).map((s) => (
+          <button
+            key={s}
+            onClick={() => setSource(s)}
+            className={clsx(
+              'px-3 py-1.5 rounded-lg text-sm transition-all',
+              source === s
+                ? 'bg-claw-500/20 text-claw-400 border border-claw-500/30'
+                : 'text-gray-400 hover:text-white border border-transparent'
+            )}
+          >
+            {s === 'manager' ? 'Manager' : 'Gateway'}
+          </button>
+        ))}
+      </div>
+      <div className="flex-1 overflow-hidden">
+        {source === 'manager' ? <ManagerLogs /> : <GatewayLogs />}
+      </div>
+    </div>
+  );
+}
+
+function ManagerLogs() {
   const [logs, setLogs] = useState<LogEntry[]>([]);
   const [filter, setFilter] = useState<FilterLevel>('all');
   const [moduleFilter, setModuleFilter] = useState<string>('all');
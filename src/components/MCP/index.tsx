@@ -1,8 +1,9 @@
 import { useState, useEffect } from 'react';
 import { motion, AnimatePresence } from 'framer-motion';
-import { api, MCPConfig, isTauri } from '../../lib/tauri';
+import { api, MCPConfig, isTauri, errorMessage } from '../../lib/tauri';
 import { Plus, Trash2, Edit2, Save, Terminal, Blocks, AlertCircle, GitBranch, Loader2, Download, CheckCircle, Package, Plug, Globe, Zap } from 'lucide-react';
 import clsx from 'clsx';
+import { useConfigChanges } from '../../hooks/useConfigChanges';
 
 export function MCP() {
     const [configs, setConfigs] = useState<Record<string, MCPConfig>>({});
@@ -79,7 +80,7 @@ export function MCP() {
             setMcporterInstalled(true);
             setSuccess('mcporter installed successfully!');
         } catch (e) {
-            setError(`Failed to install mcporter: ${e}`);
+            setError(`Failed to install mcporter: ${errorMessage(e)}`);
         } finally {
             setInstallingMcporter(false);
         }
@@ -90,6 +91,8 @@ export function MCP() {
         checkMcporter();
     }, []);
 
+    useConfigChanges(fetchConfigs, 'mcps');
+
     // Auto-clear success messages
     useEffect(() => {
         if (success) {
@@ -153,7 +156,7 @@ export function MCP() {
                 setTestResult(prev => ({ ...prev, [id]: { ok: true, msg: result } }));
             }
         } catch (e) {
-            setTestResult(prev => ({ ...prev, [id]: { ok: false, msg: String(e) } }));
+            setTestResult(prev => ({ ...prev, [id]: { ok: false, msg: errorMessage(e) } }));
         } finally {
             setTestingId(null);
         }
@@ -166,7 +169,7 @@ export function MCP() {
             setSuccess(`Successfully uninstalled ${id}`);
             await fetchConfigs();
         } catch (e) {
-            setError(`Failed to uninstall MCP: ${e}`);
+            setError(`Failed to uninstall MCP: ${errorMessage(e)}`);
         }
     };
 
@@ -222,7 +225,7 @@ export function MCP() {
             setSuccess(`Saved configuration for ${formData.name}`);
             await fetchConfigs();
         } catch (e) {
-            setError(`Failed to save: ${e}`);
+            setError(`Failed to save: ${errorMessage(e)}`);
         }
     };
 
@@ -250,7 +253,7 @@ export function MCP() {
             setInstallProgress('');
             await fetchConfigs();
         } catch (e) {
-            setError(`Installation failed: ${e}`);
+            setError(`Installation failed: ${errorMessage(e)}`);
             setInstallProgress('');
         } finally {
             setInstalling(false);
@@ -266,7 +269,7 @@ export function MCP() {
             setMcporterInstalled(false);
             setSuccess('mcporter uninstalled successfully');
         } catch (e) {
-            setError(`Failed to uninstall mcporter: ${e}`);
+            setError(`Failed to uninstall mcporter: ${errorMessage(e)}`);
         } finally {
             setInstallingMcporter(false);
         }
@@ -678,7 +681,7 @@ export function MCP() {
                                         <textarea
                                             value={formData.env}
                                             onChange={(e) => setFormData({ ...formData, env: e.target.value })}
-                                            placeholder={'KEY=VALUE\nAPI_TOKEN=xyz'}
+                                            placeholder={'KEY=VALUE\nAPI_TOKEN=${secret:API_TOKEN}\nHOST=${env:HOST}'}
                                             rows={5}
                                             className="w-full bg-dark-800 border border-dark-600 rounded-xl px-4 py-3 text-white focus:ring-2 focus:ring-claw-500 focus:border-transparent outline-none font-mono text-sm resize-none"
                                         />
@@ -0,0 +1,108 @@
+import { useEffect, useState } from 'react';
+import { Server, Loader2, Power, PowerOff, Trash2 } from 'lucide-react';
+import clsx from 'clsx';
+import { api, errorMessage, UnitStatus } from '../../lib/tauri';
+
+// Manages the openclaw-gateway systemd user unit; hidden where systemd is not available
+export function SystemdUnitCard() {
+  const [status, setStatus] = useState<UnitStatus | null>(null);
+  const [busy, setBusy] = useState<'enable' | 'disable' | 'remove' | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const fetchStatus = async () => {
+    try {
+      setStatus(await api.getGatewayUnitStatus());
+    } catch {
+      // Not running in Tauri or not on Linux
+    }
+  };
+
+  useEffect(() => {
+    fetchStatus();
+  }, []);
+
+  const run = async (action: 'enable' | 'disable' | 'remove') => {
+    setBusy(action);
+    setError(null);
+    try {
+      const next =
+        action === 'enable' ? await api.enableGatewayUnit() : await api.disableGatewayUnit(action === 'remove');
+      setStatus(next);
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  if (!status || (!status.systemd_available && !status.installed)) {
+    return null;
+  }
+
+  const enabled = status.unit_file_state === 'enabled';
+  const active = status.active_state === 'active';
+
+  return (
+    <div className="mb-4 bg-dark-800 rounded-xl border border-dark-600 px-4 py-3">
+      <div className="flex items-center gap-3">
+        <Server size={16} className="text-gray-500" />
+        <div className="flex-1 min-w-0">
+          <p className="text-sm text-white font-medium">systemd user service</p>
+          <p className="text-xs text-gray-500 truncate">
+            {status.installed ? status.unit_path : 'Not installed'}
+            {status.installed && (
+              <>
+                {' · '}
+                <span className={enabled ? 'text-green-400' : 'text-gray-400'}>{status.unit_file_state || 'unknown'}</span>
+                {' · '}
+                <span className={active ? 'text-green-400' : status.active_state === 'failed' ? 'text-red-400' : 'text-gray-400'}>
+                  {status.active_state || 'unknown'}
+                  {status.sub_state ? ` (${status.sub_state})` : ''}
+                </span>
+                {status.main_pid ? ` · PID ${status.main_pid}` : ''}
+              </>
+            )}
+          </p>
+        </div>
+
+        <button
+          onClick={() => run('enable')}
+          disabled={busy !== null}
+          className={clsx(
+            'flex items-center gap-2 px-3 py-1.5 rounded-lg text-sm transition-all',
+            'bg-green-500/20 text-green-400 border border-green-500/30',
+            'hover:bg-green-500/30 disabled:opacity-50'
+          )}
+        >
+          {busy === 'enable' ? <Loader2 size={14} className="animate-spin" /> : <Power size={14} />}
+          {status.installed ? 'Regenerate & Enable' : 'Enable'}
+        </button>
+
+        {status.installed && (
+          <>
+            <button
+              onClick={() => run('disable')}
+              disabled={busy !== null || !enabled}
+              className="flex items-center gap-2 px-3 py-1.5 rounded-lg text-sm transition-all bg-dark-600 text-gray-300 border border-dark-500 hover:bg-dark-500 disabled:opacity-50"
+            >
+              {busy === 'disable' ? <Loader2 size={14} className="animate-spin" /> : <PowerOff size={14} />}
+              Disable
+            </button>
+            <button
+              onClick={() => run('remove')}
+              disabled={busy !== null}
+              title="Disable and delete the unit file"
+              className="icon-button text-gray-400 hover:text-red-400"
+            >
+              {busy === 'remove' ? <Loader2 size={14} className="animate-spin" /> : <Trash2 size={14} />}
+            </button>
+          </>
+        )}
+      </div>
+
+      {(error || status.message) && (
+        <p className={clsx('mt-2 text-xs', error ? 'text-red-400' : 'text-gray-400')}>{error || status.message}</p>
+      )}
+    </div>
+  );
+}
@@ -8,14 +8,42 @@ import {
   RefreshCw,
   Terminal,
   Loader2,
+  AlertTriangle,
+  History,
+  X,
 } from 'lucide-react';
 import clsx from 'clsx';
 import { serviceLogger } from '../../lib/logger';
+import { SystemdUnitCard } from './SystemdUnitCard';
+import {
+  api,
+  errorMessage,
+  isAppError,
+  onServiceCrashLoop,
+  onServiceProgress,
+  RestartHistory,
+  ServicePhase,
+  ServiceProgress,
+} from '../../lib/tauri';
+
+const PHASE_LABELS: Record<ServicePhase, string> = {
+  queued: 'Queued',
+  stopping: 'Stopping gateway',
+  spawning: 'Spawning gateway',
+  waiting_for_port: 'Waiting for port',
+  waiting_for_health: 'Waiting for health check',
+  healthy: 'Healthy',
+  stopped: 'Stopped',
+  failed: 'Failed',
+  cancelled: 'Cancelled',
+};
 
 export function ServiceManager() {
   const [logs, setLogs] = useState<string[]>([]);
   const [actionLoading, setActionLoading] = useState<string | null>(null);
   const [autoRefresh, setAutoRefresh] = useState(true);
+  const [restartHistory, setRestartHistory] = useState<RestartHistory | null>(null);
+  const [progress, setProgress] = useState<ServiceProgress | null>(null);
   const logsEndRef = useRef<HTMLDivElement>(null);
 
   serviceLogger.debug('ServiceManager component rendered');
@@ -30,6 +58,35 @@ export function ServiceManager() {
     }
   };
 
+  const fetchRestartHistory = async () => {
+    try {
+      setRestartHistory(await api.getRestartHistory());
+    } catch (e) {
+      serviceLogger.error('Failed to fetch restart history', e);
+    }
+  };
+
+  useEffect(() => {
+    fetchRestartHistory();
+    const unlisten = onServiceCrashLoop((history) => {
+      serviceLogger.warn('Gateway crash loop detected, supervisor gave up');
+      setRestartHistory(history);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  useEffect(() => {
+    const unlisten = onServiceProgress((update) => {
+      serviceLogger.debug(`Service ${update.operation}: ${update.phase}`);
+      setProgress(update);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
   useEffect(() => {
     serviceLogger.info('ServiceManager component mounted');
     fetchLogs();
@@ -57,14 +114,28 @@ export function ServiceManager() {
       const result = await invoke(`${action}_service`);
       serviceLogger.info(`✅ ${action} succeeded`, result);
       await fetchLogs();
+      await fetchRestartHistory();
     } catch (e) {
+      if (isAppError(e) && e.code === 'cancelled') {
+        serviceLogger.info(`${action} cancelled`);
+        return;
+      }
       serviceLogger.error(`❌ ${action} failed`, e);
-      alert(`Operation failed: ${e}`);
+      alert(`Operation failed: ${errorMessage(e)}`);
     } finally {
       setActionLoading(null);
     }
   };
 
+  const handleCancel = async () => {
+    serviceLogger.action('Cancel service operation');
+    try {
+      await api.cancelServiceOperation();
+    } catch (e) {
+      serviceLogger.error('Failed to cancel service operation', e);
+    }
+  };
+
   const getLogLineClass = (line: string) => {
     if (line.includes('error') || line.includes('Error') || line.includes('ERROR')) {
       return 'text-red-400';
@@ -133,8 +204,25 @@ export function ServiceManager() {
             )}
             Restart
           </button>
+
+          {(actionLoading === 'start' || actionLoading === 'restart') && (
+            <button
+              onClick={handleCancel}
+              className="flex items-center gap-2 px-4 py-2 rounded-lg font-medium transition-all bg-dark-600 text-gray-300 border border-dark-500 hover:bg-dark-500"
+            >
+              <X size={16} />
+              Cancel
+            </button>
+          )}
         </div>
 
+        {actionLoading !== null && progress && (
+          <span className="text-sm text-gray-400">
+            {PHASE_LABELS[progress.phase]}
+            {progress.message ? ` — ${progress.message}` : ''} ({Math.round(progress.elapsed_ms / 1000)}s)
+          </span>
+        )}
+
         <div className="flex-1" />
 
         <div className="flex items-center gap-3">
@@ -157,13 +245,50 @@ export function ServiceManager() {
         </div>
       </div>
 
+      <SystemdUnitCard />
+
+      {/* Crash loop banner */}
+      {restartHistory?.crash_loop && (
+        <div className="flex items-center gap-3 mb-4 px-4 py-3 rounded-lg bg-red-500/10 border border-red-500/30 text-sm text-red-300">
+          <AlertTriangle size={16} className="shrink-0" />
+          <span>
+            The gateway kept crashing and automatic restarts were paused. Check the logs below, then press Start.
+          </span>
+        </div>
+      )}
+
+      {/* Recent supervisor restarts */}
+      {restartHistory && restartHistory.records.length > 0 && (
+        <div className="mb-4 bg-dark-800 rounded-xl border border-dark-600 overflow-hidden">
+          <div className="flex items-center gap-2 px-4 py-2 bg-dark-700 border-b border-dark-600">
+            <History size={14} className="text-gray-500" />
+            <span className="text-xs text-gray-400 font-medium">Restart history</span>
+            <div className="flex-1" />
+            <span className="text-xs text-gray-500">{restartHistory.records.length} restarts</span>
+          </div>
+          <div className="max-h-32 overflow-y-auto px-4 py-2 font-mono text-xs">
+            {restartHistory.records.slice(-10).reverse().map((record, index) => (
+              <div key={index} className="flex gap-3 py-0.5 text-gray-400">
+                <span className="text-gray-600">{new Date(record.timestamp).toLocaleString()}</span>
+                <span className={record.outcome === 'restarted' ? 'text-green-400' : 'text-red-400'}>
+                  {record.outcome.replace('_', ' ')}
+                </span>
+                <span>{record.exit_status || 'exit status unknown'}</span>
+                <span className="text-gray-600">up {record.uptime_seconds}s</span>
+                {record.message && <span className="text-gray-500">{record.message}</span>}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+
       {/* Log viewer */}
       <div className="flex-1 bg-dark-800 rounded-xl border border-dark-600 overflow-hidden flex flex-col">
         {/* Log title bar */}
         <div className="flex items-center gap-2 px-4 py-2 bg-dark-700 border-b border-dark-600">
           <Terminal size={14} className="text-gray-500" />
           <span className="text-xs text-gray-400 font-medium">
-            /tmp/openclaw-gateway.log
+            Gateway output
           </span>
           <div className="flex-1" />
           <span className="text-xs text-gray-500">
@@ -0,0 +1,224 @@
+import { useEffect, useState } from 'react';
+import { Ban, Copy, Link2, Loader2, Plus, Save } from 'lucide-react';
+import { api, DashboardAccessOverview, DashboardScope, errorMessage } from '../../lib/tauri';
+
+const EXPIRY_OPTIONS = [
+  { hours: 1, label: '1 hour' },
+  { hours: 24, label: '1 day' },
+  { hours: 24 * 7, label: '7 days' },
+  { hours: 24 * 30, label: '30 days' },
+  { hours: 24 * 90, label: '90 days' },
+];
+
+const STATUS_STYLE = {
+  active: 'text-green-400',
+  expired: 'text-gray-500',
+  revoked: 'text-red-400',
+};
+
+// Per-person dashboard links with their own expiry and scope, served through a proxy
+// so the gateway token itself is never handed out
+export function DashboardAccess() {
+  const [access, setAccess] = useState<DashboardAccessOverview | null>(null);
+  const [enabled, setEnabled] = useState(false);
+  const [bind, setBind] = useState('127.0.0.1');
+  const [port, setPort] = useState(18799);
+  const [publicUrl, setPublicUrl] = useState('');
+  const [name, setName] = useState('');
+  const [scope, setScope] = useState<DashboardScope>('read_only');
+  const [hours, setHours] = useState(24 * 7);
+  const [busy, setBusy] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+
+  const apply = (overview: DashboardAccessOverview) => {
+    setAccess(overview);
+    setEnabled(overview.enabled);
+    setBind(overview.bind);
+    setPort(overview.port);
+    setPublicUrl(overview.public_url ?? '');
+  };
+
+  useEffect(() => {
+    api.getDashboardAccess().then(apply).catch(() => {});
+  }, []);
+
+  const run = async (action: string, task: () => Promise<void>) => {
+    setBusy(action);
+    setError(null);
+    setNotice(null);
+    try {
+      await task();
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleSave = () =>
+    run('save', async () => {
+      apply(await api.saveDashboardProxy(enabled, bind, port, publicUrl.trim() || null));
+      setNotice(enabled ? 'Dashboard proxy saved and started.' : 'Dashboard proxy stopped.');
+    });
+
+  const handleCreate = () =>
+    run('create', async () => {
+      const created = await api.createDashboardToken(name, scope, hours);
+      await navigator.clipboard.writeText(created.url);
+      setName('');
+      setNotice(`Link for ${created.token.name} copied to the clipboard.`);
+      setAccess(await api.getDashboardAccess());
+    });
+
+  const handleCopy = (id: string) =>
+    run(`copy-${id}`, async () => {
+      await navigator.clipboard.writeText(await api.getDashboardTokenLink(id));
+      setNotice('Link copied to the clipboard.');
+    });
+
+  const handleRevoke = (id: string, who: string) => {
+    if (!confirm(`Revoke the dashboard link for ${who}? Open dashboards using it will be disconnected.`)) {
+      return;
+    }
+    return run(`revoke-${id}`, async () => {
+      setAccess(await api.revokeDashboardToken(id));
+    });
+  };
+
+  const spinner = (action: string, icon: JSX.Element) =>
+    busy === action ? <Loader2 size={14} className="animate-spin" /> : icon;
+
+  return (
+    <div className="bg-dark-700 rounded-2xl p-6 border border-dark-500">
+      <div className="flex items-center gap-3 mb-6">
+        <div className="w-10 h-10 rounded-xl bg-sky-500/20 flex items-center justify-center">
+          <Link2 size={20} className="text-sky-400" />
+        </div>
+        <div className="flex-1">
+          <h3 className="text-lg font-semibold text-white">Shared Dashboard Links</h3>
+          <p className="text-xs text-gray-500">
+            Give each teammate their own expiring link instead of the gateway token
+            {access && (access.running ? ' · proxy running' : ' · proxy stopped')}
+          </p>
+        </div>
+      </div>
+
+      <div className="space-y-4">
+        <div className="grid grid-cols-3 gap-3">
+          <label className="flex items-center gap-2 text-sm text-gray-300 col-span-3">
+            <input type="checkbox" checked={enabled} onChange={e => setEnabled(e.target.checked)} />
+            Run the dashboard proxy
+          </label>
+          <div>
+            <label className="block text-xs text-gray-500 mb-1">Listen address</label>
+            <input value={bind} onChange={e => setBind(e.target.value)} className="input-base font-mono text-xs" />
+          </div>
+          <div>
+            <label className="block text-xs text-gray-500 mb-1">Port</label>
+            <input
+              type="number"
+              value={port}
+              onChange={e => setPort(Number(e.target.value))}
+              className="input-base font-mono text-xs"
+            />
+          </div>
+          <div>
+            <label className="block text-xs text-gray-500 mb-1">Public URL (optional)</label>
+            <input
+              value={publicUrl}
+              onChange={e => setPublicUrl(e.target.value)}
+              placeholder="https://my-host.example:18799"
+              className="input-base font-mono text-xs"
+            />
+          </div>
+        </div>
+        <div className="flex items-center gap-3">
+          <p className="flex-1 text-xs text-gray-500">Use 0.0.0.0 to reach the proxy from other machines.</p>
+          <button onClick={handleSave} disabled={busy !== null} className="btn-secondary flex items-center gap-2">
+            {spinner('save', <Save size={14} />)}
+            Save
+          </button>
+        </div>
+
+        <div className="flex items-center gap-2">
+          <input
+            value={name}
+            onChange={e => setName(e.target.value)}
+            placeholder="Who is this link for?"
+            className="input-base flex-1 text-sm"
+          />
+          <select
+            value={scope}
+            onChange={e => setScope(e.target.value as DashboardScope)}
+            className="input-base w-32 text-sm"
+          >
+            <option value="read_only">Read-only</option>
+            <option value="admin">Admin</option>
+          </select>
+          <select value={hours} onChange={e => setHours(Number(e.target.value))} className="input-base w-28 text-sm">
+            {EXPIRY_OPTIONS.map(option => (
+              <option key={option.hours} value={option.hours}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+          <button
+            onClick={handleCreate}
+            disabled={busy !== null || !name.trim()}
+            className="btn-primary flex items-center gap-2"
+          >
+            {spinner('create', <Plus size={14} />)}
+            Create link
+          </button>
+        </div>
+
+        {access && access.tokens.length > 0 && (
+          <div className="space-y-2">
+            {access.tokens.map(token => (
+              <div key={token.id} className="flex items-center gap-3 text-sm bg-dark-800 rounded-xl px-3 py-2">
+                <div className="flex-1 min-w-0">
+                  <p className="text-gray-300 truncate">
+                    {token.name}{' '}
+                    <span className="text-xs text-gray-500">
+                      · {token.scope === 'admin' ? 'Admin' : 'Read-only'} ·{' '}
+                      <span className={STATUS_STYLE[token.status]}>{token.status}</span>
+                    </span>
+                  </p>
+                  <p className="text-xs text-gray-500">
+                    {token.status === 'revoked' && token.revoked_at
+                      ? `Revoked ${new Date(token.revoked_at).toLocaleString()}`
+                      : `Expires ${new Date(token.expires_at).toLocaleString()}`}
+                  </p>
+                </div>
+                {token.status === 'active' && (
+                  <>
+                    <button
+                      onClick={() => handleCopy(token.id)}
+                      disabled={busy !== null}
+                      className="icon-button"
+                      title="Copy link"
+                    >
+                      {spinner(`copy-${token.id}`, <Copy size={14} />)}
+                    </button>
+                    <button
+                      onClick={() => handleRevoke(token.id, token.name)}
+                      disabled={busy !== null}
+                      className="icon-button text-red-400"
+                      title="Revoke"
+                    >
+                      {spinner(`revoke-${token.id}`, <Ban size={14} />)}
+                    </button>
+                  </>
+                )}
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+
+      {error && <p className="mt-3 text-xs text-red-400">{error}</p>}
+      {notice && <p className="mt-3 text-xs text-green-400">{notice}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,65 @@
+import { useEffect, useState } from 'react';
+import { AlertTriangle, KeyRound, Loader2, RefreshCw } from 'lucide-react';
+import { api, errorMessage, TokenInfo } from '../../lib/tauri';
+
+// Rotating the token restarts a running gateway so old dashboard URLs stop working
+export function GatewayToken() {
+  const [info, setInfo] = useState<TokenInfo | null>(null);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+
+  useEffect(() => {
+    api.getGatewayTokenState().then(setInfo).catch(() => {});
+  }, []);
+
+  const handleRotate = async () => {
+    if (!confirm('Rotate the gateway token? Open dashboards and clients using the old token will be disconnected.')) {
+      return;
+    }
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const result = await api.rotateGatewayToken();
+      setNotice(
+        result.restarted
+          ? 'Token rotated and gateway restarted. Reopen the dashboard to use the new token.'
+          : 'Token rotated. It takes effect the next time the gateway starts.'
+      );
+      setInfo(await api.getGatewayTokenState());
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  return (
+    <div className="bg-dark-700 rounded-2xl p-6 border border-dark-500">
+      <div className="flex items-center gap-3">
+        <div className="w-10 h-10 rounded-xl bg-orange-500/20 flex items-center justify-center">
+          <KeyRound size={20} className="text-orange-400" />
+        </div>
+        <div className="flex-1">
+          <h3 className="text-lg font-semibold text-white">Gateway Token</h3>
+          <p className="text-xs text-gray-500">
+            {info?.rotated_at ? `Last rotated ${new Date(info.rotated_at).toLocaleString()}` : 'Never rotated'}
+          </p>
+        </div>
+        <button onClick={handleRotate} disabled={busy} className="btn-secondary flex items-center gap-2">
+          {busy ? <Loader2 size={14} className="animate-spin" /> : <RefreshCw size={14} />}
+          Rotate token
+        </button>
+      </div>
+
+      {info?.weak && (
+        <p className="mt-3 text-xs text-yellow-500/80 flex items-center gap-1">
+          <AlertTriangle size={12} /> The current token is shorter than a generated one and may be guessable. Rotate it.
+        </p>
+      )}
+      {error && <p className="mt-3 text-xs text-red-400">{error}</p>}
+      {notice && <p className="mt-3 text-xs text-green-400">{notice}</p>}
+    </div>
+  );
+}
@@ -0,0 +1,262 @@
+import { useEffect, useState } from 'react';
+import { CalendarClock, Loader2, Play, Plus, Save, Trash2 } from 'lucide-react';
+import {
+  api,
+  errorMessage,
+  onMaintenanceRun,
+  JobRun,
+  MaintenanceAction,
+  MaintenanceJob,
+  MaintenanceOverview,
+  MaintenanceSettings as Settings,
+  TimeWindow,
+} from '../../lib/tauri';
+
+const ACTION_LABELS: Record<MaintenanceAction, string> = {
+  restart: 'Restart gateway',
+  update: 'Update OpenClaw',
+  repairDeviceToken: 'Repair device token + restart',
+};
+
+const OUTCOME_CLASSES: Record<JobRun['outcome'], string> = {
+  succeeded: 'text-green-400',
+  failed: 'text-red-400',
+  skipped: 'text-gray-500',
+};
+
+const EMPTY: Settings = { enabled: false, quietHours: null, maintenanceWindow: null, jobs: [] };
+
+function WindowInput({
+  label,
+  value,
+  onChange,
+}: {
+  label: string;
+  value: TimeWindow | null;
+  onChange: (value: TimeWindow | null) => void;
+}) {
+  return (
+    <div>
+      <label className="flex items-center gap-2 text-sm text-gray-400 mb-2">
+        <input
+          type="checkbox"
+          checked={value !== null}
+          onChange={e => onChange(e.target.checked ? { start: '02:00', end: '05:00' } : null)}
+        />
+        {label}
+      </label>
+      <div className="flex items-center gap-2">
+        <input
+          type="time"
+          value={value?.start ?? ''}
+          disabled={value === null}
+          onChange={e => value && onChange({ ...value, start: e.target.value })}
+          className="input-base"
+        />
+        <span className="text-gray-500">–</span>
+        <input
+          type="time"
+          value={value?.end ?? ''}
+          disabled={value === null}
+          onChange={e => value && onChange({ ...value, end: e.target.value })}
+          className="input-base"
+        />
+      </div>
+    </div>
+  );
+}
+
+// Timed restarts, update windows and on-demand repairs run by the Manager's scheduler
+export function MaintenanceSettings() {
+  const [settings, setSettings] = useState<Settings>(EMPTY);
+  const [nextRuns, setNextRuns] = useState<MaintenanceOverview['next_runs']>([]);
+  const [runs, setRuns] = useState<JobRun[]>([]);
+  const [saving, setSaving] = useState(false);
+  const [running, setRunning] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const apply = (overview: MaintenanceOverview) => {
+    setSettings(overview.settings);
+    setNextRuns(overview.next_runs);
+  };
+
+  const fetchHistory = async () => {
+    try {
+      const history = await api.getMaintenanceHistory();
+      setRuns(history.runs.slice(-10).reverse());
+    } catch {
+      // Not running in Tauri
+    }
+  };
+
+  useEffect(() => {
+    api.getMaintenanceSettings().then(apply).catch(() => {});
+    fetchHistory();
+    const unlisten = onMaintenanceRun(() => fetchHistory());
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  const updateJob = (index: number, change: Partial<MaintenanceJob>) => {
+    setSettings({ ...settings, jobs: settings.jobs.map((job, i) => (i === index ? { ...job, ...change } : job)) });
+  };
+
+  const addJob = () => {
+    const job: MaintenanceJob = {
+      id: `job-${Date.now()}`,
+      name: 'Nightly restart',
+      action: 'restart',
+      schedule: '0 4 * * *',
+      enabled: true,
+    };
+    setSettings({ ...settings, jobs: [...settings.jobs, job] });
+  };
+
+  const handleSave = async () => {
+    setSaving(true);
+    setError(null);
+    try {
+      apply(await api.saveMaintenanceSettings(settings));
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handleRun = async (id: string) => {
+    setRunning(id);
+    setError(null);
+    try {
+      await api.runMaintenanceJob(id);
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setRunning(null);
+      fetchHistory();
+    }
+  };
+
+  const nextRun = (id: string) => {
+    const at = nextRuns.find(n => n.job_id === id)?.at;
+    return at ? new Date(at).toLocaleString() : '—';
+  };
+
+  return (
+    <div className="bg-dark-700 rounded-2xl p-6 border border-dark-500">
+      <div className="flex items-center gap-3 mb-6">
+        <div className="w-10 h-10 rounded-xl bg-amber-500/20 flex items-center justify-center">
+          <CalendarClock size={20} className="text-amber-400" />
+        </div>
+        <div className="flex-1">
+          <h3 className="text-lg font-semibold text-white">Scheduled Maintenance</h3>
+          <p className="text-xs text-gray-500">Timed restarts, update windows and quiet hours</p>
+        </div>
+        <label className="flex items-center gap-2 text-sm text-gray-400">
+          <input
+            type="checkbox"
+            checked={settings.enabled}
+            onChange={e => setSettings({ ...settings, enabled: e.target.checked })}
+          />
+          Scheduler enabled
+        </label>
+      </div>
+
+      <div className="grid grid-cols-1 md:grid-cols-2 gap-4 mb-4">
+        <WindowInput
+          label="Quiet hours (no scheduled jobs)"
+          value={settings.quietHours}
+          onChange={quietHours => setSettings({ ...settings, quietHours })}
+        />
+        <WindowInput
+          label="Maintenance window (scheduled updates)"
+          value={settings.maintenanceWindow}
+          onChange={maintenanceWindow => setSettings({ ...settings, maintenanceWindow })}
+        />
+      </div>
+
+      <div className="space-y-2">
+        {settings.jobs.map((job, index) => (
+          <div key={job.id} className="grid grid-cols-12 gap-2 items-center">
+            <input
+              type="checkbox"
+              checked={job.enabled}
+              onChange={e => updateJob(index, { enabled: e.target.checked })}
+              className="col-span-1 justify-self-center"
+            />
+            <input
+              value={job.name}
+              onChange={e => updateJob(index, { name: e.target.value })}
+              className="input-base col-span-3"
+            />
+            <select
+              value={job.action}
+              onChange={e => updateJob(index, { action: e.target.value as MaintenanceAction })}
+              className="input-base col-span-3"
+            >
+              {Object.entries(ACTION_LABELS).map(([value, label]) => (
+                <option key={value} value={value}>{label}</option>
+              ))}
+            </select>
+            <input
+              value={job.schedule}
+              onChange={e => updateJob(index, { schedule: e.target.value })}
+              placeholder="0 4 * * *"
+              title="Cron: minute hour day month weekday"
+              className="input-base col-span-2 font-mono"
+            />
+            <span className="col-span-2 text-xs text-gray-500 truncate" title="Next run">{nextRun(job.id)}</span>
+            <div className="col-span-1 flex items-center gap-1">
+              <button
+                onClick={() => handleRun(job.id)}
+                disabled={running !== null}
+                title="Run now (saved jobs only)"
+                className="icon-button text-gray-400 hover:text-green-400"
+              >
+                {running === job.id ? <Loader2 size={14} className="animate-spin" /> : <Play size={14} />}
+              </button>
+              <button
+                onClick={() => setSettings({ ...settings, jobs: settings.jobs.filter((_, i) => i !== index) })}
+                className="icon-button text-gray-400 hover:text-red-400"
+              >
+                <Trash2 size={14} />
+              </button>
+            </div>
+          </div>
+        ))}
+      </div>
+
+      <div className="flex items-center gap-2 mt-4">
+        <button onClick={addJob} className="btn-secondary flex items-center gap-2">
+          <Plus size={14} />
+          Add job
+        </button>
+        <div className="flex-1" />
+        <button onClick={handleSave} disabled={saving} className="btn-primary flex items-center gap-2">
+          {saving ? <Loader2 size={14} className="animate-spin" /> : <Save size={14} />}
+          Save schedule
+        </button>
+      </div>
+
+      {error && <p className="mt-3 text-xs text-red-400">{error}</p>}
+
+      {runs.length > 0 && (
+        <div className="mt-6 pt-4 border-t border-dark-500">
+          <p className="text-sm text-gray-300 mb-2">Recent runs</p>
+          <div className="space-y-1 font-mono text-xs">
+            {runs.map((run, index) => (
+              <div key={index} className="flex gap-3 text-gray-400">
+                <span className="text-gray-600">{new Date(run.started_at).toLocaleString()}</span>
+                <span>{run.job_name}</span>
+                <span className={OUTCOME_CLASSES[run.outcome]}>{run.outcome}</span>
+                <span className="text-gray-600">{run.trigger}</span>
+                {run.message && <span className="text-gray-500 truncate">{run.message}</span>}
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,124 @@
+import { useState } from 'react';
+import { FileWarning, KeyRound, Loader2, ScanSearch, ShieldCheck } from 'lucide-react';
+import { api, errorMessage, LeakFinding, LeakScanReport, LeakTarget } from '../../lib/tauri';
+
+const findingKey = (f: LeakFinding) => `${f.file}:${f.line}:${f.fingerprint}`;
+
+// Finds API keys and tokens pasted into workspace notes, models.json, sessions, helper scripts and exports
+export function SecretLeakScan() {
+  const [report, setReport] = useState<LeakScanReport | null>(null);
+  const [names, setNames] = useState<Record<string, string>>({});
+  const [busy, setBusy] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+
+  const handleScan = async () => {
+    setBusy('scan');
+    setError(null);
+    setNotice(null);
+    try {
+      setReport(await api.scanForLeakedSecrets());
+      setNames({});
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleMove = async (finding: LeakFinding, target: LeakTarget) => {
+    const key = findingKey(finding);
+    setBusy(`${target}-${key}`);
+    setError(null);
+    setNotice(null);
+    try {
+      setNotice(await api.moveLeakedSecret(finding, names[key] ?? finding.suggested_name, target));
+      setReport(await api.scanForLeakedSecrets());
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const spinner = (action: string, icon: JSX.Element) =>
+    busy === action ? <Loader2 size={14} className="animate-spin" /> : icon;
+
+  return (
+    <div className="bg-dark-700 rounded-2xl p-6 border border-dark-500">
+      <div className="flex items-center gap-3 mb-6">
+        <div className="w-10 h-10 rounded-xl bg-amber-500/20 flex items-center justify-center">
+          <FileWarning size={20} className="text-amber-400" />
+        </div>
+        <div className="flex-1">
+          <h3 className="text-lg font-semibold text-white">Secret Leak Scan</h3>
+          <p className="text-xs text-gray-500">
+            Looks for keys and tokens in workspace files, agents' models.json, sessions, /tmp/openclaw_* scripts,
+            exported configs and config history snapshots
+          </p>
+        </div>
+        <button onClick={handleScan} disabled={busy !== null} className="btn-secondary flex items-center gap-2">
+          {spinner('scan', <ScanSearch size={14} />)}
+          Scan
+        </button>
+      </div>
+
+      {report && report.findings.length === 0 && (
+        <p className="text-sm text-gray-400">No secrets found in {report.files_scanned} file(s).</p>
+      )}
+
+      {report && report.findings.length > 0 && (
+        <div className="space-y-2">
+          <p className="text-xs text-gray-500">
+            {report.findings.length}
+            {report.truncated ? '+' : ''} finding(s) in {report.files_scanned} scanned file(s). Moving a value
+            replaces it with a {'${NAME}'} reference.
+          </p>
+          {report.findings.map(finding => {
+            const key = findingKey(finding);
+            return (
+              <div key={key} className="flex items-center gap-3 text-sm bg-dark-800 rounded-xl px-3 py-2">
+                <div className="flex-1 min-w-0">
+                  <p className="font-mono text-xs text-gray-300 truncate" title={finding.file}>
+                    {finding.file}:{finding.line}
+                  </p>
+                  <p className="text-xs text-gray-500">
+                    {finding.kind} · <span className="font-mono">{finding.masked}</span>
+                  </p>
+                </div>
+                {finding.movable && (
+                  <>
+                    <input
+                      value={names[key] ?? finding.suggested_name}
+                      onChange={e => setNames({ ...names, [key]: e.target.value.toUpperCase() })}
+                      className="input-base w-48 font-mono text-xs"
+                    />
+                    <button
+                      onClick={() => handleMove(finding, 'vault')}
+                      disabled={busy !== null}
+                      className="btn-secondary flex items-center gap-1 text-xs"
+                    >
+                      {spinner(`vault-${key}`, <ShieldCheck size={12} />)}
+                      Vault
+                    </button>
+                    <button
+                      onClick={() => handleMove(finding, 'env_file')}
+                      disabled={busy !== null}
+                      className="btn-secondary flex items-center gap-1 text-xs"
+                    >
+                      {spinner(`env_file-${key}`, <KeyRound size={12} />)}
+                      Env file
+                    </button>
+                  </>
+                )}
+              </div>
+            );
+          })}
+        </div>
+      )}
+
+      {error && <p className="mt-3 text-xs text-red-400">{error}</p>}
+      {notice && <p className="mt-3 text-xs text-green-400">{notice}</p>}
+    </div>
+  );
+}
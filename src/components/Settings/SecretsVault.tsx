@@ -0,0 +1,209 @@
+import { useEffect, useState } from 'react';
+import { KeyRound, Loader2, Lock, LockOpen, Plus, ShieldCheck, Trash2 } from 'lucide-react';
+import { api, errorMessage, VaultStatus } from '../../lib/tauri';
+
+// Encrypted store for API keys and bot tokens; configs reference entries as ${secret:NAME} or ${NAME}
+export function SecretsVault() {
+  const [status, setStatus] = useState<VaultStatus | null>(null);
+  const [passphrase, setPassphrase] = useState('');
+  const [newName, setNewName] = useState('');
+  const [newValue, setNewValue] = useState('');
+  const [busy, setBusy] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [notice, setNotice] = useState<string | null>(null);
+
+  useEffect(() => {
+    api.getVaultStatus().then(setStatus).catch(() => {});
+  }, []);
+
+  const run = async (action: string, fn: () => Promise<VaultStatus>) => {
+    setBusy(action);
+    setError(null);
+    setNotice(null);
+    try {
+      setStatus(await fn());
+      return true;
+    } catch (e) {
+      setError(errorMessage(e));
+      return false;
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleCreate = async (withPassphrase: boolean) => {
+    if (await run('create', () => api.createVault(withPassphrase ? passphrase : undefined))) {
+      setPassphrase('');
+    }
+  };
+
+  const handleUnlock = async () => {
+    if (await run('unlock', () => api.unlockVault(passphrase))) {
+      setPassphrase('');
+    }
+  };
+
+  const handleAdd = async () => {
+    if (await run('add', () => api.setSecret(newName.trim(), newValue))) {
+      setNewName('');
+      setNewValue('');
+    }
+  };
+
+  const handleMigrate = async () => {
+    setBusy('migrate');
+    setError(null);
+    setNotice(null);
+    try {
+      const report = await api.migrateSecretsToVault();
+      setStatus(await api.getVaultStatus());
+      setNotice(
+        report.moved.length === 0
+          ? 'No plaintext secrets found.'
+          : `Moved ${report.moved.join(', ')} out of ${report.files.length} file(s). Restart the gateway to apply. Older snapshots in Config History still contain the plaintext values.`
+      );
+    } catch (e) {
+      setError(errorMessage(e));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  if (!status) {
+    return null;
+  }
+
+  const spinner = (action: string, icon: JSX.Element) =>
+    busy === action ? <Loader2 size={14} className="animate-spin" /> : icon;
+
+  return (
+    <div className="bg-dark-700 rounded-2xl p-6 border border-dark-500">
+      <div className="flex items-center gap-3 mb-6">
+        <div className="w-10 h-10 rounded-xl bg-emerald-500/20 flex items-center justify-center">
+          <ShieldCheck size={20} className="text-emerald-400" />
+        </div>
+        <div className="flex-1">
+          <h3 className="text-lg font-semibold text-white">Secrets Vault</h3>
+          <p className="text-xs text-gray-500">
+            Encrypted API keys and bot tokens, passed to the gateway as environment variables
+          </p>
+        </div>
+        {status.exists && status.unlocked && status.key_source === 'passphrase' && (
+          <button onClick={() => run('lock', api.lockVault)} className="btn-secondary flex items-center gap-2">
+            {spinner('lock', <Lock size={14} />)}
+            Lock
+          </button>
+        )}
+      </div>
+
+      {!status.exists && (
+        <div className="space-y-3">
+          <p className="text-sm text-gray-400">
+            Protect the vault with a passphrase (asked once per session), or keep a random key in a local key file
+            that only your user can read.
+          </p>
+          <div className="flex items-center gap-2">
+            <input
+              type="password"
+              value={passphrase}
+              onChange={e => setPassphrase(e.target.value)}
+              placeholder="Passphrase (min. 8 characters)"
+              className="input-base flex-1"
+            />
+            <button
+              onClick={() => handleCreate(true)}
+              disabled={busy !== null || passphrase.length === 0}
+              className="btn-primary flex items-center gap-2"
+            >
+              {spinner('create', <Lock size={14} />)}
+              Create with passphrase
+            </button>
+            <button onClick={() => handleCreate(false)} disabled={busy !== null} className="btn-secondary flex items-center gap-2">
+              <KeyRound size={14} />
+              Use key file
+            </button>
+          </div>
+        </div>
+      )}
+
+      {status.exists && !status.unlocked && (
+        <div className="flex items-center gap-2">
+          <input
+            type="password"
+            value={passphrase}
+            onChange={e => setPassphrase(e.target.value)}
+            onKeyDown={e => e.key === 'Enter' && handleUnlock()}
+            placeholder="Vault passphrase"
+            className="input-base flex-1"
+          />
+          <button onClick={handleUnlock} disabled={busy !== null} className="btn-primary flex items-center gap-2">
+            {spinner('unlock', <LockOpen size={14} />)}
+            Unlock
+          </button>
+        </div>
+      )}
+
+      {status.exists && status.unlocked && (
+        <div className="space-y-3">
+          {status.names.length === 0 ? (
+            <p className="text-sm text-gray-500">The vault is empty.</p>
+          ) : (
+            <div className="space-y-1">
+              {status.names.map(name => (
+                <div key={name} className="flex items-center gap-3 text-sm">
+                  <span className="font-mono text-gray-300 flex-1">{name}</span>
+                  <span className="font-mono text-xs text-gray-500">{'${secret:' + name + '}'}</span>
+                  <button
+                    onClick={() => run(`delete-${name}`, () => api.deleteSecret(name))}
+                    disabled={busy !== null}
+                    className="icon-button text-gray-400 hover:text-red-400"
+                  >
+                    {spinner(`delete-${name}`, <Trash2 size={14} />)}
+                  </button>
+                </div>
+              ))}
+            </div>
+          )}
+
+          <div className="flex items-center gap-2">
+            <input
+              value={newName}
+              onChange={e => setNewName(e.target.value.toUpperCase())}
+              placeholder="NAME"
+              className="input-base w-56 font-mono"
+            />
+            <input
+              type="password"
+              value={newValue}
+              onChange={e => setNewValue(e.target.value)}
+              placeholder="Value"
+              className="input-base flex-1"
+            />
+            <button
+              onClick={handleAdd}
+              disabled={busy !== null || !newName.trim() || !newValue}
+              className="btn-secondary flex items-center gap-2"
+            >
+              {spinner('add', <Plus size={14} />)}
+              Add
+            </button>
+          </div>
+
+          <div className="pt-3 border-t border-dark-500 flex items-center gap-3">
+            <p className="text-xs text-gray-500 flex-1">
+              Move provider API keys, Telegram bot tokens, the Brave key and the gateway token out of openclaw.json
+              and the agents' models.json files. New keys saved in the Manager go to the vault automatically.
+            </p>
+            <button onClick={handleMigrate} disabled={busy !== null} className="btn-primary flex items-center gap-2">
+              {spinner('migrate', <ShieldCheck size={14} />)}
+              Move plaintext secrets
+            </button>
+          </div>
+        </div>
+      )}
+
+      {error && <p className="mt-3 text-xs text-red-400">{error}</p>}
+      {notice && <p className="mt-3 text-xs text-green-400">{notice}</p>}
+    </div>
+  );
+}
@@ -20,7 +20,13 @@ import {
   GitMerge
 } from 'lucide-react';
 import { appLogger } from '../../lib/logger';
-import { isTauri } from '../../lib/tauri';
+import { api, isTauri, errorMessage, StopPolicy } from '../../lib/tauri';
+import { MaintenanceSettings } from './MaintenanceSettings';
+import { SecretsVault } from './SecretsVault';
+import { GatewayToken } from './GatewayToken';
+import { DashboardAccess } from './DashboardAccess';
+import { SecretLeakScan } from './SecretLeakScan';
+import { useConfigChanges } from '../../hooks/useConfigChanges';
 
 interface InstallResult {
   success: boolean;
@@ -61,6 +67,12 @@ interface GatewayConfig {
   log_level: string;
 }
 
+interface GatewaySaveResult {
+  message: string;
+  restart_required: boolean;
+  previous_port: number | null;
+}
+
 interface SubagentDefaults {
   max_spawn_depth: number | null;
   max_children_per_agent: number | null;
@@ -93,48 +105,54 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
   const [webConfig, setWebConfig] = useState<WebConfig>({ brave_api_key: null });
   const [compaction, setCompaction] = useState<CompactionConfig>({ enabled: false, threshold: null, context_pruning: false, max_context_messages: null });
   const [workspace, setWorkspace] = useState<WorkspaceConfig>({ workspace: null, timezone: null, time_format: null, skip_bootstrap: false, bootstrap_max_chars: null });
-  const [gateway, setGateway] = useState<GatewayConfig>({ port: 3000, log_level: 'info' });
+  const [gateway, setGateway] = useState<GatewayConfig>({ port: 18789, log_level: 'info' });
   const [subagentDefaults, setSubagentDefaults] = useState<SubagentDefaults>({ max_spawn_depth: null, max_children_per_agent: null, max_concurrent: null });
+  const [stopPolicy, setStopPolicy] = useState<StopPolicy>({ cliStop: true, cliTimeoutMs: 3000, terminate: true, gracePeriodMs: 10000, forceKill: true });
   const [appVersion, setAppVersion] = useState<string>('...');
 
+  const loadConfig = async () => {
+    setLoading(true);
+    try {
+      const [br, web, comp, ws, gw, sub, stop] = await Promise.all([
+        invoke<BrowserConfig>('get_browser_config'),
+        invoke<WebConfig>('get_web_config'),
+        invoke<CompactionConfig>('get_compaction_config'),
+        invoke<WorkspaceConfig>('get_workspace_config'),
+        invoke<GatewayConfig>('get_gateway_config'),
+        invoke<SubagentDefaults>('get_subagent_defaults'),
+        api.getStopPolicy(),
+      ]);
+      setBrowser(br);
+      setWebConfig(web);
+      setCompaction(comp);
+      setWorkspace(ws);
+      setGateway(gw);
+      setSubagentDefaults(sub);
+      setStopPolicy(stop);
+
+      if (isTauri()) {
+        const { getVersion } = await import('@tauri-apps/api/app');
+        setAppVersion(await getVersion());
+      }
+    } catch (e) {
+      appLogger.error('Failed to load settings', e);
+    } finally {
+      setLoading(false);
+    }
+  };
+
   // Load initial data
   useEffect(() => {
-    const loadConfig = async () => {
-      setLoading(true);
-      try {
-        const [br, web, comp, ws, gw, sub] = await Promise.all([
-          invoke<BrowserConfig>('get_browser_config'),
-          invoke<WebConfig>('get_web_config'),
-          invoke<CompactionConfig>('get_compaction_config'),
-          invoke<WorkspaceConfig>('get_workspace_config'),
-          invoke<GatewayConfig>('get_gateway_config'),
-          invoke<SubagentDefaults>('get_subagent_defaults'),
-        ]);
-        setBrowser(br);
-        setWebConfig(web);
-        setCompaction(comp);
-        setWorkspace(ws);
-        setGateway(gw);
-        setSubagentDefaults(sub);
-
-        if (isTauri()) {
-          const { getVersion } = await import('@tauri-apps/api/app');
-          setAppVersion(await getVersion());
-        }
-      } catch (e) {
-        appLogger.error('Failed to load settings', e);
-      } finally {
-        setLoading(false);
-      }
-    };
     loadConfig();
   }, []);
 
+  useConfigChanges(loadConfig, 'openclaw', ['agents', 'browser', 'tools', 'web', 'gateway', 'manager']);
+
   const handleSave = async () => {
     setSaving(true);
     setSaveSuccess(false);
     try {
-      await Promise.all([
+      const [, , , , gatewayResult] = await Promise.all([
         invoke('save_browser_config', { enabled: browser.enabled, color: browser.color }),
         invoke('save_web_config', { braveApiKey: webConfig.brave_api_key }),
         invoke('save_compaction_config', {
@@ -150,15 +168,21 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
           skipBootstrap: workspace.skip_bootstrap,
           bootstrapMaxChars: workspace.bootstrap_max_chars
         }),
-        invoke('save_gateway_config', { port: gateway.port, logLevel: gateway.log_level }),
+        invoke<GatewaySaveResult>('save_gateway_config', { port: gateway.port, logLevel: gateway.log_level }),
         invoke('save_subagent_defaults', { defaults: subagentDefaults }),
+        api.saveStopPolicy(stopPolicy),
       ]);
 
       setSaveSuccess(true);
       setTimeout(() => setSaveSuccess(false), 2000);
+
+      if (gatewayResult.restart_required
+        && confirm(`The gateway is still running on port ${gatewayResult.previous_port}. Restart it now on port ${gateway.port}?`)) {
+        await invoke('restart_service');
+      }
     } catch (e) {
       console.error('Failed to save:', e);
-      alert('Failed to save settings: ' + String(e));
+      alert('Failed to save settings: ' + errorMessage(e));
     } finally {
       setSaving(false);
     }
@@ -180,7 +204,7 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
       }
     } catch (e) {
       console.error('Export failed:', e);
-      alert('Failed to export configuration: ' + String(e));
+      alert('Failed to export configuration: ' + errorMessage(e));
     }
   };
 
@@ -203,7 +227,7 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
       }
     } catch (e) {
       console.error('Import failed:', e);
-      alert('Failed to import configuration: ' + String(e));
+      alert('Failed to import configuration: ' + errorMessage(e));
     }
   };
 
@@ -221,7 +245,7 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
       setUninstallResult({
         success: false,
         message: 'An error occurred during uninstallation',
-        error: String(e),
+        error: errorMessage(e),
       });
     } finally {
       setUninstalling(false);
@@ -249,7 +273,7 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
       }
     } catch (e: any) {
       appLogger.error('Manager update check failed', e);
-      setManagerUpdateError(e?.message || String(e));
+      setManagerUpdateError(errorMessage(e));
     } finally {
       setManagerChecking(false);
     }
@@ -281,7 +305,7 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
       setManagerUpdateDone(true);
     } catch (e: any) {
       appLogger.error('Manager update download failed', e);
-      setManagerUpdateError(e?.message || String(e));
+      setManagerUpdateError(errorMessage(e));
     } finally {
       setManagerDownloading(false);
     }
@@ -440,7 +464,7 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
               <input
                 type="number"
                 value={gateway.port}
-                onChange={e => setGateway({ ...gateway, port: parseInt(e.target.value) || 3000 })}
+                onChange={e => setGateway({ ...gateway, port: parseInt(e.target.value) || 18789 })}
                 className="input-base"
               />
               <p className="text-xs text-yellow-500/80 mt-1 flex items-center gap-1">
@@ -461,8 +485,75 @@ export function Settings({ onEnvironmentChange }: SettingsProps) {
               </select>
             </div>
           </div>
+
+          <div className="mt-6 pt-4 border-t border-dark-500">
+            <p className="text-sm text-gray-300 mb-1">Stop Policy</p>
+            <p className="text-xs text-gray-500 mb-4">
+              Stop asks openclaw first, then sends SIGTERM and waits the grace period so sessions can flush, then SIGKILL
+            </p>
+            <div className="grid grid-cols-1 md:grid-cols-3 gap-4">
+              <div>
+                <label className="flex items-center gap-2 text-sm text-gray-400 mb-2">
+                  <input
+                    type="checkbox"
+                    checked={stopPolicy.cliStop}
+                    onChange={e => setStopPolicy({ ...stopPolicy, cliStop: e.target.checked })}
+                  />
+                  CLI stop, wait (s)
+                </label>
+                <input
+                  type="number"
+                  min={0}
+                  max={120}
+                  value={stopPolicy.cliTimeoutMs / 1000}
+                  onChange={e => setStopPolicy({ ...stopPolicy, cliTimeoutMs: (parseInt(e.target.value) || 0) * 1000 })}
+                  disabled={!stopPolicy.cliStop}
+                  className="input-base"
+                />
+              </div>
+              <div>
+                <label className="flex items-center gap-2 text-sm text-gray-400 mb-2">
+                  <input
+                    type="checkbox"
+                    checked={stopPolicy.terminate}
+                    onChange={e => setStopPolicy({ ...stopPolicy, terminate: e.target.checked })}
+                  />
+                  SIGTERM, grace period (s)
+                </label>
+                <input
+                  type="number"
+                  min={0}
+                  max={120}
+                  value={stopPolicy.gracePeriodMs / 1000}
+                  onChange={e => setStopPolicy({ ...stopPolicy, gracePeriodMs: (parseInt(e.target.value) || 0) * 1000 })}
+                  disabled={!stopPolicy.terminate}
+                  className="input-base"
+                />
+              </div>
+              <div>
+                <label className="flex items-center gap-2 text-sm text-gray-400 mb-2">
+                  <input
+                    type="checkbox"
+                    checked={stopPolicy.forceKill}
+                    onChange={e => setStopPolicy({ ...stopPolicy, forceKill: e.target.checked })}
+                  />
+                  SIGKILL if still running
+                </label>
+              </div>
+            </div>
+          </div>
         </div>
 
+        <GatewayToken />
+
+        <DashboardAccess />
+
+        <MaintenanceSettings />
+
+        <SecretsVault />
+
+        <SecretLeakScan />
+
         {/* Subagent Defaults */}
         <div className="bg-dark-700 rounded-2xl p-6 border border-dark-500">
           <div className="flex items-center gap-3 mb-6">
@@ -12,6 +12,7 @@ import {
   Package
 } from 'lucide-react';
 import { setupLogger } from '../../lib/logger';
+import { errorMessage } from '../../lib/tauri';
 
 interface EnvironmentStatus {
   node_installed: boolean;
@@ -65,7 +66,7 @@ export function Setup({ onComplete, embedded = false }: SetupProps) {
       }
     } catch (e) {
       setupLogger.error('Environment check failed', e);
-      setError(`Environment check failed: ${e}`);
+      setError(`Environment check failed: ${errorMessage(e)}`);
     } finally {
       setChecking(false);
     }
@@ -104,7 +105,7 @@ export function Setup({ onComplete, embedded = false }: SetupProps) {
         await invoke<string>('open_install_terminal', { installType: 'nodejs' });
         setError('Installation terminal opened, please complete the installation in the terminal then click "Re-check"');
       } catch (termErr) {
-        setError(`Installation failed: ${e}. ${termErr}`);
+        setError(`Installation failed: ${errorMessage(e)}. ${errorMessage(termErr)}`);
       }
     } finally {
       setInstalling(null);
@@ -139,7 +140,7 @@ export function Setup({ onComplete, embedded = false }: SetupProps) {
         await invoke<string>('open_install_terminal', { installType: 'openclaw' });
         setError('Installation terminal opened, please complete the installation in the terminal then click "Re-check"');
       } catch (termErr) {
-        setError(`Installation failed: ${e}. ${termErr}`);
+        setError(`Installation failed: ${errorMessage(e)}. ${errorMessage(termErr)}`);
       }
     } finally {
       setInstalling(null);
@@ -1,5 +1,5 @@
 import { useState, useEffect } from 'react';
-import { api, Skill, isTauri } from '../../lib/tauri';
+import { api, Skill, isTauri, errorMessage } from '../../lib/tauri';
 import { Book, Package, AlertCircle, Loader2, Download, Terminal, CheckCircle, Plus, Trash2 } from 'lucide-react';
 
 export function Skills() {
@@ -32,7 +32,7 @@ export function Skills() {
                 setSkills([]);
             }
         } catch (e) {
-            setError('Failed to load skills: ' + String(e));
+            setError('Failed to load skills: ' + errorMessage(e));
         }
     };
 
@@ -66,7 +66,7 @@ export function Skills() {
             await checkClawhub();
             setInstallResult({ success: true, message: 'Clawhub installed successfully' });
         } catch (e) {
-            setError('Failed to install clawhub: ' + String(e));
+            setError('Failed to install clawhub: ' + errorMessage(e));
         } finally {
             setInstallingClawhub(false);
         }
@@ -84,7 +84,7 @@ export function Skills() {
             setShowInstallDialog(false);
             await fetchSkills();
         } catch (e) {
-            setInstallResult({ success: false, message: String(e) });
+            setInstallResult({ success: false, message: errorMessage(e) });
         } finally {
             setInstallingSkill(false);
         }
@@ -98,7 +98,7 @@ export function Skills() {
             setShowUninstallConfirm(null);
             await fetchSkills();
         } catch (e) {
-            setError('Failed to uninstall skill: ' + String(e));
+            setError('Failed to uninstall skill: ' + errorMessage(e));
         } finally {
             setUninstallingSkill(false);
         }
@@ -113,7 +113,7 @@ export function Skills() {
             await checkClawhub();
             setInstallResult({ success: true, message: 'Clawhub uninstalled successfully' });
         } catch (e) {
-            setError('Failed to uninstall clawhub: ' + String(e));
+            setError('Failed to uninstall clawhub: ' + errorMessage(e));
         } finally {
             setUninstallingClawhub(false);
         }
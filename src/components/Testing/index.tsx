@@ -10,6 +10,7 @@ import {
 } from 'lucide-react';
 import clsx from 'clsx';
 import { testingLogger } from '../../lib/logger';
+import { errorMessage } from '../../lib/tauri';
 
 interface DiagnosticResult {
   name: string;
@@ -38,7 +39,7 @@ export function Testing() {
       setDiagnosticResults([{
         name: 'Diagnostics Execution',
         passed: false,
-        message: String(e),
+        message: errorMessage(e),
         suggestion: 'Please check if OpenClaw is properly installed',
       }]);
     } finally {
@@ -0,0 +1,33 @@
+import { useEffect, useRef } from 'react';
+import { ConfigChangeEvent, onConfigChanged } from '../lib/tauri';
+import { configLogger } from '../lib/logger';
+
+// Reload a page when its config changes on disk (openclaw CLI, a text editor).
+// `sections` limits openclaw.json changes to the top-level keys the page shows;
+// the Manager's own saves don't emit events.
+export function useConfigChanges(
+  reload: () => void,
+  file: ConfigChangeEvent['file'] = 'openclaw',
+  sections?: string[]
+) {
+  const reloadRef = useRef(reload);
+  reloadRef.current = reload;
+  const sectionKey = sections?.join(',');
+
+  useEffect(() => {
+    const watched = sectionKey?.split(',');
+    const unlisten = onConfigChanged((event) => {
+      if (event.file !== file || event.error) {
+        return;
+      }
+      if (watched && !event.sections.some((section) => watched.includes(section))) {
+        return;
+      }
+      configLogger.info(`${event.file} changed on disk (${event.sections.join(', ')}), reloading`);
+      reloadRef.current();
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [file, sectionKey]);
+}
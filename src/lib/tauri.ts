@@ -1,4 +1,5 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
 import { apiLogger } from './logger';
 
 // Check if running in Tauri environment
@@ -22,14 +23,100 @@ async function invokeWithLog<T>(cmd: string, args?: Record<string, unknown>): Pr
   }
 }
 
+// Same as invokeWithLog, but keeps the arguments (passphrases, secret values) out of the log
+async function invokeSecretWithLog<T>(cmd: string, args: Record<string, unknown>): Promise<T> {
+  if (!isTauri()) {
+    throw new Error('Not running in Tauri environment, please start via Tauri application');
+  }
+  apiLogger.apiCall(cmd, '<redacted>');
+  try {
+    const result = await invoke<T>(cmd, args);
+    apiLogger.apiResponse(cmd, result);
+    return result;
+  } catch (error) {
+    apiLogger.apiError(cmd, error);
+    throw error;
+  }
+}
+
+// Error returned by every backend command
+export type ErrorCode =
+  | 'openclaw_not_installed'
+  | 'command_failed'
+  | 'config_read'
+  | 'config_parse'
+  | 'config_invalid'
+  | 'config_conflict'
+  | 'config_locked'
+  | 'config_write'
+  | 'port_in_use'
+  | 'service_timeout'
+  | 'service_failed'
+  | 'cancelled'
+  | 'confirmation_required'
+  | 'not_found'
+  | 'invalid_input'
+  | 'io'
+  | 'network'
+  | 'internal';
+
+export interface AppError {
+  code: ErrorCode;
+  message: string;
+  hint: string | null;
+  detail: string | null;
+}
+
+export function isAppError(e: unknown): e is AppError {
+  return typeof e === 'object' && e !== null && 'code' in e && 'message' in e;
+}
+
+// Human readable text for anything thrown by invoke (AppError, Error or string)
+export function errorMessage(e: unknown): string {
+  if (isAppError(e)) {
+    const text = e.detail && !e.message.includes(e.detail) ? `${e.message}: ${e.detail}` : e.message;
+    return e.hint ? `${text}. ${e.hint}` : text;
+  }
+  if (e instanceof Error) {
+    return e.message;
+  }
+  return String(e);
+}
+
+// Who owns the gateway port: the Manager's gateway, another process, or nobody
+export type GatewayState = 'ours' | 'foreign' | 'stopped';
+
+// Result of the gateway HTTP health probe
+export type HealthState = 'healthy' | 'degraded' | 'down';
+
+export interface HealthReport {
+  state: HealthState;
+  ready: boolean;
+  version: string | null;
+  latency_ms: number | null;
+  http_status: number | null;
+  message: string | null;
+}
+
 // Service status
 export interface ServiceStatus {
   running: boolean;
+  state: GatewayState;
   pid: number | null;
   port: number;
   uptime_seconds: number | null;
   memory_mb: number | null;
   cpu_percent: number | null;
+  health: HealthReport | null;
+}
+
+// One point of the gateway CPU / memory series
+export interface ResourceSample {
+  timestamp_ms: number;
+  pid: number;
+  cpu_percent: number | null;
+  memory_mb: number | null;
+  uptime_seconds: number | null;
 }
 
 // System information
@@ -162,14 +249,354 @@ export interface Skill {
   path: string;
 }
 
+// Configuration history snapshot
+export interface ConfigSnapshot {
+  id: string;
+  timestamp: string;
+  command: string;
+  size: number;
+}
+
+// Difference between two configuration versions
+export interface ConfigDiffEntry {
+  path: string;
+  kind: 'added' | 'removed' | 'changed';
+  before: unknown | null;
+  after: unknown | null;
+}
+
+// Schema problem in openclaw.json, located by JSON pointer
+export interface ConfigValidationIssue {
+  path: string;
+  kind: 'unknown_field' | 'invalid_value';
+  message: string;
+}
+
+export interface MigrationResult {
+  id: string;
+  description: string;
+  changes: string[];
+}
+
+export interface MigrationReport {
+  from_version: string | null;
+  to_version: string | null;
+  migrations: MigrationResult[];
+  config_changed: boolean;
+  manager_changed: boolean;
+}
+
+// Emitted when openclaw.json, mcps.json or mcporter.json changes on disk
+export interface ConfigChangeEvent {
+  file: 'openclaw' | 'mcps' | 'mcporter';
+  path: string;
+  sections: string[];
+  error: string | null;
+  revision: string | null;
+}
+
+// Subscribe to config file changes (including edits made outside the Manager)
+export async function onConfigChanged(handler: (event: ConfigChangeEvent) => void): Promise<UnlistenFn> {
+  if (!isTauri()) {
+    return () => {};
+  }
+  return listen<ConfigChangeEvent>('config-changed', (event) => handler(event.payload));
+}
+
+// Captured gateway output
+export type GatewayLogLevel = 'debug' | 'info' | 'warn' | 'error';
+
+export interface GatewayLogEntry {
+  timestamp: string;
+  stream: 'stdout' | 'stderr';
+  level: GatewayLogLevel;
+  message: string;
+}
+
+export interface GatewayLogPage {
+  entries: GatewayLogEntry[];
+  // Matching entries from the newest through this page; pass as `skip` for the page before
+  next_skip: number;
+  has_more: boolean;
+}
+
+// Follow gateway output live
+export async function onGatewayLog(handler: (entry: GatewayLogEntry) => void): Promise<UnlistenFn> {
+  if (!isTauri()) {
+    return () => {};
+  }
+  return listen<GatewayLogEntry>('gateway-log', (event) => handler(event.payload));
+}
+
+// Gateway supervisor restart history
+export type RestartOutcome = 'restarted' | 'spawn_failed' | 'start_timeout' | 'gave_up';
+
+export interface RestartRecord {
+  timestamp: string;
+  attempt: number;
+  exit_status?: string | null;
+  uptime_seconds: number;
+  delay_ms: number;
+  outcome: RestartOutcome;
+  message?: string | null;
+}
+
+export interface RestartHistory {
+  records: RestartRecord[];
+  crash_loop: boolean;
+}
+
+// Subscribe to the supervisor giving up on a crash-looping gateway
+export async function onServiceCrashLoop(handler: (history: RestartHistory) => void): Promise<UnlistenFn> {
+  if (!isTauri()) {
+    return () => {};
+  }
+  return listen<RestartHistory>('service-crash-loop', (event) => handler(event.payload));
+}
+
+// How Stop escalates: CLI stop, SIGTERM + grace period, SIGKILL (stored in manager.json)
+export interface StopPolicy {
+  cliStop: boolean;
+  cliTimeoutMs: number;
+  terminate: boolean;
+  gracePeriodMs: number;
+  forceKill: boolean;
+}
+
+// systemd user unit generated for the gateway (Linux)
+export interface UnitStatus {
+  installed: boolean;
+  systemd_available: boolean;
+  unit_path: string | null;
+  load_state: string | null;
+  active_state: string | null;
+  sub_state: string | null;
+  unit_file_state: string | null;
+  main_pid: number | null;
+  linger: boolean | null;
+  message: string | null;
+}
+
+// Encrypted secrets vault; configs reference secrets as ${NAME}
+export interface VaultStatus {
+  exists: boolean;
+  key_source: 'passphrase' | 'key_file' | null;
+  unlocked: boolean;
+  names: string[];
+}
+
+export interface SecretsMigrationReport {
+  moved: string[];
+  files: string[];
+}
+
+// Secret-looking values found outside the vault and env file; values arrive masked
+export interface LeakFinding {
+  file: string;
+  line: number;
+  kind: string;
+  masked: string;
+  fingerprint: string;
+  suggested_name: string;
+  movable: boolean;
+}
+
+export interface LeakScanReport {
+  findings: LeakFinding[];
+  files_scanned: number;
+  truncated: boolean;
+}
+
+export type LeakTarget = 'vault' | 'env_file';
+
+// Gateway token bookkeeping; the token itself is never sent
+export interface TokenInfo {
+  rotated_at: string | null;
+  weak: boolean;
+}
+
+export interface TokenRotation {
+  rotated_at: string;
+  restarted: boolean;
+}
+
+// Shared dashboard links (stored in manager.json under "dashboardAccess")
+export type DashboardScope = 'read_only' | 'admin';
+
+export interface DashboardTokenInfo {
+  id: string;
+  name: string;
+  scope: DashboardScope;
+  created_at: string;
+  expires_at: string;
+  revoked_at: string | null;
+  status: 'active' | 'expired' | 'revoked';
+}
+
+export interface DashboardAccessOverview {
+  enabled: boolean;
+  bind: string;
+  port: number;
+  public_url: string | null;
+  running: boolean;
+  tokens: DashboardTokenInfo[];
+}
+
+export interface DashboardLink {
+  token: DashboardTokenInfo;
+  url: string;
+}
+
+// Scheduled maintenance (stored in manager.json under "maintenance")
+export type MaintenanceAction = 'restart' | 'update' | 'repairDeviceToken';
+
+export interface TimeWindow {
+  start: string; // HH:MM, local time
+  end: string;
+}
+
+export interface MaintenanceJob {
+  id: string;
+  name: string;
+  action: MaintenanceAction;
+  schedule: string; // cron: minute hour day month weekday
+  enabled: boolean;
+}
+
+export interface MaintenanceSettings {
+  enabled: boolean;
+  quietHours: TimeWindow | null;
+  maintenanceWindow: TimeWindow | null;
+  jobs: MaintenanceJob[];
+}
+
+export interface MaintenanceOverview {
+  settings: MaintenanceSettings;
+  next_runs: { job_id: string; at: string | null }[];
+}
+
+export interface JobRun {
+  job_id: string;
+  job_name: string;
+  action: MaintenanceAction;
+  trigger: 'scheduled' | 'manual';
+  started_at: string;
+  duration_ms: number;
+  outcome: 'succeeded' | 'failed' | 'skipped';
+  message?: string;
+}
+
+// Subscribe to finished / skipped maintenance runs
+export async function onMaintenanceRun(handler: (run: JobRun) => void): Promise<UnlistenFn> {
+  if (!isTauri()) {
+    return () => {};
+  }
+  return listen<JobRun>('maintenance-run', (event) => handler(event.payload));
+}
+
+// Start / stop / restart progress
+export type ServiceOperation = 'start' | 'stop' | 'restart';
+
+export type ServicePhase =
+  | 'queued'
+  | 'stopping'
+  | 'spawning'
+  | 'waiting_for_port'
+  | 'waiting_for_health'
+  | 'healthy'
+  | 'stopped'
+  | 'failed'
+  | 'cancelled';
+
+export interface ServiceProgress {
+  operation_id: number;
+  operation: ServiceOperation;
+  phase: ServicePhase;
+  message: string | null;
+  elapsed_ms: number;
+}
+
+export interface ActiveServiceOperation {
+  id: number;
+  operation: ServiceOperation;
+  phase: ServicePhase;
+  cancel_requested: boolean;
+}
+
+// Subscribe to progress of service operations
+export async function onServiceProgress(handler: (progress: ServiceProgress) => void): Promise<UnlistenFn> {
+  if (!isTauri()) {
+    return () => {};
+  }
+  return listen<ServiceProgress>('service-progress', (event) => handler(event.payload));
+}
+
+// OpenClaw profile (separate config dir / gateway port / env file)
+export interface Profile {
+  name: string;
+  config_dir?: string | null;
+  gateway_port?: number | null;
+  env_file?: string | null;
+}
+
+export interface ProfilesOverview {
+  active: string;
+  config_dir: string;
+  openclaw_home: string | null;
+  profiles: Profile[];
+}
+
 // API wrapper (with logging)
 export const api = {
   // Service management
   getServiceStatus: () => invokeWithLog<ServiceStatus>('get_service_status'),
+  getResourceHistory: () => invokeWithLog<ResourceSample[]>('get_resource_history'),
+  probeGatewayHealth: () => invokeWithLog<HealthReport>('probe_gateway_health'),
   startService: () => invokeWithLog<string>('start_service'),
   stopService: () => invokeWithLog<string>('stop_service'),
   restartService: () => invokeWithLog<string>('restart_service'),
+  cancelServiceOperation: () => invokeWithLog<boolean>('cancel_service_operation'),
+  getServiceOperation: () => invokeWithLog<ActiveServiceOperation | null>('get_service_operation'),
+  getStopPolicy: () => invokeWithLog<StopPolicy>('get_stop_policy'),
+  saveStopPolicy: (policy: StopPolicy) => invokeWithLog<StopPolicy>('save_stop_policy', { policy }),
+  killAllPortProcesses: (confirm?: boolean) => invokeWithLog<string>('kill_all_port_processes', { confirm }),
+  getGatewayUnitStatus: () => invokeWithLog<UnitStatus>('get_gateway_unit_status'),
+  enableGatewayUnit: () => invokeWithLog<UnitStatus>('enable_gateway_unit'),
+  disableGatewayUnit: (remove?: boolean) => invokeWithLog<UnitStatus>('disable_gateway_unit', { remove }),
+  getVaultStatus: () => invokeWithLog<VaultStatus>('get_vault_status'),
+  createVault: (passphrase?: string) => invokeSecretWithLog<VaultStatus>('create_vault', { passphrase }),
+  unlockVault: (passphrase: string) => invokeSecretWithLog<VaultStatus>('unlock_vault', { passphrase }),
+  lockVault: () => invokeWithLog<VaultStatus>('lock_vault'),
+  setSecret: (name: string, value: string) => invokeSecretWithLog<VaultStatus>('set_secret', { name, value }),
+  deleteSecret: (name: string) => invokeWithLog<VaultStatus>('delete_secret', { name }),
+  migrateSecretsToVault: () => invokeWithLog<SecretsMigrationReport>('migrate_secrets_to_vault'),
+  getGatewayTokenState: () => invokeWithLog<TokenInfo>('get_gateway_token_state'),
+  rotateGatewayToken: () => invokeWithLog<TokenRotation>('rotate_gateway_token'),
+  getDashboardAccess: () => invokeWithLog<DashboardAccessOverview>('get_dashboard_access'),
+  saveDashboardProxy: (enabled: boolean, bind: string, port: number, publicUrl: string | null) =>
+    invokeWithLog<DashboardAccessOverview>('save_dashboard_proxy', { enabled, bind, port, publicUrl }),
+  createDashboardToken: (name: string, scope: DashboardScope, expiresInHours: number) =>
+    invokeWithLog<DashboardLink>('create_dashboard_token', { name, scope, expiresInHours }),
+  getDashboardTokenLink: (id: string) => invokeWithLog<string>('get_dashboard_token_link', { id }),
+  revokeDashboardToken: (id: string) => invokeWithLog<DashboardAccessOverview>('revoke_dashboard_token', { id }),
+  scanForLeakedSecrets: () => invokeWithLog<LeakScanReport>('scan_for_leaked_secrets'),
+  moveLeakedSecret: (finding: LeakFinding, name: string, target: LeakTarget) =>
+    invokeWithLog<string>('move_leaked_secret', {
+      file: finding.file,
+      line: finding.line,
+      fingerprint: finding.fingerprint,
+      name,
+      target,
+    }),
+  getMaintenanceSettings: () => invokeWithLog<MaintenanceOverview>('get_maintenance_settings'),
+  saveMaintenanceSettings: (settings: MaintenanceSettings) =>
+    invokeWithLog<MaintenanceOverview>('save_maintenance_settings', { settings }),
+  runMaintenanceJob: (id: string) => invokeWithLog<JobRun>('run_maintenance_job', { id }),
+  getMaintenanceHistory: () => invokeWithLog<{ runs: JobRun[] }>('get_maintenance_history'),
+  getRestartHistory: () => invokeWithLog<RestartHistory>('get_restart_history'),
   getLogs: (lines?: number) => invokeWithLog<string[]>('get_logs', { lines }),
+  readGatewayLogs: (skip?: number, limit?: number, level?: GatewayLogLevel) =>
+    invokeWithLog<GatewayLogPage>('read_gateway_logs', { skip, limit, level }),
 
   // System information
   getSystemInfo: () => invokeWithLog<SystemInfo>('get_system_info'),
@@ -178,7 +605,19 @@ export const api = {
 
   // Configuration management
   getConfig: () => invokeWithLog<unknown>('get_config'),
+  validateConfig: (config: unknown) =>
+    invokeWithLog<ConfigValidationIssue[]>('validate_config', { config }),
   saveConfig: (config: unknown) => invokeWithLog<string>('save_config', { config }),
+  listConfigSnapshots: () => invokeWithLog<ConfigSnapshot[]>('list_config_snapshots'),
+  diffConfigSnapshots: (from: string, to: string) =>
+    invokeWithLog<ConfigDiffEntry[]>('diff_config_snapshots', { from, to }),
+  restoreConfigSnapshot: (id: string) => invokeWithLog<string>('restore_config_snapshot', { id }),
+  getProfiles: () => invokeWithLog<ProfilesOverview>('get_profiles'),
+  saveProfile: (profile: Profile) => invokeWithLog<ProfilesOverview>('save_profile', { profile }),
+  deleteProfile: (name: string) => invokeWithLog<ProfilesOverview>('delete_profile', { name }),
+  switchProfile: (name: string) => invokeWithLog<ProfilesOverview>('switch_profile', { name }),
+  getConfigMigrationReport: () => invokeWithLog<MigrationReport>('get_config_migration_report'),
+  runConfigMigrations: () => invokeWithLog<MigrationReport>('run_config_migrations'),
   getEnvValue: (key: string) => invokeWithLog<string | null>('get_env_value', { key }),
   saveEnvValue: (key: string, value: string) =>
     invokeWithLog<string>('save_env_value', { key, value }),